@@ -0,0 +1,51 @@
+//! Fit time by number of standards and iteration budget, run with `cargo bench`.
+//!
+//! `cargo bench -- --save-baseline fit.csv` keeps the times, `cargo bench -- --baseline fit.csv` compares with them
+//! and fails if a case got more than 20 % slower.
+
+use std::{env, fs, process::ExitCode};
+
+use elisa::benchmark::*;
+
+const REPEATS: usize = 5;
+
+fn main() -> ExitCode {
+    let arguments: Vec<String> = env::args().collect();
+    let value_of = |flag: &str| arguments.iter().position(|argument| argument == flag).and_then(|i| arguments.get(i + 1));
+
+    println!("{:>9} {:>9} {:>12} {:>12} {:>11} {:>10}", "standards", "budget", "median", "fastest", "iterations", "converged");
+    let cases = run_benchmark(REPEATS);
+    for case in &cases {
+        let converged = if case.failed { "failed" } else if case.converged { "yes" } else { "no" };
+        println!("{:>9} {:>9} {:>12.3?} {:>12.3?} {:>11} {:>10}", case.size, case.max_iterations, case.median, case.fastest, case.iterations, converged);
+    }
+
+    if let Some(path) = value_of("--save-baseline") {
+        let lines: Vec<String> = cases.iter().map(BenchmarkCase::to_csv).collect();
+        let csv = format!("size,max_iterations,median_ns,fastest_ns,iterations,converged\n{}\n", lines.join("\n"));
+        if let Err(error) = fs::write(path, csv) {
+            eprintln!("Can't write {path}: {error}");
+            return ExitCode::FAILURE
+        }
+        println!("Saved the baseline to {path}");
+    }
+
+    if let Some(path) = value_of("--baseline") {
+        let baseline = match fs::read_to_string(path) {
+            Ok(baseline) => baseline,
+            Err(error) => {
+                eprintln!("Can't read {path}: {error}");
+                return ExitCode::FAILURE
+            },
+        };
+        let regressions = regressions(&cases, &baseline);
+        for (case, median) in &regressions {
+            println!("Slower: {} standards, {} iterations, {:.3?} instead of {median:.3?}", case.size, case.max_iterations, case.median);
+        }
+        if !regressions.is_empty() {
+            return ExitCode::FAILURE
+        }
+        println!("No case is more than {:.0} % slower than {path}", REGRESSION_THRESHOLD * 100.0);
+    }
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,886 @@
+# Top bar
+save = Speichern
+load = Laden
+export-layout = Layout exportieren
+import-layout = Layout importieren
+duplicate-plate = Platte duplizieren
+duplicate-plate-hover = Eine neue Platte mit denselben Probentypen, Gruppen und Konzentrationen beginnen, ohne die Werte
+copy-layout = Layout auf Platte kopieren…
+copy-layout-hover = Einem gespeicherten Assay die Probentypen, Gruppen und Konzentrationen dieser Platte geben, seine Werte bleiben
+back = Zurück
+settings = Einstellungen
+ok = Ok
+dismiss = Ausblenden
+
+# Settings
+language = Sprache
+
+# File errors
+error-cant-read-file = Der Inhalt der Datei konnte nicht gelesen werden
+error-cant-write-file = Der Inhalt konnte nicht in die Datei geschrieben werden
+error-cant-deserialize = Die Mikrotiterplatte konnte nicht aus dem Inhalt geladen werden
+error-invalid-layout = Das Layout konnte nicht übernommen werden: { $error }
+error-cant-print = Der Bericht konnte nicht an den Drucker gesendet werden.
+error-try-different-file = Bitte versuchen Sie eine andere Datei.
+error-cant-print-hint = Prüfen Sie, ob ein Drucker eingerichtet ist, oder speichern Sie den Bericht stattdessen als PDF.
+
+# Value errors
+error-unassigned-concentration = Die Platte enthält einen Standard ohne Konzentration.
+error-unassigned-value = Die Platte enthält eine Probe ohne Messwert.
+error-invalid-concentration = Die Platte enthält einen Standard mit ungültiger Konzentration.
+error-invalid-value = Die Platte enthält eine Probe mit ungültigem Messwert.
+error-not-enough-standards = Die Platte enthält nicht genug Standards für eine Vier-Parameter-Analyse.
+error-blank-too-big = Der Leerwert ist größer als einer der Standard-Messwerte
+error-control-too-big = Die Kontrolle ist größer als einer der Standard-Messwerte
+error-missing-path-length = Ein Well hat keine Weglängenmessung
+error-concentration-hint = Geben Sie für jede Standardgruppe eine positive Konzentration ein.
+error-value-hint = Füllen Sie die markierten Wells aus oder schließen Sie sie aus, Messwerte müssen Zahlen sein.
+error-not-enough-standards-hint = Weisen Sie mindestens vier Standards mit unterschiedlichen Konzentrationen zu.
+error-too-big-hint = Prüfen Sie, ob Leerwert und Kontrolle den richtigen Wells zugewiesen sind.
+error-missing-path-length-hint = Laden Sie in den Fit-Optionen eine Wasserpeak-Messung für jedes Well oder korrigieren Sie mit einem festen Faktor.
+
+# Regression errors
+error-invalid-standards = Die Standards können nicht angepasst werden, sie brauchen positive Konzentrationen und ein Signal, das sich mit ihnen ändert.
+error-invalid-standards-hint = Verwenden Sie mindestens vier verschiedene Konzentrationen über null und prüfen Sie die Standard-Wells auf vertauschte oder leere Werte.
+error-did-not-converge = Die Anpassung ist divergiert und hat keine Kurve gefunden.
+error-did-not-converge-hint = Schließen Sie abweichende Standards aus oder versuchen Sie eine andere Gewichtung oder Leerwertkorrektur in den Optionen.
+error-singular-jacobian = Die Standards bestimmen nicht alle vier Parameter der Kurve.
+error-singular-jacobian-hint = Ergänzen Sie Standards auf beiden Plateaus und im steilen Teil der Kurve oder legen Sie die untere Asymptote auf den Leerwert fest.
+error-degenerate-curve = Die angepasste Kurve ist flach, Konzentrationen können nicht abgelesen werden.
+error-degenerate-curve-hint = Prüfen Sie, ob die Standards einen Bereich abdecken, in dem sich das Signal ändert, und ob keine Standardgruppe falschen Wells zugewiesen ist.
+error-not-monotonic = Die mittleren Signale der Standards folgen nicht ihren Konzentrationen, eine daran angepasste Kurve wäre sinnlos:
+error-not-monotonic-hint = Prüfen Sie die Standard-Wells auf vertauschte Positionen oder Konzentrationen und schließen Sie fehlerhafte Standards aus. Für eine Kurve, die wirklich umkehrt, z. B. durch einen Hook-Effekt bei den höchsten Standards, erlauben Sie dies in den Fit-Optionen.
+swap-suggestion = Standards nicht in Reihenfolge
+swap-suggestion-monotonic = Die Standards folgen wieder ihren Konzentrationen.
+swap-suggestion-none = Kein Tausch zweier Standards bringt sie in Reihenfolge. Schließen Sie fehlerhafte Standards aus oder erlauben Sie eine umkehrende Kurve in den Fit-Optionen.
+swap-standards = { $first } und { $second } tauschen und anpassen
+swap-standards-hover = Die Wells jedes Standards werden dem anderen zugewiesen, die Messwerte bleiben, wo sie sind. Die Änderung wird im Audit-Trail festgehalten.
+monotonic-reads-lower = { $standard } misst niedriger als { $previous }
+monotonic-reads-higher = { $standard } misst höher als { $previous }
+monotonic-swap = { $first } (Wells { $first_wells }) und { $second } (Wells { $second_wells }) wurden wahrscheinlich vertauscht, ohne dies ist die Kurve monoton.
+
+# Import errors
+error-reader-export = Der Export des Plattenlesers konnte nicht gelesen werden: { $error }
+error-reader-export-hint = Prüfen Sie, ob die Datei von einem unterstützten Lesegerät exportiert und nicht bearbeitet wurde.
+error-spreadsheet = Die Tabelle konnte nicht gelesen werden: { $error }
+error-empty-spreadsheet = Die Tabelle enthält keine Blätter.
+error-spreadsheet-hint = Wählen Sie das Blatt mit den Messwerten oder speichern Sie die Datei erneut als .xlsx.
+error-screenshot = Der Plot konnte nicht aufgenommen werden.
+error-screenshot-hint = Vergrößern Sie das Fenster, sodass der Plot vollständig sichtbar ist, und versuchen Sie es erneut.
+error-acquisition-hint = Prüfen Sie das Kabel, den Anschluss und die Baudrate des Lesegeräts.
+acquisition-connection-error = Verbindung zum Lesegerät fehlgeschlagen: { $error }
+acquisition-disconnected = Die Verbindung zum Lesegerät wurde getrennt.
+acquisition-protocol-error = Das Lesegerät hat eine unlesbare Zeile gesendet: "{ $line }"
+error-archive-hint = Das Archiv ist eine Datei namens archive.sqlite im Datenverzeichnis von Elisa. Prüfen Sie, ob sie beschreibbar ist.
+archive-no-data-directory = Es gibt kein Datenverzeichnis für das Archiv.
+archive-database-error = Das Archiv konnte nicht gelesen oder geschrieben werden: { $error }
+archive-cant-deserialize = Die archivierte Platte konnte nicht geöffnet werden, sie wurde eventuell mit einer neueren Version von Elisa archiviert.
+error-manifest-hint = Das Manifest braucht eine Kopfzeile mit einer Spalte "Well" oder "Position", oder in jeder Zeile eine Position und eine Bezeichnung, z. B. "A3,Patient 12".
+manifest-empty = Das Manifest ist leer.
+manifest-no-well-column = Das Manifest hat keine Spalte mit Positionen.
+error-temperature-range = Die Platte wurde bei { $temperature } °C gemessen, außerhalb von { $min } bis { $max } °C
+error-temperature-range-hint = Prüfen Sie die Temperatur von Reader und Platte. Den zulässigen Bereich legen Sie in den Einstellungen fest.
+error-watch-folder = Die Ordnerüberwachung wurde beendet: { $error }
+error-watch-folder-hint = Prüfen Sie, ob der Eingangsordner noch existiert und lesbar ist, und starten Sie die Überwachung erneut.
+error-api-server = Der API-Server konnte nicht gestartet werden: { $error }
+error-api-server-hint = Möglicherweise belegt ein anderes Programm den Port. Wählen Sie in den Einstellungen einen anderen.
+error-hook-hint = Prüfen Sie Ordner und Befehl der Aktion nach der Auswertung in den Einstellungen.
+
+# Layout errors
+layout-wrong-schema = Die Datei ist kein Elisa-Plattenlayout (erwartetes Schema "{ $schema }")
+layout-unsupported-version = Layout-Version { $version } wird nicht unterstützt
+layout-wrong-dimensions = Das Layout ist für eine { $width }x{ $height }-Platte
+layout-invalid-well = "{ $well }" ist keine Vertiefung dieser Platte
+layout-duplicate-well = Vertiefung { $well } ist mehrfach aufgeführt
+layout-invalid-group = Vertiefung { $well } hat eine ungültige Gruppe
+layout-conflicting-group = Vertiefung { $well } widerspricht dem Rest ihrer Gruppe
+layout-locked = Die Platte ist zur Prüfung gesperrt
+layout-invalid-token = "{ $token }" ist keine Probe, verwenden Sie S1, U1, B, C oder -
+
+# Sample types
+sample-unused = Unbenutzt
+sample-standard = Standard
+sample-control = Kontrolle
+sample-unknown = Probe
+sample-blank = Leerwert
+
+# Measurements
+measurements = Messwerte
+open = Öffnen
+excel-spreadsheet = Excel-Tabelle
+plate-reader-export = Reader-Export
+edit-manually = oder Werte in das Raster eingeben:
+reader-export-info = { $format }-Export
+invalid-wells = Keine Zahl: { $wells }
+wells-outside-linear-range = { $count } Wells außerhalb des linearen Bereichs: { $wells }
+wells-outside-linear-range-hover = Mit den Ausschlussregeln ausschließen oder die Reader-Einstellungen prüfen
+invalid-well-value = "{ $value }" ist keine Zahl
+
+# Sample menu
+sample-menu = Probenmenü
+selected-sample = Ausgewählte Probe { $number }
+sample-type = Probentyp
+measurement = Messwert
+group = Gruppe
+label = Bezeichnung
+dilution = Verdünnung
+spike = Aufstockung
+spike-hover = Bekannte zugesetzte Konzentration, für die Berechnung der Wiederfindung
+group-color = Farbe
+group-color-hover = Eigene Farbe der Gruppe auf der Platte, im Diagramm und im Bericht
+groups = Gruppen
+groups-hover = Standard- und Probengruppen umbenennen, umsortieren, zusammenführen und löschen
+group-wells = { $count ->
+    [one] 1 Well
+   *[other] { $count } Wells
+}
+move-up = Nach oben
+move-down = Nach unten
+merge-into = Zusammenführen mit
+merge-into-hover = Verschiebt die Wells in eine andere Gruppe und entfernt diese
+delete = Löschen
+delete-group-hover = Entfernt die Gruppe, ihre Wells werden unbenutzt
+add-group = Gruppe hinzufügen
+detect-replicates = Replikate erkennen
+detect-replicates-hover = Findet Replikate in benachbarten Wells und nummeriert die Gruppen in Plattenreihenfolge
+replicates-column-pairs = Benachbarte Spaltenpaare (A1 und A2)
+replicates-row-pairs = Benachbarte Zeilenpaare (A1 und B1)
+replicates-detected = Erkannt: { $pattern }
+replicates-none = Kein Muster passt, jeder Standard und jede Probe braucht einen Nachbarn desselben Typs
+replicates-pattern-mismatch = Bei diesem Muster bleiben Standards oder Proben ohne Partner
+replicates-preview = Gruppen nach dem Übernehmen
+replicates-difference = Mittlere Abweichung innerhalb der Paare: { $difference } %
+apply-replicates = Gruppen übernehmen
+excluded = Ausgeschlossen
+excluded-hover = Dieses Well bei der Anpassung und den Replikat-VKs nicht berücksichtigen
+not-available = k. A.
+select-sample = Bitte wählen Sie eine Probe auf der Mikrotiterplatte aus.
+swap-with = Tauschen mit
+swap-values = Werte
+swap-values-hover = Danach das andere Well anklicken. Messwerte und Ausschlüsse tauschen die Plätze, das Layout bleibt, z. B. für Proben, die in das Well der anderen pipettiert wurden.
+swap-assignments = Layout
+swap-assignments-hover = Danach das andere Well anklicken. Probentyp und Gruppe tauschen die Plätze, die Messwerte bleiben, z. B. für ein verkehrt eingegebenes Layout.
+swap-pick-second = Well anklicken, das mit { $well } getauscht wird ({ $mode })
+plate-row = Zeile { $row }
+plate-column = Spalte { $column }
+selected-line = { $line }: { $count } Wells
+set-type = Typ setzen
+clear-line = Leeren
+clear-line-hover = Macht die Wells unbenutzt, ihre Messwerte bleiben
+sample-metadata = Probenmetadaten
+sample-metadata-hover = Proband, Zeitpunkt, Matrix und Entnahmedatum der Proben, für den CSV-Export und den Bericht
+paste-layout = Layout einfügen
+paste-layout-hover = Den Plattenplan einer Kit-Beilage als Raster eingeben oder einfügen
+paste-layout-help = Eine Zeile pro Plattenreihe: S1 Standard 1, U1 Probe 1, B Leerwert, C Kontrolle, - unbenutzt
+apply-layout = Layout übernehmen
+apply-layout-hover = Probentypen und Gruppen der Platte ersetzen, die Werte bleiben
+subject-id = Proband-ID
+timepoint = Zeitpunkt
+matrix = Matrix
+collection-date = Entnahmedatum
+corrected-concentration = Korrigierte Konzentration
+
+# Standards concentrations
+standards-concentrations = Standardkonzentrationen
+anchor = Anker
+anchor-hover = Ankerpunkt außerhalb des Quantifizierungsbereichs, er stützt den Fit, wird aber nicht am Wiederfindungsbereich gemessen und erweitert den berichtbaren Bereich nicht
+concentrations = Konzentrationen
+standard-number = Standard { $number }
+unknown-number = Probe { $number }
+
+# Run notes
+run-notes = Laufnotizen
+name = Name
+kit-lot = Kit-Charge
+description = Beschreibung
+calculate = Berechnen
+options = Optionen
+
+# Fit options
+fit-options = Anpassungsoptionen
+blank-correction = Leerwertkorrektur
+blank-per-row-note = Zeilen oder Spalten ohne Leerwert verwenden den Leerwert der Platte.
+blank-none = Keine Leerwertkorrektur
+blank-plate = Leerwert der Platte abziehen
+blank-per-row = Leerwerte pro Zeile abziehen
+blank-per-column = Leerwerte pro Spalte abziehen
+blank-lower-asymptote = Leerwert als untere Asymptote
+path-length = Weglänge
+path-length-none = Keine Weglängenkorrektur
+path-length-factor = Mit einem Faktor multiplizieren
+path-length-reference = Pro Well aus einer Wasserpeak-Messung
+path-length-factor-value = Faktor
+path-length-factor-hover = 1 cm geteilt durch die Weglänge der Wells, z. B. 1,8 für 0,56 cm Flüssigkeit
+path-length-k-factor = K-Faktor
+path-length-k-factor-hover = OD des Wasserpeaks (977 nm minus 900 nm) pro cm Flüssigkeit, 0,18 bei Raumtemperatur
+path-length-read = Wasserpeak-Messung...
+path-length-reads = { $count ->
+    [one] { $count } Well mit Messung
+   *[other] { $count } Wells mit Messung
+}
+drift-correction = Driftkorrektur
+drift-correction-hover = Korrigiert ein Signal, das während des Lesens der Platte stetig steigt oder fällt, geschätzt aus Wells, die gleich messen sollten
+drift-none = Keine
+drift-blanks = Aus den Leerwerten
+drift-replicates = Aus den Replikaten aller Gruppen
+drift-estimate = Drift von { $total } vom ersten bis zum letzten Well, aus { $wells } Wells
+drift-comparison = Replikat-VK { $cv_before } → { $cv_after }, Leerwert-SD { $sd_before } → { $sd_after }
+drift-not-estimated = Nicht genug Wells einer Gruppe an verschiedenen Positionen, um eine Drift zu schätzen
+control = Kontrolle
+control-initial-guess = Als Startwert für a verwenden
+control-anchor = Ankerpunkt bei Pseudo-Nulldosis
+control-exclude = Kontrolle ausschließen
+automatic-zero-dose = Automatische Nulldosis
+automatic-zero-dose-hover = Ein Hundertstel der niedrigsten Standardkonzentration
+extrapolation = Extrapolation
+extrapolate = Über die Standards hinaus extrapolieren um
+extrapolate-hover = Proben bis zu diesem Abstand jenseits des niedrigsten oder höchsten Standards erhalten eine Konzentration mit Warnung, weiter entfernte Proben gelten als außerhalb des Bereichs
+standard-curve = Standardkurve
+allow-non-monotonic = Umkehrende Kurve erlauben
+allow-non-monotonic-hover = Passt Standards an, deren mittlere Signale nicht stetig mit der Konzentration steigen oder fallen, z. B. durch einen Hook-Effekt. Sonst bricht der Fit ab und nennt die Standards, die dem Verlauf widersprechen.
+cutoff = Cutoff
+classify = Proben klassifizieren
+classify-hover = Positiv, grenzwertig oder negativ gegenüber einem Cutoff, in den Ergebnissen, dem CSV-Export und dem Bericht
+cutoff-concentration = Feste Konzentration der unverdünnten Probe
+cutoff-control-factor = Mittleres Signal der Negativkontrollen × Faktor
+cutoff-value = Cutoff
+cutoff-factor = Faktor
+cutoff-borderline = Grauzone
+cutoff-borderline-hover = Proben so nah am Cutoff sind grenzwertig
+cutoff-no-control = Cutoff: keine Negativkontrollen auf der Platte
+cutoff-threshold-concentration = Cutoff: Konzentration { $threshold }
+cutoff-threshold-signal = Cutoff: Signal { $threshold }
+classification = Klasse
+positive = Positiv
+borderline = Grenzwertig
+negative = Negativ
+
+# Results
+dose = Dosis
+parameters = Parameter
+parameter = Parameter
+value = Wert
+unit-signal = Signal
+unit-signal-squared = Signal²
+unit-concentration = Konz.
+curve-grade = Kurvenbewertung { $grade }
+curve-grade-summary = Kurvenbewertung { $grade }: R² { $r_sq }, { $sy_x ->
+        [none] kein Sy.x mit nur 4 Standardpunkten
+       *[other] Sy.x { $sy_x } % des Signalbereichs
+    }, { $recovered } von { $standards } Standards rückgerechnet innerhalb von { $min }–{ $max } %
+back-calculated-standards = Rückgerechnete Standards
+back-calculated-summary = { $passed } von { $count } Standards rückgerechnet innerhalb von { $min }-{ $max } % ihrer Konzentration
+back-calculated-passed = Im Bereich
+backfit-concentrations = Rückgerechnete Konzentrationen
+raw-corrected = Korrigierter Rohwert
+raw-signal = Rohwert
+backfit = Rückgerechnet
+recovery = Wiederfindung
+recovery-out-of-range = Wiederfindung außerhalb von { $min }-{ $max } %
+above-curve = > { $highest }
+below-lod = < NWG
+above-curve-warning = Signal über dem höchsten Standard ({ $highest }), möglicherweise durch einen Hook-Effekt. Messen Sie die Probe mit einer höheren Verdünnung erneut.
+below-lod-warning = Signal unter dem niedrigsten Standard ({ $lowest }), die Konzentration kann nicht bestimmt werden.
+extrapolated = ~{ $concentration }
+extrapolated-warning = Über die Standards hinaus extrapoliert, die Konzentration ist weniger verlässlich.
+filter-results = Filter, z.B. VK > 15
+filter-results-hover = Teil eines Namens, einer Bezeichnung oder Proband-ID, ein Vergleich von vk, konz, roh oder wiederfindung wie "vk > 15", oder "im bereich", "außerhalb", "extrapoliert", "über kurve", "unter nwg". Mehrere mit ";" trennen.
+filter-count = { $shown } von { $total }
+filter-cv = vk
+filter-concentration = konz
+filter-measurement = roh
+filter-recovery = wiederfindung
+filter-in-range = im bereich
+filter-out-of-range = außerhalb
+filter-extrapolated = extrapoliert
+filter-above-curve = über kurve
+filter-below-lod = unter nwg
+cv-percent = VK %
+sort-hover = Klicken zum Sortieren, erneut zum Umkehren, ein drittes Mal für die Gruppenreihenfolge
+save-as-png = Als PNG speichern
+save-as-pdf = Als PDF speichern
+save-as-csv = Als CSV speichern
+save-as-json = Als JSON speichern
+save-as-json-hover = Die ganze Auswertung für R oder Python: Belegung, Roh- und korrigierte Werte, Anpassung mit Fehlern und jede Probe
+save-plot-data = Plotdaten speichern
+save-plot-data-hover = Die Punkte des Plots als CSV, Standards mit ihrer SD, Proben und die abgetastete Kurve, um sie in Prism oder Excel neu zu zeichnen
+series = Reihe
+sd = SD
+copy-results = Ergebnisse kopieren
+copy-results-hover = Kopiert die Tabelle wie angezeigt, gefiltert und sortiert, zum Einfügen in Excel
+reportable-range-summary = Berichtbarer Bereich { $lowest } – { $upper }: Standards { $lowest } – { $highest } ({ $passed } von { $standards } erfüllen die Kriterien), verdünnt bis 1:{ $dilution }
+reportable-range-summary-undiluted = Berichtbarer Bereich { $lowest } – { $highest } ({ $passed } von { $standards } Standards erfüllen die Kriterien), verdünnte Proben ohne Grenze
+reportable-range-none = Kein Standard erfüllt die Kriterien für Wiederfindung und VK, es gibt keinen berichtbaren Bereich
+reportable-range-warning = Außerhalb des berichtbaren Bereichs von { $lowest } – { $highest }, die Standards darüber hinaus erfüllen die Kriterien für Wiederfindung oder VK nicht
+reportable-range-dilution-warning = Stärker verdünnt als die höchste erlaubte Verdünnung von 1:{ $dilution }
+print-report = Bericht drucken
+plot-replicates = Replikate
+plot-replicates-hover = Zeigt jedes Replikat der Standards, mit der Standardabweichung um die Mittelwerte
+raw-values = Rohwerte
+raw-values-hover = Zeigt die gemessenen statt der blankkorrigierten Werte, mit der um den Blank verschobenen Kurve
+error-bars = Fehlerbalken: { $bars }
+error-bars-none = keine
+error-bars-sd = ± SD
+error-bars-sem = ± SEM
+error-bars-legend = Fehlerbalken: { $bars } der Replikate
+crosshair = Fadenkreuz: { $mode }
+crosshair-off = aus
+crosshair-free = frei
+crosshair-snap = an Kurve einrasten
+crosshair-hover = Zeigt Dosis und Messwert unter dem Zeiger, oder die Kurve bei der Dosis unter dem Zeiger
+crosshair-readout = Dosis: { $dose }
+    Messwert: { $value }
+crosshair-curve = Kurve: { $value }
+x-axis = Dosisachse: { $scale }
+y-axis = Messwertachse: { $scale }
+axis-linear = linear
+axis-logarithmic = logarithmisch
+curve = Kurve
+standards = Standards
+anchor-standards = Ankerstandards
+unknowns = Proben
+unknowns-out-of-range = Außerhalb des Bereichs
+replicates = Replikate
+lod-lines = Niedrigster und höchster Standard
+labels = Beschriftungen
+show-all = Alle zeigen
+hide-all = Alle ausblenden
+reset-view = Ansicht zurücksetzen
+reset-view-hover = Mit dem Mausrad bzw. Strg und Mausrad zoomen, zum Verschieben ziehen. Exporte zeigen dieselbe Ansicht.
+fit-to-standards = An Standards anpassen
+fit-to-standards-hover = Zoomt auf die Standards und hält die Ansicht dort
+
+# PNG export
+export-png = PNG exportieren
+width = Breite
+height = Höhe
+resolution = Auflösung
+export-png-info = Der Plot wird mit { $width }x{ $height } pt gesetzt, Text skaliert mit der Auflösung.
+export = Exportieren
+
+# Report options
+report-options = Berichtsoptionen
+report-header = Kopfzeile
+report-logo = Logo
+choose = Auswählen...
+image = Bild
+remove = Entfernen
+report-sections = Abschnitte
+plate-map = Plattenbelegung
+report-section-plot = Diagramm
+report-section-residuals = Residuen
+report-section-results = Ergebnisse
+report-section-sample-metadata = Probenmetadaten
+report-section-qc = Akzeptanzkriterien
+report-section-excluded-wells = Ausgeschlossene Wells
+excluded-wells = Ausgeschlossene Wells
+report-templates = Vorlagen
+save-template = Vorlage speichern
+apply = Anwenden
+
+# Dilution linearity
+dilution-linearity = Verdünnungslinearität
+linearity-empty =
+    Geben Sie Probengruppen dieselbe Bezeichnung und unterschiedliche
+    Verdünnungen, um sie hier zu vergleichen.
+linearity-info = Verdünnungskorrigierte Konzentrationen verglichen mit der niedrigsten Verdünnung, Toleranz ±{ $tolerance } %
+endpoint-titers = Endpunkttiter
+titers-no-cutoff = Legen Sie zuerst unter "Optionen" einen Cutoff fest, der Titer ist die Verdünnung, bei der das Signal darunter fällt.
+titers-empty = Keine Verdünnungsreihe, geben Sie Proben bei verschiedenen Verdünnungen dieselbe Proband-ID oder Bezeichnung.
+titers-info = Cutoff-Signal { $cutoff }, Proben nach Proband-ID oder Bezeichnung gruppiert
+subject = Proband
+dilutions = Verdünnungen
+endpoint-titer = Endpunkttiter
+interpolated-titer = Interpoliert
+sample = Probe
+corrected = Korrigiert
+difference = Abweichung
+reference = Referenz
+hook-effect = Möglicher Hook-Effekt
+matrix-interference = Mögliche Matrixinterferenz
+
+# Curve history
+record-curve = Kurve erfassen
+record-curve-hover = Diese Standardkurve dem Kurvenverlauf ihrer Kit-Charge hinzufügen
+curve-history = Kurvenverlauf
+reference-curve = Referenzkurve
+reference-curve-hover = Die Kurve eines früheren Laufs oder die Chargenreferenz gestrichelt über den Plot legen
+reference-from-file = Assay laden…
+reference-from-file-hover = Einen gespeicherten Assay anpassen und seine Kurve als Referenz verwenden
+reference-from-history = Aus dem Verlauf
+reference-none = Keine Referenzkurve ausgewählt
+reference-source = { $plate }, Charge { $lot }, { $date }
+reference-parameter = Parameter
+reference-current = Aktuell
+reference-value = Referenz
+all-lots = Alle Chargen
+no-lot = Keine Charge
+history-empty = Noch keine Kurven erfasst. Nutzen Sie nach der Anpassung "{ record-curve }".
+date = Datum
+plate = Platte
+lot = Charge
+run = Lauf
+mean = Mittelwert
+history-metric = Diagramm
+blank-mean = Leerwert-Mittel
+control-mean = Kontroll-Mittel
+max-cv = Höchster VK
+westgard-none = Keine Westgard-Regel ist verletzt.
+westgard-violations = Verletzte Westgard-Regeln nach Lauf: { $runs }
+westgard-hover = Die Grenzen sind Mittelwert und SD der gezeigten Läufe. 1-2s (orange) ist eine Warnung, 1-3s, 2-2s, R-4s, 4-1s und 10x (rot) verwerfen den Lauf.
+
+# Audit trail
+audit-trail = Audit-Trail
+audit-trail-empty = Noch keine Änderungen erfasst.
+audit-time = Zeit
+audit-change = Änderung
+operator = Bearbeiter
+operator-hover = Wird bei jeder Änderung im Audit-Trail vermerkt.
+number-format = Ergebnisse anzeigen mit
+significant-figures = signifikanten Stellen
+decimal-places = Nachkommastellen
+scientific-notation = Wissenschaftliche Schreibweise für sehr kleine und große Werte
+scientific-notation-hover = Unter 0,001 und ab 1 000 000
+number-format-example = Zum Beispiel { $example }
+theme = Design
+theme-light = Hell
+theme-dark = Dunkel
+cv-threshold = Replikat-VK markieren über
+cv-threshold-hover = Wird in den Ergebnissen rot markiert.
+autosave = Automatisch speichern alle
+autosave-hover = Speichert eine Kopie des Assays als autosave.elisa im Datenverzeichnis, 0 schaltet es aus.
+export-folder = Dateien speichern in
+export-folder-system = Zuletzt verwendeter Ordner
+archive-plates = Jede ausgewertete Platte archivieren
+archive-plates-hover = Layout, Messwerte und Ergebnisse jeder Anpassung werden in einer lokalen Datenbank gespeichert und sind später durchsuchbar.
+api-server = Lokale HTTP-API
+api-port = Port
+api-server-hover = Erlaubt einem LIMS oder einem anderen Programm auf diesem Computer, Platten auszuwerten, siehe "API" im README.
+api-server-running = Erreichbar unter { $url }, { $requests } Anfragen beantwortet
+api-bad-request = Die Anfrage konnte nicht gelesen werden: { $error }
+api-not-found = Unter { $path } gibt es nichts.
+api-method-not-allowed = Die Methode wird hier nicht unterstützt.
+api-too-large = Die Anfrage ist zu groß.
+api-invalid-well = { $well } ist kein Well der Platte.
+post-analysis-hook = Aktion nach der Auswertung
+post-analysis-hook-hover = Ergebnisse nach einer Anpassung automatisch exportieren und einen Befehl ausführen
+post-analysis-hook-info = Nach jeder Anpassung werden die Exporte in den Ordner geschrieben, z. B. eine Netzwerkfreigabe, und der Befehl wird ausgeführt, damit die Ergebnisse ohne manuelles Speichern ins Datenmanagement gelangen.
+hook-enabled = Nach jeder Anpassung ausführen
+hook-trigger = Wann
+hook-qc-passed = Die Anpassung besteht die QC
+hook-always = Immer
+hook-folder = Ordner
+hook-temporary-folder = Temporärer Ordner
+hook-exports = Exporte
+hook-results-csv = Ergebnis-CSV
+hook-analysis-json = Analyse-JSON
+hook-report-pdf = PDF-Bericht
+hook-command = Befehl
+hook-command-hover = Wird ohne Shell ausgeführt, sobald die Exporte geschrieben sind. {"{"}csv{"}"}, {"{"}json{"}"}, {"{"}pdf{"}"}, {"{"}folder{"}"} und {"{"}plate{"}"} werden ersetzt, Argumente mit Leerzeichen in Anführungszeichen setzen.
+hook-run-now = Jetzt ausführen
+hook-run-now-hover = Exportiert die aktuelle Platte und führt den Befehl aus, ob sie die QC besteht oder nicht
+hook-running = { $count } Befehle laufen
+hook-done = { $plate }: { $count } Exporte nach { $folder } geschrieben
+hook-skipped = { $plate } hat die QC nicht bestanden, es wurde nichts exportiert.
+hook-write-error = Die Aktion nach der Auswertung konnte einen Export nicht schreiben: { $error }
+hook-unclosed-quote = Im Befehl der Aktion nach der Auswertung fehlt ein schließendes Anführungszeichen.
+hook-start-error = Der Befehl der Aktion nach der Auswertung konnte nicht gestartet werden: { $error }
+hook-failed = { $program } der Aktion nach der Auswertung endete mit Exit-Code { $code }.
+new-plates = Neue Platten
+plate-size = Plattengröße
+plate-size-wells = { $wells } Wells
+new-plates-hover = Gilt für die Platte beim nächsten Start.
+export-csv = Als CSV exportieren
+audit-value-changed = Messwert von { $well } von { $from } auf { $to } geändert
+audit-type-changed = { $well } von { $from } zu { $to } geändert
+audit-group-changed = Gruppe von { $well } von { $from } auf { $to } geändert
+audit-concentration-changed = Konzentration von Standard { $group } von { $from } auf { $to } geändert
+audit-fit-run = Kurve angepasst: a = { $a }, b = { $b }, c = { $c }, d = { $d }, R² = { $r_sq }
+audit-fit-adjusted = Kurve von Hand angepasst: a = { $a }, b = { $b }, c = { $c }, d = { $d }, R² = { $r_sq }
+audit-well-excluded = { $well } von der Anpassung ausgeschlossen
+audit-well-excluded-reason = { $well } von der Anpassung ausgeschlossen: { $reason }
+audit-well-included = { $well } wieder in die Anpassung aufgenommen
+
+# Signatures
+signatures = Unterschriften
+analyzed-by = Ausgewertet von
+reviewed-by = Geprüft von
+role = Funktion
+signature = Unterschrift
+signatures-info = Wird mit dem Assay gespeichert und im PDF-Bericht mit Unterschrifts- und Datumszeilen gedruckt.
+
+# Review lock
+lock = Sperren
+unlock = Entsperren
+cancel = Abbrechen
+read-only = Schreibgeschützt
+unlock-confirmation = Dieser Assay ist zur Prüfung gesperrt. Entsperren, um Platte und Anpassung wieder ändern zu können? Das Entsperren wird im Audit-Trail vermerkt.
+duplicate = Duplizieren
+duplicate-confirmation = Die Werte dieser Platte werden nicht in das Duplikat übernommen. Speichern Sie die Platte vorher, wenn Sie sie noch brauchen.
+duplicate-name = { $name } (Kopie)
+audit-locked = Assay zur Prüfung gesperrt
+audit-unlocked = Assay zur Bearbeitung entsperrt
+
+# Log console
+log = Meldungen
+log-problems = Meldungen ({ $count })
+log-empty = Noch keine Meldungen.
+clear = Leeren
+
+# Fit diagnostics
+fit-diagnostics = Anpassungsdiagnose
+fit-not-converged = Die Anpassung ist nicht konvergiert, die Parameter stammen aus der letzten Iteration und sind möglicherweise unzuverlässig.
+plate-changed = Die Platte wurde seit dieser Anpassung geändert.
+fit-curve = Kurve anpassen
+details = Details
+iterations = Iterationen
+iterations-of = { $iterations } von { $max }
+gradient-norm = Norm des letzten Gradienten
+converged = Konvergiert
+winning-start = Start
+winning-start-hover = Der Startwert, von dem die Parameter stammen. Start 1 ist die übliche Schätzung aus den Standards, die anderen sind davon ausgehend verschoben, und der mit dem kleinsten Verlust wird behalten.
+start-of = { $start } von { $starts }
+fit-duration = Dauer
+fit-duration-hover = Wie lange der Optimierer für alle Starts gebraucht hat, einschließlich der Standardfehler
+yes = Ja
+no = Nein
+standard-errors = Standardfehler
+standard-errors-unavailable = Standardfehler benötigen mehr Standards als angepasste Parameter.
+estimate = Schätzwert
+standard-error = Standardfehler
+confidence-interval = 95 %-KI
+p-value = p
+parameter-fixed = fest
+log-c-hover = log₁₀ von c, dem Wendepunkt. Die Anpassung arbeitet mit log c, sein Intervall ist auf der log-Skala symmetrisch.
+degrees-of-freedom = { $df } Freiheitsgrade, p des t-Tests gegen 0
+correlation-matrix = Korrelationsmatrix
+correlation-note = Korrelationen nahe ±1 bedeuten, dass die Standards die beiden Parameter nicht unterscheiden können.
+# Bootstrap
+bootstrap = Bootstrap
+bootstrap-hover = Konfidenzintervalle aus dem erneuten Anpassen der Kurve an neu gezogene Replikate der Standards
+bootstrap-no-replicates = Der Bootstrap zieht die Replikate der Standards neu, keiner davon hat mehr als eines.
+resamples = Stichproben
+start-bootstrap = Starten
+bootstrap-progress = { $done } von { $total } Anpassungen
+bootstrap-summary = Perzentile aus { $resamples } Anpassungen, { $failed } weitere sind nicht konvergiert.
+# Simulation
+simulation = Simulieren
+simulation-hover = Eine Platte aus einer gewählten Kurve und Rauschen, um Auswerteeinstellungen zu erproben oder für die Lehre. Sie ersetzt den aktuellen Assay.
+simulation-curve = Kurve
+simulation-blank = Blank-Signal
+simulation-noise = Rauschen
+noise-constant = SD
+noise-proportional = VK %
+simulation-layout = Belegung
+simulation-top-standard = Höchster Standard
+simulation-dilution-factor = Verdünnungsfaktor
+simulation-standards = Standards
+simulation-unknowns = Proben
+simulation-replicates = Replikate
+simulation-wells = Belegt { $wells } von { $total } Wells, der Spike jeder Probe ist ihre wahre Konzentration.
+simulation-plate-too-small = { $wells } Wells passen nicht auf die Platte.
+simulate-plate = Platte simulieren
+simulated-plate = Simulierte Platte
+command-palette = Befehle
+command-palette-hint = Befehl eingeben…
+no-matching-commands = Keine passenden Befehle
+open-measurements = Messwerte öffnen
+show-plate = Platte anzeigen
+show-results = Ergebnisse anzeigen
+keyboard-shortcuts = Tastenkürzel
+self-test = Selbsttest
+self-test-info = Vergleicht den Gradienten der Anpassung mit finiten Differenzen und passt Standards aus bekannten Parametern und einen veröffentlichten Datensatz an, deren Parameter die Anpassung wiederfinden muss.
+self-test-dataset = Datensatz
+self-test-gradient = Gradient
+self-test-fit = Anpassung
+self-test-parameters = Angepasste a, b, c, d
+self-test-gradient-hover = Größte relative Abweichung von den finiten Differenzen: { $error }
+self-test-expected = Bekannte Parameter: { $parameters }
+self-test-run-again = Erneut ausführen
+self-test-sandwich = Sandwich, steigend
+self-test-competitive = Kompetitiv, fallend
+self-test-steep = Steile Steigung
+self-test-picograms = pg/mL, Gewichtung 1/y²
+self-test-rounded = Auf 3 Nachkommastellen gerundet, Gewichtung 1/y
+self-test-dnase = DNase-ELISA, veröffentlicht in R
+shortcut-save = Assay speichern
+shortcut-open = Assay laden
+shortcut-export-csv = Ergebnisse als CSV speichern
+shortcut-refit = Kurve neu anpassen
+shortcut-sample-type = Ausgewählte Wells: { $type }
+sample-type-keys-hover = Die Tasten 1–5 setzen den Typ der ausgewählten Wells
+press-keys = Tasten drücken…
+change-shortcut-hover = Klicken Sie und drücken Sie die neuen Tasten, Rücktaste entfernt das Kürzel, Escape behält es
+shortcuts-hover = Strg ist ⌘ unter macOS. Die Zifferntasten wirken auf der Platte, solange kein Textfeld ausgewählt ist.
+reset-shortcuts = Kürzel zurücksetzen
+replicate-mean = Mittelwert von { $count }
+status-wells = { $standards } Standards, { $unknowns } Proben, { $blanks } Leerwerte, { $controls } Kontrollen
+status-wells-hover = Platte anzeigen
+status-linear-range = { $count } außerhalb des linearen Bereichs
+status-linear-range-hover = Wells außerhalb des linearen Bereichs des Readers, Ausschlussregeln öffnen
+status-not-monotonic = Standards nicht in Reihenfolge
+status-not-monotonic-hover = Die mittleren Signale der Standards folgen nicht ihren Konzentrationen, die Anpassung würde abbrechen. Wahrscheinlich vertauschte Standards anzeigen
+status-not-fitted = Nicht angepasst
+status-fit-current = Anpassung aktuell
+status-fit-outdated = Platte seit der Anpassung geändert
+status-fit-hover = Kurve neu anpassen
+status-r-squared = R² { $value }
+status-r-squared-hover = Anpassungsdiagnose anzeigen
+status-worst-cv = Höchster VK { $cv } % ({ $name })
+status-cv-hover = Ergebnisse nach VK sortiert anzeigen
+status-qc-passed = QC bestanden
+status-qc-failed = { $count ->
+    [one] 1 QC-Prüfung nicht bestanden
+   *[other] { $count } QC-Prüfungen nicht bestanden
+}
+status-qc-hover = QC-Zusammenfassung anzeigen
+live-acquisition = Live-Erfassung
+read-live = Live lesen
+read-live-hover = Messwerte von einem Plattenlesegerät direkt in die Platte übernehmen
+reader = Lesegerät
+mock-reader = Simuliertes Lesegerät
+serial-reader = Seriell / USB
+serial-port = Anschluss
+baud-rate = Baudrate
+reading-order = Lesereihenfolge
+reading-order-rows = Zeilenweise (A1, A2, …)
+reading-order-columns = Spaltenweise (A1, B1, …)
+write-live = Während des Lesens in die Platte schreiben
+write-live-hover = Messwerte ersetzen die Werte ihrer Wells, sobald sie ankommen
+acquisition-protocol-hint = Ein Messwert pro Zeile, "A1 0.123" oder nur "0.123" für das nächste Well in Lesereihenfolge.
+connect = Verbinden
+disconnect = Trennen
+write-into-plate = In Platte schreiben
+acquisition-readings = { $count } Messwerte von { $reader }
+acquisition-unmapped = { $count } Messwerte liegen nicht auf der Platte und wurden ausgelassen
+watch-folder = Ordner überwachen
+watch-folder-hover = Reader-Exporte automatisch auswerten, sobald sie in einem Ordner erscheinen
+watch-folder-info = Neue Reader-Exporte im Eingangsordner werden in eine Kopie der aktuellen Platte eingelesen, ausgewertet und als Ergebnis-CSV und PDF-Bericht im Ausgabeordner gespeichert. Dateien, die schon im Ordner liegen, bleiben unberührt.
+watch-input = Eingangsordner
+watch-output = Ausgabeordner
+watch-pattern = Dateinamen
+watch-pattern-hover = Muster getrennt durch ";", "*" steht für beliebige Zeichen und "?" für eines, z. B. "Platte_*.txt"
+watch-start = Überwachung starten
+watch-start-hover = Die aktuelle Platte ist die Vorlage für Belegung und Fit-Einstellungen jeder eingelesenen Platte
+watch-stop = Überwachung beenden
+watch-clear = Erledigte entfernen
+watch-status = { $waiting } von { $count } Dateien warten
+watch-found = Gefunden
+watch-file = Datei
+watch-state = Status
+watch-waiting = Wartet
+watch-done = Erledigt
+watch-failed = Fehlgeschlagen
+watch-no-template = Keine Vorlagenplatte
+watch-no-output = Kein Ausgabeordner
+paste-values = Werte einfügen
+paste-plate = Platte, { $rows } Zeilen × { $columns } Spalten
+paste-transposed = Transponiert, { $rows } Zeilen × { $columns } Spalten
+paste-well-list = Liste von Wells und Werten
+paste-detected = Erkanntes Layout: { $layout }
+paste-not-detected = Die eingefügten Werte passen in keinem Layout zur Platte.
+paste-layout-mismatch = Die eingefügten Werte passen in diesem Layout nicht zur Platte.
+paste-wells = { $count } Wells erhalten einen neuen Wert, die grauen behalten ihren.
+apply-paste = Werte übernehmen
+transform-plate = Platte drehen
+transform-plate-hover = Eine in falscher Ausrichtung gelesene Platte drehen, Layout und Werte gleichermaßen
+transform-transpose = Transponieren (Zeilen ↔ Spalten)
+transform-rotate = Um 180° drehen
+transform-mirror-columns = Links ↔ rechts spiegeln
+transform-mirror-rows = Oben ↔ unten spiegeln
+analyzed-wells = Ausgewertete Wells
+analyze-region = Nur einen Bereich der Platte
+analyze-region-hover = Einen Teil der Platte für sich auswerten, z. B. Spalten 1 bis 6, wenn die Platte zwei Assays enthält
+region-rows = Zeilen
+region-columns = Spalten
+region-wells = Die Wells { $region } werden ausgewertet, die übrigen ausgelassen.
+outside-region = außerhalb des ausgewerteten Bereichs
+compare-plates = Platten vergleichen
+compare-plates-hover = Unterschiede der Werte und Konzentrationen zu einer anderen Platte, z. B. einer Duplikatplatte oder denselben Proben von einem anderen Bediener
+compare-from-file = Andere Platte...
+compare-from-file-hover = Mit einem gespeicherten Assay derselben Plattengröße vergleichen. Nach dem Duplizieren wird mit der Ausgangsplatte verglichen.
+compare-none = Keine Platte zum Vergleichen. Laden Sie einen gespeicherten Assay oder duplizieren Sie diese Platte und messen Sie die Kopie.
+compare-source = Aktuelle Platte im Vergleich mit { $plate }
+unnamed-plate = unbenannte Platte
+compare-wrong-dimensions = Die andere Platte hat { $width } × { $height } Wells, die Platten können nicht Well für Well verglichen werden.
+compare-wells = Unterschied pro Well, % der anderen Platte
+compare-well-hover = { $well }: { $current } hier, { $other } auf der anderen Platte, Δ { $difference }
+compare-mean-difference = Mittlere absolute Abweichung { $percent } %
+compare-concentrations = Konzentrationen
+compare-no-concentrations = Keine der Platten hat Konzentrationen innerhalb ihrer Standards.
+compare-current = Diese Platte
+compare-other = Andere Platte
+archive = Archiv
+archive-hover = Früher ausgewertete Platten nach Datum, Plattenname oder Probenbezeichnung durchsuchen
+archive-off = Platten werden nicht archiviert, dies lässt sich in den Einstellungen einschalten.
+archive-from = Von
+archive-to = Bis
+archive-plate = Platte
+archive-label = Probenbezeichnung
+search = Suchen
+archive-none = Keine archivierten Platten gefunden.
+archive-found = { $count } Platten gefunden, neueste zuerst
+archive-open-hover = Ersetzt den aktuellen Assay durch die archivierte Platte
+archive-export-results = Ergebnisse exportieren...
+import-manifest = Manifest importieren...
+import-manifest-hover = Bezeichnungen und Probanden-IDs der Unbekannten aus einer CSV-Datei, die Positionen Proben zuordnet
+manifest = Probenmanifest
+manifest-wells = { $wells } Positionen geben { $groups } unbekannten Gruppen ihre Bezeichnung:
+manifest-replaces = Ersetzt { $label }
+manifest-issues = { $count ->
+    [one] Das Manifest passt an einer Stelle nicht zur Platte:
+   *[other] Das Manifest passt an { $count } Stellen nicht zur Platte:
+}
+manifest-invalid-well = Zeile { $line }: "{ $well }" ist keine Position dieser Platte
+manifest-duplicate-well = { $well } steht mehrmals im Manifest, die erste Zeile wird übernommen
+manifest-not-unknown = { $well } ist auf dieser Platte keine Unbekannte
+manifest-conflicting = Die Positionen von { $group } haben verschiedene Bezeichnungen, die erste wird übernommen
+manifest-missing = { $group } hat keine Position im Manifest
+apply-manifest = Bezeichnungen übernehmen
+barcode = Barcode
+barcode-hint = Scannen oder eingeben
+report-section-barcode = Barcode der Platte
+name-from-barcode = Platten nach ihrem Barcode benennen
+name-from-barcode-hover = Ein gescannter oder eingegebener Barcode wird zum Namen der Platte.
+run-metadata = Laufdetails
+run-metadata-hover = Bearbeiter, Gerät, Wellenlänge, Messzeit und Kit dieser Platte, im Kopf des Berichts gedruckt
+instrument = Gerät
+wavelength = Wellenlänge
+read-time = Messzeit
+kit-expiry = Kit-Verfallsdatum
+reference-read = Referenzmessung...
+reference-read-hover = Zieht eine Messung derselben Platte bei der Referenzwellenlänge, z. B. 570 nm, von den Werten ab
+reference-sheet = Referenzblatt
+reference-sheet-none = Keines
+wavelength-correction = Korrigiert: { $measurement } − { $reference }
+wavelength-correction-reference = Korrigiert mit einer Referenzmessung bei { $reference }
+wavelength-correction-unknown = Korrigiert mit einer Referenzmessung
+temperature = Temperatur
+temperature-out-of-range = Außerhalb des zulässigen Temperaturbereichs aus den Einstellungen
+temperature-range = Zulässige Messtemperatur
+temperature-range-hover = Platten, die außerhalb dieses Bereichs gemessen wurden, werden beim Import des Reader-Exports markiert.
+linear-range = Linearer Bereich des Readers
+linear-range-hover = Wells, die außerhalb davon gemessen wurden, werden markiert, gesättigte Wells messen zu niedrig und verfälschen die Anpassung. Verglichen wird die OD wie gemessen, vor Referenzwellenlänge und Weglängenkorrektur.
+linear-range-above = Gemessen { $read } OD, über dem linearen Bereich bis { $high }
+linear-range-below = Gemessen { $read } OD, unter dem linearen Bereich ab { $low }
+read-settings = Messeinstellungen
+
+# Manual fit
+adjust-curve = Kurve anpassen
+adjust-curve-hover = Die vier Parameter von Hand setzen, z. B. wenn die automatische Anpassung fehlschlägt
+adjust-a = Asymptote bei Dosis null
+adjust-b = Steigung am Wendepunkt
+adjust-c = Wendepunkt
+adjust-d = Asymptote bei unendlicher Dosis
+reset-fit = Automatische Anpassung wiederherstellen
+fit-adjusted = Von Hand angepasst
+
+# Protocols
+protocol = Protokoll
+no-protocol = Kein Protokoll
+new-protocol = Neues Protokoll
+protocol-info = Ein Protokoll legt die Standardkonzentrationen und die Gewichtung fest und prüft jede Anpassung gegen seine Akzeptanzkriterien.
+protocol-concentrations-invalid = Trennen Sie die Konzentrationen mit Semikolons.
+protocol-apply = Auf Platte anwenden
+protocol-apply-hover = Setzt die Standardkonzentrationen und die Gewichtung der Platte
+protocol-save = In Bibliothek speichern
+protocol-delete = Aus Bibliothek entfernen
+derived-columns = Abgeleitete Spalten
+derived-columns-info = Zusätzliche Ergebnisspalten, für jede Probe berechnet, z. B. pro_ml = corrected / 0.05. Verfügbar: { $variables } und die Spalten darüber. Zahlen mit Dezimalpunkt.
+derived-column-invalid = Spalte "{ $name }": { $error }
+add-derived-column = Spalte hinzufügen
+expression-empty = Der Ausdruck ist leer
+expression-unexpected-end = Der Ausdruck endet zu früh
+expression-unexpected = Unerwartetes "{ $text }"
+expression-unknown-name = "{ $name }" ist weder ein Wert noch eine Spalte darüber
+expression-unknown-function = "{ $name }" ist keine Funktion, verwenden Sie abs, sqrt, ln, log, exp, min oder max
+expression-wrong-arguments = { $name } erwartet { $count ->
+    [one] ein Argument
+   *[other] { $count } Argumente
+}
+expression-invalid-column-name = "{ $name }" kann kein Spaltenname sein, verwenden Sie Buchstaben, Ziffern und _ und keinen bereits vergebenen Namen
+weighting = Gewichtung
+initial-guesses = Startwerte
+initial-guesses-hover = Passt erneut von Schätzungen an, die von der üblichen verschoben sind, und behält den Fit mit dem kleinsten Verlust, falls der Optimierer in einem lokalen Minimum hängen bleibt. Die Verschiebungen sind fest initialisiert, dieselbe Platte ergibt also immer denselben Fit. Jeder Start dauert so lange wie ein Fit.
+starts = Starts
+weighting-none = Keine
+weighting-1-y = 1/Y
+weighting-1-y2 = 1/Y²
+qc-cv-limit = Max. Replikat-VK
+qc-r-squared-limit = Min. R²
+qc-recovery-range = Wiederfindung der Standards
+qc-min-curve-grade = Min. Kurvennote
+qc-min-curve-grade-hover = Die Note aus R², Sy.x und Wiederfindung der Standards, die mit der Anpassung angezeigt wird, A ist die beste
+qc-control-recovery-range = Wiederfindung der Kontrollen
+qc-control-recovery-range-hover = Wiederfindung der Proben mit bekannter gespikter Konzentration, z. B. QC-Kontrollen
+qc-max-blank = Max. Leerwertsignal
+max-dilution = Höchste Verdünnung
+max-dilution-hover = Proben dürfen bis zu dieser Verdünnung verdünnt werden, um auf die Kurve zu kommen, das erweitert den berichtbaren Bereich
+qc-summary = Akzeptanzkriterien - { $protocol }
+qc-pass = BESTANDEN
+qc-fail = NICHT BESTANDEN
+qc-concentrations = Standardkonzentrationen
+qc-cv = Replikat-VK
+qc-r-squared = R²
+qc-recovery = Wiederfindung der Standards
+qc-concentrations-match = Alle { $count } Konzentrationen wie im Protokoll
+qc-concentration-mismatch = Standard { $number } sollte { $expected } sein
+qc-cv-detail = Höchster { $cv } % ({ $name }), Grenze { $limit } %
+qc-no-replicates = Keine Replikate
+qc-r-squared-detail = { $r_sq }, mindestens { $limit }
+qc-recovery-detail = { $outside } von { $count } außerhalb von { $min }-{ $max } %
+qc-curve-grade = Kurvennote
+qc-curve-grade-detail = { $grade }, mindestens { $minimum }
+qc-control-recovery = Wiederfindung der Kontrollen
+qc-no-spiked-controls = Keine gespikten Kontrollen
+qc-blank-level = Leerwert
+qc-blank-level-detail = { $blank }, höchstens { $limit }
+qc-no-blanks = Keine Leerwerte
+qc-banner-passed = Alle { $count } Akzeptanzkriterien erfüllt
+qc-banner-failed = { $failed } von { $count } Akzeptanzkriterien nicht erfüllt:
+qc-banner-outdated = Die Platte wurde seit der Anpassung geändert, für ein aktuelles Ergebnis neu anpassen.
+
+# Well exclusion
+exclusions = Ausschlüsse
+exclusions-hover = Abweichende und gesättigte Wells nach Regeln ausschließen
+exclusion-rule-cv = Replikat-Ausreißer über VK
+exclusion-rule-cv-hover = Schließt in Gruppen mit mindestens 3 Replikaten das vom Mittelwert am weitesten entfernte Replikat aus
+exclusion-rule-linear-range = Außerhalb des linearen Bereichs
+exclusion-rule-linear-range-hover = Schließt Wells mit einer Messung außerhalb des linearen Bereichs des Readers aus, derselbe Bereich wie in den Einstellungen
+exclusion-replicate-outlier = Ausreißer, Replikat-VK { $cv } %
+exclusion-saturated = Gesättigt bei { $value }
+exclusion-below-linear-range = Unter dem linearen Bereich bei { $value }
+exclusion-reasons = Gründe für Ausschlüsse von Hand
+exclusion-reasons-hover = Ein im Probenmenü ausgeschlossenes Well wird erst ausgeschlossen, wenn einer dieser Gründe gewählt ist, er erscheint im Audit-Trail und im Bericht. Ohne Gründe werden Wells ohne Grund ausgeschlossen.
+add-exclusion-reason = Grund hinzufügen
+exclusion-reason-bubble = Luftblase
+exclusion-reason-contamination = Kontamination
+exclusion-reason-pipetting-error = Pipettierfehler
+exclusion-reason-outlier = Ausreißer
+exclusion-reason-prompt = Warum wird { $well } ({ $sample }) ausgeschlossen?
+exclusion-no-reason = Kein Grund angegeben
+exclusions-none = Die Regeln schließen keine Wells aus.
+exclusions-proposed = Vorgeschlagene Ausschlüsse
+well = Well
+reason = Grund
+apply-exclusions = Ausschließen und neu anpassen
+
+# PDF report
+report-title = Assay-Auswertung - 4PL
+report-name = Name: { $name }
+concentration = Konzentration
+recovery-percent = Wiederfindung %
+backfit-concentration = Rückgerechnete Konz.
+report-recovery-note = * Aufstockungs-Wiederfindung außerhalb von { $min }-{ $max } %
+report-standards-recovery-note = * Rückgerechneter Standard außerhalb von { $min }-{ $max } %
+report-extrapolated-note = ~: über die Standards hinaus extrapoliert
+report-range-note = > und <: Signal außerhalb der Standards. Proben über dem höchsten Standard können einen Hook-Effekt zeigen und sollten mit einer höheren Verdünnung erneut gemessen werden.
+report-fitted = Angepasst
+report-residual = Residuum
+report-link = Link zu Eliavaux' GitHub
+report-page = Seite { $page } von { $pages }
+report-generated = Erstellt { $date } mit Elisa { $version }
+report-source-hash = SHA-256 der Projektdatei: { $hash }
+report-reader-file-hash = SHA-256 der Quelldatei: { $hash }
+report-source-unchanged = Fit-Eingaben unverändert
+report-source-modified = Fit-Eingaben seitdem geändert
@@ -0,0 +1,886 @@
+# Top bar
+save = Save
+load = Load
+export-layout = Export Layout
+import-layout = Import Layout
+duplicate-plate = Duplicate Plate
+duplicate-plate-hover = Start a new plate with the same sample types, groups and concentrations, without the values
+copy-layout = Copy Layout to Plate…
+copy-layout-hover = Give a saved assay the sample types, groups and concentrations of this plate, its values stay
+back = Back
+settings = Settings
+ok = Ok
+dismiss = Dismiss
+
+# Settings
+language = Language
+
+# File errors
+error-cant-read-file = Could not read contents of the file
+error-cant-write-file = Could not write contents to the file
+error-cant-deserialize = Could not load microplate from contents
+error-invalid-layout = Could not apply layout: { $error }
+error-cant-print = Could not send the report to the printer.
+error-try-different-file = Please try a different file.
+error-cant-print-hint = Check that a printer is set up, or save the report as PDF instead.
+
+# Value errors
+error-unassigned-concentration = Microplate has a standard sample without a concentration.
+error-unassigned-value = Microplate has a sample without a value.
+error-invalid-concentration = Microplate has a standard sample with an invalid concentration.
+error-invalid-value = Microplate has a sample an invalid value.
+error-not-enough-standards = Microplate does not have enough standards for four parameter analysis.
+error-blank-too-big = The blank is greater than one of the standard measurements
+error-control-too-big = The control is greater than one of the standard measurements
+error-missing-path-length = A well has no path-length read
+error-concentration-hint = Enter a positive concentration for every standard group.
+error-value-hint = Fill in or exclude the highlighted wells, values must be numbers.
+error-not-enough-standards-hint = Assign at least four standards with different concentrations.
+error-too-big-hint = Check that the blank and control wells are assigned correctly.
+error-missing-path-length-hint = Load a water peak read for every well in the fit options, or correct with a fixed factor.
+
+# Regression errors
+error-invalid-standards = The standards can't be fitted, they need positive concentrations and a signal that changes with them.
+error-invalid-standards-hint = Use at least four different concentrations above zero, and check the standard wells for swapped or empty values.
+error-did-not-converge = The fit diverged and did not find a curve.
+error-did-not-converge-hint = Exclude outlying standards, or try a different weighting or blank correction in the options.
+error-singular-jacobian = The standards don't determine all four parameters of the curve.
+error-singular-jacobian-hint = Add standards on both plateaus and in the steep part of the curve, or fix the lower asymptote to the blank.
+error-degenerate-curve = The fitted curve is flat, concentrations can't be read from it.
+error-degenerate-curve-hint = Check that the standards cover a range where the signal changes, and that no standard group is assigned to the wrong wells.
+error-not-monotonic = The mean signals of the standards don't follow their concentrations, a curve fitted to them would be meaningless:
+error-not-monotonic-hint = Check the standard wells for swapped positions or concentrations and exclude failed standards. For a curve that really turns, e.g. from a hook effect at the highest standards, allow it in the fit options.
+swap-suggestion = Standards Out of Order
+swap-suggestion-monotonic = The standards follow their concentrations again.
+swap-suggestion-none = No swap of two standards puts them in order. Exclude failed standards, or allow a curve that turns in the fit options.
+swap-standards = Swap { $first } and { $second } and Fit
+swap-standards-hover = The wells of each standard are assigned to the other one, the reads stay where they are. The change is recorded in the audit trail.
+monotonic-reads-lower = { $standard } reads lower than { $previous }
+monotonic-reads-higher = { $standard } reads higher than { $previous }
+monotonic-swap = { $first } (wells { $first_wells }) and { $second } (wells { $second_wells }) were likely swapped, the curve is monotonic without it.
+
+# Import errors
+error-reader-export = Could not read the plate reader export: { $error }
+error-reader-export-hint = Check that the file was exported from a supported reader and was not edited.
+error-spreadsheet = Could not read the spreadsheet: { $error }
+error-empty-spreadsheet = The spreadsheet does not contain any sheets.
+error-spreadsheet-hint = Select the sheet with the plate values, or save the file again as .xlsx.
+error-screenshot = Could not capture the plot.
+error-screenshot-hint = Resize the window so the plot is fully visible and try again.
+error-acquisition-hint = Check the cable, the port and the baud rate of the reader.
+acquisition-connection-error = Could not connect to the reader: { $error }
+acquisition-disconnected = The reader was disconnected.
+acquisition-protocol-error = The reader sent a line that could not be read: "{ $line }"
+error-archive-hint = The archive is a file named archive.sqlite in the data directory of Elisa. Check that it can be written to.
+archive-no-data-directory = There is no data directory to keep the archive in.
+archive-database-error = The archive could not be read or written: { $error }
+archive-cant-deserialize = The archived plate could not be opened, it may have been archived by a newer version of Elisa.
+error-manifest-hint = The manifest needs a header line with a column named "Well", or a well and a label on each line, e.g. "A3,Patient 12".
+manifest-empty = The manifest is empty.
+manifest-no-well-column = The manifest has no column of wells.
+error-temperature-range = The plate was read at { $temperature } °C, outside of { $min } to { $max } °C
+error-temperature-range-hint = Check the temperature of the reader and the plate. The acceptable range is set in the settings.
+error-watch-folder = Watching the folder stopped: { $error }
+error-watch-folder-hint = Check that the input folder still exists and can be read, then start watching again.
+error-api-server = The API server could not be started: { $error }
+error-api-server-hint = Another program may be using the port. Choose another one in the settings.
+error-hook-hint = Check the folder and the command of the post-analysis hook in the settings.
+
+# Layout errors
+layout-wrong-schema = File is not an Elisa plate layout (expected schema "{ $schema }")
+layout-unsupported-version = Layout version { $version } is not supported
+layout-wrong-dimensions = Layout is for a { $width }x{ $height } plate
+layout-invalid-well = "{ $well }" is not a well on this plate
+layout-duplicate-well = Well { $well } is listed more than once
+layout-invalid-group = Well { $well } has an invalid group
+layout-conflicting-group = Well { $well } disagrees with the rest of its group
+layout-locked = The plate is locked for review
+layout-invalid-token = "{ $token }" is not a sample, use S1, U1, B, C or -
+
+# Sample types
+sample-unused = Unused
+sample-standard = Standard
+sample-control = Control
+sample-unknown = Unknown
+sample-blank = Blank
+
+# Measurements
+measurements = Measurements
+open = Open
+excel-spreadsheet = Excel Spreadsheet
+plate-reader-export = Plate Reader Export
+edit-manually = or type values into the grid:
+reader-export-info = { $format } export
+invalid-wells = Not a number: { $wells }
+wells-outside-linear-range = { $count } wells outside the linear range: { $wells }
+wells-outside-linear-range-hover = Exclude them with the exclusion rules, or check the reader settings
+invalid-well-value = "{ $value }" is not a number
+
+# Sample menu
+sample-menu = Sample Menu
+selected-sample = Selected sample { $number }
+sample-type = Sample Type
+measurement = Measurement
+group = Group
+label = Label
+dilution = Dilution
+spike = Spike
+spike-hover = Known spiked concentration, used to calculate the recovery
+group-color = Color
+group-color-hover = Own color for the group on the plate, the plot and in the report
+groups = Groups
+groups-hover = Rename, reorder, merge and delete the standard and unknown groups
+group-wells = { $count ->
+    [one] 1 well
+   *[other] { $count } wells
+}
+move-up = Move up
+move-down = Move down
+merge-into = Merge into
+merge-into-hover = Moves the wells into another group and removes this one
+delete = Delete
+delete-group-hover = Removes the group, its wells become unused
+add-group = Add Group
+detect-replicates = Detect Replicates
+detect-replicates-hover = Finds replicates in neighbouring wells and numbers the groups in plate order
+replicates-column-pairs = Adjacent column pairs (A1 and A2)
+replicates-row-pairs = Adjacent row pairs (A1 and B1)
+replicates-detected = Detected: { $pattern }
+replicates-none = No pattern fits, every standard and unknown needs a neighbour of the same type
+replicates-pattern-mismatch = This pattern leaves standards or unknowns without a partner
+replicates-preview = Groups after applying
+replicates-difference = Mean difference within the pairs: { $difference } %
+apply-replicates = Apply Groups
+excluded = Excluded
+excluded-hover = Leave this well out of the fit and the replicate CVs
+not-available = N/A
+select-sample = Please select a sample from the microplate.
+swap-with = Swap with
+swap-values = Values
+swap-values-hover = Then click the other well. The reads and exclusions change places and the layout stays, e.g. for samples pipetted into each other's wells.
+swap-assignments = Layout
+swap-assignments-hover = Then click the other well. Sample type and group change places and the reads stay, e.g. for a layout entered the wrong way round.
+swap-pick-second = Click the well to swap with { $well } ({ $mode })
+plate-row = Row { $row }
+plate-column = Column { $column }
+selected-line = { $line }: { $count } wells
+set-type = Set Type
+clear-line = Clear
+clear-line-hover = Makes the wells unused, their values stay
+sample-metadata = Sample Metadata
+sample-metadata-hover = Subject, timepoint, matrix and collection date of the unknowns, for the CSV export and the report
+paste-layout = Paste Layout
+paste-layout-hover = Type or paste the plate map of a kit insert as a grid
+paste-layout-help = One line per plate row: S1 standard 1, U1 unknown 1, B blank, C control, - unused
+apply-layout = Apply Layout
+apply-layout-hover = Replace the sample types and groups of the plate, the values stay
+subject-id = Subject ID
+timepoint = Timepoint
+matrix = Matrix
+collection-date = Collection Date
+corrected-concentration = Corrected Concentration
+
+# Standards concentrations
+standards-concentrations = Standards Concentrations
+anchor = Anchor
+anchor-hover = Anchor point outside the quantitation range, it steadies the fit but is not held to the recovery range and doesn't widen the reportable range
+concentrations = Concentrations
+standard-number = Standard { $number }
+unknown-number = Unknown { $number }
+
+# Run notes
+run-notes = Run Notes
+name = Name
+kit-lot = Kit Lot
+description = Description
+calculate = Calculate
+options = Options
+
+# Fit options
+fit-options = Fit Options
+blank-correction = Blank correction
+blank-per-row-note = Rows or columns without blanks use the plate blank.
+blank-none = No blank correction
+blank-plate = Subtract plate blank
+blank-per-row = Subtract blanks per row
+blank-per-column = Subtract blanks per column
+blank-lower-asymptote = Blank as lower asymptote
+path-length = Path length
+path-length-none = No path-length correction
+path-length-factor = Multiply by a factor
+path-length-reference = Per well from a water peak read
+path-length-factor-value = Factor
+path-length-factor-hover = 1 cm divided by the path length of the wells, e.g. 1.8 for 0.56 cm of liquid
+path-length-k-factor = K-factor
+path-length-k-factor-hover = OD of the water peak (977 nm less 900 nm) per cm of liquid, 0.18 at room temperature
+path-length-read = Water peak read...
+path-length-reads = { $count ->
+    [one] { $count } well with a read
+   *[other] { $count } wells with a read
+}
+drift-correction = Drift correction
+drift-correction-hover = Corrects a signal that rises or falls steadily while the plate is read, estimated from wells that should read the same
+drift-none = None
+drift-blanks = From the blanks
+drift-replicates = From the replicates of every group
+drift-estimate = Drift of { $total } from the first to the last well, from { $wells } wells
+drift-comparison = Replicate CV { $cv_before } → { $cv_after }, blank SD { $sd_before } → { $sd_after }
+drift-not-estimated = Not enough wells of a group at different positions to estimate a drift
+control = Control
+control-initial-guess = Use as initial guess for a
+control-anchor = Anchor point at pseudo-zero dose
+control-exclude = Exclude control
+automatic-zero-dose = Automatic zero dose
+automatic-zero-dose-hover = A hundredth of the lowest standard concentration
+extrapolation = Extrapolation
+extrapolate = Extrapolate beyond the standards by
+extrapolate-hover = Unknowns this far beyond the lowest or highest standard get a concentration with a warning, unknowns further out are reported as out of range
+standard-curve = Standard curve
+allow-non-monotonic = Allow a curve that turns
+allow-non-monotonic-hover = Fits standards whose mean signals don't rise or fall steadily with the concentration, e.g. from a hook effect. Otherwise the fit stops and names the standards that go against the trend.
+cutoff = Cutoff
+classify = Classify unknowns
+classify-hover = Positive, borderline or negative against a cutoff, in the results, the CSV export and the report
+cutoff-concentration = Fixed concentration of the undiluted sample
+cutoff-control-factor = Mean signal of the negative controls × factor
+cutoff-value = Cutoff
+cutoff-factor = Factor
+cutoff-borderline = Borderline zone
+cutoff-borderline-hover = Unknowns this close to the cutoff are borderline
+cutoff-no-control = Cutoff: no negative controls on the plate
+cutoff-threshold-concentration = Cutoff: concentration { $threshold }
+cutoff-threshold-signal = Cutoff: signal { $threshold }
+classification = Class
+positive = Positive
+borderline = Borderline
+negative = Negative
+
+# Results
+dose = Dose
+parameters = Parameters
+parameter = Parameter
+value = Value
+unit-signal = signal
+unit-signal-squared = signal²
+unit-concentration = conc.
+curve-grade = Curve grade { $grade }
+curve-grade-summary = Curve grade { $grade }: R² { $r_sq }, { $sy_x ->
+        [none] no Sy.x with only 4 standard points
+       *[other] Sy.x { $sy_x } % of the signal range
+    }, { $recovered } of { $standards } standards back-calculated within { $min }–{ $max } %
+back-calculated-standards = Back-calculated Standards
+back-calculated-summary = { $passed } of { $count } standards back-calculated within { $min }-{ $max } % of their concentration
+back-calculated-passed = Within range
+backfit-concentrations = Backfit Concentrations
+raw-corrected = Raw Corrected
+raw-signal = Raw
+backfit = Backfit
+recovery = Recovery
+recovery-out-of-range = Recovery outside of { $min }-{ $max } %
+above-curve = > { $highest }
+below-lod = < LOD
+above-curve-warning = Signal above the highest standard ({ $highest }), possibly from a hook effect. Re-run the sample at a higher dilution.
+below-lod-warning = Signal below the lowest standard ({ $lowest }), the concentration cannot be quantified.
+extrapolated = ~{ $concentration }
+extrapolated-warning = Extrapolated beyond the standards, the concentration is less reliable.
+filter-results = Filter, e.g. CV > 15
+filter-results-hover = Part of a name, label or subject ID, a comparison of cv, conc, raw or recovery like "cv > 15", or "in range", "out of range", "extrapolated", "above curve", "below lod". Separate several with ";".
+filter-count = { $shown } of { $total }
+filter-cv = cv
+filter-concentration = conc
+filter-measurement = raw
+filter-recovery = recovery
+filter-in-range = in range
+filter-out-of-range = out of range
+filter-extrapolated = extrapolated
+filter-above-curve = above curve
+filter-below-lod = below lod
+cv-percent = CV %
+sort-hover = Click to sort, again to reverse, a third time for the group order
+save-as-png = Save as PNG
+save-as-pdf = Save as PDF
+save-as-csv = Save as CSV
+save-as-json = Save as JSON
+save-as-json-hover = The whole analysis for R or Python: layout, raw and corrected values, fit with errors and every unknown
+save-plot-data = Save Plot Data
+save-plot-data-hover = The points of the plot as CSV, standards with their SD, unknowns and the sampled curve, to plot them again in Prism or Excel
+series = Series
+sd = SD
+copy-results = Copy Results
+copy-results-hover = Copies the table as shown, filtered and sorted, to paste it into Excel
+reportable-range-summary = Reportable range { $lowest } – { $upper }: standards { $lowest } – { $highest } ({ $passed } of { $standards } meet the criteria), diluted up to 1:{ $dilution }
+reportable-range-summary-undiluted = Reportable range { $lowest } – { $highest } ({ $passed } of { $standards } standards meet the criteria), diluted samples without a limit
+reportable-range-none = No standard meets the recovery and CV criteria, there is no reportable range
+reportable-range-warning = Outside the reportable range of { $lowest } – { $highest }, the standards beyond it don't meet the recovery or CV criteria
+reportable-range-dilution-warning = Diluted further than the highest allowed dilution of 1:{ $dilution }
+print-report = Print Report
+plot-replicates = Replicates
+plot-replicates-hover = Shows every standard replicate, with the standard deviation around the means
+raw-values = Raw values
+raw-values-hover = Shows the values as read instead of blank corrected, with the curve moved up by the blank
+error-bars = Error bars: { $bars }
+error-bars-none = none
+error-bars-sd = ± SD
+error-bars-sem = ± SEM
+error-bars-legend = Error bars: { $bars } of the replicates
+crosshair = Crosshair: { $mode }
+crosshair-off = off
+crosshair-free = free
+crosshair-snap = snap to curve
+crosshair-hover = Reads off the dose and measurement under the pointer, or the curve at the dose under the pointer
+crosshair-readout = Dose: { $dose }
+    Measurement: { $value }
+crosshair-curve = Curve: { $value }
+x-axis = Dose axis: { $scale }
+y-axis = Measurement axis: { $scale }
+axis-linear = linear
+axis-logarithmic = logarithmic
+curve = Curve
+standards = Standards
+anchor-standards = Anchor standards
+unknowns = Unknowns
+unknowns-out-of-range = Out of range
+replicates = Replicates
+lod-lines = Lowest and highest standard
+labels = Labels
+show-all = Show all
+hide-all = Hide all
+reset-view = Reset View
+reset-view-hover = Zoom with the scroll wheel or Ctrl and scroll, drag to pan. Exports show the same view.
+fit-to-standards = Fit to standards
+fit-to-standards-hover = Zooms to the standards and keeps the view there
+
+# PNG export
+export-png = Export PNG
+width = Width
+height = Height
+resolution = Resolution
+export-png-info = Plot is laid out at { $width }x{ $height } pt, text scales with the resolution.
+export = Export
+
+# Report options
+report-options = Report Options
+report-header = Header
+report-logo = Logo
+choose = Choose...
+image = Image
+remove = Remove
+report-sections = Sections
+plate-map = Plate Map
+report-section-plot = Plot
+report-section-residuals = Residuals
+report-section-results = Results
+report-section-sample-metadata = Sample metadata
+report-section-qc = Acceptance Criteria
+report-section-excluded-wells = Excluded Wells
+excluded-wells = Excluded wells
+report-templates = Templates
+save-template = Save Template
+apply = Apply
+
+# Dilution linearity
+dilution-linearity = Dilution Linearity
+linearity-empty =
+    Give unknown groups the same label and different dilutions
+    to compare them here.
+linearity-info = Dilution corrected concentrations compared to the lowest dilution, tolerance ±{ $tolerance } %
+endpoint-titers = Endpoint Titers
+titers-no-cutoff = Set a cutoff under "Options" first, the titer is the dilution where the signal falls below it.
+titers-empty = No dilution series, give unknowns the same subject ID or label at different dilutions.
+titers-info = Cutoff signal { $cutoff }, unknowns grouped by subject ID or label
+subject = Subject
+dilutions = Dilutions
+endpoint-titer = Endpoint Titer
+interpolated-titer = Interpolated
+sample = Sample
+corrected = Corrected
+difference = Difference
+reference = reference
+hook-effect = Possible hook effect
+matrix-interference = Possible matrix interference
+
+# Curve history
+record-curve = Record Curve
+record-curve-hover = Add this standard curve to the curve history of its kit lot
+curve-history = Curve History
+reference-curve = Reference Curve
+reference-curve-hover = Overlay the curve of a previous run or the lot reference on the plot, dashed
+reference-from-file = Load Assay…
+reference-from-file-hover = Fit a saved assay and use its curve as the reference
+reference-from-history = From History
+reference-none = No reference curve selected
+reference-source = { $plate }, lot { $lot }, { $date }
+reference-parameter = Parameter
+reference-current = Current
+reference-value = Reference
+all-lots = All lots
+no-lot = No lot
+history-empty = No curves recorded yet. Use "{ record-curve }" after fitting to add one.
+date = Date
+plate = Plate
+lot = Lot
+run = Run
+mean = Mean
+history-metric = Chart
+blank-mean = Blank mean
+control-mean = Control mean
+max-cv = Highest CV
+westgard-none = No Westgard rule is violated.
+westgard-violations = Westgard rules violated, by run: { $runs }
+westgard-hover = Limits are the mean and SD of the runs shown. 1-2s (orange) is a warning, 1-3s, 2-2s, R-4s, 4-1s and 10x (red) reject the run.
+
+# Audit trail
+audit-trail = Audit Trail
+audit-trail-empty = No changes recorded yet.
+audit-time = Time
+audit-change = Change
+operator = Operator
+operator-hover = Recorded in the audit trail with every change.
+number-format = Results shown with
+significant-figures = significant figures
+decimal-places = decimal places
+scientific-notation = Scientific notation for very small and large values
+scientific-notation-hover = Below 0.001 and from 1 000 000 on
+number-format-example = For example { $example }
+theme = Theme
+theme-light = Light
+theme-dark = Dark
+cv-threshold = Flag replicate CVs above
+cv-threshold-hover = Marked in red in the results.
+autosave = Autosave every
+autosave-hover = Keeps a copy of the assay as autosave.elisa in the data directory, 0 turns it off.
+export-folder = Save files to
+export-folder-system = Last folder used
+archive-plates = Archive every analyzed plate
+archive-plates-hover = Layout, values and results of each fit are kept in a local database to search later.
+api-server = Local HTTP API
+api-port = Port
+api-server-hover = Lets a LIMS or another program on this computer fit plates, see "API" in the README.
+api-server-running = Listening on { $url }, { $requests } requests answered
+api-bad-request = The request could not be read: { $error }
+api-not-found = There is nothing at { $path }.
+api-method-not-allowed = The method is not supported here.
+api-too-large = The request is too large.
+api-invalid-well = { $well } is not a well of the plate.
+post-analysis-hook = Post-Analysis Hook
+post-analysis-hook-hover = Export results and run a command automatically after a fit
+post-analysis-hook-info = After every fit the exports are written to the folder, e.g. a network share, and the command is run, so the results reach the data management system without saving them by hand.
+hook-enabled = Run after every fit
+hook-trigger = When
+hook-qc-passed = The fit passes QC
+hook-always = Always
+hook-folder = Folder
+hook-temporary-folder = Temporary folder
+hook-exports = Exports
+hook-results-csv = Results CSV
+hook-analysis-json = Analysis JSON
+hook-report-pdf = PDF report
+hook-command = Command
+hook-command-hover = Run once the exports are written, without a shell. {"{"}csv{"}"}, {"{"}json{"}"}, {"{"}pdf{"}"}, {"{"}folder{"}"} and {"{"}plate{"}"} are replaced, use quotes around arguments with spaces.
+hook-run-now = Run Now
+hook-run-now-hover = Exports the current plate and runs the command, whether it passed QC or not
+hook-running = { $count } commands running
+hook-done = { $plate }: { $count } exports written to { $folder }
+hook-skipped = { $plate } did not pass QC, nothing was exported.
+hook-write-error = The post-analysis hook could not write an export: { $error }
+hook-unclosed-quote = The command of the post-analysis hook has an unclosed quote.
+hook-start-error = The command of the post-analysis hook could not be started: { $error }
+hook-failed = { $program } of the post-analysis hook ended with exit code { $code }.
+new-plates = New plates
+plate-size = Plate size
+plate-size-wells = { $wells } wells
+new-plates-hover = Used for the plate at the next start.
+export-csv = Export CSV
+audit-value-changed = Value of { $well } changed from { $from } to { $to }
+audit-type-changed = { $well } changed from { $from } to { $to }
+audit-group-changed = Group of { $well } changed from { $from } to { $to }
+audit-concentration-changed = Concentration of standard { $group } changed from { $from } to { $to }
+audit-fit-run = Curve fitted: a = { $a }, b = { $b }, c = { $c }, d = { $d }, R² = { $r_sq }
+audit-fit-adjusted = Curve adjusted by hand: a = { $a }, b = { $b }, c = { $c }, d = { $d }, R² = { $r_sq }
+audit-well-excluded = { $well } excluded from the fit
+audit-well-excluded-reason = { $well } excluded from the fit: { $reason }
+audit-well-included = { $well } included in the fit again
+
+# Signatures
+signatures = Signatures
+analyzed-by = Analyzed by
+reviewed-by = Reviewed by
+role = Role
+signature = Signature
+signatures-info = Saved with the assay, printed with signature and date lines in the PDF report.
+
+# Review lock
+lock = Lock
+unlock = Unlock
+cancel = Cancel
+read-only = Read-only
+unlock-confirmation = This assay is locked for review. Unlock it to allow changes to the plate and the fit again? Unlocking is recorded in the audit trail.
+duplicate = Duplicate
+duplicate-confirmation = The values of this plate are not carried over to the duplicate. Save the plate first if you still need them.
+duplicate-name = { $name } (copy)
+audit-locked = Assay locked for review
+audit-unlocked = Assay unlocked for editing
+
+# Log console
+log = Log
+log-problems = Log ({ $count })
+log-empty = Nothing logged yet.
+clear = Clear
+
+# Fit diagnostics
+fit-diagnostics = Fit Diagnostics
+fit-not-converged = The fit did not converge, the parameters are from the last iteration and may be unreliable.
+plate-changed = The plate has changed since this fit.
+fit-curve = Fit Curve
+details = Details
+iterations = Iterations
+iterations-of = { $iterations } of { $max }
+gradient-norm = Final gradient norm
+converged = Converged
+winning-start = Start
+winning-start-hover = The initial guess the parameters came from. Start 1 is the usual guess from the standards, the others are perturbed from it, and the one with the lowest loss is kept.
+start-of = { $start } of { $starts }
+fit-duration = Duration
+fit-duration-hover = How long the optimizer took for all starts, the standard errors included
+yes = Yes
+no = No
+standard-errors = Standard errors
+standard-errors-unavailable = Standard errors need more standards than fitted parameters.
+estimate = Estimate
+standard-error = Standard error
+confidence-interval = 95 % CI
+p-value = p
+parameter-fixed = fixed
+log-c-hover = log₁₀ of c, the inflection point. The fit works with log c, its interval is symmetric on the log scale.
+degrees-of-freedom = { $df } degrees of freedom, p of the t-test against 0
+correlation-matrix = Correlation matrix
+correlation-note = Correlations close to ±1 mean the standards can't tell the two parameters apart.
+# Bootstrap
+bootstrap = Bootstrap
+bootstrap-hover = Confidence intervals from refitting the curve to resampled replicates of the standards
+bootstrap-no-replicates = The bootstrap resamples the replicates of the standards, none of them has more than one.
+resamples = Resamples
+start-bootstrap = Start
+bootstrap-progress = { $done } of { $total } refits
+bootstrap-summary = Percentiles of { $resamples } refits, { $failed } more failed to converge.
+# Simulation
+simulation = Simulate
+simulation-hover = A plate from a chosen curve and noise, to try analysis settings or for teaching. It replaces the current assay.
+simulation-curve = Curve
+simulation-blank = Blank signal
+simulation-noise = Noise
+noise-constant = SD
+noise-proportional = CV %
+simulation-layout = Layout
+simulation-top-standard = Highest standard
+simulation-dilution-factor = Dilution factor
+simulation-standards = Standards
+simulation-unknowns = Unknowns
+simulation-replicates = Replicates
+simulation-wells = Uses { $wells } of { $total } wells, the spike of each unknown is its true concentration.
+simulation-plate-too-small = { $wells } wells don't fit on the plate.
+simulate-plate = Simulate Plate
+simulated-plate = Simulated plate
+command-palette = Commands
+command-palette-hint = Type a command…
+no-matching-commands = No matching commands
+open-measurements = Open Measurements
+show-plate = Show Plate
+show-results = Show Results
+keyboard-shortcuts = Keyboard Shortcuts
+self-test = Self-Test
+self-test-info = Compares the gradient of the fit with finite differences and fits standards made from known parameters and a published dataset, whose parameters the fit has to find again.
+self-test-dataset = Dataset
+self-test-gradient = Gradient
+self-test-fit = Fit
+self-test-parameters = Fitted a, b, c, d
+self-test-gradient-hover = Largest relative difference to finite differences: { $error }
+self-test-expected = Known parameters: { $parameters }
+self-test-run-again = Run Again
+self-test-sandwich = Sandwich, rising
+self-test-competitive = Competitive, falling
+self-test-steep = Steep slope
+self-test-picograms = pg/mL, 1/y² weighting
+self-test-rounded = Rounded to 3 decimals, 1/y weighting
+self-test-dnase = DNase ELISA, published in R
+shortcut-save = Save the assay
+shortcut-open = Load an assay
+shortcut-export-csv = Save the results as CSV
+shortcut-refit = Fit the curve again
+shortcut-sample-type = Selected wells: { $type }
+sample-type-keys-hover = Keys 1–5 set the type of the selected wells
+press-keys = Press keys…
+change-shortcut-hover = Click and press the new keys, Backspace removes the shortcut, Escape keeps it
+shortcuts-hover = Ctrl is ⌘ on macOS. The number keys work on the plate while no text field is focused.
+reset-shortcuts = Reset Shortcuts
+replicate-mean = Mean of { $count }
+status-wells = { $standards } standards, { $unknowns } unknowns, { $blanks } blanks, { $controls } controls
+status-wells-hover = Show the plate
+status-linear-range = { $count } outside linear range
+status-linear-range-hover = Wells read outside the linear range of the reader, open the exclusion rules
+status-not-monotonic = Standards out of order
+status-not-monotonic-hover = The mean signals of the standards don't follow their concentrations, the fit would stop. Show the likely swapped standards
+status-not-fitted = Not fitted
+status-fit-current = Fit up to date
+status-fit-outdated = Plate changed since the fit
+status-fit-hover = Fit the curve again
+status-r-squared = R² { $value }
+status-r-squared-hover = Show the fit diagnostics
+status-worst-cv = Highest CV { $cv } % ({ $name })
+status-cv-hover = Show the results sorted by CV
+status-qc-passed = QC passed
+status-qc-failed = { $count ->
+    [one] 1 QC check failed
+   *[other] { $count } QC checks failed
+}
+status-qc-hover = Show the QC summary
+live-acquisition = Live Acquisition
+read-live = Read live
+read-live-hover = Stream values from a plate reader into the plate
+reader = Reader
+mock-reader = Simulated reader
+serial-reader = Serial / USB
+serial-port = Port
+baud-rate = Baud rate
+reading-order = Reading order
+reading-order-rows = By rows (A1, A2, …)
+reading-order-columns = By columns (A1, B1, …)
+write-live = Write into the plate while reading
+write-live-hover = Readings replace the values of their wells as they arrive
+acquisition-protocol-hint = One reading per line, "A1 0.123" or just "0.123" for the next well in reading order.
+connect = Connect
+disconnect = Disconnect
+write-into-plate = Write into Plate
+acquisition-readings = { $count } readings from { $reader }
+acquisition-unmapped = { $count } readings are not on the plate and were left out
+watch-folder = Watch Folder
+watch-folder-hover = Fit reader exports automatically as they appear in a folder
+watch-folder-info = New reader exports in the input folder are read into a copy of the current plate, fitted, and saved as results CSV and PDF report in the output folder. Files already in the folder are left alone.
+watch-input = Input folder
+watch-output = Output folder
+watch-pattern = File names
+watch-pattern-hover = Patterns separated by ";", "*" stands for any characters and "?" for one, e.g. "Plate_*.txt"
+watch-start = Start Watching
+watch-start-hover = The current plate is the template for the layout and fit settings of every imported plate
+watch-stop = Stop Watching
+watch-clear = Clear Finished
+watch-status = { $waiting } of { $count } files waiting
+watch-found = Found
+watch-file = File
+watch-state = Status
+watch-waiting = Waiting
+watch-done = Done
+watch-failed = Failed
+watch-no-template = No template plate
+watch-no-output = No output folder
+paste-values = Paste Values
+paste-plate = Plate, { $rows } rows × { $columns } columns
+paste-transposed = Transposed, { $rows } rows × { $columns } columns
+paste-well-list = List of wells and values
+paste-detected = Detected layout: { $layout }
+paste-not-detected = The pasted values don't fit the plate in any layout.
+paste-layout-mismatch = The pasted values don't fit the plate in this layout.
+paste-wells = { $count } wells get a new value, the gray ones keep theirs.
+apply-paste = Apply Values
+transform-plate = Transform Plate
+transform-plate-hover = Turn a plate that was read in the wrong orientation, layout and values alike
+transform-transpose = Transpose (rows ↔ columns)
+transform-rotate = Rotate 180°
+transform-mirror-columns = Mirror left ↔ right
+transform-mirror-rows = Mirror top ↔ bottom
+analyzed-wells = Analyzed Wells
+analyze-region = Only a region of the plate
+analyze-region-hover = Analyze part of the plate on its own, e.g. columns 1 to 6 when the plate holds two assays
+region-rows = Rows
+region-columns = Columns
+region-wells = Wells { $region } are analyzed, the others are left out.
+outside-region = outside the analyzed region
+compare-plates = Compare Plates
+compare-plates-hover = Differences of the values and concentrations to another plate, e.g. a duplicate plate or the same samples run by another operator
+compare-from-file = Other Plate...
+compare-from-file-hover = Compare with a saved assay of the same plate size. After duplicating, the plate it was duplicated from is compared.
+compare-none = No plate to compare with. Load a saved assay, or duplicate this plate and measure the copy.
+compare-source = Current plate compared with { $plate }
+unnamed-plate = unnamed plate
+compare-wrong-dimensions = The other plate has { $width } × { $height } wells, the plates can't be compared well by well.
+compare-wells = Difference per well, % of the other plate
+compare-well-hover = { $well }: { $current } here, { $other } on the other plate, Δ { $difference }
+compare-mean-difference = Mean absolute difference { $percent } %
+compare-concentrations = Concentrations
+compare-no-concentrations = Neither plate has concentrations within its standards.
+compare-current = This plate
+compare-other = Other plate
+archive = Archive
+archive-hover = Search plates analyzed before by date, plate name or sample label
+archive-off = Plates are not being archived, turn it on in the settings.
+archive-from = From
+archive-to = To
+archive-plate = Plate
+archive-label = Sample label
+search = Search
+archive-none = No archived plates found.
+archive-found = { $count } plates found, newest first
+archive-open-hover = Replaces the current assay with the archived plate
+archive-export-results = Export Results...
+import-manifest = Import Manifest...
+import-manifest-hover = Labels and subject IDs of the unknowns from a CSV file mapping wells to samples
+manifest = Sample Manifest
+manifest-wells = { $wells } wells give { $groups } unknown groups their label:
+manifest-replaces = Replaces { $label }
+manifest-issues = { $count ->
+    [one] The manifest doesn't match the plate in one place:
+   *[other] The manifest doesn't match the plate in { $count } places:
+}
+manifest-invalid-well = Line { $line }: "{ $well }" is not a well of this plate
+manifest-duplicate-well = { $well } is in the manifest more than once, the first line is taken
+manifest-not-unknown = { $well } is not an unknown on this plate
+manifest-conflicting = The wells of { $group } have different labels, the first one is taken
+manifest-missing = { $group } has no well in the manifest
+apply-manifest = Apply Labels
+barcode = Barcode
+barcode-hint = Scan or type
+report-section-barcode = Plate barcode
+name-from-barcode = Name plates after their barcode
+name-from-barcode-hover = A scanned or typed plate barcode becomes the name of the plate.
+run-metadata = Run Details
+run-metadata-hover = Operator, instrument, wavelength, read time and kit of this plate, printed in the report header
+instrument = Instrument
+wavelength = Wavelength
+read-time = Read Time
+kit-expiry = Kit Expiry
+reference-read = Reference read...
+reference-read-hover = Subtracts a read of the same plate at the reference wavelength, e.g. 570 nm, from the values
+reference-sheet = Reference sheet
+reference-sheet-none = None
+wavelength-correction = Corrected: { $measurement } − { $reference }
+wavelength-correction-reference = Corrected with a reference read at { $reference }
+wavelength-correction-unknown = Corrected with a reference read
+temperature = Temperature
+temperature-out-of-range = Outside of the acceptable temperature range in the settings
+temperature-range = Acceptable read temperature
+temperature-range-hover = Plates read outside of this range are flagged when the reader export is imported.
+linear-range = Linear range of the reader
+linear-range-hover = Wells read outside of it are flagged, saturated wells read too low and distort the fit. Compared with the OD as read, before the reference wavelength and the path length correction.
+linear-range-above = Read { $read } OD, above the linear range up to { $high }
+linear-range-below = Read { $read } OD, below the linear range from { $low }
+read-settings = Read settings
+
+# Manual fit
+adjust-curve = Adjust Curve
+adjust-curve-hover = Set the four parameters by hand, e.g. when the automatic fit fails
+adjust-a = Asymptote at zero dose
+adjust-b = Slope at the inflection point
+adjust-c = Inflection point
+adjust-d = Asymptote at infinite dose
+reset-fit = Reset to Automatic Fit
+fit-adjusted = Adjusted by hand
+
+# Protocols
+protocol = Protocol
+no-protocol = No protocol
+new-protocol = New protocol
+protocol-info = A protocol sets the standard concentrations and the weighting, and checks every fit against its acceptance criteria.
+protocol-concentrations-invalid = Separate the concentrations with semicolons.
+protocol-apply = Apply to Plate
+protocol-apply-hover = Sets the standard concentrations and the weighting of the plate
+protocol-save = Save to Library
+protocol-delete = Remove from Library
+derived-columns = Derived Columns
+derived-columns-info = Extra result columns computed for each unknown, e.g. per_ml = corrected / 0.05. Available: { $variables } and the columns above. Numbers use a decimal point.
+derived-column-invalid = Column "{ $name }": { $error }
+add-derived-column = Add Column
+expression-empty = The expression is empty
+expression-unexpected-end = The expression ends too early
+expression-unexpected = Unexpected "{ $text }"
+expression-unknown-name = "{ $name }" is not a value or a column above
+expression-unknown-function = "{ $name }" is not a function, use abs, sqrt, ln, log, exp, min or max
+expression-wrong-arguments = { $name } takes { $count ->
+    [one] one argument
+   *[other] { $count } arguments
+}
+expression-invalid-column-name = "{ $name }" can't be a column name, use letters, digits and _ and no name that is already taken
+weighting = Weighting
+initial-guesses = Initial guesses
+initial-guesses-hover = Fits again from guesses perturbed from the usual one and keeps the fit with the lowest loss, in case the optimizer got stuck in a local minimum. The perturbations are seeded, so the same plate always gives the same fit. Each start takes as long as a fit.
+starts = starts
+weighting-none = None
+weighting-1-y = 1/Y
+weighting-1-y2 = 1/Y²
+qc-cv-limit = Max. replicate CV
+qc-r-squared-limit = Min. R²
+qc-recovery-range = Standard recovery
+qc-min-curve-grade = Min. curve grade
+qc-min-curve-grade-hover = The grade of R², Sy.x and standard recovery shown with the fit, A is the best
+qc-control-recovery-range = Control recovery
+qc-control-recovery-range-hover = Recovery of the unknowns with a known spiked concentration, e.g. QC controls
+qc-max-blank = Max. blank signal
+max-dilution = Highest dilution
+max-dilution-hover = Samples may be diluted up to this to bring them onto the curve, which widens the reportable range
+qc-summary = Acceptance criteria - { $protocol }
+qc-pass = PASS
+qc-fail = FAIL
+qc-concentrations = Standard concentrations
+qc-cv = Replicate CV
+qc-r-squared = R²
+qc-recovery = Standard recovery
+qc-concentrations-match = All { $count } concentrations as in the protocol
+qc-concentration-mismatch = Standard { $number } should be { $expected }
+qc-cv-detail = Highest { $cv } % ({ $name }), limit { $limit } %
+qc-no-replicates = No replicates
+qc-r-squared-detail = { $r_sq }, at least { $limit }
+qc-recovery-detail = { $outside } of { $count } outside of { $min }-{ $max } %
+qc-curve-grade = Curve grade
+qc-curve-grade-detail = { $grade }, at least { $minimum }
+qc-control-recovery = Control recovery
+qc-no-spiked-controls = No spiked controls
+qc-blank-level = Blank level
+qc-blank-level-detail = { $blank }, at most { $limit }
+qc-no-blanks = No blanks
+qc-banner-passed = All { $count } acceptance criteria met
+qc-banner-failed = { $failed } of { $count } acceptance criteria not met:
+qc-banner-outdated = The plate changed since the fit, fit again for a current verdict.
+
+# Well exclusion
+exclusions = Exclusions
+exclusions-hover = Exclude outlying and saturated wells by rule
+exclusion-rule-cv = Replicate outlier above CV
+exclusion-rule-cv-hover = Excludes the replicate furthest from the mean of groups with at least 3 replicates
+exclusion-rule-linear-range = Outside the linear range
+exclusion-rule-linear-range-hover = Excludes wells with a read outside the linear range of the reader, the same range as in the settings
+exclusion-replicate-outlier = Outlier, replicate CV { $cv } %
+exclusion-saturated = Saturated at { $value }
+exclusion-below-linear-range = Below the linear range at { $value }
+exclusion-reasons = Reasons for excluding by hand
+exclusion-reasons-hover = A well excluded in the sample menu is only left out once one of these is chosen, it goes into the audit trail and the report. Without any, wells are excluded without a reason.
+add-exclusion-reason = Add Reason
+exclusion-reason-bubble = Bubble
+exclusion-reason-contamination = Contamination
+exclusion-reason-pipetting-error = Pipetting error
+exclusion-reason-outlier = Outlier
+exclusion-reason-prompt = Why is { $well } ({ $sample }) excluded?
+exclusion-no-reason = No reason given
+exclusions-none = The rules don't exclude any wells.
+exclusions-proposed = Proposed exclusions
+well = Well
+reason = Reason
+apply-exclusions = Exclude and Refit
+
+# PDF report
+report-title = Assay Analysis - 4PL
+report-name = Name: { $name }
+concentration = Concentration
+recovery-percent = Recovery %
+backfit-concentration = Backfit Concentration
+report-recovery-note = * Spike recovery outside of { $min }-{ $max } %
+report-standards-recovery-note = * Back-calculated standard outside of { $min }-{ $max } %
+report-extrapolated-note = ~: extrapolated beyond the standards
+report-range-note = > and <: signal outside of the standards. Samples above the highest standard may show a hook effect and should be re-run at a higher dilution.
+report-fitted = Fitted
+report-residual = Residual
+report-link = Link to Eliavaux's GitHub
+report-page = Page { $page } of { $pages }
+report-generated = Generated { $date } with Elisa { $version }
+report-source-hash = SHA-256 of the project file: { $hash }
+report-reader-file-hash = SHA-256 of the source file: { $hash }
+report-source-unchanged = fit inputs unchanged
+report-source-modified = fit inputs changed since
@@ -0,0 +1,288 @@
+use std::{collections::VecDeque, fmt::Display};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{bootstrap::Rng, i18n::*, logistic_regression::Microplate, simulation::Simulation};
+
+/// A value as it comes from the reader, for the well it names or else the next one in reading order
+#[derive(Clone, Debug, PartialEq)]
+pub struct Reading {
+    pub well: Option<String>,
+    pub value: f64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum AcquisitionError {
+    Connection(String),
+    Disconnected,
+    Protocol(String), // the line that could not be read
+}
+
+impl Display for AcquisitionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let error = match self {
+            Self::Connection(error) => tr_args("acquisition-connection-error", &[("error", error.as_str().into())]),
+            Self::Disconnected => tr("acquisition-disconnected"),
+            Self::Protocol(line) => tr_args("acquisition-protocol-error", &[("line", line.as_str().into())]),
+        };
+        write!(f, "{}", error)
+    }
+}
+
+/// A plate reader the app can take values from while it measures. Drivers are polled every frame, so `poll` must not block.
+pub trait ReaderDriver: Send {
+    fn name(&self) -> String;
+
+    /// Readings that arrived since the last call
+    fn poll(&mut self) -> Result<Vec<Reading>, AcquisitionError>;
+
+    /// No more readings will come
+    fn finished(&self) -> bool {
+        false
+    }
+}
+
+/// One line of the generic ASCII protocol: "A1 0.123", "A1,0.123" or "A1;0,123" for a named well,
+/// "0.123" or several values like "0.123 0.456 …" for the next wells in reading order.
+/// Values separated by ";" or tabs may use a decimal comma. Empty lines and lines starting with "#" are skipped.
+pub fn parse_ascii_line(line: &str) -> Result<Vec<Reading>, AcquisitionError> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') { return Ok(Vec::new()) }
+
+    let decimal_comma = line.contains(';') || line.contains('\t');
+    let tokens: Vec<&str> = if decimal_comma {
+        line.split([';', '\t']).map(str::trim).filter(|token| !token.is_empty()).collect()
+    } else {
+        line.split([' ', ',']).filter(|token| !token.is_empty()).collect()
+    };
+    let number = |token: &str| {
+        let token = if decimal_comma { token.replace(',', ".") } else { token.to_string() };
+        token.parse::<f64>().ok().filter(|value| value.is_finite())
+    };
+    let is_well = |token: &str| {
+        let mut chars = token.chars();
+        chars.next().is_some_and(|c| c.is_ascii_alphabetic()) && !chars.as_str().is_empty() && chars.all(|c| c.is_ascii_digit())
+    };
+
+    let error = || AcquisitionError::Protocol(line.to_string());
+    match tokens.as_slice() {
+        [well, value] if is_well(well) => Ok(vec![Reading { well: Some(well.to_uppercase()), value: number(value).ok_or_else(error)? }]),
+        values => values.iter().map(|value| Some(Reading { well: None, value: number(value)? })).collect::<Option<_>>().ok_or_else(error),
+    }
+}
+
+/// Bytes of a stream cut into lines, for drivers speaking a line based protocol
+#[derive(Clone, Debug, Default)]
+pub struct LineBuffer {
+    pending: Vec<u8>,
+}
+
+impl LineBuffer {
+    /// Complete lines of the bytes so far, a line cut off at the end waits for the rest
+    pub fn push(&mut self, bytes: &[u8]) -> Vec<String> {
+        self.pending.extend_from_slice(bytes);
+        let mut lines = Vec::new();
+        while let Some(end) = self.pending.iter().position(|&byte| byte == b'\n' || byte == b'\r') {
+            let line: Vec<u8> = self.pending.drain(..=end).collect();
+            let line = String::from_utf8_lossy(&line[..end]).to_string();
+            if !line.trim().is_empty() {
+                lines.push(line);
+            }
+        }
+        lines
+    }
+}
+
+/// Reads a simulated plate a few wells per frame, to try the acquisition without a reader
+pub struct MockDriver {
+    lines: VecDeque<String>,
+    per_poll: usize,
+}
+
+impl MockDriver {
+    pub fn new(lines: Vec<String>, per_poll: usize) -> Self {
+        Self { lines: lines.into(), per_poll: per_poll.max(1) }
+    }
+
+    /// The default simulation on a plate of this size, sent as ASCII lines in reading order
+    pub fn simulated(width: usize, height: usize, seed: u64) -> Self {
+        let mut rng = Rng::new(seed);
+        let simulation = Simulation::default();
+        let microplate = simulation.microplate(width, height, &mut rng)
+            .unwrap_or_else(|_| Microplate::new(width, height));
+        let lines = (0..width * height)
+            .map(|position| ReadingOrder::RowMajor.well(width, height, position))
+            .map(|index| format!("{} {:.4}", microplate.well_name(index), microplate.samples[index].value.unwrap_or(simulation.blank)))
+            .collect();
+        Self::new(lines, 1)
+    }
+}
+
+impl ReaderDriver for MockDriver {
+    fn name(&self) -> String {
+        tr("mock-reader")
+    }
+
+    fn poll(&mut self) -> Result<Vec<Reading>, AcquisitionError> {
+        let count = self.per_poll.min(self.lines.len());
+        let mut readings = Vec::new();
+        for line in self.lines.drain(..count) {
+            readings.extend(parse_ascii_line(&line)?);
+        }
+        Ok(readings)
+    }
+
+    fn finished(&self) -> bool {
+        self.lines.is_empty()
+    }
+}
+
+/// A reader sending the generic ASCII protocol over a serial port or a USB serial adapter
+#[cfg(all(feature = "serial", not(target_arch = "wasm32")))]
+pub struct SerialDriver {
+    port_name: String,
+    port: Box<dyn serialport::SerialPort>,
+    lines: LineBuffer,
+}
+
+#[cfg(all(feature = "serial", not(target_arch = "wasm32")))]
+impl SerialDriver {
+    pub fn open(port_name: &str, baud_rate: u32) -> Result<Self, AcquisitionError> {
+        let port = serialport::new(port_name, baud_rate)
+            .timeout(std::time::Duration::from_millis(5))
+            .open()
+            .map_err(|error| AcquisitionError::Connection(error.to_string()))?;
+        Ok(Self { port_name: port_name.to_string(), port, lines: LineBuffer::default() })
+    }
+
+    pub fn available_ports() -> Vec<String> {
+        serialport::available_ports().unwrap_or_default().into_iter().map(|port| port.port_name).collect()
+    }
+}
+
+#[cfg(all(feature = "serial", not(target_arch = "wasm32")))]
+impl ReaderDriver for SerialDriver {
+    fn name(&self) -> String {
+        self.port_name.clone()
+    }
+
+    fn poll(&mut self) -> Result<Vec<Reading>, AcquisitionError> {
+        use std::io::{ErrorKind, Read};
+
+        let mut buffer = [0; 1024];
+        let read = match self.port.read(&mut buffer) {
+            Ok(0) => return Err(AcquisitionError::Disconnected),
+            Ok(read) => read,
+            Err(error) if error.kind() == ErrorKind::TimedOut || error.kind() == ErrorKind::WouldBlock => 0,
+            Err(error) => return Err(AcquisitionError::Connection(error.to_string())),
+        };
+        let mut readings = Vec::new();
+        for line in self.lines.push(&buffer[..read]) {
+            readings.extend(parse_ascii_line(&line)?);
+        }
+        Ok(readings)
+    }
+}
+
+/// The order in which a reader measures the wells, for readings without a well name
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReadingOrder {
+    #[default]
+    RowMajor, // A1, A2, … A12, B1, …
+    ColumnMajor, // A1, B1, … H1, A2, …
+}
+
+impl ReadingOrder {
+    pub const ALL: [Self; 2] = [Self::RowMajor, Self::ColumnMajor];
+
+    pub fn description(&self) -> String {
+        let id = match self {
+            Self::RowMajor => "reading-order-rows",
+            Self::ColumnMajor => "reading-order-columns",
+        };
+        tr(id)
+    }
+
+    /// Index of the well measured at this position, past the end of the plate for positions past its wells
+    pub fn well(&self, width: usize, height: usize, position: usize) -> usize {
+        match self {
+            Self::RowMajor if position < width * height => position % width * height + position / width,
+            Self::RowMajor | Self::ColumnMajor => position,
+        }
+    }
+
+    /// How many wells are read before this one
+    pub fn position(&self, width: usize, height: usize, index: usize) -> usize {
+        match self {
+            Self::RowMajor => index % height * width + index / height,
+            Self::ColumnMajor => index,
+        }
+    }
+}
+
+/// A running acquisition, with the readings received so far
+pub struct Acquisition {
+    pub driver: Box<dyn ReaderDriver>,
+    pub order: ReadingOrder,
+    pub readings: Vec<Reading>,
+    pub error: Option<AcquisitionError>,
+}
+
+impl Acquisition {
+    pub fn new(driver: Box<dyn ReaderDriver>, order: ReadingOrder) -> Self {
+        Self { driver, order, readings: Vec::new(), error: None }
+    }
+
+    /// Takes what the driver has, the number of new readings. Stops at the first error.
+    pub fn poll(&mut self) -> usize {
+        if self.error.is_some() || self.driver.finished() { return 0 }
+        match self.driver.poll() {
+            Ok(readings) => {
+                self.readings.extend_from_slice(&readings);
+                readings.len()
+            },
+            Err(error) => {
+                log::warn!("Acquisition from {} stopped: {error:?}", self.driver.name());
+                self.error = Some(error);
+                0
+            },
+        }
+    }
+
+    pub fn finished(&self) -> bool {
+        self.error.is_some() || self.driver.finished()
+    }
+
+    /// The well of every reading so far, None for well names not on the plate and readings past its end.
+    /// Readings without a name go to the well after the previous reading.
+    pub fn mapping(&self, microplate: &Microplate) -> Vec<Option<usize>> {
+        let Microplate { width, height, .. } = *microplate;
+        let mut position = 0;
+        self.readings.iter().map(|reading| {
+            let index = match &reading.well {
+                Some(well) => microplate.well_index(well),
+                None => Some(self.order.well(width, height, position)).filter(|&index| index < width * height),
+            };
+            position = match (&reading.well, index) {
+                (Some(_), Some(index)) => self.order.position(width, height, index) + 1,
+                _ => position + 1,
+            };
+            index
+        }).collect()
+    }
+
+    /// Writes the readings into the plate, the number of wells written
+    pub fn apply(&self, microplate: &mut Microplate) -> usize {
+        let mapping = self.mapping(microplate);
+        let mut written = 0;
+        for (reading, index) in self.readings.iter().zip(mapping) {
+            let Some(index) = index else { continue };
+            microplate.assign_value(index, Some(reading.value));
+            written += 1;
+        }
+        if written > 0 {
+            microplate.metadata.reader_format = Some(self.driver.name());
+        }
+        written
+    }
+}
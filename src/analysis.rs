@@ -0,0 +1,140 @@
+use crate::{cutoff::Cutoff, history::mean_sd, i18n::*, logistic_regression::*};
+
+/// Allowed deviation from the reference dilution in percent
+pub const LINEARITY_TOLERANCE: f64 = 20.0;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LinearityFlag {
+    HookEffect,         // more diluted samples read higher, the reference is likely saturated
+    MatrixInterference, // more diluted samples read lower
+}
+
+impl LinearityFlag {
+    pub fn description(&self) -> String {
+        match self {
+            Self::HookEffect => tr("hook-effect"),
+            Self::MatrixInterference => tr("matrix-interference"),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct DilutionPoint {
+    pub dilution: f64,
+    pub concentration: f64, // dilution corrected, the mean of the groups at this dilution
+    pub difference: f64,    // from the reference dilution, in percent
+    pub flag: Option<LinearityFlag>,
+}
+
+/// One sample measured at several dilutions
+#[derive(Clone, Debug)]
+pub struct DilutionSeries {
+    pub label: String,
+    pub points: Vec<DilutionPoint>, // sorted by dilution, the first one is the reference
+}
+
+impl Regression {
+    /// Unknown groups sharing a label but differing in dilution are treated as one dilution series.
+    /// Groups at the same dilution count with the mean of their concentrations.
+    /// The lowest dilution with a valid concentration is the reference.
+    pub fn dilution_linearity(&self) -> Vec<DilutionSeries> {
+        let mut labels: Vec<&str> = self.unknowns.iter()
+            .map(|unknown| unknown.label.as_str())
+            .filter(|label| !label.is_empty())
+            .collect();
+        labels.sort();
+        labels.dedup();
+
+        labels.into_iter().filter_map(|label| {
+            let mut unknowns: Vec<_> = self.unknowns.iter()
+                .filter(|unknown| unknown.label == label && unknown.in_range())
+                .collect();
+            unknowns.sort_by(|a, b| a.dilution.total_cmp(&b.dilution));
+            let concentrations: Vec<(f64, f64)> = unknowns.chunk_by(|a, b| a.dilution == b.dilution)
+                .map(|same| {
+                    let values: Vec<f64> = same.iter().map(|unknown| unknown.corrected_concentration()).collect();
+                    (same[0].dilution, mean_sd(&values).0)
+                })
+                .collect();
+            if concentrations.len() < 2 { return None }
+
+            let reference = concentrations[0].1;
+            let points = concentrations.into_iter().map(|(dilution, concentration)| {
+                let difference = (concentration - reference) / reference * 100.0;
+                let flag = if difference > LINEARITY_TOLERANCE {
+                    Some(LinearityFlag::HookEffect)
+                } else if difference < -LINEARITY_TOLERANCE {
+                    Some(LinearityFlag::MatrixInterference)
+                } else {
+                    None
+                };
+                DilutionPoint { dilution, concentration, difference, flag }
+            }).collect();
+
+            Some(DilutionSeries { label: label.to_string(), points })
+        }).collect()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TiterEnd {
+    Reached,
+    AboveAll, // still above the cutoff at the highest dilution, the titer is at least that
+    BelowAll, // below the cutoff at the lowest dilution
+}
+
+/// Endpoint titer of one subject measured across a dilution series
+#[derive(Clone, Debug)]
+pub struct Titer {
+    pub subject: String,
+    pub points: Vec<(f64, f64)>, // dilution and mean signal of the groups at it, sorted by dilution
+    pub endpoint: Option<f64>,     // highest dilution above the cutoff
+    pub interpolated: Option<f64>, // dilution where the signal crosses the cutoff
+    pub end: TiterEnd,
+}
+
+impl Regression {
+    /// Unknowns of the same subject, by subject ID or else by label, at two or more dilutions.
+    /// Their blank corrected signals are compared with the signal in the well at the cutoff, see [`Cutoff::signal_threshold`],
+    /// none without one. The crossing is interpolated linearly in the signal over the log of the dilution.
+    pub fn endpoint_titers(&self, cutoff: &Cutoff) -> Vec<Titer> {
+        let Some(cutoff) = cutoff.signal_threshold(self) else { return Vec::new() };
+        let subject = |unknown: &UnknownResult| if unknown.metadata.subject_id.trim().is_empty() {
+            unknown.label.clone()
+        } else {
+            unknown.metadata.subject_id.trim().to_string()
+        };
+        let mut subjects: Vec<String> = self.unknowns.iter().map(subject).filter(|subject| !subject.is_empty()).collect();
+        subjects.sort();
+        subjects.dedup();
+
+        subjects.into_iter().filter_map(|name| {
+            // without a dilution there is no place on the log scale
+            let mut unknowns: Vec<&UnknownResult> = self.unknowns.iter()
+                .filter(|unknown| subject(unknown) == name && unknown.dilution.is_finite() && unknown.dilution > 0.0)
+                .collect();
+            unknowns.sort_by(|a, b| a.dilution.total_cmp(&b.dilution));
+            let points: Vec<(f64, f64)> = unknowns.chunk_by(|a, b| a.dilution == b.dilution)
+                .map(|same| {
+                    let signals: Vec<f64> = same.iter().map(|unknown| unknown.measurement).collect();
+                    (same[0].dilution, mean_sd(&signals).0)
+                })
+                .collect();
+            if points.len() < 2 { return None }
+
+            let last_above = points.iter().rposition(|&(_dilution, signal)| signal > cutoff);
+            let (endpoint, interpolated, end) = match last_above {
+                None => (None, None, TiterEnd::BelowAll),
+                Some(last) if last + 1 == points.len() => (Some(points[last].0), None, TiterEnd::AboveAll),
+                Some(last) => {
+                    let ((d1, s1), (d2, s2)) = (points[last], points[last + 1]);
+                    let fraction = (s1 - cutoff) / (s1 - s2);
+                    let interpolated = 10f64.powf(d1.log10() + fraction * (d2.log10() - d1.log10()));
+                    (Some(d1), Some(interpolated), TiterEnd::Reached)
+                },
+            };
+
+            Some(Titer { subject: name, points, endpoint, interpolated, end })
+        }).collect()
+    }
+}
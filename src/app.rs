@@ -1,10 +1,19 @@
 use crate::*;
-use super::logistic_regression::*;
+use super::{acquisition::*, archive::ArchiveError, audit::AuditSnapshot, bootstrap::*, clipboard::PasteLayout, plate_diff::*, files::*, exclusion::Exclusion, history::*, i18n::*, layout::*, logistic_regression::*, manifest::*, protocol::*, replicates::ReplicatePattern, report::*, results_filter::ResultsSort, self_test::SelfTestResult, settings::*, simulation::Simulation, ui::{acquisition::AcquisitionSetup, bootstrap::BootstrapJob, command_palette::Command, export::*, plot::FLAG_COLOR, shortcuts::shortcut_tooltip}, well_swap::SwapMode};
 
+#[cfg(all(feature = "archive", not(target_arch = "wasm32")))]
+use super::ui::archive::ArchiveBrowser;
+#[cfg(not(target_arch = "wasm32"))]
+use super::{export_hook::HookError, server::ApiServer, ui::{export_hook::HookRunner, watch_folder::WatchFolder}};
 use calamine::Xlsx;
-use eframe::{egui::{self, pos2, vec2, Button, Color32, Context, FontData, FontDefinitions, FontFamily, Id, Margin, Modal, OpenUrl, Rect, Response, RichText, Shadow, Shape, Style, Theme, Ui, Vec2}, CreationContext};
+use eframe::{egui::{self, pos2, vec2, Align2, Button, Color32, Context, FontData, FontDefinitions, FontFamily, Id, Margin, Modal, OpenUrl, Order, Rect, Response, RichText, Shadow, Shape, Style, TextStyle, TextWrapMode, Theme, Ui, UiBuilder, Vec2, WidgetText}, CreationContext};
+use egui_plot::PlotBounds;
+#[cfg(not(target_arch = "wasm32"))]
 use font_loader::system_fonts;
-use std::{fs::File, io::{BufReader, Read, Write}, sync::Arc};
+use std::{collections::HashSet, io::Cursor, sync::Arc, time::Duration};
+
+pub const MIN_WINDOW_SIZE: Vec2 = vec2(480.0, 360.0);
+pub const MIN_CONTENT_SIZE: Vec2 = vec2(830.0, 660.0);
 
 #[derive(Default, PartialEq)]
 pub enum ElisaTab {
@@ -15,20 +24,191 @@ pub enum ElisaTab {
 
 #[derive(Clone)]
 pub enum SerdeError {
-    FileNotFound,
     CantReadFile,
     CantWriteFile,
     CantDeserialize,
+    InvalidLayout(LayoutError),
+    CantPrint,
+}
+
+/// Everything that goes wrong in front of the user. File, value and fit errors need an answer
+/// and get a dialog, the rest a toast that goes away by itself.
+#[derive(Clone)]
+pub enum AppError {
+    Serde(SerdeError),
+    Value(ValueError),
+    Regression(RegressionError),
+    ReaderExport(String),
+    Spreadsheet(String),
+    EmptySpreadsheet,
+    Screenshot,
+    Acquisition(AcquisitionError),
+    Archive(ArchiveError),
+    Manifest(ManifestError),
+    Temperature(f64, (f64, f64)), // read temperature and the acceptable range
+    WatchFolder(String),
+    Server(String),
+    #[cfg(not(target_arch = "wasm32"))]
+    Hook(HookError),
+}
+
+impl From<SerdeError> for AppError {
+    fn from(error: SerdeError) -> Self {
+        Self::Serde(error)
+    }
+}
+
+impl From<ValueError> for AppError {
+    fn from(error: ValueError) -> Self {
+        Self::Value(error)
+    }
+}
+
+impl From<AcquisitionError> for AppError {
+    fn from(error: AcquisitionError) -> Self {
+        Self::Acquisition(error)
+    }
+}
+
+impl From<ArchiveError> for AppError {
+    fn from(error: ArchiveError) -> Self {
+        Self::Archive(error)
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl From<HookError> for AppError {
+    fn from(error: HookError) -> Self {
+        Self::Hook(error)
+    }
+}
+
+impl From<ManifestError> for AppError {
+    fn from(error: ManifestError) -> Self {
+        Self::Manifest(error)
+    }
+}
+
+impl From<RegressionError> for AppError {
+    fn from(error: RegressionError) -> Self {
+        match error {
+            RegressionError::Value(error) => Self::Value(error),
+            error => Self::Regression(error),
+        }
+    }
+}
+
+impl AppError {
+    pub fn message(&self) -> String {
+        use SerdeError::*;
+
+        match self {
+            Self::Serde(error) => match error {
+                CantReadFile => tr("error-cant-read-file"),
+                CantWriteFile => tr("error-cant-write-file"),
+                CantDeserialize => tr("error-cant-deserialize"),
+                InvalidLayout(error) => tr_args("error-invalid-layout", &[("error", error.to_string().into())]),
+                CantPrint => tr("error-cant-print"),
+            },
+            Self::Value(error) => error.to_string(),
+            Self::Regression(error) => error.to_string(),
+            Self::ReaderExport(error) => tr_args("error-reader-export", &[("error", error.as_str().into())]),
+            Self::Spreadsheet(error) => tr_args("error-spreadsheet", &[("error", error.as_str().into())]),
+            Self::EmptySpreadsheet => tr("error-empty-spreadsheet"),
+            Self::Screenshot => tr("error-screenshot"),
+            Self::Acquisition(error) => error.to_string(),
+            Self::Archive(error) => error.to_string(),
+            Self::Manifest(error) => error.to_string(),
+            Self::Temperature(temperature, (min, max)) => tr_args("error-temperature-range", &[
+                ("temperature", format_number(*temperature).into()),
+                ("min", format_number(*min).into()),
+                ("max", format_number(*max).into()),
+            ]),
+            Self::WatchFolder(error) => tr_args("error-watch-folder", &[("error", error.as_str().into())]),
+            Self::Server(error) => tr_args("error-api-server", &[("error", error.as_str().into())]),
+            #[cfg(not(target_arch = "wasm32"))]
+            Self::Hook(error) => error.to_string(),
+        }
+    }
+
+    /// What the user can do about it
+    pub fn hint(&self) -> Option<String> {
+        use ValueError::*;
+
+        let id = match self {
+            Self::Serde(SerdeError::CantPrint) => "error-cant-print-hint",
+            Self::Serde(SerdeError::InvalidLayout(_)) => return None,
+            Self::Serde(_) => "error-try-different-file",
+            Self::Value(error) => match error {
+                UnassignedConcentration | InvalidConcentration => "error-concentration-hint",
+                UnassignedValue | InvalidValue => "error-value-hint",
+                NotEnoughStandards => "error-not-enough-standards-hint",
+                BlankTooBig | ControlTooBig => "error-too-big-hint",
+                MissingPathLength => "error-missing-path-length-hint",
+            },
+            Self::Regression(error) => match error {
+                RegressionError::Value(error) => return Self::Value(error.clone()).hint(),
+                RegressionError::InvalidStandards => "error-invalid-standards-hint",
+                RegressionError::DidNotConverge => "error-did-not-converge-hint",
+                RegressionError::SingularJacobian => "error-singular-jacobian-hint",
+                RegressionError::DegenerateCurve => "error-degenerate-curve-hint",
+                RegressionError::NotMonotonic(_) => "error-not-monotonic-hint",
+            },
+            Self::ReaderExport(_) => "error-reader-export-hint",
+            Self::Spreadsheet(_) | Self::EmptySpreadsheet => "error-spreadsheet-hint",
+            Self::Screenshot => "error-screenshot-hint",
+            Self::Acquisition(_) => "error-acquisition-hint",
+            Self::Archive(_) => "error-archive-hint",
+            Self::Manifest(_) => "error-manifest-hint",
+            Self::Temperature(..) => "error-temperature-range-hint",
+            Self::WatchFolder(_) => "error-watch-folder-hint",
+            Self::Server(_) => "error-api-server-hint",
+            #[cfg(not(target_arch = "wasm32"))]
+            Self::Hook(_) => "error-hook-hint",
+        };
+        Some(tr(id))
+    }
+
+    fn is_dialog(&self) -> bool {
+        matches!(self, Self::Serde(_) | Self::Value(_) | Self::Regression(_))
+    }
+}
+
+/// Seconds a toast stays on screen
+pub const TOAST_DURATION: f64 = 8.0;
+
+#[derive(Default)]
+pub struct Notifications {
+    pub dialog: Option<AppError>,
+    pub toasts: Vec<(AppError, Option<f64>)>, // with the time it was first shown
 }
 
-fn setup_fonts(context: &Context) {
+impl Notifications {
+    /// Logs the error and shows it, as a toast if a dialog is already open
+    pub fn push(&mut self, error: impl Into<AppError>) {
+        let error = error.into();
+        log::error!("{}", error.message());
+        if error.is_dialog() && self.dialog.is_none() {
+            self.dialog = Some(error);
+        } else {
+            self.toasts.push((error, None));
+        }
+    }
+}
+
+pub fn setup_fonts(context: &Context) {
     let mut fonts = FontDefinitions::default();
 
     // Since Times New Roman is under copyright, try to load it from the system
-    // If we can't find it, embed Computer Modern, a similar font
-    let property = system_fonts::FontPropertyBuilder::new().family("Times New Roman").build();
-    let default_font = font_loader::system_fonts::get(&property)
-        .map(|(data, _)| data)
+    // If we can't find it, or run in the browser, embed Computer Modern, a similar font
+    #[cfg(not(target_arch = "wasm32"))]
+    let system_font = {
+        let property = system_fonts::FontPropertyBuilder::new().family("Times New Roman").build();
+        font_loader::system_fonts::get(&property).map(|(data, _)| data)
+    };
+    #[cfg(target_arch = "wasm32")]
+    let system_font = None;
+    let default_font = system_font
     .unwrap_or_else(|| include_bytes!("../resources/Computer Modern.ttf").to_vec());
 
     fonts.font_data.insert(
@@ -42,12 +222,16 @@ fn setup_fonts(context: &Context) {
     context.set_fonts(fonts);
 }
 
-fn setup_style(style: &mut Style) {
-    let white = Color32::from_hex("#FBFBFE").unwrap();
-    let light_blue = Color32::from_hex("#F4F7FE").unwrap();    
-    let gray = Color32::from_hex("#B2B6C0").unwrap();
-    let dark_gray = Color32::from_hex("#585C65").unwrap();
-    
+/// For the light and the dark theme, picked by the visuals egui starts them from
+pub fn setup_style(style: &mut Style) {
+    let dark = style.visuals.dark_mode;
+    let color = |light_hex: &str, dark_hex: &str| Color32::from_hex(if dark { dark_hex } else { light_hex }).unwrap();
+    let white = color("#FBFBFE", "#1E2027");
+    let light_blue = color("#F4F7FE", "#262A34");
+    let gray = color("#B2B6C0", "#5A5F6B");
+    let dark_gray = color("#585C65", "#C4C8D2");
+    let text = color("#000000", "#E8EAF0");
+
     let spacing = &mut style.spacing;
     spacing.item_spacing = Vec2::splat(0.0);
     spacing.window_margin = Margin::same(0);
@@ -62,9 +246,9 @@ fn setup_style(style: &mut Style) {
 
     style.visuals.faint_bg_color = light_blue;
     style.visuals.menu_corner_radius = 0.into();
-    style.visuals.override_text_color = Some(Color32::BLACK);
+    style.visuals.override_text_color = Some(text);
     style.visuals.popup_shadow = Shadow::NONE;
-    style.visuals.selection.stroke = (0.0, Color32::BLACK).into();
+    style.visuals.selection.stroke = (0.0, text).into();
     style.visuals.window_fill = white;
 
     let widgets = &mut style.visuals.widgets;
@@ -93,19 +277,101 @@ fn setup_style(style: &mut Style) {
 pub struct Elisa {
     pub current_tab: ElisaTab,
     pub microplate: Microplate,
-    pub data_textfield: String,
-    pub excel: Option<Xlsx<BufReader<File>>>,
+    pub value_textfields: Vec<String>,
+    pub excel: Option<Xlsx<Cursor<Vec<u8>>>>,
     pub pdf_report: bool,
+    pub print_report: bool,
     pub plot_response: Option<Response>,
     pub plot_parameters: Option<[(&'static str, f64); 9]>,
     pub sheet_names: Vec<String>,
     pub regression: Option<Regression>,
     pub selected_sheet: usize,
+    pub reference_sheet: Option<usize>,
     pub selected_sample: Option<usize>,
     pub selected_sample_group: usize,
+    pub selected_line: Option<PlateLine>,
+    pub swap_well: Option<(usize, SwapMode)>, // first well of a swap, waiting for the second
     pub standards_textfield: Vec<String>,
-    pub serde_error_modal: Option<SerdeError>,
-    pub value_error_modal: Option<ValueError>,
+    pub notifications: Notifications,
+    pub show_linearity: bool,
+    pub show_titers: bool,
+    pub show_history: bool,
+    pub show_fit_options: bool,
+    pub show_manual_fit: bool,
+    pub show_diagnostics: bool,
+    pub show_back_calculation: bool,
+    pub show_bootstrap: bool,
+    pub show_png_export: bool,
+    pub show_settings: bool,
+    pub show_report_options: bool,
+    pub show_audit_trail: bool,
+    pub show_log_console: bool,
+    pub show_signatures: bool,
+    pub show_protocol: bool,
+    pub show_exclusions: bool,
+    pub show_swap_suggestion: bool,
+    pub exclusion_prompt: Option<usize>, // well waiting for the reason it is excluded
+    pub show_groups: bool,
+    pub show_replicates: bool,
+    pub show_sample_metadata: bool,
+    pub show_run_metadata: bool,
+    pub show_paste_layout: bool,
+    pub show_simulation: bool,
+    pub show_self_test: bool,
+    pub show_command_palette: bool,
+    pub show_shortcuts: bool,
+    pub show_acquisition: bool,
+    pub results_filter: String,
+    pub results_sort: Option<ResultsSort>,
+    pub replicate_pattern: Option<ReplicatePattern>, // chosen instead of the detected one
+    pub pasted_values: Option<String>, // pasted plate waiting to be applied
+    pub paste_layout: Option<PasteLayout>, // chosen instead of the detected one
+    pub manifest: Option<Manifest>, // imported, waiting to be applied
+    pub unlock_modal: bool,
+    pub duplicate_modal: bool,
+    pub settings: Settings,
+    pub png_export: PngExport,
+    pub report_templates: Vec<ReportTemplate>,
+    pub template_name: String,
+    pub protocols: Vec<Protocol>,
+    pub protocol_concentrations: String,
+    pub layout_text: String, // pasted grid of the layout
+    pub exclusion_preview: Vec<Exclusion>,
+    pub hidden_labels: HashSet<usize>, // unknown groups without a label on the plot
+    pub plot_bounds: Option<PlotBounds>, // view of the plot on screen, exports show the same
+    pub reset_plot: bool,
+    pub audit_snapshot: Option<AuditSnapshot>,
+    pub source_file: Option<SourceFile>, // the project file the plate was loaded from or saved to, for the report footer
+    pub audit_focus: Option<Id>,
+    pub curve_history: Vec<CurveRecord>,
+    pub history_lot: Option<String>,
+    pub history_metric: HistoryMetric,
+    pub reference_curve: Option<CurveRecord>, // overlaid on the plot
+    pub show_reference: bool,
+    pub compare_plate: Option<Microplate>, // shown in the comparison, the plate duplicated from by default
+    pub plate_diff: Option<(u64, Result<PlateDiff, PlateDiffError>)>, // with the fingerprint of the current plate
+    pub show_compare: bool,
+    pub opened_files: OpenedFiles,
+    pub last_autosave: f64, // seconds since the start
+    pub bootstrap: Option<BootstrapJob>, // running in the background
+    pub bootstrap_result: Option<BootstrapResult>,
+    pub bootstrap_resamples: usize,
+    pub simulation: Simulation,
+    pub self_test: Vec<SelfTestResult>, // of the last run, empty until the self-test is opened
+    pub command_query: String,
+    pub command_selected: usize,
+    pub pending_command: Option<Command>, // runs next frame
+    pub recording_shortcut: Option<ShortcutAction>, // waiting for the keys of this one
+    pub acquisition: Option<Acquisition>, // connected to a reader
+    pub acquisition_setup: AcquisitionSetup,
+    #[cfg(all(feature = "archive", not(target_arch = "wasm32")))]
+    pub archive: ArchiveBrowser,
+    #[cfg(not(target_arch = "wasm32"))]
+    pub watch_folder: WatchFolder,
+    #[cfg(not(target_arch = "wasm32"))]
+    pub api_server: Option<ApiServer>, // running while turned on in the settings
+    #[cfg(not(target_arch = "wasm32"))]
+    pub export_hook: HookRunner,
 }
 
 impl Elisa {
@@ -113,42 +379,97 @@ impl Elisa {
         let ctx = &creation_context.egui_ctx;
         setup_fonts(ctx);
 
-        ctx.set_theme(Theme::Light);
         ctx.style_mut_of(Theme::Light, setup_style);
-
-        let width = 12;
-        let height = 8;
-        let max_groups = 100;
-        Self {
-            microplate: Microplate::new(width, height),
-            standards_textfield: vec![String::new(); max_groups],
+        ctx.style_mut_of(Theme::Dark, setup_style);
+
+        let settings = load_settings();
+        set_language(settings.language);
+        set_number_format(settings.number_format);
+        ctx.set_theme(settings.theme.egui_theme());
+        #[cfg(not(target_arch = "wasm32"))]
+        set_export_folder(settings.export_folder.clone());
+
+        let (width, height) = settings.plate_size.dimensions();
+        let mut microplate = Microplate::new(width, height);
+        microplate.fit_options.weighting = settings.weighting;
+        let mut elisa = Self {
+            microplate,
+            standards_textfield: vec![String::new(); MAX_GROUPS],
+            curve_history: load_curve_history(),
+            report_templates: load_report_templates(),
+            protocols: load_protocols(),
+            settings,
+            bootstrap_resamples: DEFAULT_RESAMPLES,
             ..default()
-        }
+        };
+        #[cfg(not(target_arch = "wasm32"))]
+        elisa.update_api_server();
+        elisa
     }
 }
 
 impl eframe::App for Elisa {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        self.open_picked_files();
+        self.command_palette(ctx);
+        self.keyboard_shortcuts(ctx);
+        self.status_bar(ctx);
         match self.current_tab {
             ElisaTab::Edit => self.assay_edit(ctx),
             ElisaTab::Result => self.assay_result(ctx),
         }
+        self.curve_history_window(ctx);
+        self.reference_curve_window(ctx);
+        self.compare_window(ctx);
+        #[cfg(all(feature = "archive", not(target_arch = "wasm32")))]
+        self.archive_window(ctx);
+        self.fit_options_window(ctx);
+        self.manual_fit_window(ctx);
+        self.fit_diagnostics_window(ctx);
+        self.back_calculation_window(ctx);
+        self.bootstrap_window(ctx);
+        self.png_export_window(ctx);
+        self.report_options_window(ctx);
+        self.signatures_window(ctx);
+        self.protocol_window(ctx);
+        self.paste_layout_window(ctx);
+        self.paste_values_window(ctx);
+        self.simulation_window(ctx);
+        self.self_test_window(ctx);
+        self.acquisition_window(ctx);
+        #[cfg(not(target_arch = "wasm32"))]
+        self.watch_folder_window(ctx);
+        #[cfg(not(target_arch = "wasm32"))]
+        self.export_hook_window(ctx);
+        self.exclusion_window(ctx);
+        self.exclusion_reason_modal(ctx);
+        self.swap_suggestion_window(ctx);
+        self.groups_window(ctx);
+        self.replicates_window(ctx);
+        self.sample_metadata_window(ctx);
+        self.run_metadata_window(ctx);
+        self.manifest_window(ctx);
+        self.audit_trail_window(ctx);
+        self.log_console_window(ctx);
+        self.record_changes(ctx);
+        self.settings_window(ctx);
+        self.shortcuts_window(ctx);
+        self.autosave(ctx);
+        self.show_notifications(ctx);
     }
 }
 
 impl Elisa {
     fn assay_edit(&mut self, ctx: &egui::Context) {
-        let white = Color32::from_hex("#FBFBFE").unwrap();
-        egui::CentralPanel::default().frame(egui::Frame::default().inner_margin(0.0).fill(white)).show(ctx, |ui| {
+        let background = ctx.style().visuals.window_fill;
+        egui::CentralPanel::default().frame(egui::Frame::default().inner_margin(0.0).fill(background)).show(ctx, |ui| {
             let stroke = ui.visuals().widgets.noninteractive.bg_stroke;
-            self.show_modals(ui);
 
             ui.painter().hline(0.0..=ui.max_rect().width(), 30.0, stroke);
             ui.painter().vline(30.0, 0.0..=ui.max_rect().height(), stroke);
 
-            egui::Frame::new()
-                .inner_margin(Margin { left: 60, right: 30, top: 60, bottom: 30})
-                .show(ui, |ui| {
+            let locked = self.microplate.locked;
+            Self::content_area(ui, |ui| {
                 let available_height = ui.available_height();
                 ui.horizontal(|ui| {
                     ui.set_height(available_height);
@@ -160,20 +481,22 @@ impl Elisa {
                             ui.set_height(remaining_height);
                             self.run_notes(ui); 
                             ui.add_space(30.0);
-                            self.measurements(ui);
+                            ui.add_enabled_ui(!locked, |ui| self.measurements(ui));
                         });
                     });
                     ui.add_space(30.0);
-                    ui.vertical(|ui| {
-                        self.sample_menu(ui);
-                        ui.add_space(30.0);
-                        self.standards_concentrations(ui);
-                    })
+                    ui.add_enabled_ui(!locked, |ui| {
+                        ui.vertical(|ui| {
+                            self.sample_menu(ui);
+                            ui.add_space(30.0);
+                            self.standards_concentrations(ui);
+                        })
+                    });
                 });
             });
 
             self.save_load_buttons(ui);
-            let mut rect = ctx.input(|i| i.screen_rect());
+            let mut rect = ui.max_rect(); // above the status bar
             rect.min = rect.max - vec2(120.0, 30.0);
             let link = ui.put(rect, Button::new("∞ Eliavaux"));
             let url = "https://github.com/eliavaux";
@@ -195,39 +518,61 @@ impl Elisa {
     }
     
     fn assay_result(&mut self, ctx: &egui::Context) {
-        let white = Color32::from_hex("#FBFBFE").unwrap();
+        let background = ctx.style().visuals.window_fill;
 
-        egui::CentralPanel::default().frame(egui::Frame::default().inner_margin(0.0).fill(white)).show(ctx, |ui| {
+        egui::CentralPanel::default().frame(egui::Frame::default().inner_margin(0.0).fill(background)).show(ctx, |ui| {
             let stroke = ui.visuals().widgets.noninteractive.bg_stroke;
 
             ui.painter().hline(0.0..=ui.max_rect().width(), 30.0, stroke);
             ui.painter().vline(30.0, 0.0..=ui.max_rect().height(), stroke);
 
-            egui::Frame::new()
-                .inner_margin(Margin { left: 60, right: 30, top: 60, bottom: 30})
-                .show(ui, |ui| {
-                    ui.vertical(|ui| {
-                        ui.horizontal(|ui| {
-                            self.plot(ui);
+            Self::content_area(ui, |ui| {
+                ui.vertical(|ui| {
+                    let available_height = ui.available_height();
+                    ui.horizontal(|ui| {
+                        ui.set_height(available_height - 60.0); // room for the buttons below
+                        self.plot(ui);
+                        ui.add_space(30.0);
+                        ui.vertical(|ui| {
+                            self.qc_banner(ui);
+                            self.convergence_warning(ui);
+                            self.plot_parameters(ui);
                             ui.add_space(30.0);
-                            ui.vertical(|ui| {
-                                self.plot_parameters(ui);
-                                ui.add_space(30.0);
-                                self.backfit_concentrations(ui);
-                            });
+                            self.qc_summary(ui);
+                            self.backfit_concentrations(ui);
                         });
-                        ui.add_space(30.0);
+                    });
+                    ui.add_space(30.0);
+                    ui.horizontal(|ui| {
                         self.save_as(ui);
+                        ui.add_space(30.0);
+                        self.analysis_menu(ui);
+                        ui.add_space(10.0);
+                        self.manual_fit_button(ui);
+                        ui.add_space(10.0);
+                        self.diagnostics_button(ui);
+                        ui.add_space(10.0);
+                        self.back_calculation_button(ui);
+                        ui.add_space(10.0);
+                        self.bootstrap_button(ui);
+                        ui.add_space(10.0);
+                        self.history_buttons(ui);
+                        ui.add_space(10.0);
+                        self.reference_curve_button(ui);
+                        ui.add_space(10.0);
+                        self.compare_plates_button(ui);
+                        ui.add_space(10.0);
+                        self.plot_options(ui);
                     });
-                    ui.spacing_mut().button_padding = vec2(4.0, 2.0);
-                    let rect = Rect::from_min_size(pos2(45.0, 5.0), vec2(50.0, 20.0));
-                    let button = ui.put(rect, Button::new(RichText::new("Back").size(13.5)));
-                    Self::dashed_outline(ui, &button);
-                    if button.clicked() {
-                        self.current_tab = ElisaTab::Edit;
-                    }
+                });
             });
-            let mut rect = ctx.input(|i| i.screen_rect());
+            ui.spacing_mut().button_padding = vec2(4.0, 2.0);
+            let button = Self::top_bar_button(ui, 45.0, tr("back"));
+            if button.clicked() {
+                self.current_tab = ElisaTab::Edit;
+            }
+            self.right_top_bar_buttons(ui);
+            let mut rect = ui.max_rect(); // above the status bar
             rect.min = rect.max - vec2(120.0, 30.0);
             let link = ui.put(rect, Button::new("∞ Eliavaux"));
             let url = "https://github.com/eliavaux";
@@ -248,102 +593,364 @@ impl Elisa {
         });
     }
     
+    /// Scrollable content below the top bar, never laid out smaller than `MIN_CONTENT_SIZE`
+    fn content_area(ui: &mut Ui, add_contents: impl FnOnce(&mut Ui)) {
+        let rect = Rect::from_min_max(pos2(31.0, 31.0), ui.max_rect().max);
+        ui.allocate_new_ui(UiBuilder::new().max_rect(rect), |ui| {
+            egui::ScrollArea::both().auto_shrink(false).show(ui, |ui| {
+                let size = ui.available_size().max(MIN_CONTENT_SIZE);
+                ui.set_min_size(size);
+                ui.set_max_size(size);
+                egui::Frame::new()
+                    .inner_margin(Margin { left: 29, right: 30, top: 29, bottom: 30})
+                    .show(ui, add_contents);
+            });
+        });
+    }
+
+    fn top_bar_button_width(ui: &Ui, text: &str) -> f32 {
+        let text = WidgetText::from(RichText::new(text).size(13.5));
+        let galley = text.into_galley(ui, Some(TextWrapMode::Extend), f32::INFINITY, TextStyle::Button);
+        (galley.size().x + 2.0 * ui.spacing().button_padding.x).max(50.0)
+    }
+
+    /// Button in the top bar at `x`, as wide as its text needs
+    fn top_bar_button(ui: &mut Ui, x: f32, text: String) -> Response {
+        let width = Self::top_bar_button_width(ui, &text);
+        let rect = Rect::from_min_size(pos2(x, 5.0), vec2(width, 20.0));
+        let button = ui.put(rect, Button::new(RichText::new(text).size(13.5)));
+        Self::dashed_outline(ui, &button);
+        button
+    }
+
+    /// Settings, audit trail and lock, laid out from the right edge of the top bar
+    fn right_top_bar_buttons(&mut self, ui: &mut Ui) {
+        let mut right = ui.max_rect().max.x - 15.0;
+        let mut button = |ui: &mut Ui, text: String| {
+            let x = right - Self::top_bar_button_width(ui, &text);
+            right = x - 10.0;
+            Self::top_bar_button(ui, x, text)
+        };
+
+        if button(ui, tr("settings")).clicked() {
+            self.show_settings = !self.show_settings;
+        }
+        if button(ui, Self::log_button_text()).clicked() {
+            self.show_log_console = !self.show_log_console;
+        }
+        if button(ui, tr("audit-trail")).clicked() {
+            self.show_audit_trail = !self.show_audit_trail;
+        }
+        let locked = self.microplate.locked;
+        if button(ui, if locked { tr("unlock") } else { tr("lock") }).clicked() {
+            if locked {
+                self.unlock_modal = true;
+            } else {
+                self.microplate.set_locked(true, &mut self.audit_snapshot, &self.settings.operator);
+            }
+        }
+
+        if locked {
+            let text = tr("read-only");
+            let rect = Rect::from_min_max(pos2(right - Self::top_bar_button_width(ui, &text), 5.0), pos2(right, 25.0));
+            ui.put(rect, egui::Label::new(RichText::new(text).size(13.5).color(FLAG_COLOR)));
+        }
+
+        if self.unlock_modal {
+            Modal::new(Id::new("Unlock Assay")).show(ui.ctx(), |ui| {
+                ui.set_width(250.0);
+                ui.label(tr("unlock-confirmation"));
+                ui.add_space(10.0);
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui.button(tr("unlock")).clicked() {
+                        self.microplate.set_locked(false, &mut self.audit_snapshot, &self.settings.operator);
+                        self.unlock_modal = false;
+                    }
+                    ui.add_space(10.0);
+                    if ui.button(tr("cancel")).clicked() {
+                        self.unlock_modal = false;
+                    }
+                });
+            });
+        }
+    }
+
+    /// Keeps a copy of the assay in the data directory every few minutes, loaded like any saved assay
+    fn autosave(&mut self, ctx: &egui::Context) {
+        if self.settings.autosave_minutes == 0 { return }
+
+        let interval = f64::from(self.settings.autosave_minutes) * 60.0;
+        let elapsed = ctx.input(|i| i.time) - self.last_autosave;
+        if elapsed < interval {
+            ctx.request_repaint_after(Duration::from_secs_f64(interval - elapsed));
+            return
+        }
+        self.last_autosave += elapsed;
+        let written = serde_json::to_string(&self.microplate).map_err(std::io::Error::other)
+            .and_then(|serialized| write_data("autosave.elisa", &serialized));
+        match written {
+            Ok(()) => log::info!("Autosaved the assay"),
+            Err(error) => log::warn!("Could not autosave the assay: {error}"),
+        }
+    }
+
+    /// Files picked in an earlier frame, on the web the browser hands them over asynchronously
+    fn open_picked_files(&mut self) {
+        use SerdeError::*;
+
+        for file in self.opened_files.take() {
+            let Ok(file) = file else {
+                self.notifications.push(CantReadFile);
+                continue
+            };
+            match file.action {
+                FileAction::LoadAssay => match serde_json::from_slice::<Microplate>(&file.bytes) {
+                    Ok(microplate) => {
+                        self.source_file = Some(SourceFile::new(SourceKind::Project, &file.bytes, &microplate));
+                        self.microplate = microplate;
+                        self.audit_snapshot = None;
+                        self.hidden_labels.clear();
+                        self.reset_plot = true;
+                    },
+                    Err(_) => self.notifications.push(CantDeserialize),
+                },
+                FileAction::ImportLayout => match serde_json::from_slice::<PlateLayout>(&file.bytes) {
+                    Ok(layout) => match self.microplate.apply_layout(&layout) {
+                        Ok(()) => self.standards_textfield.iter_mut().for_each(String::clear),
+                        Err(error) => self.notifications.push(InvalidLayout(error)),
+                    },
+                    Err(_) => self.notifications.push(CantDeserialize),
+                },
+                FileAction::CopyLayout => match serde_json::from_slice::<Microplate>(&file.bytes) {
+                    Ok(mut other) => {
+                        // the changes go into the audit trail of the other plate
+                        let mut snapshot = Some(AuditSnapshot::new(&other));
+                        match self.microplate.copy_layout_to(&mut other) {
+                            Ok(()) => {
+                                other.audit_changes(&mut snapshot, &self.settings.operator);
+                                let written = serde_json::to_string(&other).ok()
+                                    .and_then(|serialized| write_back(&file, serialized.as_bytes()).ok());
+                                if written.is_none() {
+                                    self.notifications.push(CantWriteFile);
+                                }
+                            },
+                            Err(error) => self.notifications.push(InvalidLayout(error)),
+                        }
+                    },
+                    Err(_) => self.notifications.push(CantDeserialize),
+                },
+                FileAction::Measurements => self.open_measurements(&file),
+                FileAction::ReferenceCurve => self.open_reference_curve(&file),
+                FileAction::ComparePlate => self.open_compare_plate(&file),
+                FileAction::Manifest => self.open_manifest(&file),
+                FileAction::ReferenceRead => self.open_reference_read(&file),
+                FileAction::PathLengthRead => self.open_path_length_read(&file),
+            }
+        }
+    }
+
     fn save_load_buttons(&mut self, ui: &mut Ui) {
         ui.horizontal(|ui| {
-            use SerdeError::*;
-            
             ui.spacing_mut().button_padding = vec2(4.0, 2.0);
-            let rect = Rect::from_min_size(pos2(45.0, 5.0), vec2(50.0, 20.0));
-            let button = ui.put(rect, Button::new(RichText::new("Save").size(13.5)));
-            Self::dashed_outline(ui, &button);
+            let mut x = 45.0;
+            let button = shortcut_tooltip(Self::top_bar_button(ui, x, tr("save")), self.shortcut_hint(ui.ctx(), ShortcutAction::Save));
+            x = button.rect.max.x + 10.0;
             if button.clicked() {
-                if let Some(path) = rfd::FileDialog::new()
-                    .add_filter("Text", &["json"])
-                    .set_file_name("Assay")
-                    .save_file() {
-                    if let Ok(mut file) = File::create(path) {
-                        let serialized = serde_json::to_string(&self.microplate).unwrap();
-                        if file.write_all(serialized.as_bytes()).is_err() {
-                            self.serde_error_modal = Some(CantWriteFile);
-                        }
-                    } else {
-                        self.serde_error_modal = Some(FileNotFound);
-                    }
+                self.save_assay();
+            }
+
+            let button = shortcut_tooltip(Self::top_bar_button(ui, x, tr("load")), self.shortcut_hint(ui.ctx(), ShortcutAction::Open));
+            x = button.rect.max.x + 10.0;
+            if button.clicked() {
+                self.opened_files.pick(ui.ctx(), FileAction::LoadAssay, &[("Elisa".into(), &PROJECT_EXTENSIONS)]);
+            }
+
+            let button = Self::top_bar_button(ui, x, tr("export-layout"));
+            x = button.rect.max.x + 10.0;
+            if button.clicked() {
+                self.export_layout();
+            }
+
+            let button = ui.add_enabled_ui(!self.microplate.locked, |ui| Self::top_bar_button(ui, x, tr("import-layout"))).inner;
+            x = button.rect.max.x + 10.0;
+            if button.clicked() {
+                self.opened_files.pick(ui.ctx(), FileAction::ImportLayout, &[("Plate Layout".into(), &["json"])]);
+            }
+
+            let button = Self::top_bar_button(ui, x, tr("duplicate-plate")).on_hover_text(tr("duplicate-plate-hover"));
+            x = button.rect.max.x + 10.0;
+            if button.clicked() {
+                if self.microplate.samples.iter().any(|sample| sample.value.is_some()) {
+                    self.duplicate_modal = true;
+                } else {
+                    self.duplicate_plate();
                 }
             }
 
-            let rect = Rect::from_min_size(pos2(45.0 + 50.0 + 10.0, 5.0), vec2(50.0, 20.0));
-            let button = ui.put(rect, Button::new(RichText::new("Load").size(13.5)));
-            Self::dashed_outline(ui, &button);
+            let button = Self::top_bar_button(ui, x, tr("copy-layout")).on_hover_text(tr("copy-layout-hover"));
+            x = button.rect.max.x + 10.0;
             if button.clicked() {
-                if let Some(path) = rfd::FileDialog::new()
-                    .add_filter("Text", &["json"])
-                    .pick_file() {
-                    if let Ok(mut file) = File::open(path) {
-                        let mut buf = Vec::new();
-                        if file.read_to_end(&mut buf).is_err() {
-                            self.serde_error_modal = Some(CantReadFile);                                
-                        }
-                        if let Ok(microplate) = serde_json::from_slice::<Microplate>(&buf) {
-                            self.microplate = microplate;
-                        } else {
-                            self.serde_error_modal = Some(CantDeserialize);
-                        }
-                    } else {
-                        self.serde_error_modal = Some(FileNotFound);
-                    }
+                self.opened_files.pick(ui.ctx(), FileAction::CopyLayout, &[("Elisa".into(), &PROJECT_EXTENSIONS)]);
+            }
+
+            let button = Self::top_bar_button(ui, x, tr("simulation")).on_hover_text(tr("simulation-hover"));
+            #[cfg(all(feature = "archive", not(target_arch = "wasm32")))]
+            let x = button.rect.max.x + 10.0;
+            if button.clicked() {
+                self.show_simulation = !self.show_simulation;
+            }
+
+            #[cfg(all(feature = "archive", not(target_arch = "wasm32")))]
+            {
+                let button = Self::top_bar_button(ui, x, tr("archive")).on_hover_text(tr("archive-hover"));
+                if button.clicked() {
+                    self.archive.show = !self.archive.show;
                 }
             }
+            self.right_top_bar_buttons(ui);
         });
-    }
-    
-    fn show_modals(&mut self, ui: &mut Ui) {
-        use SerdeError::*;
 
-        if let Some(serde_error) = self.serde_error_modal.clone() {
-            Modal::new(Id::new("Load Assay Error")).show(ui.ctx(), |ui| {
-                ui.vertical(|ui| {
-                    ui.set_width(250.0);
-                    let label = match serde_error {
-                        FileNotFound => "Could not find file",
-                        CantReadFile => "Could not read contents of the file",
-                        CantWriteFile => "Could not write contents to the file",
-                        CantDeserialize => "Could not load microplate from contents",
-                    };
-                    ui.label(format!("{}\nPlease try a different file.", label));
+        if self.duplicate_modal {
+            Modal::new(Id::new("Duplicate Plate")).show(ui.ctx(), |ui| {
+                ui.set_width(250.0);
+                ui.label(tr("duplicate-confirmation"));
+                ui.add_space(10.0);
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui.button(tr("duplicate")).clicked() {
+                        self.duplicate_plate();
+                        self.duplicate_modal = false;
+                    }
                     ui.add_space(10.0);
-                    ui.separator();
-                    if ui.button("Ok").clicked() {
-                        self.serde_error_modal = None;
-                    } 
+                    if ui.button(tr("cancel")).clicked() {
+                        self.duplicate_modal = false;
+                    }
                 });
             });
         }
-        
-        if let Some(value_error) = self.value_error_modal.clone() {
-            Modal::new(Id::new("Value Error")).show(ui.ctx(), |ui| {
+    }
+
+    pub fn save_assay(&mut self) {
+        let saved = serde_json::to_string(&self.microplate).ok()
+            .and_then(|serialized| save_file("Assay", ("Elisa", &PROJECT_EXTENSIONS), serialized.as_bytes()).ok().map(|written| (serialized, written)));
+        match saved {
+            Some((serialized, true)) => self.source_file = Some(SourceFile::new(SourceKind::Project, serialized.as_bytes(), &self.microplate)),
+            Some((_, false)) => (), // cancelled
+            None => self.notifications.push(SerdeError::CantWriteFile),
+        }
+    }
+
+    pub fn export_layout(&mut self) {
+        let saved = serde_json::to_string_pretty(&self.microplate.layout()).ok()
+            .and_then(|serialized| save_file("Layout", ("Plate Layout", &["json"]), serialized.as_bytes()).ok());
+        if saved.is_none() {
+            self.notifications.push(SerdeError::CantWriteFile);
+        }
+    }
+
+    /// Fits the curve to the plate as it is now, false if the fit failed and the user was told why
+    pub fn fit_curve(&mut self) -> bool {
+        match Regression::new(&self.microplate) {
+            Ok(regression) => {
+                // so edits still in progress end up before the fit
+                self.microplate.audit_changes(&mut self.audit_snapshot, &self.settings.operator);
+                self.microplate.audit_fit(&regression, &self.settings.operator);
+                self.regression = Some(regression);
+                #[cfg(all(feature = "archive", not(target_arch = "wasm32")))]
+                self.archive_fit();
+                #[cfg(not(target_arch = "wasm32"))]
+                self.run_export_hook(false);
+                true
+            },
+            Err(error) => {
+                // two standards in each other's wells can be put back right away
+                if let RegressionError::NotMonotonic(check) = &error {
+                    self.show_swap_suggestion = check.swap.is_some();
+                }
+                self.notifications.push(error);
+                false
+            },
+        }
+    }
+
+    /// The plate was edited since the curve shown was fitted
+    pub fn plate_changed(&self) -> bool {
+        self.regression.as_ref().is_some_and(|regression| regression.fingerprint != self.microplate.fit_fingerprint())
+    }
+
+    /// Continues with a new plate set up like the current one, as if it had been loaded
+    pub fn duplicate_plate(&mut self) {
+        // the values of the new plate can be compared with the ones of this one
+        let duplicate = self.microplate.duplicate();
+        self.compare_plate = Some(std::mem::replace(&mut self.microplate, duplicate));
+        self.plate_diff = None;
+        self.regression = None;
+        self.audit_snapshot = None;
+        self.source_file = None;
+        self.hidden_labels.clear();
+        self.selected_sample = None;
+        self.reset_plot = true;
+    }
+    
+    /// Errors as a modal dialog, one at a time, and toasts stacked in the lower right corner
+    fn show_notifications(&mut self, ctx: &Context) {
+        let notifications = &mut self.notifications;
+
+        if let Some(error) = notifications.dialog.clone() {
+            Modal::new(Id::new("Error Dialog")).show(ctx, |ui| {
                 ui.vertical(|ui| {
                     ui.set_width(250.0);
-                    
-                    use ValueError::*;
-                    let text = match value_error {
-                        UnassignedConcentration => "Microplate has a standard sample without a concentration.",
-                        UnassignedValue => "Microplate has a sample without a value.",
-                        InvalidConcentration => "Microplate has a standard sample with an invalid concentration.",
-                        InvalidValue => "Microplate has a sample an invalid value.",
-                        NotEnoughStandards => "Microplate does not have enough standards for four parameter analysis.",
-                        BlankTooBig => "The blank is greater than one of the standard measurements",
-                        ControlTooBig => "The control is greater than one of the standard measurements",
-                    };
-                    ui.label(text);
+                    ui.label(error.message());
+                    if let Some(hint) = error.hint() {
+                        ui.add_space(5.0);
+                        ui.label(RichText::new(hint).size(11.0));
+                    }
                     ui.add_space(10.0);
                     ui.separator();
-                    if ui.button("Ok").clicked() {
-                        self.value_error_modal = None;
-                    } 
+                    if ui.button(tr("ok")).clicked() {
+                        notifications.dialog = None;
+                    }
                 });
             });
         }
+
+        let now = ctx.input(|i| i.time);
+        notifications.toasts.retain(|(_, since)| since.is_none_or(|since| now - since < TOAST_DURATION));
+        if notifications.toasts.is_empty() { return }
+
+        let background = ctx.style().visuals.window_fill;
+        let mut dismissed = None;
+        egui::Area::new(Id::new("Toasts"))
+            .anchor(Align2::RIGHT_BOTTOM, vec2(-15.0, -45.0))
+            .order(Order::Foreground)
+            .show(ctx, |ui| {
+                for (i, (error, since)) in notifications.toasts.iter_mut().enumerate() {
+                    since.get_or_insert(now);
+                    egui::Frame::new()
+                        .fill(background)
+                        .stroke((1.0, FLAG_COLOR))
+                        .inner_margin(8.0)
+                        .show(ui, |ui| {
+                            ui.set_width(280.0);
+                            ui.label(RichText::new(error.message()).color(FLAG_COLOR));
+                            if let Some(hint) = error.hint() {
+                                ui.label(RichText::new(hint).size(11.0));
+                            }
+                            ui.add_space(5.0);
+                            let button = ui.button(tr("dismiss"));
+                            Self::dashed_outline(ui, &button);
+                            if button.clicked() {
+                                dismissed = Some(i);
+                            }
+                        });
+                    ui.add_space(5.0);
+                }
+            });
+        if let Some(i) = dismissed {
+            notifications.toasts.remove(i);
+        }
+        ctx.request_repaint_after(Duration::from_secs(1));
     }
 
     pub fn dashed_outline(ui: &mut Ui, response: &Response) {
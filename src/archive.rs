@@ -0,0 +1,184 @@
+use std::fmt::Display;
+
+use crate::{i18n::*, logistic_regression::*};
+#[cfg(all(feature = "archive", not(target_arch = "wasm32")))]
+use crate::{history::data_dir, json_export::AnalysisExport};
+#[cfg(all(feature = "archive", not(target_arch = "wasm32")))]
+use rusqlite::{params, Connection};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ArchiveError {
+    NoDataDirectory,
+    Database(String),
+    CantDeserialize, // e.g. a plate archived by a newer version
+}
+
+impl Display for ArchiveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let error = match self {
+            Self::NoDataDirectory => tr("archive-no-data-directory"),
+            Self::Database(error) => tr_args("archive-database-error", &[("error", error.as_str().into())]),
+            Self::CantDeserialize => tr("archive-cant-deserialize"),
+        };
+        write!(f, "{}", error)
+    }
+}
+
+#[cfg(all(feature = "archive", not(target_arch = "wasm32")))]
+impl From<rusqlite::Error> for ArchiveError {
+    fn from(error: rusqlite::Error) -> Self {
+        Self::Database(error.to_string())
+    }
+}
+
+/// What the archive is searched for, empty fields match every plate
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ArchiveQuery {
+    pub from: String, // YYYY-MM-DD, inclusive
+    pub to: String,
+    pub plate: String, // part of the plate name
+    pub label: String, // part of the label of an unknown
+}
+
+/// An archived plate as listed in a search, its layout and values are only loaded when it is opened
+#[derive(Clone, Debug, PartialEq)]
+pub struct ArchivedPlate {
+    pub id: i64,
+    pub date: String, // YYYY-MM-DD HH:MM
+    pub name: String,
+    pub kit_lot: String,
+    pub results: Vec<ArchivedResult>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ArchivedResult {
+    pub label: String,
+    pub concentration: Option<f64>, // of the undiluted sample, None outside of the standards
+}
+
+impl ArchivedResult {
+    pub fn new(unknown: &UnknownResult) -> Self {
+        let concentration = matches!(unknown.range, CurveRange::Within | CurveRange::Extrapolated)
+            .then(|| unknown.corrected_concentration());
+        Self { label: unknown.name(), concentration }
+    }
+}
+
+/// Most plates a search lists, the newest first
+pub const SEARCH_LIMIT: usize = 500;
+
+#[cfg(all(feature = "archive", not(target_arch = "wasm32")))]
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS plates (
+        id INTEGER PRIMARY KEY,
+        date TEXT NOT NULL,
+        name TEXT NOT NULL,
+        kit_lot TEXT NOT NULL,
+        plate TEXT NOT NULL,
+        results TEXT NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS samples (
+        plate_id INTEGER NOT NULL REFERENCES plates(id) ON DELETE CASCADE,
+        label TEXT NOT NULL,
+        concentration REAL
+    );
+    CREATE INDEX IF NOT EXISTS plates_date ON plates(date);
+    CREATE INDEX IF NOT EXISTS samples_plate ON samples(plate_id);
+";
+
+/// `text` for a LIKE pattern, so % and _ in a search are taken as they are
+#[cfg(all(feature = "archive", not(target_arch = "wasm32")))]
+fn like_pattern(text: &str) -> String {
+    let escaped = text.trim().replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_");
+    format!("%{escaped}%")
+}
+
+/// Every analyzed plate with its layout, values and results in an SQLite database in the data directory.
+/// Each plate is stored as the project file, the JSON export of its analysis and the concentrations of its unknowns to search for.
+#[cfg(all(feature = "archive", not(target_arch = "wasm32")))]
+pub struct Archive {
+    connection: Connection,
+}
+
+#[cfg(all(feature = "archive", not(target_arch = "wasm32")))]
+impl Archive {
+    pub const FILE_NAME: &str = "archive.sqlite";
+
+    /// The archive in the data directory, created the first time
+    pub fn open() -> Result<Self, ArchiveError> {
+        let dir = data_dir().ok_or(ArchiveError::NoDataDirectory)?;
+        std::fs::create_dir_all(&dir).map_err(|error| ArchiveError::Database(error.to_string()))?;
+        Self::open_path(&dir.join(Self::FILE_NAME))
+    }
+
+    pub fn open_path(path: &std::path::Path) -> Result<Self, ArchiveError> {
+        let connection = Connection::open(path)?;
+        connection.execute_batch(SCHEMA)?;
+        Ok(Self { connection })
+    }
+
+    /// Stores the plate as fitted, returns its id in the archive
+    pub fn archive_plate(&mut self, microplate: &Microplate, regression: &Regression) -> Result<i64, ArchiveError> {
+        let plate = serde_json::to_string(microplate).map_err(|error| ArchiveError::Database(error.to_string()))?;
+        let results = AnalysisExport::new(microplate, regression).to_json().map_err(|error| ArchiveError::Database(error.to_string()))?;
+        let date = chrono::offset::Local::now().format("%Y-%m-%d %H:%M").to_string();
+
+        let transaction = self.connection.transaction()?;
+        transaction.execute(
+            "INSERT INTO plates (date, name, kit_lot, plate, results) VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![date, microplate.name, microplate.metadata.kit_lot, plate, results],
+        )?;
+        let id = transaction.last_insert_rowid();
+        {
+            let mut statement = transaction.prepare("INSERT INTO samples (plate_id, label, concentration) VALUES (?1, ?2, ?3)")?;
+            for result in regression.unknowns.iter().map(ArchivedResult::new) {
+                statement.execute(params![id, result.label, result.concentration])?;
+            }
+        }
+        transaction.commit()?;
+        Ok(id)
+    }
+
+    pub fn search(&self, query: &ArchiveQuery) -> Result<Vec<ArchivedPlate>, ArchiveError> {
+        let mut statement = self.connection.prepare("
+            SELECT id, date, name, kit_lot FROM plates
+            WHERE (?1 = '' OR substr(date, 1, 10) >= ?1)
+            AND (?2 = '' OR substr(date, 1, 10) <= ?2)
+            AND (?3 = '' OR name LIKE ?4 ESCAPE '\\')
+            AND (?5 = '' OR EXISTS (SELECT 1 FROM samples WHERE plate_id = plates.id AND label LIKE ?6 ESCAPE '\\'))
+            ORDER BY date DESC, id DESC
+            LIMIT ?7
+        ")?;
+        let (plate, label) = (query.plate.trim(), query.label.trim());
+        let rows = statement.query_map(
+            params![query.from.trim(), query.to.trim(), plate, like_pattern(plate), label, like_pattern(label), SEARCH_LIMIT as i64],
+            |row| Ok(ArchivedPlate { id: row.get(0)?, date: row.get(1)?, name: row.get(2)?, kit_lot: row.get(3)?, results: Vec::new() }),
+        )?;
+        let mut plates = rows.collect::<Result<Vec<_>, _>>()?;
+
+        let mut statement = self.connection.prepare("SELECT label, concentration FROM samples WHERE plate_id = ?1 ORDER BY rowid")?;
+        for plate in &mut plates {
+            plate.results = statement
+                .query_map(params![plate.id], |row| Ok(ArchivedResult { label: row.get(0)?, concentration: row.get(1)? }))?
+                .collect::<Result<_, _>>()?;
+        }
+        Ok(plates)
+    }
+
+    /// The plate as it was archived, with its layout and values
+    pub fn load_plate(&self, id: i64) -> Result<Microplate, ArchiveError> {
+        let plate: String = self.connection.query_row("SELECT plate FROM plates WHERE id = ?1", params![id], |row| row.get(0))?;
+        serde_json::from_str(&plate).map_err(|_| ArchiveError::CantDeserialize)
+    }
+
+    /// The JSON export of the analysis as it was archived
+    pub fn load_results(&self, id: i64) -> Result<String, ArchiveError> {
+        Ok(self.connection.query_row("SELECT results FROM plates WHERE id = ?1", params![id], |row| row.get(0))?)
+    }
+
+    pub fn delete_plate(&self, id: i64) -> Result<(), ArchiveError> {
+        self.connection.execute("DELETE FROM samples WHERE plate_id = ?1", params![id])?;
+        self.connection.execute("DELETE FROM plates WHERE id = ?1", params![id])?;
+        Ok(())
+    }
+}
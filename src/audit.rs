@@ -0,0 +1,161 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{exclusion::ExclusionReason, i18n::*, logistic_regression::*};
+
+pub const AUDIT_TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// One line of the audit trail. Entries are only ever appended, and are saved with the assay.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub time: String,
+    pub operator: String,
+    pub action: AuditAction,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum AuditAction {
+    ValueChanged { well: String, from: Option<f64>, to: Option<f64> },
+    TypeChanged { well: String, from: SampleType, to: SampleType },
+    GroupChanged { well: String, from: usize, to: usize },
+    ConcentrationChanged { group: usize, from: Option<f64>, to: Option<f64> },
+    WellExcluded { well: String, reason: Option<ExclusionReason> }, // None when excluded by hand without one
+    WellIncluded { well: String },
+    FitRun { abcd: (f64, f64, f64, f64), r_sq: f64 },
+    FitAdjusted { abcd: (f64, f64, f64, f64), r_sq: f64 },
+    Locked,
+    Unlocked,
+}
+
+impl AuditEntry {
+    pub fn new(operator: &str, action: AuditAction) -> Self {
+        Self {
+            time: chrono::offset::Local::now().format(AUDIT_TIME_FORMAT).to_string(),
+            operator: operator.to_string(),
+            action,
+        }
+    }
+
+    /// Time in the format of the language, falls back to the stored text
+    pub fn display_time(&self) -> String {
+        chrono::NaiveDateTime::parse_from_str(&self.time, AUDIT_TIME_FORMAT)
+            .map(|time| format!("{}:{}", format_date_time(&time), time.format("%S")))
+            .unwrap_or_else(|_| self.time.clone())
+    }
+}
+
+fn value_text(value: Option<f64>) -> String {
+    value.map(format_number).unwrap_or_else(|| "-".to_string())
+}
+
+fn fit_description(id: &str, (a, b, c, d): (f64, f64, f64, f64), r_sq: f64) -> String {
+    tr_args(id, &[
+        ("a", format_number(a).into()), ("b", format_number(b).into()),
+        ("c", format_number(c).into()), ("d", format_number(d).into()),
+        ("r_sq", format_decimal(r_sq, 4).into()),
+    ])
+}
+
+impl AuditAction {
+    pub fn description(&self) -> String {
+        match self {
+            Self::ValueChanged { well, from, to } => tr_args("audit-value-changed", &[
+                ("well", well.as_str().into()), ("from", value_text(*from).into()), ("to", value_text(*to).into()),
+            ]),
+            Self::TypeChanged { well, from, to } => tr_args("audit-type-changed", &[
+                ("well", well.as_str().into()), ("from", from.name().into()), ("to", to.name().into()),
+            ]),
+            Self::GroupChanged { well, from, to } => tr_args("audit-group-changed", &[
+                ("well", well.as_str().into()), ("from", (from + 1).into()), ("to", (to + 1).into()),
+            ]),
+            Self::ConcentrationChanged { group, from, to } => tr_args("audit-concentration-changed", &[
+                ("group", (group + 1).into()), ("from", value_text(*from).into()), ("to", value_text(*to).into()),
+            ]),
+            Self::WellExcluded { well, reason: None } => tr_args("audit-well-excluded", &[("well", well.as_str().into())]),
+            Self::WellExcluded { well, reason: Some(reason) } => tr_args("audit-well-excluded-reason", &[
+                ("well", well.as_str().into()), ("reason", reason.description().into()),
+            ]),
+            Self::WellIncluded { well } => tr_args("audit-well-included", &[("well", well.as_str().into())]),
+            Self::FitRun { abcd, r_sq } => fit_description("audit-fit-run", *abcd, *r_sq),
+            Self::FitAdjusted { abcd, r_sq } => fit_description("audit-fit-adjusted", *abcd, *r_sq),
+            Self::Locked => tr("audit-locked"),
+            Self::Unlocked => tr("audit-unlocked"),
+        }
+    }
+}
+
+/// The parts of a microplate the audit trail watches, to find out what an edit changed
+#[derive(Clone, PartialEq)]
+pub struct AuditSnapshot {
+    wells: Vec<(SampleType, usize, Option<f64>, bool)>,
+    concentrations: Vec<Option<f64>>,
+}
+
+impl AuditSnapshot {
+    pub fn new(microplate: &Microplate) -> Self {
+        Self {
+            wells: microplate.samples.iter().map(|sample| (sample.typ, sample.group, sample.value, sample.excluded)).collect(),
+            concentrations: microplate.standard_groups.iter().map(|group| group.concentration).collect(),
+        }
+    }
+
+    pub fn changes(&self, new: &Self, microplate: &Microplate) -> Vec<AuditAction> {
+        let mut changes = Vec::new();
+        for (index, (old, new)) in self.wells.iter().zip(&new.wells).enumerate() {
+            let well = || microplate.well_name(index);
+            if old.0 != new.0 {
+                changes.push(AuditAction::TypeChanged { well: well(), from: old.0, to: new.0 });
+            } else if old.1 != new.1 && matches!(new.0, SampleType::Standard | SampleType::Unknown) {
+                changes.push(AuditAction::GroupChanged { well: well(), from: old.1, to: new.1 });
+            }
+            if old.2 != new.2 {
+                changes.push(AuditAction::ValueChanged { well: well(), from: old.2, to: new.2 });
+            }
+            if old.3 != new.3 {
+                changes.push(if new.3 {
+                    AuditAction::WellExcluded { well: well(), reason: microplate.samples[index].exclusion_reason.clone() }
+                } else {
+                    AuditAction::WellIncluded { well: well() }
+                });
+            }
+        }
+        let groups = self.concentrations.len().max(new.concentrations.len());
+        for group in 0..groups {
+            let from = self.concentrations.get(group).copied().flatten();
+            let to = new.concentrations.get(group).copied().flatten();
+            if from != to {
+                changes.push(AuditAction::ConcentrationChanged { group, from, to });
+            }
+        }
+        changes
+    }
+}
+
+impl Microplate {
+    /// Appends whatever changed since `snapshot` to the audit trail and takes a new snapshot.
+    /// Without a snapshot, e.g. right after loading, nothing is recorded.
+    pub fn audit_changes(&mut self, snapshot: &mut Option<AuditSnapshot>, operator: &str) {
+        let current = AuditSnapshot::new(self);
+        if let Some(previous) = snapshot {
+            if *previous == current { return }
+            let changes = previous.changes(&current, self);
+            self.audit_trail.extend(changes.into_iter().map(|action| AuditEntry::new(operator, action)));
+        }
+        *snapshot = Some(current);
+    }
+
+    pub fn set_locked(&mut self, locked: bool, snapshot: &mut Option<AuditSnapshot>, operator: &str) {
+        self.audit_changes(snapshot, operator);
+        self.locked = locked;
+        let action = if locked { AuditAction::Locked } else { AuditAction::Unlocked };
+        self.audit_trail.push(AuditEntry::new(operator, action));
+    }
+
+    pub fn audit_fit(&mut self, regression: &Regression, operator: &str) {
+        let action = if regression.is_adjusted() {
+            AuditAction::FitAdjusted { abcd: regression.abcd, r_sq: regression.r_sq }
+        } else {
+            AuditAction::FitRun { abcd: regression.abcd, r_sq: regression.r_sq }
+        };
+        self.audit_trail.push(AuditEntry::new(operator, action));
+    }
+}
@@ -0,0 +1,37 @@
+/// Widths of the bars and spaces of the Code 128 symbols in modules, starting with a bar
+const PATTERNS: [&str; 107] = [
+    "212222", "222122", "222221", "121223", "121322", "131222", "122213", "122312", "132212", "221213",
+    "221312", "231212", "112232", "122132", "122231", "113222", "123122", "123221", "223211", "221132",
+    "221231", "213212", "223112", "312131", "311222", "321122", "321221", "312212", "322112", "322211",
+    "212123", "212321", "232121", "111323", "131123", "131321", "112313", "132113", "132311", "211313",
+    "231113", "231311", "112133", "112331", "132131", "113123", "113321", "133121", "313121", "211331",
+    "231131", "213113", "213311", "213131", "311123", "311321", "331121", "312113", "312311", "332111",
+    "314111", "221411", "431111", "111224", "111422", "121124", "121421", "141122", "141221", "112214",
+    "112412", "122114", "122411", "142112", "142211", "241211", "221114", "413111", "241112", "134111",
+    "111242", "121142", "121241", "114212", "124112", "124211", "411212", "421112", "421211", "212141",
+    "214121", "412121", "111143", "111341", "131141", "114113", "114311", "411113", "411311", "113141",
+    "114131", "311141", "411131", "211412", "211214", "211232", "2331112",
+];
+
+const START_B: usize = 104;
+const STOP: usize = 106;
+
+/// `text` as Code 128 in code set B, the modules from the first bar to the last, true for a bar.
+/// None if it is empty or has characters outside of printable ASCII, which code set B can't encode.
+pub fn code128(text: &str) -> Option<Vec<bool>> {
+    if text.is_empty() { return None }
+    let values: Vec<usize> = text.chars()
+        .map(|c| (' '..='~').contains(&c).then(|| c as usize - ' ' as usize))
+        .collect::<Option<_>>()?;
+    let checksum = values.iter().enumerate().map(|(i, value)| (i + 1) * value).sum::<usize>() + START_B;
+
+    let symbols = std::iter::once(START_B).chain(values).chain([checksum % 103, STOP]);
+    let mut modules = Vec::new();
+    for symbol in symbols {
+        for (i, width) in PATTERNS[symbol].bytes().enumerate() {
+            let bar = i % 2 == 0;
+            modules.extend(std::iter::repeat_n(bar, (width - b'0') as usize));
+        }
+    }
+    Some(modules)
+}
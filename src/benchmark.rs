@@ -0,0 +1,99 @@
+use std::time::{Duration, Instant};
+
+use crate::{bootstrap::Rng, logistic_regression::*};
+
+/// Standards per fit, from a short series to replicates over a whole plate
+pub const BENCHMARK_SIZES: [usize; 5] = [8, 16, 32, 64, 128];
+/// Iterations each fit may take, the last one the budget of the app
+pub const BENCHMARK_BUDGETS: [usize; 3] = [1_000, 10_000, MAX_ITERATIONS];
+/// Slower than the baseline by more than this fraction counts as a regression
+pub const REGRESSION_THRESHOLD: f64 = 0.2;
+
+const CURVE: (f64, f64, f64, f64) = (0.05, 1.2, 50.0, 2.5);
+const NOISE_CV: f64 = 0.03;
+
+/// Fit time of one size and budget over a few repeats
+#[derive(Clone, Debug, PartialEq)]
+pub struct BenchmarkCase {
+    pub size: usize,
+    pub max_iterations: usize,
+    pub median: Duration,
+    pub fastest: Duration,
+    pub iterations: usize,
+    pub converged: bool,
+    pub failed: bool, // the fit returned an error, the times are of the failing fit
+}
+
+impl BenchmarkCase {
+    /// A line of the CSV a baseline is saved as
+    pub fn to_csv(&self) -> String {
+        format!("{},{},{},{},{},{}", self.size, self.max_iterations, self.median.as_nanos(), self.fastest.as_nanos(), self.iterations, self.converged)
+    }
+
+    /// Size, budget and median time of a line written by to_csv
+    pub fn parse_csv(line: &str) -> Option<(usize, usize, Duration)> {
+        let mut fields = line.split(',');
+        let size = fields.next()?.trim().parse().ok()?;
+        let max_iterations = fields.next()?.trim().parse().ok()?;
+        let median = Duration::from_nanos(fields.next()?.trim().parse().ok()?);
+        Some((size, max_iterations, median))
+    }
+}
+
+/// Standards on a known sandwich curve with a 3 % CV of noise, so the optimizer has a real minimum to find.
+/// Concentrations are spread evenly on the log axis, the same seed gives the same standards.
+pub fn benchmark_standards(size: usize, seed: u64) -> Vec<(f64, f64)> {
+    let (a, b, c, d) = CURVE;
+    let mut rng = Rng::new(seed);
+    (0..size).map(|i| {
+        let x = 0.5 * 4000f64.powf(i as f64 / (size - 1).max(1) as f64);
+        let y = d + (a - d) / (1.0 + (x / c).powf(b));
+        (x, y * (1.0 + NOISE_CV * rng.normal()))
+    }).collect()
+}
+
+/// Fits the standards of a size `repeats` times with the budget
+pub fn benchmark_fit(size: usize, max_iterations: usize, repeats: usize) -> BenchmarkCase {
+    let standards = benchmark_standards(size, size as u64);
+    let options = FitOptions { max_iterations: Some(max_iterations), ..Default::default() };
+    let mut times = Vec::new();
+    let mut outcome = None;
+    for _ in 0..repeats.max(1) {
+        let started = Instant::now();
+        let fit = Regression::from_standards_with_options(&standards, options.clone());
+        times.push(started.elapsed());
+        outcome = Some(fit);
+    }
+    times.sort();
+
+    let diagnostics = match outcome {
+        Some(Ok(regression)) => Some(regression.diagnostics),
+        _ => None,
+    };
+    BenchmarkCase {
+        size,
+        max_iterations,
+        median: times[times.len() / 2],
+        fastest: times[0],
+        iterations: diagnostics.as_ref().map(|diagnostics| diagnostics.iterations).unwrap_or_default(),
+        converged: diagnostics.as_ref().is_some_and(|diagnostics| diagnostics.converged),
+        failed: diagnostics.is_none(),
+    }
+}
+
+/// Every size with every budget
+pub fn run_benchmark(repeats: usize) -> Vec<BenchmarkCase> {
+    BENCHMARK_SIZES.iter()
+        .flat_map(|&size| BENCHMARK_BUDGETS.iter().map(move |&max_iterations| (size, max_iterations)))
+        .map(|(size, max_iterations)| benchmark_fit(size, max_iterations, repeats))
+        .collect()
+}
+
+/// Cases slower than the same size and budget in the baseline, with the median time of the baseline
+pub fn regressions<'a>(cases: &'a [BenchmarkCase], baseline: &str) -> Vec<(&'a BenchmarkCase, Duration)> {
+    let baseline: Vec<_> = baseline.lines().filter_map(BenchmarkCase::parse_csv).collect();
+    cases.iter().filter_map(|case| {
+        let &(_, _, median) = baseline.iter().find(|(size, max_iterations, _)| *size == case.size && *max_iterations == case.max_iterations)?;
+        (case.median.as_secs_f64() > median.as_secs_f64() * (1.0 + REGRESSION_THRESHOLD)).then_some((case, median))
+    }).collect()
+}
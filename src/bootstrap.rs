@@ -0,0 +1,181 @@
+use crate::{history::mean_sd, logistic_regression::*};
+
+pub const DEFAULT_RESAMPLES: usize = 200;
+
+/// SplitMix64, plenty for drawing replicates and simulated noise
+#[derive(Clone, Debug)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Index below n, n has to be positive
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    /// Uniform in (0, 1]
+    pub fn uniform(&mut self) -> f64 {
+        ((self.next_u64() >> 11) + 1) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Standard normal, Box-Muller
+    pub fn normal(&mut self) -> f64 {
+        (-2.0 * self.uniform().ln()).sqrt() * (std::f64::consts::TAU * self.uniform()).cos()
+    }
+}
+
+/// Parameters and unknown concentrations of one refit, NaN for unknowns off the curve
+#[derive(Clone, Debug)]
+pub struct BootstrapFit {
+    pub abcd: [f64; 4],
+    pub concentrations: Vec<f64>,
+}
+
+/// 2.5 and 97.5 percentiles over the refits
+#[derive(Clone, Debug)]
+pub struct BootstrapResult {
+    pub fitted_abcd: (f64, f64, f64, f64), // of the fit that was resampled
+    pub resamples: usize,
+    pub failed: usize, // refits that did not converge
+    pub parameters: [(f64, f64); 4],
+    pub concentrations: Vec<Option<(f64, f64)>>, // None if too few refits reached the signal of the unknown
+}
+
+/// Resampling only makes a difference with replicates
+pub fn has_replicates(regression: &Regression) -> bool {
+    regression.standard_replicates.iter().any(|replicates| replicates.len() > 1)
+}
+
+/// Refits with every standard replaced by the mean of its replicates drawn with replacement, None if the fit fails
+pub fn resample_fit(regression: &Regression, rng: &mut Rng) -> Option<BootstrapFit> {
+    let mut resampled = regression.clone();
+    for (standard, replicates) in resampled.standards.iter_mut().zip(&regression.standard_replicates) {
+        if replicates.len() < 2 { continue }
+        let drawn: Vec<f64> = (0..replicates.len()).map(|_| replicates[rng.below(replicates.len())]).collect();
+        standard.1 = mean_sd(&drawn).0;
+    }
+    resampled.four_pl_curve_fit().ok()?;
+    resampled.calculate_unknowns();
+    let (a, b, c, d) = resampled.abcd;
+    Some(BootstrapFit {
+        abcd: [a, b, c, d],
+        concentrations: resampled.unknowns.iter().map(|unknown| unknown.concentration).collect(),
+    })
+}
+
+/// Linear interpolation between the closest ranks
+fn percentile(sorted: &[f64], fraction: f64) -> f64 {
+    let position = fraction * (sorted.len() - 1) as f64;
+    let (lower, upper) = (position.floor() as usize, position.ceil() as usize);
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (position - lower as f64)
+}
+
+fn interval(values: impl Iterator<Item = f64>) -> Option<(f64, f64)> {
+    let mut values: Vec<f64> = values.filter(|value| value.is_finite()).collect();
+    if values.len() < 2 { return None }
+    values.sort_by(f64::total_cmp);
+    Some((percentile(&values, 0.025), percentile(&values, 0.975)))
+}
+
+/// Refits a batch at a time, so the caller can show progress and stop in between
+pub struct BootstrapRun {
+    regression: Regression,
+    seed: u64,
+    fits: Vec<BootstrapFit>,
+    failed: usize,
+    pub total: usize,
+}
+
+impl BootstrapRun {
+    #[cfg(not(target_arch = "wasm32"))]
+    const REFITS_PER_THREAD: usize = 4;
+
+    pub fn new(regression: Regression, total: usize, seed: u64) -> Self {
+        Self { regression, seed, fits: Vec::new(), failed: 0, total }
+    }
+
+    pub fn done(&self) -> usize {
+        self.fits.len() + self.failed
+    }
+
+    /// Every refit draws from a generator of its own, so the result doesn't depend on how many run at once
+    fn refit(&self, index: usize) -> Option<BootstrapFit> {
+        let mut rng = Rng::new(self.seed.wrapping_add(index as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15));
+        resample_fit(&self.regression, &mut rng)
+    }
+
+    fn collect(&mut self, fits: Vec<Option<BootstrapFit>>) {
+        for fit in fits {
+            match fit {
+                Some(fit) => self.fits.push(fit),
+                None => self.failed += 1,
+            }
+        }
+    }
+
+    /// One refit, false once all are done
+    pub fn step(&mut self) -> bool {
+        if self.done() >= self.total { return false }
+        let fit = self.refit(self.done());
+        self.collect(vec![fit]);
+        true
+    }
+
+    /// A few refits for each of `threads` threads, false once all are done. The threads take the next refit
+    /// as soon as they are free, some refits take many more iterations than others.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn step_parallel(&mut self, threads: usize) -> bool {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let first = self.done();
+        if first >= self.total { return false }
+        let threads = threads.max(1);
+        let end = self.total.min(first + Self::REFITS_PER_THREAD * threads);
+        let next = AtomicUsize::new(first);
+        let run = &*self;
+        let mut fits = vec![None; end - first];
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..threads).map(|_| scope.spawn(|| {
+                let mut fits = Vec::new();
+                loop {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    if index >= end { return fits }
+                    fits.push((index, run.refit(index)));
+                }
+            })).collect();
+            // a refit that panicked counts as failed
+            for (index, fit) in handles.into_iter().flat_map(|handle| handle.join().unwrap_or_default()) {
+                fits[index - first] = fit;
+            }
+        });
+        self.collect(fits);
+        true
+    }
+
+    /// Percentiles of the refits so far, None if none of them succeeded
+    pub fn result(&self) -> Option<BootstrapResult> {
+        if self.fits.is_empty() { return None }
+        let parameter = |i: usize| interval(self.fits.iter().map(|fit| fit.abcd[i])).unwrap_or((self.fits[0].abcd[i], self.fits[0].abcd[i]));
+        let concentrations = (0..self.regression.unknowns.len())
+            .map(|i| interval(self.fits.iter().map(|fit| fit.concentrations[i])))
+            .collect();
+        Some(BootstrapResult {
+            fitted_abcd: self.regression.fitted_abcd,
+            resamples: self.fits.len(),
+            failed: self.failed,
+            parameters: [parameter(0), parameter(1), parameter(2), parameter(3)],
+            concentrations,
+        })
+    }
+}
@@ -0,0 +1,110 @@
+use crate::{i18n::*, layout::ALPHABET, logistic_regression::Microplate};
+
+/// How values copied from a spreadsheet or the software of a reader are laid out
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PasteLayout {
+    Plate,      // rows A, B, … and columns 1, 2, …, like the plate
+    Transposed, // rows 1, 2, … and columns A, B, …
+    WellList,   // a well and its value per line, e.g. "A1,0.123"
+}
+
+impl PasteLayout {
+    pub const ALL: [Self; 3] = [Self::Plate, Self::Transposed, Self::WellList];
+
+    pub fn description(&self, microplate: &Microplate) -> String {
+        let (width, height) = (microplate.width, microplate.height);
+        match self {
+            Self::Plate => tr_args("paste-plate", &[("rows", height.into()), ("columns", width.into())]),
+            Self::Transposed => tr_args("paste-transposed", &[("rows", width.into()), ("columns", height.into())]),
+            Self::WellList => tr("paste-well-list"),
+        }
+    }
+}
+
+/// Pasted values and the wells they go to, as (well, value)
+#[derive(Clone, Debug, PartialEq)]
+pub struct PastedValues {
+    pub layout: PasteLayout,
+    pub values: Vec<(usize, Option<f64>)>,
+}
+
+/// Cells of a line, empty cells between tabs or semicolons stay in place
+fn cells(line: &str) -> Vec<&str> {
+    if line.contains('\t') {
+        line.split('\t').map(str::trim).collect()
+    } else if line.contains(';') {
+        line.split(';').map(str::trim).collect()
+    } else if line.trim().contains(char::is_whitespace) {
+        line.split_whitespace().map(|cell| cell.trim_matches(',')).collect()
+    } else {
+        line.split(',').map(str::trim).collect()
+    }
+}
+
+fn value(cell: &str) -> Option<Option<f64>> {
+    match cell {
+        "" | "_" => Some(None),
+        cell => parse_decimal(cell).map(Some),
+    }
+}
+
+/// Row or column headers, 1, 2, 3, … or A, B, C, …
+fn is_labels(cells: &[&str]) -> bool {
+    !cells.is_empty() && cells.iter().enumerate().all(|(i, cell)| {
+        *cell == (i + 1).to_string() || (cell.len() == 1 && cell.chars().next().map(|c| c.to_ascii_uppercase()) == ALPHABET.get(i).copied())
+    })
+}
+
+/// The cells of a pasted block, without the headers of its rows and columns
+fn grid(text: &str) -> Vec<Vec<&str>> {
+    let mut rows: Vec<Vec<&str>> = text.lines().filter(|line| !line.trim().is_empty()).map(cells).collect();
+    if let Some(header) = rows.first() {
+        let header = header.strip_prefix(&[""]).unwrap_or(header);
+        if is_labels(header) {
+            rows.remove(0);
+        }
+    }
+    let first: Vec<&str> = rows.iter().filter_map(|row| row.first().copied()).collect();
+    if first.len() == rows.len() && is_labels(&first) {
+        rows.iter_mut().for_each(|row| { row.remove(0); });
+    }
+    rows
+}
+
+/// The values of `text` read as `layout`, None if they don't fit the plate that way
+pub fn read_paste(text: &str, microplate: &Microplate, layout: PasteLayout) -> Option<PastedValues> {
+    let (width, height) = (microplate.width, microplate.height);
+    let values = match layout {
+        PasteLayout::Plate | PasteLayout::Transposed => {
+            let rows = grid(text);
+            let (row_count, column_count) = if layout == PasteLayout::Plate { (height, width) } else { (width, height) };
+            if rows.len() != row_count || rows.iter().any(|row| row.len() != column_count) { return None }
+            let mut values = Vec::new();
+            for (r, row) in rows.iter().enumerate() {
+                for (c, cell) in row.iter().enumerate() {
+                    let index = if layout == PasteLayout::Plate { c * height + r } else { r * height + c };
+                    values.push((index, value(cell)?));
+                }
+            }
+            values
+        },
+        PasteLayout::WellList => {
+            let mut lines: Vec<Vec<&str>> = text.lines().filter(|line| !line.trim().is_empty()).map(cells).collect();
+            // e.g. "Well,Value"
+            if lines.first().is_some_and(|line| line.first().is_some_and(|cell| microplate.well_index(cell).is_none())) {
+                lines.remove(0);
+            }
+            if lines.len() < 2 { return None }
+            lines.iter().map(|line| match line.as_slice() {
+                [well, cell] => Some((microplate.well_index(well)?, value(cell)?)),
+                _ => None,
+            }).collect::<Option<_>>()?
+        },
+    };
+    Some(PastedValues { layout, values })
+}
+
+/// The first layout the pasted text fits, None for blocks smaller than the plate
+pub fn detect_paste(text: &str, microplate: &Microplate) -> Option<PastedValues> {
+    PasteLayout::ALL.into_iter().find_map(|layout| read_paste(text, microplate, layout))
+}
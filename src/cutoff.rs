@@ -0,0 +1,105 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{i18n::*, logistic_regression::*};
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum CutoffMode {
+    Concentration, // fixed concentration of the undiluted sample
+    ControlFactor, // mean signal of the negative controls times a factor
+}
+
+impl CutoffMode {
+    pub const ALL: [Self; 2] = [Self::Concentration, Self::ControlFactor];
+
+    pub fn description(&self) -> String {
+        match self {
+            Self::Concentration => tr("cutoff-concentration"),
+            Self::ControlFactor => tr("cutoff-control-factor"),
+        }
+    }
+}
+
+/// Positive/negative cutoff of the unknowns, saved with the microplate
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Cutoff {
+    pub mode: CutoffMode,
+    pub value: f64,     // concentration, or factor on the control signal
+    pub borderline: f64, // % around the cutoff that is neither positive nor negative
+}
+
+impl Default for Cutoff {
+    fn default() -> Self {
+        Self {
+            mode: CutoffMode::ControlFactor,
+            value: 2.0,
+            borderline: 10.0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub enum Classification {
+    Positive,
+    Borderline,
+    Negative,
+}
+
+impl Classification {
+    pub fn name(&self) -> String {
+        match self {
+            Self::Positive => tr("positive"),
+            Self::Borderline => tr("borderline"),
+            Self::Negative => tr("negative"),
+        }
+    }
+}
+
+impl Cutoff {
+    /// The signal or concentration unknowns are compared with, None without negative controls for a factor
+    pub fn threshold(&self, regression: &Regression) -> Option<f64> {
+        match self.mode {
+            CutoffMode::Concentration => Some(self.value),
+            CutoffMode::ControlFactor => regression.has_control.then_some(regression.control * self.value),
+        }
+    }
+
+    /// Signal in the well at the cutoff, for titers where the dilution of the sample is what varies
+    pub fn signal_threshold(&self, regression: &Regression) -> Option<f64> {
+        match self.mode {
+            CutoffMode::Concentration => Some(regression.four_pl(self.value)).filter(|signal| signal.is_finite()),
+            CutoffMode::ControlFactor => self.threshold(regression),
+        }
+    }
+
+    pub fn threshold_text(&self, regression: &Regression) -> String {
+        match (self.mode, self.threshold(regression)) {
+            (_, None) => tr("cutoff-no-control"),
+            (CutoffMode::Concentration, Some(threshold)) => tr_args("cutoff-threshold-concentration", &[("threshold", format_value(threshold).into())]),
+            (CutoffMode::ControlFactor, Some(threshold)) => tr_args("cutoff-threshold-signal", &[("threshold", format_value(threshold).into())]),
+        }
+    }
+
+    pub fn classify(&self, regression: &Regression, unknown: &UnknownResult) -> Option<Classification> {
+        let threshold = self.threshold(regression)?;
+        let value = match self.mode {
+            CutoffMode::ControlFactor => unknown.measurement,
+            // outside of the standards the concentration is only known to be below or above them
+            CutoffMode::Concentration => match unknown.range {
+                CurveRange::Below(lowest) => return (lowest * unknown.dilution <= threshold).then_some(Classification::Negative),
+                CurveRange::Above(highest) => return (highest * unknown.dilution >= threshold).then_some(Classification::Positive),
+                CurveRange::Within | CurveRange::Extrapolated => unknown.corrected_concentration(),
+            },
+        };
+        if !value.is_finite() { return None }
+
+        let margin = threshold.abs() * self.borderline / 100.0;
+        Some(if value > threshold + margin {
+            Classification::Positive
+        } else if value < threshold - margin {
+            Classification::Negative
+        } else {
+            Classification::Borderline
+        })
+    }
+}
@@ -0,0 +1,150 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{history::mean_sd, i18n::*, logistic_regression::{SampleType::*, *}};
+
+/// Signal changing steadily while the plate is read, e.g. as the substrate keeps reacting after the stop solution.
+/// Wells that should read the same show the drift as a trend with their read position.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DriftCorrection {
+    #[default]
+    None,
+    Blanks,     // the blanks spread over the plate
+    Replicates, // the wells of every group, pooled
+}
+
+impl DriftCorrection {
+    pub const ALL: [Self; 3] = [Self::None, Self::Blanks, Self::Replicates];
+
+    pub fn description(&self) -> String {
+        let id = match self {
+            Self::None => "drift-none",
+            Self::Blanks => "drift-blanks",
+            Self::Replicates => "drift-replicates",
+        };
+        tr(id)
+    }
+}
+
+/// Linear drift over the read order, zero in the middle of the read so the plate keeps its mean
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Drift {
+    pub slope: f64,  // signal per well read
+    pub center: f64, // read position without drift
+    pub wells: usize, // the estimate is based on
+}
+
+impl Drift {
+    /// What the drift added to a well at this read position
+    pub fn of_position(&self, position: usize) -> f64 {
+        self.slope * (position as f64 - self.center)
+    }
+
+    /// From the first to the last well of the plate
+    pub fn total(&self) -> f64 {
+        self.slope * self.center * 2.0
+    }
+}
+
+/// Spread of wells that should read the same, with and without the drift correction
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct DriftComparison {
+    pub cv_before: Option<f64>, // %, mean of the replicate CVs of the standards, unknowns and controls
+    pub cv_after: Option<f64>,
+    pub blank_sd_before: Option<f64>,
+    pub blank_sd_after: Option<f64>,
+}
+
+impl Microplate {
+    /// Wells that should read the same, with blanks and controls one group each like in the well details
+    fn replicate_sets(&self) -> Vec<(SampleType, Vec<usize>)> {
+        let mut sets = vec![(Blank, Vec::new()), (Control, Vec::new())];
+        sets.extend((0..self.standard_groups.len()).map(|_| (Standard, Vec::new())));
+        sets.extend((0..self.unknown_groups.len()).map(|_| (Unknown, Vec::new())));
+        let standards = 2;
+        let unknowns = standards + self.standard_groups.len();
+
+        for (i, sample) in self.samples.iter().enumerate() {
+            if sample.excluded || !self.in_region(i) { continue }
+            let set = match sample.typ {
+                Blank => 0,
+                Control => 1,
+                Standard => standards + sample.group,
+                Unknown => unknowns + sample.group,
+                Unused => continue,
+            };
+            if let Some((_typ, wells)) = sets.get_mut(set) {
+                wells.push(i);
+            }
+        }
+        sets
+    }
+
+    /// Slope of the signal over the read order pooled over the groups of the drift correction, None without one
+    /// or without two wells of a group at different positions
+    pub fn drift(&self) -> Option<Drift> {
+        let order = self.fit_options.reading_order;
+        let sets = self.replicate_sets();
+        let sets = match self.fit_options.drift_correction {
+            DriftCorrection::None => return None,
+            DriftCorrection::Blanks => &sets[..1],
+            DriftCorrection::Replicates => &sets[..],
+        };
+
+        // least squares within each group, so the differences between the groups don't count
+        let (mut sxy, mut sxx, mut wells) = (0.0, 0.0, 0);
+        for (_typ, set) in sets {
+            let points: Vec<(f64, f64)> = set.iter()
+                .filter_map(|&i| Some((order.position(self.width, self.height, i) as f64, self.normalized_value(i)?)))
+                .collect();
+            if points.len() < 2 { continue }
+            let n = points.len() as f64;
+            let mean_x = points.iter().map(|(x, _y)| x).sum::<f64>() / n;
+            let mean_y = points.iter().map(|(_x, y)| y).sum::<f64>() / n;
+            for (x, y) in &points {
+                sxy += (x - mean_x) * (y - mean_y);
+                sxx += (x - mean_x) * (x - mean_x);
+            }
+            wells += points.len();
+        }
+        if sxx == 0.0 { return None }
+
+        Some(Drift {
+            slope: sxy / sxx,
+            center: (self.samples.len() as f64 - 1.0) / 2.0,
+            wells,
+        })
+    }
+
+    /// Values of the wells as they are fitted: normalized to 1 cm, then corrected for the drift
+    pub fn fitted_values(&self) -> Vec<Option<f64>> {
+        let drift = self.drift();
+        (0..self.samples.len()).map(|i| {
+            let value = self.normalized_value(i)?;
+            let position = self.fit_options.reading_order.position(self.width, self.height, i);
+            Some(value - drift.map_or(0.0, |drift| drift.of_position(position)))
+        }).collect()
+    }
+
+    pub fn drift_comparison(&self) -> DriftComparison {
+        let fitted = self.fitted_values();
+        let sets = self.replicate_sets();
+        let spread = |corrected: bool| {
+            let values = |wells: &[usize]| -> Vec<f64> {
+                wells.iter().filter_map(|&i| if corrected { fitted[i] } else { self.normalized_value(i) }).collect()
+            };
+            let cvs: Vec<f64> = sets.iter().filter(|(typ, _wells)| *typ != Blank).filter_map(|(_typ, wells)| {
+                let values = values(wells);
+                if values.len() < 2 { return None }
+                let (mean, sd) = mean_sd(&values);
+                (mean != 0.0).then(|| (sd / mean).abs() * 100.0)
+            }).collect();
+            let cv = (!cvs.is_empty()).then(|| cvs.iter().sum::<f64>() / cvs.len() as f64);
+            let blanks = values(&sets[0].1);
+            let blank_sd = (blanks.len() > 1).then(|| mean_sd(&blanks).1);
+            (cv, blank_sd)
+        };
+        let (cv_before, blank_sd_before) = spread(false);
+        let (cv_after, blank_sd_after) = spread(true);
+        DriftComparison { cv_before, cv_after, blank_sd_before, blank_sd_after }
+    }
+}
@@ -0,0 +1,133 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{audit::*, history::mean_sd, i18n::*, linear_range::RangeViolation, logistic_regression::*};
+
+/// Automatic well exclusion, proposed in a preview and only applied once accepted
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExclusionRules {
+    pub replicate_cv: Option<f64>, // %, the replicate furthest from the mean goes above this CV
+    pub linear_range: bool,        // wells read outside the linear range of the reader, set in the settings
+    pub reasons: Vec<String>,      // one of which is asked for when a well is excluded by hand, none to not ask
+}
+
+impl Default for ExclusionRules {
+    fn default() -> Self {
+        Self {
+            replicate_cv: Some(20.0),
+            linear_range: true,
+            reasons: default_exclusion_reasons(),
+        }
+    }
+}
+
+/// In the language the settings were first created in, they can be edited like any other reason
+pub fn default_exclusion_reasons() -> Vec<String> {
+    ["exclusion-reason-bubble", "exclusion-reason-contamination", "exclusion-reason-pipetting-error", "exclusion-reason-outlier"]
+        .into_iter()
+        .map(tr)
+        .collect()
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum ExclusionReason {
+    ReplicateOutlier { cv: f64 },
+    Saturated { value: f64 }, // above the linear range
+    BelowLinearRange { value: f64 },
+    Chosen { reason: String }, // from the list of reasons when excluded by hand
+}
+
+impl ExclusionReason {
+    pub fn description(&self) -> String {
+        match self {
+            Self::ReplicateOutlier { cv } => tr_args("exclusion-replicate-outlier", &[("cv", format_decimal(*cv, 1).into())]),
+            Self::Saturated { value } => tr_args("exclusion-saturated", &[("value", format_value(*value).into())]),
+            Self::BelowLinearRange { value } => tr_args("exclusion-below-linear-range", &[("value", format_value(*value).into())]),
+            Self::Chosen { reason } => reason.clone(),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Exclusion {
+    pub well: usize,
+    pub reason: ExclusionReason,
+    pub accepted: bool,
+}
+
+/// Wells the rules would exclude, wells outside the linear range (low, high) of the reader first, then at most one
+/// outlier per replicate group. Groups need at least 3 replicates for an outlier, of 2 neither is further from the mean.
+pub fn propose_exclusions(microplate: &Microplate, rules: &ExclusionRules, linear_range: Option<(f64, f64)>) -> Vec<Exclusion> {
+    let mut exclusions = Vec::new();
+    // wells outside the analyzed region are left alone like excluded ones
+    let mut excluded: Vec<bool> = microplate.samples.iter().enumerate().map(|(i, sample)| sample.excluded || !microplate.in_region(i)).collect();
+
+    if let Some(range) = linear_range.filter(|_| rules.linear_range) {
+        for (well, sample) in microplate.samples.iter().enumerate() {
+            if sample.typ == SampleType::Unused || excluded[well] { continue }
+            let reason = match microplate.linear_range_violation(well, range) {
+                Some(RangeViolation::Above(value)) => ExclusionReason::Saturated { value },
+                Some(RangeViolation::Below(value)) => ExclusionReason::BelowLinearRange { value },
+                None => continue,
+            };
+            excluded[well] = true;
+            exclusions.push(Exclusion { well, reason, accepted: true });
+        }
+    }
+
+    if let Some(cv_limit) = rules.replicate_cv {
+        // blanks and controls are one group each, whatever their group index
+        let key = |sample: &Sample| match sample.typ {
+            SampleType::Standard | SampleType::Unknown => (sample.typ, sample.group),
+            typ => (typ, 0),
+        };
+        let mut groups: Vec<(SampleType, usize)> = microplate.samples.iter()
+            .filter(|sample| sample.typ != SampleType::Unused)
+            .map(key)
+            .collect();
+        groups.sort_by_key(|&(typ, group)| (typ as usize, group));
+        groups.dedup();
+
+        for group in groups {
+            let replicates: Vec<(usize, f64)> = microplate.samples.iter().enumerate()
+                .filter(|&(well, sample)| key(sample) == group && !excluded[well])
+                .filter_map(|(well, sample)| sample.value.map(|value| (well, value)))
+                .collect();
+            if replicates.len() < 3 { continue }
+
+            let values: Vec<f64> = replicates.iter().map(|&(_well, value)| value).collect();
+            let (mean, sd) = mean_sd(&values);
+            if mean == 0.0 { continue }
+            let cv = (sd / mean).abs() * 100.0;
+            if cv <= cv_limit { continue }
+
+            let Some(&(well, _value)) = replicates.iter()
+                .max_by(|(_a, a), (_b, b)| (a - mean).abs().total_cmp(&(b - mean).abs())) else { continue };
+            exclusions.push(Exclusion { well, reason: ExclusionReason::ReplicateOutlier { cv }, accepted: true });
+        }
+    }
+
+    exclusions
+}
+
+impl Microplate {
+    /// Excludes the accepted wells, with the reason in the audit trail
+    pub fn apply_exclusions(&mut self, exclusions: &[Exclusion], snapshot: &mut Option<AuditSnapshot>, operator: &str) {
+        self.audit_changes(snapshot, operator);
+        for exclusion in exclusions.iter().filter(|exclusion| exclusion.accepted) {
+            self.samples[exclusion.well].excluded = true;
+            self.samples[exclusion.well].exclusion_reason = Some(exclusion.reason.clone());
+            let well = self.well_name(exclusion.well);
+            self.audit_trail.push(AuditEntry::new(operator, AuditAction::WellExcluded { well, reason: Some(exclusion.reason.clone()) }));
+        }
+        *snapshot = Some(AuditSnapshot::new(self));
+    }
+
+    /// Excluded wells with why, in the order of the wells
+    pub fn excluded_wells(&self) -> Vec<(usize, Option<&ExclusionReason>)> {
+        self.samples.iter().enumerate()
+            .filter(|(_i, sample)| sample.excluded && sample.typ != SampleType::Unused)
+            .map(|(i, sample)| (i, sample.exclusion_reason.as_ref()))
+            .collect()
+    }
+}
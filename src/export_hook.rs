@@ -0,0 +1,138 @@
+use std::{fmt::Display, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{i18n::*, protocol::QcCheck};
+
+/// Runs after a fit, so the results reach the data management system without anyone saving them. Saved with the settings.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExportHook {
+    pub enabled: bool,
+    pub trigger: HookTrigger,
+    pub folder: Option<PathBuf>, // where the exports go, e.g. a network share, None for a temporary folder
+    pub results_csv: bool,
+    pub analysis_json: bool,
+    pub report_pdf: bool,
+    pub command: String, // run once the exports are written, empty for none. See `command_arguments`.
+}
+
+impl Default for ExportHook {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            trigger: HookTrigger::QcPassed,
+            folder: None,
+            results_csv: true,
+            analysis_json: true,
+            report_pdf: false,
+            command: String::new(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HookTrigger {
+    #[default]
+    QcPassed, // every check of the protocol of the plate, or of the default protocol
+    Always,
+}
+
+impl HookTrigger {
+    pub const ALL: [Self; 2] = [Self::QcPassed, Self::Always];
+
+    pub fn description(&self) -> String {
+        let id = match self {
+            Self::QcPassed => "hook-qc-passed",
+            Self::Always => "hook-always",
+        };
+        tr(id)
+    }
+
+    pub fn fires(&self, checks: &[QcCheck]) -> bool {
+        match self {
+            Self::QcPassed => checks.iter().all(|check| check.passed),
+            Self::Always => true,
+        }
+    }
+}
+
+/// What the hook wrote for a plate, for the placeholders of the command
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct HookFiles {
+    pub plate: String,
+    pub folder: PathBuf,
+    pub csv: Option<PathBuf>,
+    pub json: Option<PathBuf>,
+    pub pdf: Option<PathBuf>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum HookError {
+    Write(String),       // an export could not be written
+    UnclosedQuote,       // in the command
+    Start(String),       // the command could not be started
+    Failed(String, i32), // the program and its exit code
+}
+
+impl Display for HookError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let error = match self {
+            Self::Write(error) => tr_args("hook-write-error", &[("error", error.as_str().into())]),
+            Self::UnclosedQuote => tr("hook-unclosed-quote"),
+            Self::Start(error) => tr_args("hook-start-error", &[("error", error.as_str().into())]),
+            Self::Failed(program, code) => tr_args("hook-failed", &[("program", program.as_str().into()), ("code", (*code).into())]),
+        };
+        write!(f, "{}", error)
+    }
+}
+
+/// The plate name as a file name, characters that some file systems don't take become "_"
+pub fn export_name(plate: &str) -> String {
+    let name: String = plate.trim().chars()
+        .map(|c| if c.is_control() || r#"/\:*?"<>|"#.contains(c) { '_' } else { c })
+        .collect();
+    if name.is_empty() { "Plate".to_string() } else { name }
+}
+
+/// The program and its arguments, split at spaces outside of double or single quotes. `{csv}`, `{json}`, `{pdf}`,
+/// `{folder}` and `{plate}` are replaced in every argument, with nothing for exports that weren't written.
+/// No shell runs the command, so a plate name can't inject another one. A plate name that starts with "-" gets a "_"
+/// in front, so it can't pass an option to the program either.
+pub fn command_arguments(command: &str, files: &HookFiles) -> Result<Vec<String>, HookError> {
+    let mut arguments = Vec::new();
+    let mut argument = String::new();
+    let mut quote = None;
+    let mut started = false; // "" is an argument too
+    for c in command.chars() {
+        match (quote, c) {
+            (Some(open), c) if c == open => quote = None,
+            (Some(_), c) => argument.push(c),
+            (None, '"' | '\'') => {
+                quote = Some(c);
+                started = true;
+            },
+            (None, c) if c.is_whitespace() => if started || !argument.is_empty() {
+                arguments.push(std::mem::take(&mut argument));
+                started = false;
+            },
+            (None, c) => argument.push(c),
+        }
+    }
+    if quote.is_some() { return Err(HookError::UnclosedQuote) }
+    if started || !argument.is_empty() {
+        arguments.push(argument);
+    }
+
+    let path = |path: &Option<PathBuf>| path.as_ref().map(|path| path.display().to_string()).unwrap_or_default();
+    let placeholders = [
+        ("{csv}", path(&files.csv)),
+        ("{json}", path(&files.json)),
+        ("{pdf}", path(&files.pdf)),
+        ("{folder}", files.folder.display().to_string()),
+        ("{plate}", if files.plate.starts_with('-') { format!("_{}", files.plate) } else { files.plate.clone() }),
+    ];
+    Ok(arguments.into_iter().map(|argument| {
+        placeholders.iter().fold(argument, |argument, (placeholder, value)| argument.replace(placeholder, value))
+    }).collect())
+}
@@ -0,0 +1,301 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{i18n::*, logistic_regression::*, results_filter::unknown_cv};
+
+/// Values of an unknown that derived columns can use
+pub const VARIABLES: [&str; 7] = ["conc", "dilution", "corrected", "raw", "cv", "recovery", "spike"];
+
+const FUNCTIONS: [(&str, usize); 7] = [("abs", 1), ("sqrt", 1), ("ln", 1), ("log", 1), ("exp", 1), ("min", 2), ("max", 2)];
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ExpressionError {
+    Empty,
+    UnexpectedEnd,
+    Unexpected(String),
+    UnknownName(String),
+    UnknownFunction(String),
+    WrongArguments(String, usize),
+    InvalidColumnName(String),
+}
+
+impl Display for ExpressionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let error = match self {
+            Self::Empty => tr("expression-empty"),
+            Self::UnexpectedEnd => tr("expression-unexpected-end"),
+            Self::Unexpected(text) => tr_args("expression-unexpected", &[("text", text.as_str().into())]),
+            Self::UnknownName(name) => tr_args("expression-unknown-name", &[("name", name.as_str().into())]),
+            Self::UnknownFunction(name) => tr_args("expression-unknown-function", &[("name", name.as_str().into())]),
+            Self::WrongArguments(name, count) => tr_args("expression-wrong-arguments", &[("name", name.as_str().into()), ("count", (*count).into())]),
+            Self::InvalidColumnName(name) => tr_args("expression-invalid-column-name", &[("name", name.as_str().into())]),
+        };
+        write!(f, "{}", error)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Number(f64),
+    Name(String),
+    Symbol(char),
+}
+
+/// Numbers always use a decimal point, commas separate the arguments of functions
+fn tokenize(text: &str) -> Result<Vec<Token>, ExpressionError> {
+    let mut tokens = Vec::new();
+    let mut chars = text.char_indices().peekable();
+    while let Some(&(start, char)) = chars.peek() {
+        if char.is_whitespace() {
+            chars.next();
+        } else if char.is_ascii_digit() || char == '.' {
+            let mut end = start;
+            while let Some(&(index, char)) = chars.peek() {
+                // exponents like 1e-3 take their sign along
+                let sign = (char == '-' || char == '+') && text[..index].ends_with(['e', 'E']);
+                if !(char.is_ascii_digit() || char == '.' || char == 'e' || char == 'E' || sign) { break }
+                end = index + char.len_utf8();
+                chars.next();
+            }
+            let number = &text[start..end];
+            tokens.push(Token::Number(number.parse().map_err(|_| ExpressionError::Unexpected(number.to_string()))?));
+        } else if char.is_alphabetic() || char == '_' {
+            let mut end = start;
+            while let Some(&(index, char)) = chars.peek() {
+                if !(char.is_alphanumeric() || char == '_') { break }
+                end = index + char.len_utf8();
+                chars.next();
+            }
+            tokens.push(Token::Name(text[start..end].to_string()));
+        } else if "+-*/^(),".contains(char) {
+            tokens.push(Token::Symbol(char));
+            chars.next();
+        } else {
+            return Err(ExpressionError::Unexpected(char.to_string()))
+        }
+    }
+    Ok(tokens)
+}
+
+/// Arithmetic on the values of an unknown, e.g. `conc * dilution / 50`
+#[derive(Clone, Debug, PartialEq)]
+pub enum Expression {
+    Number(f64),
+    Variable(String),
+    Negate(Box<Expression>),
+    Binary(char, Box<Expression>, Box<Expression>),
+    Call(String, Vec<Expression>),
+}
+
+/// Recursive descent over the tokens, with the usual precedence: ^ before * and / before + and -
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Result<Token, ExpressionError> {
+        let token = self.tokens.get(self.position).cloned().ok_or(ExpressionError::UnexpectedEnd)?;
+        self.position += 1;
+        Ok(token)
+    }
+
+    fn eat(&mut self, symbol: char) -> bool {
+        let found = self.peek() == Some(&Token::Symbol(symbol));
+        if found {
+            self.position += 1;
+        }
+        found
+    }
+
+    fn expect(&mut self, symbol: char) -> Result<(), ExpressionError> {
+        match self.next()? {
+            Token::Symbol(found) if found == symbol => Ok(()),
+            token => Err(ExpressionError::Unexpected(token_text(&token))),
+        }
+    }
+
+    fn sum(&mut self) -> Result<Expression, ExpressionError> {
+        let mut expression = self.product()?;
+        while let Some(&Token::Symbol(operator @ ('+' | '-'))) = self.peek() {
+            self.position += 1;
+            expression = Expression::Binary(operator, Box::new(expression), Box::new(self.product()?));
+        }
+        Ok(expression)
+    }
+
+    fn product(&mut self) -> Result<Expression, ExpressionError> {
+        let mut expression = self.unary()?;
+        while let Some(&Token::Symbol(operator @ ('*' | '/'))) = self.peek() {
+            self.position += 1;
+            expression = Expression::Binary(operator, Box::new(expression), Box::new(self.unary()?));
+        }
+        Ok(expression)
+    }
+
+    fn unary(&mut self) -> Result<Expression, ExpressionError> {
+        if self.eat('-') {
+            return Ok(Expression::Negate(Box::new(self.unary()?)))
+        }
+        let base = self.atom()?;
+        // right associative, 2^3^2 is 2^9
+        if self.eat('^') {
+            return Ok(Expression::Binary('^', Box::new(base), Box::new(self.unary()?)))
+        }
+        Ok(base)
+    }
+
+    fn atom(&mut self) -> Result<Expression, ExpressionError> {
+        match self.next()? {
+            Token::Number(number) => Ok(Expression::Number(number)),
+            Token::Name(name) if self.eat('(') => {
+                let mut arguments = vec![self.sum()?];
+                while self.eat(',') {
+                    arguments.push(self.sum()?);
+                }
+                self.expect(')')?;
+                let Some(&(_, count)) = FUNCTIONS.iter().find(|(function, _)| *function == name) else {
+                    return Err(ExpressionError::UnknownFunction(name))
+                };
+                if arguments.len() != count { return Err(ExpressionError::WrongArguments(name, count)) }
+                Ok(Expression::Call(name, arguments))
+            },
+            Token::Name(name) => Ok(Expression::Variable(name)),
+            Token::Symbol('(') => {
+                let expression = self.sum()?;
+                self.expect(')')?;
+                Ok(expression)
+            },
+            token => Err(ExpressionError::Unexpected(token_text(&token))),
+        }
+    }
+}
+
+fn token_text(token: &Token) -> String {
+    match token {
+        Token::Number(number) => number.to_string(),
+        Token::Name(name) => name.clone(),
+        Token::Symbol(symbol) => symbol.to_string(),
+    }
+}
+
+impl Expression {
+    pub fn parse(text: &str) -> Result<Self, ExpressionError> {
+        let tokens = tokenize(text)?;
+        if tokens.is_empty() { return Err(ExpressionError::Empty) }
+        let mut parser = Parser { tokens, position: 0 };
+        let expression = parser.sum()?;
+        match parser.peek() {
+            Some(token) => Err(ExpressionError::Unexpected(token_text(token))),
+            None => Ok(expression),
+        }
+    }
+
+    /// Names of the variables, in order of appearance
+    pub fn variables(&self) -> Vec<&str> {
+        match self {
+            Self::Number(_) => Vec::new(),
+            Self::Variable(name) => vec![name],
+            Self::Negate(expression) => expression.variables(),
+            Self::Binary(_, left, right) => [left.variables(), right.variables()].concat(),
+            Self::Call(_, arguments) => arguments.iter().flat_map(Self::variables).collect(),
+        }
+    }
+
+    /// None if a variable has no value or the result isn't a number, e.g. after dividing by zero
+    pub fn evaluate(&self, variable: &impl Fn(&str) -> Option<f64>) -> Option<f64> {
+        let result = match self {
+            Self::Number(number) => *number,
+            Self::Variable(name) => variable(name)?,
+            Self::Negate(expression) => -expression.evaluate(variable)?,
+            Self::Binary(operator, left, right) => {
+                let (left, right) = (left.evaluate(variable)?, right.evaluate(variable)?);
+                match operator {
+                    '+' => left + right,
+                    '-' => left - right,
+                    '*' => left * right,
+                    '/' => left / right,
+                    _ => left.powf(right),
+                }
+            },
+            Self::Call(name, arguments) => {
+                let arguments = arguments.iter().map(|argument| argument.evaluate(variable)).collect::<Option<Vec<f64>>>()?;
+                match (name.as_str(), arguments.as_slice()) {
+                    ("abs", [x]) => x.abs(),
+                    ("sqrt", [x]) => x.sqrt(),
+                    ("ln", [x]) => x.ln(),
+                    ("log", [x]) => x.log10(),
+                    ("exp", [x]) => x.exp(),
+                    ("min", [x, y]) => x.min(*y),
+                    ("max", [x, y]) => x.max(*y),
+                    _ => return None,
+                }
+            },
+        };
+        Some(result).filter(|result| result.is_finite())
+    }
+}
+
+/// Extra result column defined by a protocol, e.g. `per_ml = corrected / 0.05`
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct DerivedColumn {
+    pub name: String,
+    pub expression: String,
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|char| char.is_alphabetic() || char == '_') && chars.all(|char| char.is_alphanumeric() || char == '_')
+}
+
+fn unknown_variable(unknown: &UnknownResult, name: &str) -> Option<f64> {
+    let concentration = Some(unknown.concentration).filter(|concentration| concentration.is_finite());
+    match name {
+        "conc" => concentration,
+        "dilution" => Some(unknown.dilution),
+        "corrected" => concentration.map(|_| unknown.corrected_concentration()),
+        "raw" => Some(unknown.measurement),
+        "cv" => unknown_cv(unknown),
+        "recovery" => unknown.recovery(),
+        "spike" => unknown.spike,
+        _ => None,
+    }
+}
+
+/// Parses every column, which can use the values of the unknown and the columns before it.
+/// The error comes with the index of the first column that is wrong.
+pub fn parse_columns(columns: &[DerivedColumn]) -> Result<Vec<Expression>, (usize, ExpressionError)> {
+    let mut expressions = Vec::new();
+    for (index, column) in columns.iter().enumerate() {
+        let name = column.name.trim();
+        let known = |name: &str| VARIABLES.contains(&name) || columns[..index].iter().any(|column| column.name.trim() == name);
+        if !is_identifier(name) || known(name) {
+            return Err((index, ExpressionError::InvalidColumnName(name.to_string())))
+        }
+        let expression = Expression::parse(&column.expression).map_err(|error| (index, error))?;
+        if let Some(unknown) = expression.variables().into_iter().find(|&variable| !known(variable)) {
+            return Err((index, ExpressionError::UnknownName(unknown.to_string())))
+        }
+        expressions.push(expression);
+    }
+    Ok(expressions)
+}
+
+/// Value of every column for an unknown, empty if the protocol has a column that doesn't parse
+pub fn derived_values(columns: &[DerivedColumn], unknown: &UnknownResult) -> Vec<Option<f64>> {
+    let Ok(expressions) = parse_columns(columns) else { return Vec::new() };
+    let mut values: Vec<Option<f64>> = Vec::new();
+    for expression in &expressions {
+        let value = expression.evaluate(&|name: &str| match columns.iter().position(|column| column.name.trim() == name) {
+            Some(index) if index < values.len() => values[index],
+            _ => unknown_variable(unknown, name),
+        });
+        values.push(value);
+    }
+    values
+}
@@ -0,0 +1,134 @@
+use std::{cell::RefCell, io, rc::Rc};
+
+use eframe::egui::Context;
+
+/// Saved assays, older ones are plain `.json`
+pub const PROJECT_EXTENSIONS: [&str; 2] = ["elisa", "json"];
+
+/// What a picked file is for, the file arrives a few frames later on the web
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FileAction {
+    LoadAssay,
+    ImportLayout,
+    CopyLayout,
+    Measurements,
+    ReferenceCurve,
+    ComparePlate,
+    Manifest,
+    ReferenceRead,
+    PathLengthRead,
+}
+
+pub struct OpenedFile {
+    pub action: FileAction,
+    pub name: String,
+    pub bytes: Vec<u8>,
+    #[cfg(not(target_arch = "wasm32"))]
+    pub path: std::path::PathBuf,
+}
+
+/// Files picked with the native dialog or the browser file picker, until the app handles them
+#[derive(Clone, Default)]
+pub struct OpenedFiles {
+    files: Rc<RefCell<Vec<io::Result<OpenedFile>>>>,
+}
+
+impl OpenedFiles {
+    /// Filters are a name and the extensions without a dot
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn pick(&self, _ctx: &Context, action: FileAction, filters: &[(String, &[&str])]) {
+        let dialog = filters.iter().fold(rfd::FileDialog::new(), |dialog, (name, extensions)| dialog.add_filter(name, extensions));
+        if let Some(path) = dialog.pick_file() {
+            self.open_path(action, path);
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub fn pick(&self, ctx: &Context, action: FileAction, filters: &[(String, &[&str])]) {
+        let dialog = filters.iter().fold(rfd::AsyncFileDialog::new(), |dialog, (name, extensions)| dialog.add_filter(name, extensions));
+        let files = self.files.clone();
+        let ctx = ctx.clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            let Some(file) = dialog.pick_file().await else { return };
+            let opened = OpenedFile { action, name: file.file_name(), bytes: file.read().await };
+            files.borrow_mut().push(Ok(opened));
+            ctx.request_repaint();
+        });
+    }
+
+    /// A file named on the command line, e.g. by the file manager when a project is double-clicked
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn open_path(&self, action: FileAction, path: std::path::PathBuf) {
+        let opened = std::fs::read(&path).map(|bytes| OpenedFile {
+            action,
+            name: path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default(),
+            bytes,
+            path,
+        });
+        self.files.borrow_mut().push(opened);
+    }
+
+    pub fn take(&self) -> Vec<io::Result<OpenedFile>> {
+        std::mem::take(&mut *self.files.borrow_mut())
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+thread_local! {
+    static EXPORT_FOLDER: RefCell<Option<std::path::PathBuf>> = const { RefCell::new(None) };
+}
+
+/// Folder the save dialogs start in, the system picks one if it's None
+#[cfg(not(target_arch = "wasm32"))]
+pub fn set_export_folder(folder: Option<std::path::PathBuf>) {
+    EXPORT_FOLDER.set(folder);
+}
+
+/// Asks where to save and writes the file, false if the dialog was cancelled and nothing was written
+#[cfg(not(target_arch = "wasm32"))]
+pub fn save_file(name: &str, filter: (&str, &[&str]), contents: &[u8]) -> io::Result<bool> {
+    let mut dialog = rfd::FileDialog::new()
+        .add_filter(filter.0, filter.1)
+        .set_file_name(name);
+    if let Some(folder) = EXPORT_FOLDER.with_borrow(|folder| folder.clone()) {
+        dialog = dialog.set_directory(folder);
+    }
+    let Some(path) = dialog.save_file() else { return Ok(false) };
+    std::fs::write(path, contents)?;
+    Ok(true)
+}
+
+/// Downloads the file, the browser decides where it goes
+#[cfg(target_arch = "wasm32")]
+pub fn save_file(name: &str, filter: (&str, &[&str]), contents: &[u8]) -> io::Result<bool> {
+    use eframe::wasm_bindgen::JsCast;
+
+    let error = |error| io::Error::other(format!("{error:?}"));
+    let name = match filter.1.first() {
+        Some(extension) if !name.ends_with(&format!(".{extension}")) => format!("{name}.{extension}"),
+        _ => name.to_string(),
+    };
+    let parts = js_sys::Array::of1(&js_sys::Uint8Array::from(contents));
+    let blob = web_sys::Blob::new_with_u8_array_sequence(&parts).map_err(error)?;
+    let url = web_sys::Url::create_object_url_with_blob(&blob).map_err(error)?;
+    let document = web_sys::window().and_then(|window| window.document())
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no document"))?;
+    let anchor = document.create_element("a").map_err(error)?
+        .dyn_into::<web_sys::HtmlAnchorElement>().map_err(|element| error(element.into()))?;
+    anchor.set_href(&url);
+    anchor.set_download(&name);
+    anchor.click();
+    web_sys::Url::revoke_object_url(&url).map_err(error)?;
+    Ok(true)
+}
+
+/// Writes a changed file back where it was opened, on the web it's downloaded again under its name
+#[cfg(not(target_arch = "wasm32"))]
+pub fn write_back(file: &OpenedFile, contents: &[u8]) -> io::Result<()> {
+    std::fs::write(&file.path, contents)
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn write_back(file: &OpenedFile, contents: &[u8]) -> io::Result<()> {
+    save_file(&file.name, ("", &[]), contents).map(|_written| ())
+}
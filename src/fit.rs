@@ -0,0 +1,120 @@
+use crate::logistic_regression::*;
+
+/// Curve fitted to the standards
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Model {
+    #[default]
+    FourPL, // y = d + (a - d) / (1 + (x / c)^b)
+}
+
+/// Fits a standard curve from other programs, without a plate or the app:
+///
+/// ```
+/// use elisa::{fit::*, logistic_regression::Weighting};
+///
+/// let fit = FitBuilder::new()
+///     .standards(&[(1.0, 0.08), (3.0, 0.2), (10.0, 0.55), (30.0, 1.2), (100.0, 1.9), (300.0, 2.3)])
+///     .weighting(Weighting::OneOverYSquared)
+///     .model(Model::FourPL)
+///     .fit()?;
+/// let concentration = fit.inverse(1.0).unwrap();
+/// assert!((fit.evaluate(concentration) - 1.0).abs() < 1e-9);
+/// # Ok::<(), elisa::logistic_regression::RegressionError>(())
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct FitBuilder {
+    standards: Vec<(f64, f64)>,
+    weighting: Weighting,
+    model: Model,
+    extra_starts: usize,
+}
+
+impl FitBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// (concentration, signal) of each standard, the signal blank corrected. Replicates can be given as separate points.
+    pub fn standards(mut self, standards: &[(f64, f64)]) -> Self {
+        self.standards = standards.to_vec();
+        self
+    }
+
+    pub fn weighting(mut self, weighting: Weighting) -> Self {
+        self.weighting = weighting;
+        self
+    }
+
+    pub fn model(mut self, model: Model) -> Self {
+        self.model = model;
+        self
+    }
+
+    /// Initial guesses to fit from, the usual one and perturbed ones, the fit with the lowest loss is kept. 1 by default.
+    pub fn starts(mut self, starts: usize) -> Self {
+        self.extra_starts = starts.saturating_sub(1);
+        self
+    }
+
+    pub fn fit(&self) -> Result<Fit, RegressionError> {
+        let regression = match self.model {
+            Model::FourPL => {
+                let options = FitOptions { weighting: self.weighting, extra_starts: self.extra_starts, ..Default::default() };
+                Regression::from_standards_with_options(&self.standards, options)?
+            },
+        };
+        let Regression { abcd: (a, b, c, d), sse, mse, rmse, sy_x, r_sq, .. } = regression;
+        Ok(Fit {
+            model: self.model,
+            parameters: FourPLParameters { a, b, c, d },
+            sse,
+            mse,
+            rmse,
+            sy_x,
+            r_sq,
+            diagnostics: regression.diagnostics,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FourPLParameters {
+    pub a: f64, // response at zero dose
+    pub b: f64, // slope at the inflection point
+    pub c: f64, // inflection point, the EC50
+    pub d: f64, // response at infinite dose
+}
+
+/// The fitted curve with its goodness of fit
+#[derive(Clone, Debug)]
+pub struct Fit {
+    pub model: Model,
+    pub parameters: FourPLParameters,
+    pub sse: f64,
+    pub mse: f64,
+    pub rmse: f64,
+    pub sy_x: f64,
+    pub r_sq: f64,
+    pub diagnostics: FitDiagnostics,
+}
+
+impl Fit {
+    /// Signal of the curve at a concentration
+    pub fn evaluate(&self, x: f64) -> f64 {
+        let FourPLParameters { a, b, c, d } = self.parameters;
+        d + ((a - d) / (1.0 + (x/c).powf(b)))
+    }
+
+    /// a, b, c, d with their standard errors, 95 % confidence intervals and p-values,
+    /// None with too few standards to estimate them
+    pub fn parameter_estimates(&self) -> Option<[ParameterEstimate; 4]> {
+        let FourPLParameters { a, b, c, d } = self.parameters;
+        self.diagnostics.parameter_estimates((a, b, c, d))
+    }
+
+    /// Concentration at a signal, None past the asymptotes where the curve never gets to it
+    pub fn inverse(&self, y: f64) -> Option<f64> {
+        let FourPLParameters { a, b, c, d } = self.parameters;
+        Some(c * ((a - d) / (y - d) - 1.0).powf(1.0 / b)).filter(|x| x.is_finite())
+    }
+}
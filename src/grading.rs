@@ -0,0 +1,99 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{i18n::*, logistic_regression::*};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum CurveGrade {
+    A,
+    B,
+    C,
+    F,
+}
+
+impl CurveGrade {
+    pub const ALL: [Self; 4] = [Self::A, Self::B, Self::C, Self::F];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::A => "A",
+            Self::B => "B",
+            Self::C => "C",
+            Self::F => "F",
+        }
+    }
+
+    /// As RGB
+    pub fn color(&self) -> [u8; 3] {
+        match self {
+            Self::A => [40, 150, 70],
+            Self::B => [110, 160, 40],
+            Self::C => [215, 150, 20],
+            Self::F => [210, 50, 50],
+        }
+    }
+}
+
+/// Grade of the standard curve from its R², its Sy.x as a share of the signal range and how many standards
+/// are found again within 80–120 % when read back through the curve, anchors left out. The worst of the three is the grade.
+/// With 4 standard points the curve goes through all of them and has no Sy.x, it's graded on the other two.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CurveQuality {
+    pub grade: CurveGrade,
+    pub r_sq: f64,
+    pub sy_x_percent: Option<f64>, // of the span between the asymptotes, None without degrees of freedom left
+    pub recovered: usize,  // standards back-calculated within the recovery range
+    pub standards: usize,  // without the anchors
+}
+
+impl CurveQuality {
+    pub fn new(regression: &Regression) -> Self {
+        let (a, _b, _c, d) = regression.abcd;
+        let span = (d - a).abs();
+        let sy_x_percent = (regression.standards.len() > 4).then(|| if span > 0.0 { regression.sy_x / span * 100.0 } else { f64::INFINITY });
+        let back_calculated: Vec<_> = regression.back_calculated_standards().into_iter().filter(|standard| !standard.anchor).collect();
+        let recovered = back_calculated.iter().filter(|standard| standard.passed()).count();
+        let standards = back_calculated.len();
+
+        let r_sq_grade = match regression.r_sq {
+            r_sq if r_sq >= 0.99 => CurveGrade::A,
+            r_sq if r_sq >= 0.98 => CurveGrade::B,
+            r_sq if r_sq >= 0.95 => CurveGrade::C,
+            _ => CurveGrade::F,
+        };
+        let sy_x_grade = match sy_x_percent.unwrap_or_default() {
+            percent if percent <= 5.0 => CurveGrade::A,
+            percent if percent <= 10.0 => CurveGrade::B,
+            percent if percent <= 20.0 => CurveGrade::C,
+            _ => CurveGrade::F,
+        };
+        // one standard off is still a usable curve, a third of them isn't
+        let outside = standards - recovered;
+        let recovery_grade = match outside {
+            0 => CurveGrade::A,
+            1 => CurveGrade::B,
+            outside if outside * 3 <= standards => CurveGrade::C,
+            _ => CurveGrade::F,
+        };
+
+        Self {
+            grade: r_sq_grade.max(sy_x_grade).max(recovery_grade),
+            r_sq: regression.r_sq,
+            sy_x_percent,
+            recovered,
+            standards,
+        }
+    }
+
+    /// What the grade is based on, e.g. for the report summary
+    pub fn summary(&self) -> String {
+        tr_args("curve-grade-summary", &[
+            ("grade", self.grade.name().into()),
+            ("r_sq", format_decimal(self.r_sq, 4).into()),
+            ("sy_x", self.sy_x_percent.map(|percent| format_decimal(percent, 1)).unwrap_or_else(|| "none".to_string()).into()),
+            ("recovered", self.recovered.into()),
+            ("standards", self.standards.into()),
+            ("min", format_number(*RECOVERY_RANGE.start()).into()),
+            ("max", format_number(*RECOVERY_RANGE.end()).into()),
+        ])
+    }
+}
@@ -0,0 +1,68 @@
+use crate::logistic_regression::*;
+
+impl Microplate {
+    /// Standard or unknown groups, None for the other sample types
+    pub fn groups_mut(&mut self, typ: SampleType) -> Option<&mut Vec<Group>> {
+        match typ {
+            SampleType::Standard => Some(&mut self.standard_groups),
+            SampleType::Unknown => Some(&mut self.unknown_groups),
+            _ => None,
+        }
+    }
+
+    pub fn group_wells(&self, typ: SampleType, group: usize) -> usize {
+        self.samples.iter().filter(|sample| sample.typ == typ && sample.group == group).count()
+    }
+
+    pub fn add_group(&mut self, typ: SampleType) {
+        let Some(groups) = self.groups_mut(typ) else { return };
+        if groups.len() < MAX_GROUPS {
+            groups.push(Group::default());
+        }
+    }
+
+    /// Removes a group, its wells become unused and the wells of the groups after it move up by one
+    pub fn delete_group(&mut self, typ: SampleType, group: usize) {
+        let Some(groups) = self.groups_mut(typ) else { return };
+        if group >= groups.len() { return }
+        groups.remove(group);
+        // like a new microplate, there is always at least one group
+        if groups.is_empty() {
+            groups.push(Group::default());
+        }
+
+        for sample in self.samples.iter_mut().filter(|sample| sample.typ == typ) {
+            if sample.group == group {
+                *sample = Sample { value: sample.value, reference: sample.reference, path_length_read: sample.path_length_read, ..Sample::default() };
+            } else if sample.group > group {
+                sample.group -= 1;
+            }
+        }
+    }
+
+    /// Swaps two groups along with their wells
+    pub fn swap_groups(&mut self, typ: SampleType, a: usize, b: usize) {
+        let Some(groups) = self.groups_mut(typ) else { return };
+        if a >= groups.len() || b >= groups.len() { return }
+        groups.swap(a, b);
+
+        for sample in self.samples.iter_mut().filter(|sample| sample.typ == typ) {
+            if sample.group == a {
+                sample.group = b;
+            } else if sample.group == b {
+                sample.group = a;
+            }
+        }
+    }
+
+    /// Moves the wells of `from` into `into` and removes `from`. `into` keeps its label, concentration and dilution.
+    pub fn merge_groups(&mut self, typ: SampleType, from: usize, into: usize) {
+        let Some(groups) = self.groups_mut(typ) else { return };
+        if from == into || from >= groups.len() || into >= groups.len() { return }
+
+        for sample in self.samples.iter_mut().filter(|sample| sample.typ == typ && sample.group == from) {
+            sample.group = into;
+        }
+        self.delete_group(typ, from);
+    }
+}
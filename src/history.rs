@@ -0,0 +1,196 @@
+use std::{io, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{i18n::*, logistic_regression::*, protocol::replicate_cvs};
+
+/// A fitted standard curve with the QC of its run, kept to monitor an assay over time
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CurveRecord {
+    pub kit_lot: String,
+    pub date: String,
+    pub plate: String,
+    pub abcd: (f64, f64, f64, f64),
+    pub r_sq: f64,
+    #[serde(default)]
+    pub blank_mean: Option<f64>, // as read
+    #[serde(default)]
+    pub control_mean: Option<f64>, // blank corrected
+    #[serde(default)]
+    pub max_cv: Option<f64>, // %, highest replicate CV of a standard or unknown
+}
+
+impl CurveRecord {
+    pub fn new(microplate: &Microplate, regression: &Regression) -> Self {
+        Self {
+            kit_lot: microplate.metadata.kit_lot.clone(),
+            date: chrono::offset::Local::now().format("%Y-%m-%d %H:%M").to_string(),
+            plate: microplate.name.clone(),
+            abcd: regression.abcd,
+            r_sq: regression.r_sq,
+            blank_mean: microplate.blanks().plate,
+            control_mean: regression.has_control.then_some(regression.control),
+            max_cv: replicate_cvs(microplate).into_iter().map(|(_, cv)| cv).max_by(f64::total_cmp),
+        }
+    }
+}
+
+/// What the control chart of the curve history follows over the runs
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum HistoryMetric {
+    A,
+    B,
+    #[default]
+    Ec50,
+    D,
+    RSquared,
+    BlankMean,
+    ControlMean,
+    MaxCv,
+}
+
+impl HistoryMetric {
+    pub const ALL: [Self; 8] = [Self::A, Self::B, Self::Ec50, Self::D, Self::RSquared, Self::BlankMean, Self::ControlMean, Self::MaxCv];
+
+    pub fn name(&self) -> String {
+        match self {
+            Self::A => "a".to_string(),
+            Self::B => "b".to_string(),
+            Self::Ec50 => "EC50 (c)".to_string(),
+            Self::D => "d".to_string(),
+            Self::RSquared => "R²".to_string(),
+            Self::BlankMean => tr("blank-mean"),
+            Self::ControlMean => tr("control-mean"),
+            Self::MaxCv => tr("max-cv"),
+        }
+    }
+
+    /// None for runs recorded before the metric was, or without blanks, controls or replicates
+    pub fn value(&self, record: &CurveRecord) -> Option<f64> {
+        let (a, b, c, d) = record.abcd;
+        match self {
+            Self::A => Some(a),
+            Self::B => Some(b),
+            Self::Ec50 => Some(c),
+            Self::D => Some(d),
+            Self::RSquared => Some(record.r_sq),
+            Self::BlankMean => record.blank_mean,
+            Self::ControlMean => record.control_mean,
+            Self::MaxCv => record.max_cv,
+        }
+    }
+}
+
+/// Westgard rules for control charts, each judged on a run and the runs before it
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum WestgardRule {
+    OneTwoS,    // one run beyond 2 SD, a warning
+    OneThreeS,  // one run beyond 3 SD
+    TwoTwoS,    // two runs in a row beyond 2 SD on the same side
+    RangeFourS, // two runs in a row beyond 2 SD on opposite sides
+    FourOneS,   // four runs in a row beyond 1 SD on the same side
+    TenX,       // ten runs in a row on the same side of the mean
+}
+
+impl WestgardRule {
+    pub const ALL: [Self; 6] = [Self::OneTwoS, Self::OneThreeS, Self::TwoTwoS, Self::RangeFourS, Self::FourOneS, Self::TenX];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::OneTwoS => "1-2s",
+            Self::OneThreeS => "1-3s",
+            Self::TwoTwoS => "2-2s",
+            Self::RangeFourS => "R-4s",
+            Self::FourOneS => "4-1s",
+            Self::TenX => "10x",
+        }
+    }
+
+    /// Only 1-2s is a warning, the others reject the run
+    pub fn rejects(&self) -> bool {
+        *self != Self::OneTwoS
+    }
+
+    fn violated(&self, z: &[f64]) -> bool {
+        let last = |n: usize| z.len().checked_sub(n).map(|start| &z[start..]);
+        let same_side = |values: &[f64], limit: f64| values.iter().all(|&z| z > limit) || values.iter().all(|&z| z < -limit);
+        match self {
+            Self::OneTwoS => last(1).is_some_and(|z| z[0].abs() > 2.0),
+            Self::OneThreeS => last(1).is_some_and(|z| z[0].abs() > 3.0),
+            Self::TwoTwoS => last(2).is_some_and(|z| same_side(z, 2.0)),
+            Self::RangeFourS => last(2).is_some_and(|z| (z[0] > 2.0 && z[1] < -2.0) || (z[0] < -2.0 && z[1] > 2.0)),
+            Self::FourOneS => last(4).is_some_and(|z| same_side(z, 1.0)),
+            Self::TenX => last(10).is_some_and(|z| same_side(z, 0.0)),
+        }
+    }
+}
+
+/// The rules each of the values violates, with the limits of `mean` and `sd`, in the order of the runs
+pub fn westgard_violations(values: &[f64], mean: f64, sd: f64) -> Vec<Vec<WestgardRule>> {
+    if !(sd.is_finite() && sd > 0.0) { return vec![Vec::new(); values.len()] }
+
+    let z: Vec<f64> = values.iter().map(|value| (value - mean) / sd).collect();
+    (1..=z.len()).map(|end| WestgardRule::ALL.into_iter().filter(|rule| rule.violated(&z[..end])).collect()).collect()
+}
+
+/// Directory for files that outlive a session, e.g. `~/.local/share/elisa`
+pub fn data_dir() -> Option<PathBuf> {
+    let base = if cfg!(target_os = "windows") {
+        std::env::var_os("APPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        std::env::var_os("HOME").map(|home| PathBuf::from(home).join("Library/Application Support"))
+    } else {
+        std::env::var_os("XDG_DATA_HOME").map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share")))
+    };
+    base.map(|base| base.join("elisa"))
+}
+
+/// Contents of a file in the data directory, on the web from the local storage of the browser
+#[cfg(not(target_arch = "wasm32"))]
+pub fn read_data(name: &str) -> Option<Vec<u8>> {
+    std::fs::read(data_dir()?.join(name)).ok()
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn read_data(name: &str) -> Option<Vec<u8>> {
+    let storage = web_sys::window()?.local_storage().ok()??;
+    storage.get_item(&format!("elisa/{name}")).ok()?.map(String::into_bytes)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn write_data(name: &str, contents: &str) -> io::Result<()> {
+    let Some(dir) = data_dir() else {
+        return Err(io::Error::new(io::ErrorKind::NotFound, "no data directory"))
+    };
+    std::fs::create_dir_all(&dir)?;
+    std::fs::write(dir.join(name), contents)
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn write_data(name: &str, contents: &str) -> io::Result<()> {
+    let storage = web_sys::window()
+        .and_then(|window| window.local_storage().ok().flatten())
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no local storage"))?;
+    storage.set_item(&format!("elisa/{name}"), contents)
+        .map_err(|error| io::Error::other(format!("{error:?}")))
+}
+
+pub fn load_curve_history() -> Vec<CurveRecord> {
+    read_data("curve_history.json")
+        .and_then(|buf| serde_json::from_slice(&buf).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_curve_history(history: &[CurveRecord]) -> io::Result<()> {
+    let serialized = serde_json::to_string_pretty(history).map_err(io::Error::other)?;
+    write_data("curve_history.json", &serialized)
+}
+
+/// Mean and sample standard deviation
+pub fn mean_sd(values: &[f64]) -> (f64, f64) {
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    let variance = values.iter().map(|value| (value - mean) * (value - mean)).sum::<f64>() / (n - 1.0);
+    (mean, variance.sqrt())
+}
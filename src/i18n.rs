@@ -0,0 +1,254 @@
+use std::{borrow::Cow, cell::{Cell, RefCell}};
+
+use chrono::NaiveDateTime;
+use fluent::{FluentArgs, FluentBundle, FluentResource, FluentValue};
+use serde::{Deserialize, Serialize};
+use unic_langid::LanguageIdentifier;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum Language {
+    #[default]
+    English,
+    German,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Self::English, Self::German];
+
+    /// Name of the language in itself, for the language picker
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::English => "English",
+            Self::German => "Deutsch",
+        }
+    }
+
+    fn identifier(&self) -> LanguageIdentifier {
+        let identifier = match self {
+            Self::English => "en-US",
+            Self::German => "de-DE",
+        };
+        identifier.parse().unwrap()
+    }
+
+    fn resource(&self) -> &'static str {
+        match self {
+            Self::English => include_str!("../resources/i18n/en-US.ftl"),
+            Self::German => include_str!("../resources/i18n/de-DE.ftl"),
+        }
+    }
+
+    pub fn decimal_separator(&self) -> char {
+        match self {
+            Self::English => '.',
+            Self::German => ',',
+        }
+    }
+
+    pub fn date_time_format(&self) -> &'static str {
+        match self {
+            Self::English => "%Y-%m-%d, %H:%M",
+            Self::German => "%d.%m.%Y, %H:%M",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum Precision {
+    #[default]
+    SignificantFigures,
+    DecimalPlaces,
+}
+
+impl Precision {
+    pub const ALL: [Self; 2] = [Self::SignificantFigures, Self::DecimalPlaces];
+
+    pub fn description(&self) -> String {
+        let id = match self {
+            Self::SignificantFigures => "significant-figures",
+            Self::DecimalPlaces => "decimal-places",
+        };
+        tr(id)
+    }
+}
+
+/// How fitted parameters and results are shown, values typed in by the user keep their full precision
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NumberFormat {
+    pub precision: Precision,
+    pub digits: usize,
+    pub scientific: bool, // for values below 0.001 and from 1 000 000 on
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        Self {
+            precision: Precision::SignificantFigures,
+            digits: 4,
+            scientific: true,
+        }
+    }
+}
+
+impl NumberFormat {
+    pub fn format(&self, value: f64) -> String {
+        if !value.is_finite() { return value.to_string() }
+
+        let magnitude = value.abs();
+        let scientific = self.scientific && value != 0.0 && !(1e-3..1e6).contains(&magnitude);
+        let digits = self.digits.min(15);
+        let text = match (self.precision, scientific) {
+            (Precision::SignificantFigures, true) => format!("{value:.*e}", digits.max(1) - 1),
+            (Precision::DecimalPlaces, true) => format!("{value:.digits$e}"),
+            (Precision::SignificantFigures, false) => {
+                // exponent after rounding, so 99.99 with 3 figures becomes 100, not 100.0
+                let rounded: f64 = format!("{magnitude:.*e}", digits.max(1) - 1).parse().unwrap_or(magnitude);
+                let exponent = if value == 0.0 { 0 } else { rounded.log10().floor() as i32 };
+                let decimals = (digits.max(1) as i32 - 1 - exponent).max(0) as usize;
+                format!("{value:.decimals$}")
+            },
+            (Precision::DecimalPlaces, false) => format!("{value:.digits$}"),
+        };
+        localize_separator(text)
+    }
+}
+
+struct Localizer {
+    language: Language,
+    bundle: FluentBundle<FluentResource>,
+    fallback: FluentBundle<FluentResource>, // English, for messages missing from a translation
+}
+
+fn bundle(language: Language) -> FluentBundle<FluentResource> {
+    let resource = FluentResource::try_new(language.resource().to_string())
+        .expect("translation resources are valid Fluent");
+    let mut bundle = FluentBundle::new(vec![language.identifier()]);
+    // egui and the PDF fonts would render the bidi isolation marks as boxes
+    bundle.set_use_isolating(false);
+    bundle.add_resource(resource).expect("translation resources have no duplicate messages");
+    bundle
+}
+
+impl Localizer {
+    fn new(language: Language) -> Self {
+        Self {
+            language,
+            bundle: bundle(language),
+            fallback: bundle(Language::English),
+        }
+    }
+
+    fn format(&self, id: &str, args: Option<&FluentArgs>) -> String {
+        for bundle in [&self.bundle, &self.fallback] {
+            let Some(pattern) = bundle.get_message(id).and_then(|message| message.value()) else { continue };
+            let mut errors = Vec::new();
+            return bundle.format_pattern(pattern, args, &mut errors).into_owned();
+        }
+        id.to_string()
+    }
+}
+
+thread_local! {
+    static LOCALIZER: RefCell<Localizer> = RefCell::new(Localizer::new(Language::default()));
+    static NUMBER_FORMAT: Cell<NumberFormat> = Cell::new(NumberFormat::default());
+}
+
+pub fn set_language(language: Language) {
+    LOCALIZER.with_borrow_mut(|localizer| {
+        if localizer.language != language {
+            *localizer = Localizer::new(language);
+        }
+    });
+}
+
+pub fn language() -> Language {
+    LOCALIZER.with_borrow(|localizer| localizer.language)
+}
+
+pub fn set_number_format(format: NumberFormat) {
+    NUMBER_FORMAT.set(format);
+}
+
+/// Translated message, or the id itself if no bundle has it
+pub fn tr(id: &str) -> String {
+    LOCALIZER.with_borrow(|localizer| localizer.format(id, None))
+}
+
+pub fn tr_args(id: &str, args: &[(&str, FluentValue)]) -> String {
+    let args: FluentArgs = args.iter().cloned().collect();
+    LOCALIZER.with_borrow(|localizer| localizer.format(id, Some(&args)))
+}
+
+fn localize_separator(number: String) -> String {
+    match language().decimal_separator() {
+        '.' => number,
+        separator => number.replace('.', &separator.to_string()),
+    }
+}
+
+/// Shortest representation, like `f64::to_string`, with the decimal separator of the language
+pub fn format_number(value: f64) -> String {
+    localize_separator(value.to_string())
+}
+
+pub fn format_decimal(value: f64, decimals: usize) -> String {
+    localize_separator(format!("{value:.decimals$}"))
+}
+
+/// Fitted parameter or result in the number format of the settings
+pub fn format_value(value: f64) -> String {
+    NUMBER_FORMAT.get().format(value)
+}
+
+/// Lower and upper bound, separated by a dash since the comma can be the decimal separator
+pub fn format_interval((low, high): (f64, f64)) -> String {
+    format!("{} – {}", format_value(low), format_value(high))
+}
+
+pub fn format_p_value(p: f64) -> String {
+    if p < 0.001 { format!("< {}", format_decimal(0.001, 3)) } else { format_decimal(p, 3) }
+}
+
+/// Accepts both `1,5` and `1.5`, and digit grouping like `1,234.5` or `1.234,5`.
+///
+/// When both separators appear, the last one is the decimal separator. A separator that
+/// appears several times groups digits. A single one is the decimal separator, unless it
+/// is the grouping separator of the language and splits off exactly three digits,
+/// so `1,234` is 1234 in English and `1.234` is 1234 in German, but `0,046` is 0.046 in both.
+pub fn parse_decimal(text: &str) -> Option<f64> {
+    let text = text.trim();
+    let decimal = language().decimal_separator();
+    let grouping = if decimal == '.' { ',' } else { '.' };
+
+    let last_point = text.rfind('.');
+    let last_comma = text.rfind(',');
+    let normalized: Cow<str> = match (last_point, last_comma) {
+        (None, None) => text.into(),
+        (Some(point), Some(comma)) => {
+            let separator = if point > comma { '.' } else { ',' };
+            let group = if separator == '.' { ',' } else { '.' };
+            text.replace(group, "").replace(separator, ".").into()
+        },
+        (Some(position), None) | (None, Some(position)) => {
+            let separator = text[position..].chars().next()?;
+            let integer = text[..position].trim_start_matches(['-', '+']);
+            let fraction = &text[position + 1..];
+            let groups_digits = text.matches(separator).count() > 1
+                || (separator == grouping
+                    && fraction.len() == 3
+                    && (1..=3).contains(&integer.len())
+                    && !integer.starts_with('0'));
+            if groups_digits {
+                text.replace(separator, "").into()
+            } else {
+                text.replace(separator, ".").into()
+            }
+        },
+    };
+    normalized.parse().ok()
+}
+
+pub fn format_date_time(date_time: &NaiveDateTime) -> String {
+    date_time.format(language().date_time_format()).to_string()
+}
@@ -0,0 +1,230 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{cutoff::Classification, expression::*, layout::PlateLayout, logistic_regression::*, results_filter::unknown_cv};
+
+pub const ANALYSIS_SCHEMA: &str = "elisa-analysis";
+pub const ANALYSIS_VERSION: u32 = 1;
+
+/// The whole analysis for statistics pipelines, e.g. `jsonlite::fromJSON` in R or `json.load` in Python.
+/// Signals are given as read (`raw`) and blank corrected (`corrected`), values that don't exist are `null`.
+///
+/// ```json
+/// {
+///   "schema": "elisa-analysis",
+///   "version": 1,
+///   "plate": { "name": "Plate 1", "layout": { "schema": "elisa-plate-layout", ... }, "values": [{ "well": "A1", "raw": 2.41, "excluded": false }] },
+///   "fit": { "model": "4PL", "parameters": { "a": { "value": 0.05, "standard_error": 0.01, ... }, ... }, "r_squared": 0.999, ... },
+///   "standards": [{ "concentration": 500.0, "raw": 2.46, "corrected": 2.41, "fitted": 2.40, "residual": 0.01, ... }],
+///   "unknowns": [{ "group": 1, "name": "Patient 4", "concentration": 12.3, "range": "within", ... }]
+/// }
+/// ```
+#[derive(Debug, Serialize)]
+pub struct AnalysisExport {
+    pub schema: String,
+    pub version: u32,
+    pub exported: String, // local time
+    pub plate: PlateExport,
+    pub fit: FitExport,
+    pub standards: Vec<StandardExport>,
+    pub unknowns: Vec<UnknownExport>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PlateExport {
+    pub name: String,
+    pub description: String,
+    pub kit_lot: String,
+    pub layout: PlateLayout,
+    pub values: Vec<WellValue>, // wells with a value
+}
+
+/// Also taken by the API, where `excluded` may be left out
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WellValue {
+    pub well: String,
+    pub raw: f64,
+    #[serde(default)]
+    pub excluded: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FitExport {
+    pub model: String,
+    pub options: FitOptions,
+    pub blank: f64,
+    pub control: Option<f64>,
+    pub adjusted: bool, // parameters set by hand, they come without errors
+    pub parameters: ParametersExport,
+    pub sse: f64,
+    pub mse: f64,
+    pub rmse: f64,
+    pub sy_x: f64,
+    pub r_squared: f64,
+    pub diagnostics: DiagnosticsExport,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ParametersExport {
+    pub a: ParameterExport,
+    pub b: ParameterExport,
+    pub c: ParameterExport,
+    pub d: ParameterExport,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ParameterExport {
+    pub value: f64,
+    pub standard_error: Option<f64>,
+    pub ci_lower: Option<f64>, // 95 %
+    pub ci_upper: Option<f64>,
+    pub p_value: Option<f64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DiagnosticsExport {
+    pub iterations: usize,
+    pub gradient_norm: f64,
+    pub converged: bool,
+    pub degrees_of_freedom: usize,
+    pub correlation: Option<[[f64; 4]; 4]>, // a, b, c, d
+    pub starts: usize,
+    pub best_start: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StandardExport {
+    pub concentration: f64,
+    pub raw: f64,
+    pub corrected: f64,
+    pub raw_replicates: Vec<f64>,
+    pub replicates: Vec<f64>,
+    pub fitted: f64,
+    pub residual: f64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UnknownExport {
+    pub group: usize, // 1-based, like in the layout
+    pub name: String,
+    pub metadata: SampleMetadata,
+    pub raw: f64,
+    pub corrected: f64,
+    pub raw_replicates: Vec<f64>,
+    pub replicates: Vec<f64>,
+    pub cv_percent: Option<f64>,
+    pub concentration: Option<f64>,
+    pub dilution: f64,
+    pub corrected_concentration: Option<f64>, // times the dilution
+    pub range: String, // within, extrapolated, above or below
+    pub spike: Option<f64>,
+    pub recovery_percent: Option<f64>,
+    pub classification: Option<Classification>,
+    pub derived: Vec<DerivedExport>, // columns of the protocol
+}
+
+#[derive(Debug, Serialize)]
+pub struct DerivedExport {
+    pub name: String,
+    pub value: Option<f64>,
+}
+
+fn finite(value: f64) -> Option<f64> {
+    Some(value).filter(|value| value.is_finite())
+}
+
+impl AnalysisExport {
+    pub fn new(microplate: &Microplate, regression: &Regression) -> Self {
+        let (a, b, c, d) = regression.abcd;
+        let estimates = regression.diagnostics.parameter_estimates(regression.fitted_abcd);
+        let parameter = |i: usize, value: f64| {
+            let estimate = estimates.filter(|_| !regression.is_adjusted()).map(|estimates| estimates[i]);
+            ParameterExport {
+                value,
+                standard_error: estimate.map(|estimate| estimate.standard_error),
+                ci_lower: estimate.map(|estimate| estimate.confidence_interval.0),
+                ci_upper: estimate.map(|estimate| estimate.confidence_interval.1),
+                p_value: estimate.and_then(|estimate| estimate.p_value),
+            }
+        };
+        let FitDiagnostics { iterations, gradient_norm, converged, correlation, degrees_of_freedom, starts, best_start, .. } = regression.diagnostics;
+
+        let standards = regression.standards.iter().zip(&regression.raw_standards)
+            .zip(regression.standard_replicates.iter().zip(&regression.raw_standard_replicates))
+            .map(|((&(concentration, corrected), &(_, raw)), (replicates, raw_replicates))| {
+                let fitted = regression.four_pl(concentration);
+                StandardExport {
+                    concentration,
+                    raw,
+                    corrected,
+                    raw_replicates: raw_replicates.clone(),
+                    replicates: replicates.clone(),
+                    fitted,
+                    residual: corrected - fitted,
+                }
+            })
+            .collect();
+
+        let derived_columns = microplate.protocol.as_ref().map(|protocol| protocol.derived_columns.as_slice()).unwrap_or_default();
+        let derived_columns = if parse_columns(derived_columns).is_ok() { derived_columns } else { &[] };
+        let unknowns = regression.unknowns.iter().map(|unknown| UnknownExport {
+            group: unknown.group + 1,
+            name: unknown.name(),
+            metadata: unknown.metadata.clone(),
+            raw: unknown.raw_measurement,
+            corrected: unknown.measurement,
+            raw_replicates: unknown.raw_replicates.clone(),
+            replicates: unknown.replicates.clone(),
+            cv_percent: unknown_cv(unknown),
+            concentration: finite(unknown.concentration),
+            dilution: unknown.dilution,
+            corrected_concentration: finite(unknown.corrected_concentration()),
+            range: match unknown.range {
+                CurveRange::Within => "within",
+                CurveRange::Extrapolated => "extrapolated",
+                CurveRange::Above(_) => "above",
+                CurveRange::Below(_) => "below",
+            }.to_string(),
+            spike: unknown.spike,
+            recovery_percent: unknown.recovery(),
+            classification: microplate.cutoff.as_ref().and_then(|cutoff| cutoff.classify(regression, unknown)),
+            derived: derived_columns.iter().zip(derived_values(derived_columns, unknown))
+                .map(|(column, value)| DerivedExport { name: column.name.trim().to_string(), value })
+                .collect(),
+        }).collect();
+
+        Self {
+            schema: ANALYSIS_SCHEMA.to_string(),
+            version: ANALYSIS_VERSION,
+            exported: chrono::offset::Local::now().format("%Y-%m-%dT%H:%M:%S%:z").to_string(),
+            plate: PlateExport {
+                name: microplate.name.clone(),
+                description: microplate.description.clone(),
+                kit_lot: microplate.metadata.kit_lot.clone(),
+                layout: microplate.layout(),
+                values: microplate.samples.iter().enumerate()
+                    .filter_map(|(i, sample)| Some(WellValue { well: microplate.well_name(i), raw: sample.value?, excluded: sample.excluded }))
+                    .collect(),
+            },
+            fit: FitExport {
+                model: "4PL".to_string(),
+                options: regression.options.clone(),
+                blank: regression.blank,
+                control: regression.has_control.then_some(regression.control),
+                adjusted: regression.is_adjusted(),
+                parameters: ParametersExport { a: parameter(0, a), b: parameter(1, b), c: parameter(2, c), d: parameter(3, d) },
+                sse: regression.sse,
+                mse: regression.mse,
+                rmse: regression.rmse,
+                sy_x: regression.sy_x,
+                r_squared: regression.r_sq,
+                diagnostics: DiagnosticsExport { iterations, gradient_norm, converged, degrees_of_freedom, correlation, starts, best_start },
+            },
+            standards,
+            unknowns,
+        }
+    }
+
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+}
@@ -0,0 +1,411 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{*, i18n::*, logistic_regression::*};
+
+pub const ALPHABET: [char; 26] = [
+    'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I', 'J', 'K', 'L', 'M',
+    'N', 'O', 'P', 'Q', 'R', 'S', 'T', 'U', 'V', 'W', 'X', 'Y', 'Z'
+];
+
+pub const LAYOUT_SCHEMA: &str = "elisa-plate-layout";
+pub const LAYOUT_VERSION: u32 = 1;
+
+/// Plate layout as exchanged with a LIMS.
+///
+/// ```json
+/// {
+///   "schema": "elisa-plate-layout",
+///   "version": 1,
+///   "width": 12,
+///   "height": 8,
+///   "wells": [
+///     { "well": "A1", "type": "Standard", "group": 1, "concentration": 100.0 },
+///     { "well": "A3", "type": "Unknown", "group": 1, "label": "Patient 4", "dilution": 10.0 },
+///     { "well": "H12", "type": "Blank" }
+///   ]
+/// }
+/// ```
+///
+/// Wells that are not listed are `Unused`. `group` is 1-based, like in the sample menu,
+/// and is only meaningful for standards and unknowns. `label` and `dilution` belong to
+/// unknown groups, `concentration` to standard groups; repeating them on every well of
+/// a group is fine as long as they agree.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PlateLayout {
+    pub schema: String,
+    pub version: u32,
+    pub width: usize,
+    pub height: usize,
+    pub wells: Vec<WellLayout>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WellLayout {
+    pub well: String,
+    #[serde(rename = "type")]
+    pub typ: SampleType,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub concentration: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dilution: Option<f64>,
+}
+
+#[derive(Clone, Debug)]
+pub enum LayoutError {
+    WrongSchema,
+    UnsupportedVersion(u32),
+    WrongDimensions(usize, usize),
+    InvalidWell(String),
+    DuplicateWell(String),
+    InvalidGroup(String),
+    ConflictingGroup(String),
+    Locked,
+    InvalidToken(String),
+}
+
+impl Display for LayoutError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let error = match self {
+            Self::WrongSchema => tr_args("layout-wrong-schema", &[("schema", LAYOUT_SCHEMA.into())]),
+            Self::UnsupportedVersion(version) => tr_args("layout-unsupported-version", &[("version", (*version).into())]),
+            Self::WrongDimensions(width, height) => tr_args("layout-wrong-dimensions", &[("width", (*width).into()), ("height", (*height).into())]),
+            Self::InvalidWell(well) => tr_args("layout-invalid-well", &[("well", well.as_str().into())]),
+            Self::DuplicateWell(well) => tr_args("layout-duplicate-well", &[("well", well.as_str().into())]),
+            Self::InvalidGroup(well) => tr_args("layout-invalid-group", &[("well", well.as_str().into())]),
+            Self::ConflictingGroup(well) => tr_args("layout-conflicting-group", &[("well", well.as_str().into())]),
+            Self::Locked => tr("layout-locked"),
+            Self::InvalidToken(token) => tr_args("layout-invalid-token", &[("token", token.as_str().into())]),
+        };
+        write!(f, "{}", error)
+    }
+}
+
+/// One cell of a pasted layout grid: `S1`/`Std1` standard, `U1`/`Unk1` unknown, `B`/`Blk` blank,
+/// `C`/`Ctrl` control and `-`, `.` or nothing unused. Case doesn't matter.
+fn parse_grid_token(token: &str) -> Result<(SampleType, Option<usize>), LayoutError> {
+    use SampleType::*;
+
+    let upper = token.trim().to_uppercase();
+    match upper.as_str() {
+        "" | "-" | "." => return Ok((Unused, None)),
+        "B" | "BLK" | "BLANK" => return Ok((Blank, None)),
+        "C" | "CTL" | "CTRL" => return Ok((Control, None)),
+        _ => (),
+    }
+    // longer prefixes first, so "STD1" isn't read as "S" and "TD1"
+    let group = if let Some(group) = upper.strip_prefix("STD").or(upper.strip_prefix('S')) {
+        group.parse::<usize>().ok().map(|group| (Standard, group))
+    } else if let Some(group) = upper.strip_prefix("UNK").or(upper.strip_prefix('U')) {
+        group.parse::<usize>().ok().map(|group| (Unknown, group))
+    } else {
+        None
+    };
+    match group {
+        Some((typ, group)) => Ok((typ, Some(group))),
+        None => Err(LayoutError::InvalidToken(token.trim().to_string())),
+    }
+}
+
+/// A whole row or column of the plate, as picked by its header
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PlateLine {
+    Row(usize),
+    Column(usize),
+}
+
+impl PlateLine {
+    pub fn name(&self) -> String {
+        match *self {
+            Self::Row(row) => tr_args("plate-row", &[("row", ALPHABET[row % 26].to_string().into())]),
+            Self::Column(column) => tr_args("plate-column", &[("column", (column + 1).into())]),
+        }
+    }
+}
+
+/// Turns a plate that was read in the wrong orientation, its layout and values alike
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PlateTransform {
+    Transpose,     // A2 becomes B1, width and height swap
+    Rotate,        // by 180°, A1 becomes the last well
+    MirrorColumns, // column 1 becomes the last column
+    MirrorRows,    // row A becomes the last row
+}
+
+impl PlateTransform {
+    pub const ALL: [Self; 4] = [Self::Transpose, Self::Rotate, Self::MirrorColumns, Self::MirrorRows];
+
+    pub fn description(&self) -> String {
+        let id = match self {
+            Self::Transpose => "transform-transpose",
+            Self::Rotate => "transform-rotate",
+            Self::MirrorColumns => "transform-mirror-columns",
+            Self::MirrorRows => "transform-mirror-rows",
+        };
+        tr(id)
+    }
+}
+
+impl Microplate {
+    pub fn line_wells(&self, line: PlateLine) -> Vec<usize> {
+        match line {
+            PlateLine::Row(row) => (0..self.width).map(|column| column * self.height + row).collect(),
+            PlateLine::Column(column) => (0..self.height).map(|row| column * self.height + row).collect(),
+        }
+    }
+
+    /// Well name in reading notation, e.g. "B7"
+    pub fn well_name(&self, index: usize) -> String {
+        let row = index % self.height;
+        let column = index / self.height;
+        format!("{}{}", ALPHABET[row % 26], column + 1)
+    }
+
+    pub fn well_index(&self, name: &str) -> Option<usize> {
+        let name = name.trim();
+        let mut chars = name.chars();
+        let letter = chars.next()?.to_ascii_uppercase();
+        let row = ALPHABET.iter().position(|&c| c == letter)?;
+        let column = chars.as_str().parse::<usize>().ok()?.checked_sub(1)?;
+        if row >= self.height || column >= self.width { return None }
+        Some(column * self.height + row)
+    }
+
+    pub fn layout(&self) -> PlateLayout {
+        use SampleType::*;
+
+        let wells = self.samples.iter().enumerate().filter(|(_, sample)| sample.typ != Unused).map(|(i, sample)| {
+            let mut well = WellLayout {
+                well: self.well_name(i),
+                typ: sample.typ,
+                group: None,
+                label: None,
+                concentration: None,
+                dilution: None,
+            };
+            match sample.typ {
+                Standard => {
+                    well.group = Some(sample.group + 1);
+                    well.concentration = self.standard_groups.get(sample.group).and_then(|group| group.concentration);
+                },
+                Unknown => {
+                    well.group = Some(sample.group + 1);
+                    if let Some(group) = self.unknown_groups.get(sample.group) {
+                        well.label = Some(group.label.clone()).filter(|label| !label.is_empty());
+                        well.dilution = group.dilution;
+                    }
+                },
+                _ => (),
+            }
+            well
+        }).collect();
+
+        PlateLayout {
+            schema: LAYOUT_SCHEMA.to_string(),
+            version: LAYOUT_VERSION,
+            width: self.width,
+            height: self.height,
+            wells,
+        }
+    }
+
+    /// Replaces sample types, groups and group information with the layout, keeping measured values.
+    pub fn apply_layout(&mut self, layout: &PlateLayout) -> Result<(), LayoutError> {
+        use LayoutError::*;
+        use SampleType::*;
+
+        if layout.schema != LAYOUT_SCHEMA { return Err(WrongSchema) }
+        if layout.version > LAYOUT_VERSION { return Err(UnsupportedVersion(layout.version)) }
+        if layout.width != self.width || layout.height != self.height {
+            return Err(WrongDimensions(layout.width, layout.height))
+        }
+
+        let mut types = vec![Unused; self.samples.len()];
+        let mut groups = vec![0; self.samples.len()];
+        let mut standard_groups: Vec<Group> = vec![default()];
+        let mut unknown_groups: Vec<Group> = vec![default()];
+        let mut seen = vec![false; self.samples.len()];
+
+        for well in &layout.wells {
+            let Some(index) = self.well_index(&well.well) else { return Err(InvalidWell(well.well.clone())) };
+            if seen[index] { return Err(DuplicateWell(well.well.clone())) }
+            seen[index] = true;
+            types[index] = well.typ;
+
+            let group_list = match well.typ {
+                Standard => &mut standard_groups,
+                Unknown => &mut unknown_groups,
+                _ => continue,
+            };
+            let group = match well.group {
+                Some(group) if (1..=MAX_GROUPS).contains(&group) => group - 1,
+                _ => return Err(InvalidGroup(well.well.clone())),
+            };
+            groups[index] = group;
+            if group_list.len() <= group {
+                group_list.resize_with(group + 1, default);
+            }
+
+            let entry = &mut group_list[group];
+            let conflicts = |old: &Option<f64>, new: &Option<f64>| matches!((old, new), (Some(old), Some(new)) if old != new);
+            if conflicts(&entry.concentration, &well.concentration) || conflicts(&entry.dilution, &well.dilution) {
+                return Err(ConflictingGroup(well.well.clone()))
+            }
+            if let Some(label) = &well.label {
+                if !entry.label.is_empty() && entry.label != *label {
+                    return Err(ConflictingGroup(well.well.clone()))
+                }
+                entry.label = label.clone();
+            }
+            entry.concentration = entry.concentration.or(well.concentration);
+            entry.dilution = entry.dilution.or(well.dilution);
+        }
+
+        for (i, sample) in self.samples.iter_mut().enumerate() {
+            sample.typ = types[i];
+            sample.group = groups[i];
+        }
+        self.standard_groups = standard_groups;
+        self.unknown_groups = unknown_groups;
+
+        Ok(())
+    }
+    /// A new plate set up like this one: sample types, groups with their concentrations, dilutions and labels,
+    /// and the fit settings. The values and the record of this run are left behind.
+    pub fn duplicate(&self) -> Self {
+        Self {
+            name: tr_args("duplicate-name", &[("name", self.name.as_str().into())]),
+            description: self.description.clone(),
+            samples: self.samples.iter().map(|sample| Sample { typ: sample.typ, group: sample.group, ..default() }).collect(),
+            standard_groups: self.standard_groups.clone(),
+            unknown_groups: self.unknown_groups.clone(),
+            metadata: RunMetadata {
+                kit_lot: self.metadata.kit_lot.clone(),
+                operator: self.metadata.operator.clone(),
+                instrument: self.metadata.instrument.clone(),
+                wavelength: self.metadata.wavelength,
+                kit_expiry: self.metadata.kit_expiry.clone(),
+                ..default()
+            },
+            fit_options: self.fit_options.clone(),
+            protocol: self.protocol.clone(),
+            plot_scale: self.plot_scale,
+            cutoff: self.cutoff.clone(),
+            ..Self::new(self.width, self.height)
+        }
+    }
+
+    /// Gives `other` the sample types and groups of this plate, its values stay
+    pub fn copy_layout_to(&self, other: &mut Microplate) -> Result<(), LayoutError> {
+        if other.locked { return Err(LayoutError::Locked) }
+        if other.width != self.width || other.height != self.height {
+            return Err(LayoutError::WrongDimensions(self.width, self.height))
+        }
+        for (sample, source) in other.samples.iter_mut().zip(&self.samples) {
+            sample.typ = source.typ;
+            sample.group = source.group;
+        }
+        other.standard_groups = self.standard_groups.clone();
+        other.unknown_groups = self.unknown_groups.clone();
+        Ok(())
+    }
+
+    /// Moves every well with its type, group, value and exclusion to where `transform` puts it
+    pub fn transform(&mut self, transform: PlateTransform) {
+        let (width, height) = (self.width, self.height);
+        let (new_width, new_height) = match transform {
+            PlateTransform::Transpose => (height, width),
+            _ => (width, height),
+        };
+        let mut samples = vec![Sample::default(); self.samples.len()];
+        for (index, sample) in self.samples.drain(..).enumerate() {
+            let (column, row) = (index / height, index % height);
+            let (column, row) = match transform {
+                PlateTransform::Transpose => (row, column),
+                PlateTransform::Rotate => (width - 1 - column, height - 1 - row),
+                PlateTransform::MirrorColumns => (width - 1 - column, row),
+                PlateTransform::MirrorRows => (column, height - 1 - row),
+            };
+            samples[column * new_height + row] = sample;
+        }
+        self.samples = samples;
+        self.width = new_width;
+        self.height = new_height;
+
+        // the analyzed region moves along, without running off a plate it didn't fit
+        if let Some(region) = &mut self.fit_options.region {
+            let PlateRegion { first_row, last_row, first_column, last_column } = *region;
+            let (flip_row, flip_column) = (|row: usize| height.saturating_sub(row + 1), |column: usize| width.saturating_sub(column + 1));
+            *region = match transform {
+                PlateTransform::Transpose => PlateRegion { first_row: first_column, last_row: last_column, first_column: first_row, last_column: last_row },
+                PlateTransform::Rotate => PlateRegion {
+                    first_row: flip_row(last_row),
+                    last_row: flip_row(first_row),
+                    first_column: flip_column(last_column),
+                    last_column: flip_column(first_column),
+                },
+                PlateTransform::MirrorColumns => PlateRegion { first_column: flip_column(last_column), last_column: flip_column(first_column), ..*region },
+                PlateTransform::MirrorRows => PlateRegion { first_row: flip_row(last_row), last_row: flip_row(first_row), ..*region },
+            };
+        }
+    }
+
+    /// Layout from a grid of tokens, e.g. copied from the plate map of a kit insert, one plate row per line.
+    /// Cells are separated by tabs, commas or semicolons as in a spreadsheet, otherwise by spaces.
+    /// A header line of column numbers and the row letters in front of the lines are skipped.
+    pub fn parse_layout_grid(&self, text: &str) -> Result<PlateLayout, LayoutError> {
+        let mut lines: Vec<Vec<&str>> = text.lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| match line.chars().find(|char| matches!(char, '\t' | ';' | ',')) {
+                Some(separator) => line.split(separator).collect(),
+                None => line.split_whitespace().collect(),
+            })
+            .collect();
+        let is_header = |line: &Vec<&str>| line.iter().all(|cell| cell.trim().is_empty() || cell.trim().parse::<usize>().is_ok());
+        if lines.first().is_some_and(is_header) {
+            lines.remove(0);
+        }
+        // "B" and "C" are also cells, so the row letters only count if every line has its own
+        let has_letters = lines.iter().enumerate().all(|(row, cells)| {
+            cells.first().is_some_and(|cell| ALPHABET.get(row).is_some_and(|letter| cell.trim().eq_ignore_ascii_case(&letter.to_string())))
+        });
+        if has_letters {
+            lines.iter_mut().for_each(|cells| { cells.remove(0); });
+        }
+
+        let width = |cells: &Vec<&str>| cells.iter().rposition(|cell| !cell.trim().is_empty()).map_or(0, |index| index + 1);
+        let columns = lines.iter().map(width).max().unwrap_or(0);
+        if lines.len() > self.height || columns > self.width {
+            return Err(LayoutError::WrongDimensions(columns, lines.len()))
+        }
+
+        let mut wells = Vec::new();
+        for (row, cells) in lines.iter().enumerate() {
+            for (column, cell) in cells.iter().enumerate().take(width(cells)) {
+                let (typ, group) = parse_grid_token(cell)?;
+                if typ == SampleType::Unused { continue }
+                wells.push(WellLayout {
+                    well: self.well_name(column * self.height + row),
+                    typ,
+                    group,
+                    label: None,
+                    concentration: None,
+                    dilution: None,
+                });
+            }
+        }
+
+        Ok(PlateLayout {
+            schema: LAYOUT_SCHEMA.to_string(),
+            version: LAYOUT_VERSION,
+            width: self.width,
+            height: self.height,
+            wells,
+        })
+    }
+}
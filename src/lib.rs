@@ -0,0 +1,61 @@
+//! The standard curve fitting of the Elisa app, for use from other programs. See [`fit::FitBuilder`].
+//!
+//! The modules only the app uses, like the API server, the watch folder or the archive, come with the `app` feature,
+//! which is on by default. With `default-features = false` the library builds without eframe and the other GUI crates.
+
+pub mod acquisition;
+pub mod analysis;
+#[cfg(feature = "app")]
+pub mod archive;
+pub mod audit;
+#[cfg(feature = "app")]
+pub mod barcode;
+pub mod benchmark;
+pub mod bootstrap;
+#[cfg(feature = "app")]
+pub mod clipboard;
+pub mod cutoff;
+pub mod drift;
+pub mod exclusion;
+#[cfg(feature = "app")]
+pub mod export_hook;
+pub mod expression;
+pub mod fit;
+pub mod grading;
+pub mod groups;
+pub mod history;
+pub mod i18n;
+pub mod json_export;
+pub mod layout;
+pub mod linear_range;
+pub mod logistic_regression;
+#[cfg(feature = "app")]
+pub mod manifest;
+pub mod monotonicity;
+pub mod path_length;
+#[cfg(feature = "app")]
+pub mod plate_diff;
+pub mod protocol;
+#[cfg(feature = "python")]
+pub mod python;
+pub mod replicates;
+pub mod reportable_range;
+pub mod results_filter;
+pub mod self_test;
+#[cfg(feature = "app")]
+pub mod server;
+#[cfg(feature = "app")]
+pub mod sha256;
+pub mod simulation;
+pub mod statistics;
+#[cfg(feature = "app")]
+pub mod watch_folder;
+pub mod wavelength;
+#[cfg(feature = "app")]
+pub mod well_details;
+#[cfg(feature = "app")]
+pub mod well_swap;
+
+pub fn default<D: Default>() -> D {
+    D::default()
+}
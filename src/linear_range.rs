@@ -0,0 +1,60 @@
+use crate::{i18n::*, logistic_regression::*};
+
+/// Typical of absorbance readers, as proposed when the range is turned on
+pub const DEFAULT_LINEAR_RANGE: (f64, f64) = (0.0, 3.5);
+
+/// A read outside the range in which the reader responds linearly. Above it the detector saturates and reads too low,
+/// which flattens the top of the curve without anything looking wrong.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RangeViolation {
+    Above(f64), // the read
+    Below(f64),
+}
+
+impl RangeViolation {
+    pub fn read(&self) -> f64 {
+        match *self {
+            Self::Above(read) | Self::Below(read) => read,
+        }
+    }
+
+    pub fn description(&self, (low, high): (f64, f64)) -> String {
+        match *self {
+            Self::Above(read) => tr_args("linear-range-above", &[("read", format_value(read).into()), ("high", format_number(high).into())]),
+            Self::Below(read) => tr_args("linear-range-below", &[("read", format_value(read).into()), ("low", format_number(low).into())]),
+        }
+    }
+}
+
+impl Microplate {
+    /// The reads of a well in OD as they came from the reader: the measurement before the reference read was
+    /// subtracted, and the reference read. Not normalized to 1 cm, the linear range is one of the detector.
+    pub fn reads(&self, index: usize) -> Vec<f64> {
+        let sample = &self.samples[index];
+        let reference = sample.reference.filter(|_| self.metadata.correction.is_some());
+        let measurement = sample.value.map(|value| value + reference.unwrap_or_default());
+        measurement.into_iter().chain(reference).collect()
+    }
+
+    /// The read of the well furthest outside of the linear range (low, high), None if all are inside
+    pub fn linear_range_violation(&self, index: usize, (low, high): (f64, f64)) -> Option<RangeViolation> {
+        let reads = self.reads(index);
+        let highest = reads.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let lowest = reads.iter().copied().fold(f64::INFINITY, f64::min);
+        if highest > high {
+            Some(RangeViolation::Above(highest))
+        } else if lowest < low {
+            Some(RangeViolation::Below(lowest))
+        } else {
+            None
+        }
+    }
+
+    /// Used wells that are not excluded with a read outside of the linear range, they would go into the fit
+    pub fn wells_outside_linear_range(&self, range: (f64, f64)) -> Vec<(usize, RangeViolation)> {
+        (0..self.samples.len())
+            .filter(|&i| self.samples[i].typ != SampleType::Unused && !self.samples[i].excluded && self.in_region(i))
+            .filter_map(|i| Some((i, self.linear_range_violation(i, range)?)))
+            .collect()
+    }
+}
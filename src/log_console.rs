@@ -0,0 +1,76 @@
+use std::sync::Mutex;
+
+use log::{Level, LevelFilter, Log, Metadata, Record};
+
+pub const MAX_LOG_ENTRIES: usize = 1000;
+
+#[derive(Clone, Debug)]
+pub struct LogEntry {
+    pub time: String,
+    pub level: Level,
+    pub target: String,
+    pub message: String,
+}
+
+static ENTRIES: Mutex<Vec<LogEntry>> = Mutex::new(Vec::new());
+
+/// Passes everything on to env_logger for the terminal, and keeps warnings, errors and our own info
+/// for the log console, as the release build on Windows has no terminal.
+struct ConsoleLogger {
+    terminal: env_logger::Logger,
+}
+
+impl Log for ConsoleLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= Level::Info || self.terminal.enabled(metadata)
+    }
+
+    fn log(&self, record: &Record) {
+        if self.terminal.matches(record) {
+            self.terminal.log(record);
+        }
+        // info from eframe and wgpu would drown out our own
+        let own = record.target().starts_with(env!("CARGO_PKG_NAME"));
+        if record.level() > Level::Warn && !(own && record.level() == Level::Info) { return }
+
+        let entry = LogEntry {
+            time: chrono::offset::Local::now().format("%H:%M:%S").to_string(),
+            level: record.level(),
+            target: record.target().to_string(),
+            message: record.args().to_string(),
+        };
+        let Ok(mut entries) = ENTRIES.lock() else { return };
+        if entries.len() >= MAX_LOG_ENTRIES {
+            entries.remove(0);
+        }
+        entries.push(entry);
+    }
+
+    fn flush(&self) {
+        self.terminal.flush();
+    }
+}
+
+/// Replaces `env_logger::init`, `RUST_LOG` still controls what reaches the terminal
+pub fn init() {
+    let terminal = env_logger::Builder::from_default_env().build();
+    let max_level = terminal.filter().max(LevelFilter::Info);
+    if log::set_boxed_logger(Box::new(ConsoleLogger { terminal })).is_ok() {
+        log::set_max_level(max_level);
+    }
+}
+
+pub fn log_entries() -> Vec<LogEntry> {
+    ENTRIES.lock().map(|entries| entries.clone()).unwrap_or_default()
+}
+
+/// Number of warnings and errors
+pub fn log_problems() -> usize {
+    ENTRIES.lock().map(|entries| entries.iter().filter(|entry| entry.level <= Level::Warn).count()).unwrap_or(0)
+}
+
+pub fn clear_log() {
+    if let Ok(mut entries) = ENTRIES.lock() {
+        entries.clear();
+    }
+}
@@ -1,8 +1,21 @@
-use crate::*;
-use egui::Color32;
+use crate::{*, acquisition::ReadingOrder, audit::AuditEntry, bootstrap::Rng, cutoff::Cutoff, drift::DriftCorrection, exclusion::ExclusionReason, i18n::*, layout::ALPHABET, monotonicity::NonMonotonic, path_length::PathLengthCorrection, protocol::Protocol, self_test::check_gradient, statistics::*, wavelength::WavelengthCorrection};
 use serde::{Deserialize, Serialize};
+use std::{ops::RangeInclusive, time::{Duration, Instant}};
 use SampleType::*;
 
+/// Acceptable spike recovery in percent
+pub const RECOVERY_RANGE: RangeInclusive<f64> = 80.0..=120.0;
+
+pub const MAX_GROUPS: usize = 100;
+
+pub const MAX_ITERATIONS: usize = 100_000;
+/// The fit stops early, and counts as converged, once the gradient is smaller than this
+pub const GRADIENT_TOLERANCE: f64 = 1e-9;
+/// Seed of the perturbed initial guesses, fixed so a fit can be repeated
+pub const MULTI_START_SEED: u64 = 0x4C50_4C46;
+/// Most perturbed starts the fit options offer, each one costs as much as a whole fit
+pub const MAX_EXTRA_STARTS: usize = 20;
+
 #[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub enum SampleType {
     #[default]
@@ -14,15 +27,27 @@ pub enum SampleType {
 }
 
 impl SampleType {
-    pub fn color(&self) -> Color32 {
+    /// As RGB, the app draws the wells and points in it
+    pub fn color(&self) -> [u8; 3] {
         match self {
-            Unused => Color32::from_hex("#D8DCE7").unwrap(),
-            Unknown => Color32::from_hex("#8CF490").unwrap(),
-            Standard => Color32::from_hex("#F57373").unwrap(),
-            Control => Color32::from_hex("#818FEF").unwrap(),
-            Blank => Color32::from_hex("#F1E07D").unwrap(),
+            Unused => [0xD8, 0xDC, 0xE7],
+            Unknown => [0x8C, 0xF4, 0x90],
+            Standard => [0xF5, 0x73, 0x73],
+            Control => [0x81, 0x8F, 0xEF],
+            Blank => [0xF1, 0xE0, 0x7D],
         }
     }
+
+    pub fn name(&self) -> String {
+        let id = match self {
+            Unused => "sample-unused",
+            Blank => "sample-blank",
+            Control => "sample-control",
+            Standard => "sample-standard",
+            Unknown => "sample-unknown",
+        };
+        tr(id)
+    }
 }
 
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
@@ -30,12 +55,286 @@ pub struct Sample {
     pub typ: SampleType,
     pub group: usize,        // index to group in microplate
     pub value: Option<f64>,
+    #[serde(default)]
+    pub excluded: bool,      // left out of the fit, e.g. by an exclusion rule
+    #[serde(default)]
+    pub exclusion_reason: Option<ExclusionReason>, // None when excluded by hand without one
+    #[serde(default)]
+    pub reference: Option<f64>, // read at the reference wavelength, subtracted from value
+    #[serde(default)]
+    pub path_length_read: Option<f64>, // water peak read for the path-length correction
 }
 
-#[derive(Default, Serialize, Deserialize)]
+#[derive(Clone, Default, Serialize, Deserialize)]
 pub struct Group {
     pub concentration: Option<f64>,
     pub label: String,
+    pub dilution: Option<f64>,
+    #[serde(default)]
+    pub spike: Option<f64>, // known spiked concentration of an unknown
+    #[serde(default)]
+    pub color: Option<[u8; 3]>, // instead of the color of the sample type
+    #[serde(default)]
+    pub metadata: SampleMetadata,
+    #[serde(default)]
+    pub anchor: bool, // a standard outside the quantitation range that only steadies the fit
+}
+
+/// Where an unknown came from, for tracing clinical samples
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SampleMetadata {
+    pub subject_id: String,
+    pub timepoint: String,       // visit or timepoint
+    pub matrix: String,          // e.g. serum, plasma
+    pub collection_date: String, // as entered, ideally YYYY-MM-DD
+}
+
+impl SampleMetadata {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// One line for the report, leaving out empty fields
+    pub fn summary(&self) -> String {
+        [
+            ("subject-id", &self.subject_id),
+            ("timepoint", &self.timepoint),
+            ("matrix", &self.matrix),
+            ("collection-date", &self.collection_date),
+        ].into_iter()
+            .filter(|(_id, value)| !value.trim().is_empty())
+            .map(|(id, value)| format!("{}: {}", tr(id), value.trim()))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// How, when and by whom the plate was run
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct RunMetadata {
+    #[serde(default)]
+    pub kit_lot: String,
+    pub reader_format: Option<String>,
+    pub temperature: Option<f64>,
+    pub read_time: Option<String>,
+    #[serde(default)]
+    pub analyzed_by: Signatory,
+    #[serde(default)]
+    pub reviewed_by: Signatory,
+    #[serde(default)]
+    pub operator: String, // who ran the plate, the one analyzing it signs in `analyzed_by`
+    #[serde(default)]
+    pub instrument: String, // ID of the reader
+    #[serde(default)]
+    pub wavelength: Option<f64>, // nm
+    #[serde(default)]
+    pub kit_expiry: String, // as entered, ideally YYYY-MM-DD
+    #[serde(default)]
+    pub correction: Option<WavelengthCorrection>, // the values are corrected with a reference read
+    #[serde(default)]
+    pub read_settings: Vec<(String, String)>, // from the reader export, name and value as it writes them
+}
+
+impl RunMetadata {
+    /// One line for the report header, leaving out empty fields
+    pub fn summary(&self) -> String {
+        let read_time = self.read_time.clone().unwrap_or_default();
+        let temperature = self.temperature.map(|temperature| format!("{} °C", format_number(temperature))).unwrap_or_default();
+        let wavelength = match &self.correction {
+            Some(correction) => correction.description(self.wavelength),
+            None => self.wavelength.map(|wavelength| format!("{} nm", format_number(wavelength))).unwrap_or_default(),
+        };
+        [
+            ("operator", &self.operator),
+            ("instrument", &self.instrument),
+            ("wavelength", &wavelength),
+            ("read-time", &read_time),
+            ("temperature", &temperature),
+            ("kit-lot", &self.kit_lot),
+            ("kit-expiry", &self.kit_expiry),
+        ].into_iter()
+            .filter(|(_id, value)| !value.trim().is_empty())
+            .map(|(id, value)| format!("{}: {}", tr(id), value.trim()))
+            .chain(self.read_settings.iter().map(|(name, value)| format!("{name}: {value}")))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// The read temperature, if it is outside of the acceptable range from min to max °C
+    pub fn temperature_out_of_range(&self, range: Option<(f64, f64)>) -> Option<f64> {
+        let (min, max) = range?;
+        self.temperature.filter(|temperature| !(min..=max).contains(temperature))
+    }
+}
+
+/// Person who signs off on the report
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Signatory {
+    pub name: String,
+    pub role: String,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum BlankCorrection {
+    None,
+    #[default]
+    Plate,          // subtract the mean of all blanks
+    PerRow,         // subtract the mean of the blanks in the same row
+    PerColumn,      // subtract the mean of the blanks in the same column
+    LowerAsymptote, // don't subtract, fix the lower asymptote a at the blank mean
+}
+
+impl BlankCorrection {
+    pub const ALL: [Self; 5] = [Self::None, Self::Plate, Self::PerRow, Self::PerColumn, Self::LowerAsymptote];
+
+    pub fn description(&self) -> String {
+        let id = match self {
+            Self::None => "blank-none",
+            Self::Plate => "blank-plate",
+            Self::PerRow => "blank-per-row",
+            Self::PerColumn => "blank-per-column",
+            Self::LowerAsymptote => "blank-lower-asymptote",
+        };
+        tr(id)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum ControlHandling {
+    #[default]
+    InitialGuess, // seed and bound the lower asymptote a
+    Anchor,       // fit the control as a point at a pseudo-zero dose
+    Exclude,
+}
+
+impl ControlHandling {
+    pub const ALL: [Self; 3] = [Self::InitialGuess, Self::Anchor, Self::Exclude];
+
+    pub fn description(&self) -> String {
+        let id = match self {
+            Self::InitialGuess => "control-initial-guess",
+            Self::Anchor => "control-anchor",
+            Self::Exclude => "control-exclude",
+        };
+        tr(id)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum Weighting {
+    #[default]
+    None,
+    OneOverY,        // 1/y, for errors growing with the signal
+    OneOverYSquared, // 1/y², for a constant relative error
+}
+
+impl Weighting {
+    pub const ALL: [Self; 3] = [Self::None, Self::OneOverY, Self::OneOverYSquared];
+
+    pub fn description(&self) -> String {
+        let id = match self {
+            Self::None => "weighting-none",
+            Self::OneOverY => "weighting-1-y",
+            Self::OneOverYSquared => "weighting-1-y2",
+        };
+        tr(id)
+    }
+
+    pub fn weight(&self, y: f64) -> f64 {
+        let y = y.abs().max(1e-9);
+        match self {
+            Self::None => 1.0,
+            Self::OneOverY => 1.0 / y,
+            Self::OneOverYSquared => 1.0 / (y * y),
+        }
+    }
+}
+
+/// Settings chosen before fitting, saved with the microplate
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FitOptions {
+    pub blank_correction: BlankCorrection,
+    pub control_handling: ControlHandling,
+    pub zero_dose: Option<f64>, // replaces the dose 0 of the control on the log axis, None for automatic
+    pub weighting: Weighting,
+    pub extrapolation: Option<f64>, // % beyond the lowest and highest standard, None to report unknowns there as out of range
+    pub region: Option<PlateRegion>, // None to analyze the whole plate
+    pub path_length: PathLengthCorrection,
+    pub drift_correction: DriftCorrection,
+    pub reading_order: ReadingOrder, // of the wells, for the drift correction
+    pub allow_non_monotonic: bool, // fit standards that turn, e.g. from a hook effect at the highest ones
+    pub extra_starts: usize, // initial guesses perturbed from the usual one, the fit with the lowest loss is kept
+    pub max_iterations: Option<usize>, // of each start, None for MAX_ITERATIONS
+}
+
+impl FitOptions {
+    /// Without a configured value we use a hundredth of the lowest standard concentration
+    pub fn zero_dose(&self, lowest_standard: f64) -> f64 {
+        self.zero_dose.unwrap_or(lowest_standard / 100.0)
+    }
+}
+
+/// Wells analyzed on their own, e.g. columns 1 to 6 when the plate holds two assays. Rows and columns count from 0, the last ones are included.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PlateRegion {
+    pub first_row: usize,
+    pub last_row: usize,
+    pub first_column: usize,
+    pub last_column: usize,
+}
+
+impl PlateRegion {
+    pub fn whole_plate(width: usize, height: usize) -> Self {
+        Self { first_row: 0, last_row: height.saturating_sub(1), first_column: 0, last_column: width.saturating_sub(1) }
+    }
+
+    pub fn contains(&self, row: usize, column: usize) -> bool {
+        (self.first_row..=self.last_row).contains(&row) && (self.first_column..=self.last_column).contains(&column)
+    }
+
+    /// In reading notation, e.g. "A1:H6"
+    pub fn name(&self) -> String {
+        format!("{}{}:{}{}", ALPHABET[self.first_row % 26], self.first_column + 1, ALPHABET[self.last_row % 26], self.last_column + 1)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum AxisScale {
+    Linear,
+    Logarithmic,
+}
+
+impl AxisScale {
+    pub fn description(&self) -> String {
+        match self {
+            Self::Linear => tr("axis-linear"),
+            Self::Logarithmic => tr("axis-logarithmic"),
+        }
+    }
+}
+
+/// Scales of the plot axes, saved with the microplate. Semilog by default.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PlotScale {
+    pub x: AxisScale,
+    pub y: AxisScale,
+}
+
+impl Default for PlotScale {
+    fn default() -> Self {
+        Self { x: AxisScale::Logarithmic, y: AxisScale::Linear }
+    }
+}
+
+/// Mean blank of the plate and of every row and column, None where there are no blanks
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PlateBlanks {
+    pub plate: Option<f64>,
+    pub rows: Vec<Option<f64>>,
+    pub columns: Vec<Option<f64>>,
 }
 
 #[derive(Default, Serialize, Deserialize)]
@@ -47,9 +346,35 @@ pub struct Microplate {
     pub samples: Vec<Sample>,
     pub standard_groups: Vec<Group>,
     pub unknown_groups: Vec<Group>,
+    #[serde(default)]
+    pub metadata: RunMetadata,
+    #[serde(default)]
+    pub fit_options: FitOptions,
+    #[serde(default)]
+    pub audit_trail: Vec<AuditEntry>,
+    #[serde(default)]
+    pub locked: bool, // read-only for review
+    #[serde(default)]
+    pub protocol: Option<Protocol>,
+    #[serde(default)]
+    pub plot_scale: PlotScale,
+    #[serde(default)]
+    pub cutoff: Option<Cutoff>,
+    #[serde(default)]
+    pub barcode: String, // scanned or typed from the label of the plate
 }
 
 impl Microplate {
+    /// Color of the group of a standard or unknown, if one was chosen, otherwise of the sample type
+    pub fn sample_color(&self, sample: &Sample) -> [u8; 3] {
+        let group = match sample.typ {
+            Standard => self.standard_groups.get(sample.group),
+            Unknown => self.unknown_groups.get(sample.group),
+            Blank | Control | Unused => None,
+        };
+        group.and_then(|group| group.color).unwrap_or_else(|| sample.typ.color())
+    }
+
     pub fn new(width: usize, height: usize) -> Self {
         Self {
             height,
@@ -60,6 +385,70 @@ impl Microplate {
             ..default()
         }
     }
+
+    /// Changes with everything the fit and the results depend on: the wells, the groups and the fit options
+    pub fn fit_fingerprint(&self) -> u64 {
+        use std::hash::{DefaultHasher, Hash, Hasher};
+
+        let inputs = (self.width, self.height, &self.samples, &self.standard_groups, &self.unknown_groups, &self.fit_options);
+        let mut hasher = DefaultHasher::new();
+        serde_json::to_string(&inputs).unwrap_or_default().hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Whether the well is part of the analysis, with a region set in the fit options
+    pub fn in_region(&self, index: usize) -> bool {
+        self.fit_options.region.is_none_or(|region| region.contains(index % self.height, index / self.height))
+    }
+
+    /// Blanks that are used, not excluded and have a value
+    pub fn blanks(&self) -> PlateBlanks {
+        // (sum, count) pairs
+        let mut blank = (0.0, 0);
+        let mut row_blanks = vec![(0.0, 0); self.height];
+        let mut column_blanks = vec![(0.0, 0); self.width];
+        let values = self.fitted_values();
+        for (i, sample) in self.samples.iter().enumerate() {
+            if sample.typ != Blank || sample.excluded || !self.in_region(i) { continue }
+            let Some(value) = values[i] else { continue };
+            for (sum, count) in [&mut blank, &mut row_blanks[i % self.height], &mut column_blanks[i / self.height]] {
+                *sum += value;
+                *count += 1;
+            }
+        }
+        let mean = |&(sum, count): &(f64, usize)| if count != 0 { Some(sum / count as f64) } else { None };
+        PlateBlanks {
+            plate: mean(&blank),
+            rows: row_blanks.iter().map(mean).collect(),
+            columns: column_blanks.iter().map(mean).collect(),
+        }
+    }
+
+    /// What the blank correction of the fit options takes off the value of a well
+    pub fn blank_of_well(&self, blanks: &PlateBlanks, index: usize) -> f64 {
+        let blank = blanks.plate.unwrap_or(0.0);
+        match self.fit_options.blank_correction {
+            BlankCorrection::None | BlankCorrection::LowerAsymptote => 0.0,
+            BlankCorrection::Plate => blank,
+            BlankCorrection::PerRow => blanks.rows[index % self.height].unwrap_or(blank),
+            BlankCorrection::PerColumn => blanks.columns[index / self.height].unwrap_or(blank),
+        }
+    }
+
+    /// Assigns rows of values as read, starting at A1. Values past the edge of the plate are dropped.
+    pub fn assign_values(&mut self, data: Vec<Vec<Option<f64>>>) {
+        for (y, line) in data.into_iter().enumerate().take(self.height) {
+            for (x, cell) in line.into_iter().enumerate().take(self.width) {
+                self.assign_value(self.height * x + y, cell);
+            }
+        }
+    }
+
+    /// A value as read, less the reference read of the well while the values are corrected
+    pub fn assign_value(&mut self, index: usize, value: Option<f64>) {
+        let reference = self.samples[index].reference.filter(|_| self.metadata.correction.is_some());
+        self.samples[index].value = value.map(|value| value - reference.unwrap_or_default());
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -71,15 +460,238 @@ pub enum ValueError {
     NotEnoughStandards,
     BlankTooBig,
     ControlTooBig,
+    MissingPathLength,
+}
+
+/// Why the fit failed, checked on the way in and on the way out of the optimizer
+#[derive(Clone, Debug)]
+pub enum RegressionError {
+    Value(ValueError),
+    InvalidStandards, // concentrations must be positive, with at least 4 different ones and a signal that changes
+    DidNotConverge,   // the optimizer diverged, the parameters are no longer finite
+    SingularJacobian, // the standards can't tell the parameters apart
+    DegenerateCurve,  // the fitted curve is flat, there is nothing to interpolate
+    NotMonotonic(Box<NonMonotonic>), // the standards go against the trend of the others, e.g. swapped wells
+}
+
+impl From<ValueError> for RegressionError {
+    fn from(error: ValueError) -> Self {
+        Self::Value(error)
+    }
+}
+
+impl std::fmt::Display for ValueError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let id = match self {
+            Self::UnassignedConcentration => "error-unassigned-concentration",
+            Self::UnassignedValue => "error-unassigned-value",
+            Self::InvalidConcentration => "error-invalid-concentration",
+            Self::InvalidValue => "error-invalid-value",
+            Self::NotEnoughStandards => "error-not-enough-standards",
+            Self::BlankTooBig => "error-blank-too-big",
+            Self::ControlTooBig => "error-control-too-big",
+            Self::MissingPathLength => "error-missing-path-length",
+        };
+        write!(f, "{}", tr(id))
+    }
+}
+
+impl std::fmt::Display for RegressionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let id = match self {
+            Self::Value(error) => return error.fmt(f),
+            Self::InvalidStandards => "error-invalid-standards",
+            Self::DidNotConverge => "error-did-not-converge",
+            Self::SingularJacobian => "error-singular-jacobian",
+            Self::DegenerateCurve => "error-degenerate-curve",
+            Self::NotMonotonic(error) => return error.fmt(f),
+        };
+        write!(f, "{}", tr(id))
+    }
+}
+
+/// Where an unknown lies relative to the standards, with the concentration of the nearest standard
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum CurveRange {
+    #[default]
+    Within,
+    Extrapolated,
+    Above(f64), // above the highest standard or the upper asymptote, e.g. from a hook effect
+    Below(f64), // below the lowest standard or the lower asymptote
+}
+
+/// A standard read back through the curve, the acceptance check of the calibration
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BackCalculatedStandard {
+    pub concentration: f64, // nominal
+    pub measurement: f64,   // blank corrected mean
+    pub backfit: f64,
+    pub recovery: f64,      // % of the nominal concentration
+    pub anchor: bool,       // not held to the recovery range
+}
+
+impl BackCalculatedStandard {
+    pub fn passed(&self) -> bool {
+        self.anchor || RECOVERY_RANGE.contains(&self.recovery)
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct UnknownResult {
+    pub group: usize,         // index to unknown group in microplate
+    pub label: String,
+    pub measurement: f64,     // blank corrected mean of the group
+    pub replicates: Vec<f64>, // blank corrected values of the wells
+    pub raw_measurement: f64, // mean of the wells as read
+    pub raw_replicates: Vec<f64>,
+    pub concentration: f64,   // interpolated from the curve
+    pub dilution: f64,
+    pub spike: Option<f64>,
+    pub range: CurveRange,
+    pub color: [u8; 3],       // RGB, see Microplate::sample_color
+    pub metadata: SampleMetadata,
+}
+
+impl UnknownResult {
+    /// Mean of the group as read or blank corrected
+    pub fn signal(&self, raw: bool) -> f64 {
+        if raw { self.raw_measurement } else { self.measurement }
+    }
+
+    pub fn signal_replicates(&self, raw: bool) -> &[f64] {
+        if raw { &self.raw_replicates } else { &self.replicates }
+    }
+
+    pub fn name(&self) -> String {
+        if self.label.is_empty() {
+            tr_args("unknown-number", &[("number", (self.group + 1).into())])
+        } else {
+            self.label.clone()
+        }
+    }
+
+    /// Concentration of the undiluted sample
+    pub fn corrected_concentration(&self) -> f64 {
+        self.concentration * self.dilution
+    }
+
+    pub fn in_range(&self) -> bool {
+        self.range == CurveRange::Within
+    }
+
+    /// The backfit concentration, or where it lies if it is outside of the standards
+    pub fn concentration_text(&self) -> String {
+        match self.range {
+            CurveRange::Within => format_value(self.concentration),
+            CurveRange::Extrapolated => tr_args("extrapolated", &[("concentration", format_value(self.concentration).into())]),
+            CurveRange::Above(highest) => tr_args("above-curve", &[("highest", format_value(highest).into())]),
+            CurveRange::Below(_) => tr("below-lod"),
+        }
+    }
+
+    pub fn range_warning(&self) -> Option<String> {
+        match self.range {
+            CurveRange::Within => None,
+            CurveRange::Extrapolated => Some(tr("extrapolated-warning")),
+            CurveRange::Above(highest) => Some(tr_args("above-curve-warning", &[("highest", format_value(highest).into())])),
+            CurveRange::Below(lowest) => Some(tr_args("below-lod-warning", &[("lowest", format_value(lowest).into())])),
+        }
+    }
+
+    /// Spike recovery in percent, only for unknowns within the standards
+    pub fn recovery(&self) -> Option<f64> {
+        if !self.in_range() { return None }
+        self.spike.map(|spike| self.concentration / spike * 100.0)
+    }
+
+    pub fn recovery_out_of_range(&self) -> bool {
+        self.recovery().is_some_and(|recovery| !RECOVERY_RANGE.contains(&recovery))
+    }
+}
+
+/// How the optimizer got to the parameters, and how well they are determined by the standards
+#[derive(Clone, Debug, Default)]
+pub struct FitDiagnostics {
+    pub iterations: usize,
+    pub gradient_norm: f64,
+    pub converged: bool,
+    pub standard_errors: Option<[f64; 4]>, // a, b, c, d, 0 for a parameter that was held fixed
+    pub correlation: Option<[[f64; 4]; 4]>,
+    pub degrees_of_freedom: usize, // points minus free parameters
+    pub starts: usize, // initial guesses tried
+    pub best_start: usize, // the one the parameters came from, 0 for the usual guess
+    pub duration: Option<Duration>, // of all starts, None on the web where std has no clock
+}
+
+/// A parameter with its 95 % confidence interval and the p-value of the t-test against 0,
+/// which is None for a parameter that was held fixed
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ParameterEstimate {
+    pub value: f64,
+    pub standard_error: f64,
+    pub confidence_interval: (f64, f64),
+    pub p_value: Option<f64>,
+}
+
+impl FitDiagnostics {
+    /// a, b, c, d with their intervals, None if the standard errors couldn't be estimated
+    pub fn parameter_estimates(&self, (a, b, c, d): (f64, f64, f64, f64)) -> Option<[ParameterEstimate; 4]> {
+        let standard_errors = self.standard_errors?;
+        if self.degrees_of_freedom == 0 { return None }
+        let degrees_of_freedom = self.degrees_of_freedom as f64;
+        let t = t_critical(0.05, degrees_of_freedom);
+        let estimate = |value: f64, standard_error: f64| ParameterEstimate {
+            value,
+            standard_error,
+            confidence_interval: (value - t * standard_error, value + t * standard_error),
+            p_value: (standard_error > 0.0).then(|| t_two_sided_p(value / standard_error, degrees_of_freedom)),
+        };
+        Some([
+            estimate(a, standard_errors[0]),
+            estimate(b, standard_errors[1]),
+            estimate(c, standard_errors[2]),
+            estimate(d, standard_errors[3]),
+        ])
+    }
+
+    /// log₁₀ c, the parameter the fit works with, with its interval. Exponentiated, the interval is the asymmetric
+    /// one of c that stays positive. There is no p-value, log c = 0 only means c = 1.
+    pub fn log_c_estimate(&self, c: f64) -> Option<ParameterEstimate> {
+        let standard_errors = self.standard_errors?;
+        if self.degrees_of_freedom == 0 || c <= 0.0 { return None }
+        let t = t_critical(0.05, self.degrees_of_freedom as f64);
+        let value = c.log10();
+        let standard_error = standard_errors[2] / (c * std::f64::consts::LN_10);
+        Some(ParameterEstimate {
+            value,
+            standard_error,
+            confidence_interval: (value - t * standard_error, value + t * standard_error),
+            p_value: None,
+        })
+    }
 }
 
+/// Standard errors and correlation matrix of a, b, c, d
+type ParameterErrors = ([f64; 4], [[f64; 4]; 4]);
+
 #[derive(Clone, Default)]
 pub struct Regression {
     pub abcd: (f64, f64, f64, f64),
+    pub fitted_abcd: (f64, f64, f64, f64), // from the automatic fit, abcd differs after a manual adjustment
+    pub diagnostics: FitDiagnostics,
+    pub options: FitOptions,
     pub blank: f64,
     pub control: f64,
-    pub unknowns: Vec<(f64, f64, String)>,
-    pub standards: Vec<(f64, f64)>,
+    pub has_control: bool,
+    pub anchor: Option<(f64, f64)>, // control at pseudo-zero dose
+    pub unknowns: Vec<UnknownResult>,
+    pub standards: Vec<(f64, f64)>, // blank corrected, what the curve is fitted to
+    pub standard_replicates: Vec<Vec<f64>>, // individual values of each of the standards
+    pub raw_standards: Vec<(f64, f64)>, // as read, the fit never changes them
+    pub raw_standard_replicates: Vec<Vec<f64>>,
+    pub standard_colors: Vec<[u8; 3]>,
+    pub standard_anchors: Vec<bool>, // anchor standards, fitted but outside the quantitation range
+    pub fingerprint: u64, // of the plate at the time of the fit, see Microplate::fit_fingerprint
     pub sse: f64,
     pub mse: f64,
     pub rmse: f64,
@@ -88,29 +700,42 @@ pub struct Regression {
 }
 
 impl Regression {
-    pub fn new(microplate: &Microplate) -> Result<Self, ValueError> {
+    pub fn new(microplate: &Microplate) -> Result<Self, RegressionError> {
         use ValueError::*;
 
         let unknowns_len = microplate.unknown_groups.len();
         let standards_len = microplate.standard_groups.len();
 
+        let options = microplate.fit_options.clone();
+
         // (sum, count) pairs
-        let mut blank = (0.0, 0);
         let mut control = (0.0, 0);
         let mut unknowns = vec![(0.0, 0); unknowns_len];
+        let mut unknown_replicates = vec![Vec::new(); unknowns_len];
+        let mut raw_unknown_replicates = vec![Vec::new(); unknowns_len];
         let mut standards = vec![(0.0, 0); standards_len];
+        let mut replicates = vec![Vec::new(); standards_len];
+        let mut raw_replicates = vec![Vec::new(); standards_len];
+
+        // check values
+        for (i, Sample { typ, value, excluded, .. }) in microplate.samples.iter().enumerate() {
+            if *typ == Unused || *excluded || !microplate.in_region(i) { continue }
+            let Some(value) = value else { return Err(UnassignedValue.into()) };
+            if !value.is_finite() { return Err(InvalidValue.into()) }
+            if microplate.normalized_value(i).is_none() { return Err(MissingPathLength.into()) }
+        }
 
-        // add up values
-        for Sample { typ, group, value } in &microplate.samples {
-            if *typ == Unused { continue }
-            let Some(value) = value else { return Err(UnassignedValue) };
-            if !value.is_finite() { return Err(InvalidValue) }
+        let blanks = microplate.blanks();
+        let blank = blanks.plate.unwrap_or(0.0);
+        let values = microplate.fitted_values();
+
+        // add up blank corrected values
+        for (i, Sample { typ, group, excluded, .. }) in microplate.samples.iter().enumerate() {
+            if *excluded || !microplate.in_region(i) { continue }
+            let Some(raw) = values[i] else { continue };
+            let value = raw - microplate.blank_of_well(&blanks, i);
 
             match typ {
-                Blank => {
-                    blank.0 += value;
-                    blank.1 += 1;
-                },
                 Control => {
                     control.0 += value;
                     control.1 += 1;
@@ -118,69 +743,186 @@ impl Regression {
                 Standard => {
                     standards[*group].0 += value;
                     standards[*group].1 += 1;
+                    replicates[*group].push(value);
+                    raw_replicates[*group].push(raw);
                 },
                 Unknown => {
                     unknowns[*group].0 += value;
                     unknowns[*group].1 += 1;
+                    unknown_replicates[*group].push(value);
+                    raw_unknown_replicates[*group].push(raw);
                 }
-                Unused => ()
+                Blank | Unused => ()
             }
         }
 
-        let blank = if blank.1 != 0 { blank.0 / blank.1 as f64 } else { 0.0 };
-        let control = if control.1 != 0 { control.0 / control.1 as f64 } else { 0.0 };
+        let has_control = control.1 != 0;
+        let control = if has_control { control.0 / control.1 as f64 } else { 0.0 };
 
-        let unknowns = unknowns.iter().enumerate().filter_map(|(i, &(sum, count))| {
+        let unknowns = unknowns.iter().zip(unknown_replicates).zip(raw_unknown_replicates).enumerate().filter_map(|(i, ((&(sum, count), replicates), raw_replicates))| {
             if count == 0 { return None }
-            let measurement = sum / count as f64;
-            let label = microplate.unknown_groups[i].label.clone();
-            Some((0.0, measurement, label))
+            let group = &microplate.unknown_groups[i];
+            Some(UnknownResult {
+                group: i,
+                label: group.label.clone(),
+                measurement: sum / count as f64,
+                replicates,
+                raw_measurement: raw_replicates.iter().sum::<f64>() / count as f64,
+                raw_replicates,
+                concentration: 0.0,
+                dilution: group.dilution.unwrap_or(1.0),
+                spike: group.spike,
+                range: CurveRange::Within,
+                color: microplate.sample_color(&Sample { typ: Unknown, group: i, ..default() }),
+                metadata: group.metadata.clone(),
+            })
         }).collect();
 
         let mut concentrations = vec![0.0; standards_len];
         for (i, group) in concentrations.iter_mut().enumerate() {
             let Some(concentration) = microplate.standard_groups[i].concentration else {
-                return Err(UnassignedConcentration)
+                return Err(UnassignedConcentration.into())
             };
-            if !concentration.is_finite() { return Err(InvalidConcentration) }
+            if !concentration.is_finite() { return Err(InvalidConcentration.into()) }
             *group = concentration;
         }
 
-        let mut standards: Vec<_> = standards.iter().enumerate().filter_map(|(i, &(sum, count))| {
+        let mut standards: Vec<_> = standards.iter().zip(replicates).zip(raw_replicates).enumerate().filter_map(|(i, ((&(sum, count), replicates), raw_replicates))| {
             if count == 0 { return None }
             let concentration = concentrations[i];
             let measurement = sum / count as f64;
-            Some((concentration, measurement))
+            let color = microplate.sample_color(&Sample { typ: Standard, group: i, ..default() });
+            let anchor = microplate.standard_groups[i].anchor;
+            Some(((concentration, measurement), ((replicates, raw_replicates), (color, anchor))))
         }).collect();
 
-        // We need at least 4 standards, preferably 8
-        if standards.len() < 4 { return Err(NotEnoughStandards) }
-
         // Sort standards by concentration
-        standards.sort_by(|(a_x, _a_y), (b_x, _b_y)| a_x.total_cmp(b_x));
+        standards.sort_by(|((a_x, _a_y), _), ((b_x, _b_y), _)| a_x.total_cmp(b_x));
+        let (standards, (replicates, colors)): (Vec<_>, (Vec<_>, Vec<_>)) = standards.into_iter().unzip();
+        let (standard_colors, standard_anchors) = colors.into_iter().unzip();
+        let (standard_replicates, raw_standard_replicates): (Vec<_>, Vec<Vec<f64>>) = replicates.into_iter().unzip();
+        let raw_standards = standards.iter().zip(&raw_standard_replicates)
+            .map(|(&(x, _y), raw)| (x, raw.iter().sum::<f64>() / raw.len() as f64))
+            .collect();
+        check_standards(&standards)?;
+        if !options.allow_non_monotonic {
+            if let Some(error) = microplate.standard_monotonicity() { return Err(RegressionError::NotMonotonic(Box::new(error))) }
+        }
 
         // Find minimum measurement, this is not necessarily standards.first()
         let standard_min = standards.iter().min_by(|(_a_x, a_y), (_b_x, b_y)| a_y.total_cmp(b_y)).unwrap().1;
 
-        if control > standard_min { return Err(ControlTooBig) }
-        if blank > standard_min { return Err(BlankTooBig) }
+        if options.control_handling != ControlHandling::Exclude && control > standard_min { return Err(ControlTooBig.into()) }
+        match options.blank_correction {
+            BlankCorrection::None => (),
+            BlankCorrection::LowerAsymptote => if blank > standard_min { return Err(BlankTooBig.into()) },
+            _ => if standard_min < 0.0 { return Err(BlankTooBig.into()) },
+        }
+
+        let anchor = (options.control_handling == ControlHandling::Anchor && has_control)
+            .then(|| (options.zero_dose(standards[0].0), control));
 
-        
         let mut regression = Self {
+            options,
             blank,
             control,
+            has_control,
+            anchor,
             unknowns,
             standards,
+            standard_replicates,
+            raw_standards,
+            raw_standard_replicates,
+            standard_colors,
+            standard_anchors,
+            fingerprint: microplate.fit_fingerprint(),
             ..default()
         };
         
-        regression.four_pl_curve_fit();
+        regression.four_pl_curve_fit()?;
+        regression.fitted_abcd = regression.abcd;
         regression.calculate_unknowns();
         regression.calculate_parameters();
 
         Ok(regression)
     }
 
+    /// Fits standards given as (concentration, signal) without a plate, the signals already blank corrected
+    pub fn from_standards(standards: &[(f64, f64)], weighting: Weighting) -> Result<Self, RegressionError> {
+        Self::from_standards_with_options(standards, FitOptions { weighting, ..default() })
+    }
+
+    /// Like from_standards with the weighting and starts of the options, there is no blank or control to handle
+    pub fn from_standards_with_options(standards: &[(f64, f64)], options: FitOptions) -> Result<Self, RegressionError> {
+        let mut standards = standards.to_vec();
+        if standards.iter().any(|(x, y)| !x.is_finite() || !y.is_finite()) { return Err(ValueError::InvalidValue.into()) }
+        standards.sort_by(|(a_x, _a_y), (b_x, _b_y)| a_x.total_cmp(b_x));
+        check_standards(&standards)?;
+
+        let options = FitOptions {
+            blank_correction: BlankCorrection::None,
+            control_handling: ControlHandling::Exclude,
+            ..options
+        };
+        let mut regression = Self {
+            options,
+            standard_replicates: standards.iter().map(|&(_x, y)| vec![y]).collect(),
+            raw_standard_replicates: standards.iter().map(|&(_x, y)| vec![y]).collect(),
+            raw_standards: standards.clone(),
+            standard_colors: vec![Standard.color(); standards.len()],
+            standard_anchors: vec![false; standards.len()],
+            standards,
+            ..default()
+        };
+        regression.four_pl_curve_fit()?;
+        regression.fitted_abcd = regression.abcd;
+        regression.calculate_parameters();
+        Ok(regression)
+    }
+
+    /// Replaces the parameters by hand and recalculates the unknowns and the goodness of fit
+    pub fn set_parameters(&mut self, abcd: (f64, f64, f64, f64)) {
+        self.abcd = abcd;
+        self.calculate_unknowns();
+        self.calculate_parameters();
+    }
+
+    /// What the blank correction took off the plate on average, to show the curve over the raw values
+    pub fn blank_offset(&self) -> f64 {
+        match self.options.blank_correction {
+            BlankCorrection::None | BlankCorrection::LowerAsymptote => 0.0,
+            BlankCorrection::Plate | BlankCorrection::PerRow | BlankCorrection::PerColumn => self.blank,
+        }
+    }
+
+    /// Every standard in the order of concentration, as in `standards`
+    pub fn back_calculated_standards(&self) -> Vec<BackCalculatedStandard> {
+        self.standards.iter().enumerate().map(|(i, &(concentration, measurement))| {
+            let backfit = self.inverse_four_pl(measurement);
+            let anchor = self.is_anchor(i);
+            BackCalculatedStandard { concentration, measurement, backfit, recovery: backfit / concentration * 100.0, anchor }
+        }).collect()
+    }
+
+    pub fn is_anchor(&self, standard: usize) -> bool {
+        self.standard_anchors.get(standard).copied().unwrap_or(false)
+    }
+
+    /// Lowest and highest concentration of the standards that aren't anchors, of all of them if every one is
+    pub fn quantitation_range(&self) -> (f64, f64) {
+        let concentrations = |anchors: bool| self.standards.iter().enumerate()
+            .filter(move |&(i, _)| anchors || !self.is_anchor(i))
+            .map(|(_, &(x, _y))| x);
+        let all = concentrations(false).next().is_none();
+        let lowest = concentrations(all).fold(f64::INFINITY, f64::min);
+        let highest = concentrations(all).fold(f64::NEG_INFINITY, f64::max);
+        (lowest, highest)
+    }
+
+    pub fn is_adjusted(&self) -> bool {
+        self.abcd != self.fitted_abcd
+    }
+
     #[inline(always)]
     pub fn four_pl(&self, x: f64) -> f64 {
         let (a, b, c, d) = self.abcd;
@@ -231,15 +973,31 @@ impl Regression {
         }).sum();
 
 
-        let r = 1.0 - self.sum_of_squares() / total_sum_of_squares;
-        r * r
+        1.0 - self.sum_of_squares() / total_sum_of_squares
     }
 
     #[inline(always)]
     pub fn calculate_unknowns(&mut self) {
         let (a, b, c, d) = self.abcd;
-        for (x, y, _) in &mut self.unknowns {
-            *x = c * ((a - d) / (*y - d) - 1.0).powf(1.0 / b)
+        let (lowest, highest) = self.quantitation_range();
+        let extrapolation = self.options.extrapolation.unwrap_or(0.0) / 100.0;
+
+        for unknown in &mut self.unknowns {
+            let concentration = c * ((a - d) / (unknown.measurement - d) - 1.0).powf(1.0 / b);
+            unknown.concentration = concentration;
+            unknown.range = if concentration.is_nan() {
+                // past one of the asymptotes, the curve never reaches this signal
+                let past_upper = (unknown.measurement - d) * (d - a) >= 0.0;
+                if past_upper { CurveRange::Above(highest) } else { CurveRange::Below(lowest) }
+            } else if concentration > highest * (1.0 + extrapolation) {
+                CurveRange::Above(highest)
+            } else if concentration < lowest * (1.0 - extrapolation) {
+                CurveRange::Below(lowest)
+            } else if concentration > highest || concentration < lowest {
+                CurveRange::Extrapolated
+            } else {
+                CurveRange::Within
+            };
         }
     }
    
@@ -251,27 +1009,31 @@ impl Regression {
         self.r_sq = self.r_squared();
     }
     
-    pub fn four_pl_curve_fit(&mut self) {
-        let Self { blank, unknowns, standards, control, .. } = self;
-        let n = standards.len() as f64;
+    pub fn four_pl_curve_fit(&mut self) -> Result<(), RegressionError> {
+        let started = now();
+        let Self { blank, standards, control, anchor, options, .. } = self;
+        let fixed_a = (options.blank_correction == BlankCorrection::LowerAsymptote).then_some(*blank);
+        let exclude_control = options.control_handling == ControlHandling::Exclude;
 
-        // subtract blank
-        unknowns.iter_mut().for_each(|(_, v, _)| *v -= *blank);
-        standards.iter_mut().for_each(|(_, v)| *v -= *blank);
-        *control -= *blank;
+        // find the minimum and maximum measurement, this is not necessarily standards.first()
+        let min = *standards.iter().min_by(|(_a_x, a_y), (_b_x, b_y)| a_y.total_cmp(b_y)).unwrap();
+        let max = *standards.iter().max_by(|(_a_x, a_y), (_b_x, b_y)| a_y.total_cmp(b_y)).unwrap();
 
-        // convert standards x to x hat
-        let standards: Vec<_> = standards.iter().map(|&(x, y)| (x.ln(), y)).collect();
+        // convert standards x to x hat, the anchor goes first as it has the lowest dose
+        let standards: Vec<_> = anchor.iter().chain(standards.iter()).map(|&(x, y)| (x.ln(), y)).collect();
+        let n = standards.len() as f64;
 
-        // find the minimum and maximum measurement, this is not necessarily standards.first()
-        let min = standards.iter().min_by(|(_a_x, a_y), (_b_x, b_y)| a_y.total_cmp(b_y)).unwrap();
-        let max = standards.iter().max_by(|(_a_x, a_y), (_b_x, b_y)| a_y.total_cmp(b_y)).unwrap();
+        // weights are scaled to a mean of 1, so the learn rates below work for every weighting
+        let weights: Vec<f64> = standards.iter().map(|&(_x, y)| options.weighting.weight(y)).collect();
+        let mean_weight = weights.iter().sum::<f64>() / n;
+        let weights: Vec<f64> = weights.iter().map(|weight| weight / mean_weight).collect();
 
 
         // guess initial values
-        let mut a = *control; // 0-dose asymptote
-        let mut b = 1.0;      // slope at IC50
-        let mut d = max.1;    // inf-dose asymptote
+        let initial_a = if exclude_control { min.1 } else { *control };
+        let a = fixed_a.unwrap_or(initial_a); // 0-dose asymptote
+        let b = 1.0;      // slope at IC50
+        let d = max.1;    // inf-dose asymptote
 
         // We assume the point of inflection, c, is close to the interpolation between two standards with the greatest slope
         let mut c_incline = 0.0;
@@ -288,53 +1050,243 @@ impl Regression {
             }
         }
 
-        dbg!(a, b, c, d, blank, *control, min);
+        log::debug!("initial guess a: {a}, b: {b}, c: {c}, d: {d}, blank: {blank}, control: {control}, min: {min:?}");
+        // with debug logging every fit checks its gradient, see self_test
+        if log::log_enabled!(log::Level::Debug) {
+            let check = check_gradient(&standards, &weights, (a, b, c, d));
+            if check.passed() {
+                log::debug!("gradient check passed, relative error {:e}", check.relative_error());
+            } else {
+                log::warn!("gradient check failed, analytical {:?}, numerical {:?}", check.analytical, check.numerical);
+            }
+        }
 
 
+        // c is fitted as ln c, on the scale of x hat, so the same learn rates work for any concentration unit
         let learn_rate = (0.1, 1.0, 1.0, 0.1);
+        let max_iterations = options.max_iterations.unwrap_or(MAX_ITERATIONS);
+        let descend = |(mut a, mut b, mut c, mut d): (f64, f64, f64, f64)| {
+            let mut iterations = max_iterations;
+            let mut gradient_norm = f64::INFINITY;
 
-        // I should really fix this
-        for i in 0..100_000 {
-            let mut sum_a = 0.0;
-            let mut sum_b = 0.0;
-            let mut sum_c = 0.0;
-            let mut sum_d = 0.0;
-
-            for (x, y) in standards.iter() {
-                let ebxc = (b * (x - c)).exp();
-                let sigmoid = 1.0 / (1.0 + ebxc);
-
-                let diff = y - d - (a - d) * sigmoid;
-                let duda = sigmoid;
-                let dudb = (x - c) * ebxc * sigmoid * sigmoid;
-                let dudc = ebxc * sigmoid * sigmoid;
-                let dudd = sigmoid;
-
-                sum_a += diff * duda;
-                sum_b += diff * dudb;
-                sum_c += diff * dudc;
-                sum_d += diff * dudd;
-            }
+            // I should really fix this
+            for i in 0..max_iterations {
+                let [da, db, dc, dd] = loss_gradient(&standards, &weights, (a, b, c, d));
 
-            let da = -2.0 / n * sum_a;
-            let db = 2.0 * (a - d) / n * sum_b;
-            let dc = -2.0 * b * (a - d) / n * sum_c;
-            let dd = -2.0 / n * sum_d;
+                let da_free = if fixed_a.is_some() { 0.0 } else { da };
+                gradient_norm = (da_free * da_free + db * db + dc * dc + dd * dd).sqrt();
+                if gradient_norm < GRADIENT_TOLERANCE {
+                    iterations = i;
+                    break
+                }
             
-            a -= learn_rate.0 * da;
-            b -= learn_rate.1 * db;
-            c -= learn_rate.2 * dc;
-            d -= learn_rate.3 * dd;
+                a -= learn_rate.0 * da;
+                b -= learn_rate.1 * db;
+                c -= learn_rate.2 * dc;
+                d -= learn_rate.3 * dd;
 
-            // We can make the reasonable assumption that the asymptotic lower bound must be between the control and the first standard
-            a = a.clamp(*control, min.1);
+                // We can make the reasonable assumption that the asymptotic lower bound must be between the control and the first standard
+                a = if exclude_control { a.min(min.1) } else { a.clamp(*control, min.1) };
+                if let Some(fixed_a) = fixed_a { a = fixed_a }
 
-            if i % 1000 == 0 { println!("a: {}, b: {}, c: {}, d: {}", a, b, c, d) };
-        }
+                if i % 1000 == 0 { log::trace!("iteration {i}, a: {a}, b: {b}, c: {c}, d: {d}") };
+            }
+            ((a, b, c, d), iterations, gradient_norm)
+        };
+
+        // the usual guess goes first, the others are perturbed from it. The perturbations are seeded,
+        // so the same standards always give the same fit.
+        let mut rng = Rng::new(MULTI_START_SEED);
+        let span = (max.1 - min.1).abs();
+        let dose_span = standards[standards.len() - 1].0 - standards[0].0;
+        let starts = 1 + options.extra_starts.min(MAX_EXTRA_STARTS);
+        // the lowest loss wins, on a tie the earlier start
+        let (best_start, (a, b, c, d), iterations, gradient_norm, _loss) = (0..starts).map(|start| {
+            let guess = if start == 0 { (a, b, c, d) } else {(
+                fixed_a.unwrap_or(a + 0.2 * span * rng.normal()),
+                b * (0.5 * rng.normal()).exp(),
+                c + 0.25 * dose_span * rng.normal(),
+                d + 0.2 * span * rng.normal(),
+            )};
+            let (parameters, iterations, gradient_norm) = descend(guess);
+            let loss = weighted_loss(&standards, &weights, parameters);
+            let loss = if loss.is_finite() && gradient_norm.is_finite() { loss } else { f64::INFINITY };
+            if starts > 1 { log::debug!("start {start} from {guess:?} ended at {parameters:?} with loss {loss}") }
+            (start, parameters, iterations, gradient_norm, loss)
+        }).min_by(|(.., a_loss), (.., b_loss)| a_loss.total_cmp(b_loss)).unwrap();
+        if starts > 1 { log::debug!("Fit took start {best_start} of {starts}") }
 
 
         let c = c.exp();
+        // running out of iterations close to the minimum only gets a warning, see FitDiagnostics
+        if ![a, b, c, d, gradient_norm].iter().all(|value| value.is_finite()) {
+            log::error!("Fit diverged after {iterations} iterations, a: {a}, b: {b}, c: {c}, d: {d}");
+            return Err(RegressionError::DidNotConverge)
+        }
+        if gradient_norm >= GRADIENT_TOLERANCE {
+            log::warn!("Fit did not converge after {max_iterations} iterations, gradient norm {gradient_norm}");
+        }
 
         self.abcd = (a, b, c, d);
+
+        // the curve has to rise or fall noticeably over the standards, or every unknown reads the same
+        let (lowest, highest) = (self.standards[0].0, self.standards[self.standards.len() - 1].0);
+        let rise = (self.four_pl(highest) - self.four_pl(lowest)).abs();
+        let signal = min.1.abs().max(max.1.abs());
+        if c == 0.0 || rise < 1e-3 * signal {
+            log::error!("Fit is degenerate, a: {a}, b: {b}, c: {c}, d: {d}");
+            return Err(RegressionError::DegenerateCurve)
+        }
+
+        let (standard_errors, correlation) = self.parameter_errors(fixed_a.is_some(), &weights)?.unzip();
+        let free_parameters = if fixed_a.is_some() { 3 } else { 4 };
+        self.diagnostics = FitDiagnostics {
+            iterations,
+            gradient_norm,
+            converged: gradient_norm < GRADIENT_TOLERANCE,
+            starts,
+            best_start,
+            duration: started.map(|started| started.elapsed()),
+            standard_errors,
+            correlation,
+            degrees_of_freedom: (self.anchor.iter().count() + self.standards.len()).saturating_sub(free_parameters),
+        };
+        if let Some(duration) = self.diagnostics.duration {
+            log::debug!("Fit took {duration:?} for {iterations} iterations of start {best_start}");
+        }
+        Ok(())
+    }
+
+    /// Standard errors and correlation of the parameters from the covariance matrix s² (JᵀWJ)⁻¹,
+    /// None with too few standards to estimate them. J is taken for ln c like the fit, which keeps JᵀWJ well
+    /// conditioned whatever the unit of the concentrations, the error of c follows as c · SE(ln c).
+    fn parameter_errors(&self, fixed_a: bool, weights: &[f64]) -> Result<Option<ParameterErrors>, RegressionError> {
+        let (a, b, c, d) = self.abcd;
+        let free: Vec<usize> = (0..4).filter(|&i| i != 0 || !fixed_a).collect();
+        let k = free.len();
+        let points: Vec<_> = self.anchor.iter().chain(self.standards.iter()).collect();
+        if points.len() <= k { return Ok(None) }
+
+        let mut jtj = vec![vec![0.0; k]; k];
+        let mut weighted_sse = 0.0;
+        for (&&(x, y), weight) in points.iter().zip(weights) {
+            let u = (x / c).powf(b);
+            let s = 1.0 / (1.0 + u);
+            let gradient = [s, -(a - d) * s * s * u * (x / c).ln(), (a - d) * s * s * u * b, 1.0 - s];
+            for (row, &i) in free.iter().enumerate() {
+                for (column, &j) in free.iter().enumerate() {
+                    jtj[row][column] += weight * gradient[i] * gradient[j];
+                }
+            }
+            let residual = y - self.four_pl(x);
+            weighted_sse += weight * residual * residual;
+        }
+        let variance = weighted_sse / (points.len() - k) as f64;
+        let Some(inverse) = invert(jtj) else {
+            log::error!("JᵀWJ is singular at a: {a}, b: {b}, c: {c}, d: {d}");
+            return Err(RegressionError::SingularJacobian)
+        };
+
+        let mut standard_errors = [0.0; 4];
+        let mut correlation = [[0.0; 4]; 4];
+        for (row, &i) in free.iter().enumerate() {
+            standard_errors[i] = (variance * inverse[row][row]).sqrt();
+            for (column, &j) in free.iter().enumerate() {
+                correlation[i][j] = inverse[row][column] / (inverse[row][row] * inverse[column][column]).sqrt();
+            }
+        }
+        // scaling ln c to c leaves the correlations as they are
+        standard_errors[2] *= c;
+        Ok(standard_errors.iter().all(|error| error.is_finite()).then_some((standard_errors, correlation)))
+    }
+}
+
+/// The weighted mean squared error of the curve over points (ln x, y), with c as ln c. This is what the fit minimizes.
+pub fn weighted_loss(points: &[(f64, f64)], weights: &[f64], (a, b, c, d): (f64, f64, f64, f64)) -> f64 {
+    let sum: f64 = points.iter().zip(weights).map(|((x, y), weight)| {
+        let residual = y - d - (a - d) / (1.0 + (b * (x - c)).exp());
+        weight * residual * residual
+    }).sum();
+    sum / points.len() as f64
+}
+
+/// Partial derivatives of weighted_loss by a, b, ln c and d, the direction the fit descends in
+pub fn loss_gradient(points: &[(f64, f64)], weights: &[f64], (a, b, c, d): (f64, f64, f64, f64)) -> [f64; 4] {
+    let mut sum_a = 0.0;
+    let mut sum_b = 0.0;
+    let mut sum_c = 0.0;
+    let mut sum_d = 0.0;
+
+    for ((x, y), weight) in points.iter().zip(weights) {
+        let ebxc = (b * (x - c)).exp();
+        let sigmoid = 1.0 / (1.0 + ebxc);
+
+        let diff = weight * (y - d - (a - d) * sigmoid);
+        let duda = sigmoid;
+        let dudb = (x - c) * ebxc * sigmoid * sigmoid;
+        let dudc = ebxc * sigmoid * sigmoid;
+        let dudd = 1.0 - sigmoid;
+
+        sum_a += diff * duda;
+        sum_b += diff * dudb;
+        sum_c += diff * dudc;
+        sum_d += diff * dudd;
+    }
+
+    let n = points.len() as f64;
+    [
+        -2.0 / n * sum_a,
+        2.0 * (a - d) / n * sum_b,
+        -2.0 * b * (a - d) / n * sum_c,
+        -2.0 / n * sum_d,
+    ]
+}
+
+/// For timing the fit, Instant::now panics on the web
+fn now() -> Option<Instant> {
+    #[cfg(not(target_arch = "wasm32"))]
+    return Some(Instant::now());
+    #[cfg(target_arch = "wasm32")]
+    return None;
+}
+
+/// Standards sorted by concentration have to be enough to fit the four parameters
+fn check_standards(standards: &[(f64, f64)]) -> Result<(), RegressionError> {
+    // We need at least 4 standards, preferably 8
+    if standards.len() < 4 { return Err(ValueError::NotEnoughStandards.into()) }
+    if standards.iter().any(|&(x, _y)| x <= 0.0) { return Err(RegressionError::InvalidStandards) }
+
+    // groups with the same concentration don't add a point to the curve
+    let distinct = 1 + standards.windows(2).filter(|window| window[0].0 != window[1].0).count();
+    let flat = standards.iter().all(|&(_x, y)| y == standards[0].1);
+    if distinct < 4 || flat { return Err(RegressionError::InvalidStandards) }
+    Ok(())
+}
+
+/// Gauss-Jordan elimination with partial pivoting, None for a singular matrix
+fn invert(mut matrix: Vec<Vec<f64>>) -> Option<Vec<Vec<f64>>> {
+    let n = matrix.len();
+    let mut inverse: Vec<Vec<f64>> = (0..n).map(|i| (0..n).map(|j| if i == j { 1.0 } else { 0.0 }).collect()).collect();
+
+    for column in 0..n {
+        let pivot = (column..n).max_by(|&i, &j| matrix[i][column].abs().total_cmp(&matrix[j][column].abs()))?;
+        if matrix[pivot][column].abs() < 1e-300 { return None }
+        matrix.swap(column, pivot);
+        inverse.swap(column, pivot);
+
+        let factor = matrix[column][column];
+        for j in 0..n {
+            matrix[column][j] /= factor;
+            inverse[column][j] /= factor;
+        }
+        for row in 0..n {
+            if row == column { continue }
+            let factor = matrix[row][column];
+            for j in 0..n {
+                matrix[row][j] -= factor * matrix[column][j];
+                inverse[row][j] -= factor * inverse[column][j];
+            }
+        }
     }
+    Some(inverse)
 }
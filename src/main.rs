@@ -1,14 +1,20 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 mod app;
-mod logistic_regression;
+mod files;
+mod log_console;
+mod reader;
+mod report;
+mod settings;
 mod ui;
 
 use crate::app::*;
+use elisa::{acquisition, analysis, archive, audit, barcode, bootstrap, clipboard, cutoff, drift, exclusion, export_hook, expression, grading, history, i18n, json_export, layout, linear_range, logistic_regression, manifest, path_length, plate_diff, protocol, replicates, results_filter, self_test, server, sha256, simulation, watch_folder, wavelength, well_details, well_swap};
 use eframe::egui::{self, IconData};
 
+#[cfg(not(target_arch = "wasm32"))]
 fn main() -> eframe::Result {
-    env_logger::init();
+    log_console::init();
 
     #[cfg(target_os = "macos")]
     let icon = include_bytes!("../resources/Icon_MacOS.png");
@@ -23,17 +29,47 @@ fn main() -> eframe::Result {
     };
     let options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()
-            // .with_resizable(false)
+            .with_resizable(true)
             .with_inner_size([890.0, 720.0])
+            .with_min_inner_size(MIN_WINDOW_SIZE)
             .with_icon(icon_data)
-            // .with_min_inner_size(vec2(890.0, 690.0))
             .with_drag_and_drop(true),
 
         ..default()
     };
-    eframe::run_native("Elisa", options, Box::new(|cc|
-        Ok(Box::from(Elisa::new(cc)))
-    ))
+    // a project opened with Elisa from the file manager, options like `--` are left alone
+    let project = std::env::args_os().skip(1)
+        .map(std::path::PathBuf::from)
+        .find(|arg| !arg.to_string_lossy().starts_with('-'));
+    eframe::run_native("Elisa", options, Box::new(|cc| {
+        let elisa = Elisa::new(cc);
+        if let Some(project) = project {
+            elisa.opened_files.open_path(files::FileAction::LoadAssay, project);
+        }
+        Ok(Box::from(elisa))
+    }))
+}
+
+/// Started by trunk from `index.html`, draws into its canvas
+#[cfg(target_arch = "wasm32")]
+fn main() {
+    use eframe::wasm_bindgen::JsCast;
+
+    log_console::init();
+
+    wasm_bindgen_futures::spawn_local(async {
+        let canvas = web_sys::window()
+            .and_then(|window| window.document())
+            .and_then(|document| document.get_element_by_id("elisa_canvas"))
+            .and_then(|canvas| canvas.dyn_into::<web_sys::HtmlCanvasElement>().ok())
+            .expect("index.html has no canvas with the id elisa_canvas");
+        let started = eframe::WebRunner::new()
+            .start(canvas, eframe::WebOptions::default(), Box::new(|cc| Ok(Box::new(Elisa::new(cc)))))
+            .await;
+        if let Err(error) = started {
+            log::error!("Could not start Elisa: {error:?}");
+        }
+    });
 }
 
 pub fn default<D: Default>() -> D {
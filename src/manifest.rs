@@ -0,0 +1,210 @@
+use std::fmt::Display;
+
+use crate::{i18n::*, logistic_regression::*};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ManifestError {
+    Empty,
+    NoWellColumn,
+}
+
+impl Display for ManifestError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let error = match self {
+            Self::Empty => tr("manifest-empty"),
+            Self::NoWellColumn => tr("manifest-no-well-column"),
+        };
+        write!(f, "{}", error)
+    }
+}
+
+/// What a line of the manifest says about a well
+#[derive(Clone, Debug, PartialEq)]
+pub struct ManifestEntry {
+    pub well: usize,
+    pub label: String,
+    pub metadata: SampleMetadata, // empty fields are left alone on the plate
+}
+
+/// Where the manifest and the layout of the plate don't agree
+#[derive(Clone, Debug, PartialEq)]
+pub enum ManifestIssue {
+    InvalidWell(usize, String), // line, cell
+    DuplicateWell(usize),
+    NotUnknown(usize),          // well that isn't an unknown on the plate
+    Conflicting(usize),         // unknown group whose wells have different labels, the first one is taken
+    Missing(usize),             // unknown group without any of its wells in the manifest
+}
+
+impl ManifestIssue {
+    pub fn description(&self, microplate: &Microplate) -> String {
+        let group = |group: &usize| tr_args("unknown-number", &[("number", (group + 1).into())]);
+        match self {
+            Self::InvalidWell(line, cell) => tr_args("manifest-invalid-well", &[("line", (*line).into()), ("well", cell.as_str().into())]),
+            Self::DuplicateWell(well) => tr_args("manifest-duplicate-well", &[("well", microplate.well_name(*well).into())]),
+            Self::NotUnknown(well) => tr_args("manifest-not-unknown", &[("well", microplate.well_name(*well).into())]),
+            Self::Conflicting(index) => tr_args("manifest-conflicting", &[("group", group(index).into())]),
+            Self::Missing(index) => tr_args("manifest-missing", &[("group", group(index).into())]),
+        }
+    }
+}
+
+/// A sample manifest read against a plate, with the label and metadata it gives each unknown group
+#[derive(Clone, Debug, PartialEq)]
+pub struct Manifest {
+    pub entries: Vec<ManifestEntry>,
+    pub groups: Vec<(usize, ManifestEntry)>, // unknown group and the entry of its first well in the manifest
+    pub issues: Vec<ManifestIssue>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum ManifestColumn {
+    Well,
+    Label,
+    SubjectId,
+    Timepoint,
+    Matrix,
+    CollectionDate,
+}
+
+impl ManifestColumn {
+    /// Column headers as spreadsheets and LIMS exports tend to name them, compared without case, spaces and underscores
+    fn from_header(header: &str) -> Option<Self> {
+        let header: String = header.chars().filter(|c| c.is_alphanumeric()).collect::<String>().to_lowercase();
+        match header.as_str() {
+            "well" | "position" | "wellposition" | "vertiefung" | "napf" => Some(Self::Well),
+            "label" | "sample" | "samplelabel" | "sampleid" | "samplename" | "name" | "probe" | "bezeichnung" => Some(Self::Label),
+            "subject" | "subjectid" | "patient" | "patientid" | "donor" | "proband" => Some(Self::SubjectId),
+            "timepoint" | "visit" | "zeitpunkt" | "visite" => Some(Self::Timepoint),
+            "matrix" | "sampletype" | "material" => Some(Self::Matrix),
+            "collectiondate" | "date" | "datum" | "entnahmedatum" => Some(Self::CollectionDate),
+            _ => None,
+        }
+    }
+}
+
+/// Cells of a CSV line split at `separator`, quoted cells may contain it
+fn split_line(line: &str, separator: char) -> Vec<String> {
+    let mut cells = Vec::new();
+    let mut cell = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                cell.push('"');
+                chars.next();
+            },
+            '"' => quoted = !quoted,
+            c if c == separator && !quoted => cells.push(std::mem::take(&mut cell).trim().to_string()),
+            c => cell.push(c),
+        }
+    }
+    cells.push(cell.trim().to_string());
+    cells
+}
+
+/// Reads a manifest with a header line, e.g. `Well,Sample,Subject ID,Visit`, separated by commas, semicolons or tabs.
+/// A manifest without a header is read as well and label, e.g. `A1,Patient 12`.
+pub fn read_manifest(text: &str, microplate: &Microplate) -> Result<Manifest, ManifestError> {
+    let mut lines = text.lines().enumerate().filter(|(_, line)| !line.trim().is_empty());
+    let Some((first_line, first)) = lines.next() else { return Err(ManifestError::Empty) };
+    let separator = ['\t', ';', ','].into_iter().find(|&separator| first.contains(separator)).unwrap_or(',');
+
+    let header = split_line(first, separator);
+    let first_well = header.iter().position(|cell| microplate.well_index(cell).is_some());
+    let has_header = first_well.is_none();
+    let columns: Vec<Option<ManifestColumn>> = match first_well {
+        // no header, the well and the cell next to it as its label
+        Some(well) => {
+            let label = if well == 0 { 1 } else { well - 1 };
+            (0..header.len()).map(|i| match i {
+                i if i == well => Some(ManifestColumn::Well),
+                i if i == label => Some(ManifestColumn::Label),
+                _ => None,
+            }).collect()
+        },
+        None => header.iter().map(|cell| ManifestColumn::from_header(cell)).collect(),
+    };
+    let Some(well_column) = columns.iter().position(|column| *column == Some(ManifestColumn::Well)) else {
+        return Err(ManifestError::NoWellColumn)
+    };
+
+    let rows = if has_header { None } else { Some((first_line, first)) }.into_iter().chain(lines);
+    let mut entries: Vec<ManifestEntry> = Vec::new();
+    let mut issues = Vec::new();
+    for (line, text) in rows {
+        let cells = split_line(text, separator);
+        let cell = cells.get(well_column).map(String::as_str).unwrap_or_default();
+        let Some(well) = microplate.well_index(cell) else {
+            issues.push(ManifestIssue::InvalidWell(line + 1, cell.to_string()));
+            continue
+        };
+        if entries.iter().any(|entry| entry.well == well) {
+            issues.push(ManifestIssue::DuplicateWell(well));
+            continue
+        }
+        let mut entry = ManifestEntry { well, label: String::new(), metadata: SampleMetadata::default() };
+        for (column, cell) in columns.iter().zip(cells) {
+            match column {
+                Some(ManifestColumn::Label) => entry.label = cell,
+                Some(ManifestColumn::SubjectId) => entry.metadata.subject_id = cell,
+                Some(ManifestColumn::Timepoint) => entry.metadata.timepoint = cell,
+                Some(ManifestColumn::Matrix) => entry.metadata.matrix = cell,
+                Some(ManifestColumn::CollectionDate) => entry.metadata.collection_date = cell,
+                Some(ManifestColumn::Well) | None => {},
+            }
+        }
+        entries.push(entry);
+    }
+
+    let mut groups: Vec<(usize, ManifestEntry)> = Vec::new();
+    for entry in &entries {
+        let sample = &microplate.samples[entry.well];
+        if sample.typ != SampleType::Unknown {
+            issues.push(ManifestIssue::NotUnknown(entry.well));
+            continue
+        }
+        match groups.iter().find(|(group, _)| *group == sample.group) {
+            Some((group, first)) if first.label != entry.label || first.metadata != entry.metadata => {
+                if !issues.contains(&ManifestIssue::Conflicting(*group)) {
+                    issues.push(ManifestIssue::Conflicting(*group));
+                }
+            },
+            Some(_) => {},
+            None => groups.push((sample.group, entry.clone())),
+        }
+    }
+    groups.sort_by_key(|(group, _)| *group);
+
+    let used_groups = microplate.samples.iter()
+        .filter(|sample| sample.typ == SampleType::Unknown)
+        .map(|sample| sample.group);
+    let mut missing: Vec<usize> = used_groups.filter(|group| groups.iter().all(|(g, _)| g != group)).collect();
+    missing.sort();
+    missing.dedup();
+    issues.extend(missing.into_iter().map(ManifestIssue::Missing));
+
+    Ok(Manifest { entries, groups, issues })
+}
+
+impl Microplate {
+    /// Labels and metadata of the unknown groups from the manifest, fields it leaves empty stay as they are
+    pub fn apply_manifest(&mut self, manifest: &Manifest) {
+        for (index, entry) in &manifest.groups {
+            let Some(group) = self.unknown_groups.get_mut(*index) else { continue };
+            let fields = [
+                (&mut group.label, &entry.label),
+                (&mut group.metadata.subject_id, &entry.metadata.subject_id),
+                (&mut group.metadata.timepoint, &entry.metadata.timepoint),
+                (&mut group.metadata.matrix, &entry.metadata.matrix),
+                (&mut group.metadata.collection_date, &entry.metadata.collection_date),
+            ];
+            for (field, value) in fields {
+                if !value.is_empty() {
+                    *field = value.clone();
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use std::fmt::Display;
+
+use crate::{i18n::*, logistic_regression::*};
+
+/// Reversals smaller than this fraction of the signal span are noise, e.g. between standards on a flat asymptote
+const TOLERANCE: f64 = 0.05;
+
+/// A standard group with its mean blank corrected response, as the fit would see it
+#[derive(Clone, Debug, PartialEq)]
+pub struct StandardMean {
+    pub group: usize,
+    pub concentration: f64,
+    pub mean: f64,
+    pub wells: Vec<String>,
+}
+
+impl StandardMean {
+    pub fn name(&self) -> String {
+        let standard = tr_args("standard-number", &[("number", (self.group + 1).into())]);
+        format!("{standard} ({})", format_number(self.concentration))
+    }
+}
+
+/// Standards whose mean responses go against the trend of the others, fitting them would give a nonsensical curve
+#[derive(Clone, Debug, PartialEq)]
+pub struct NonMonotonic {
+    pub increasing: bool, // the trend most standards follow
+    pub reversals: Vec<(StandardMean, StandardMean)>, // neighbouring standards against the trend, the lower concentration first
+    pub swap: Option<(StandardMean, StandardMean)>, // standards that, put in each other's wells, make the curve monotonic
+}
+
+impl Display for NonMonotonic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let id = if self.increasing { "monotonic-reads-lower" } else { "monotonic-reads-higher" };
+        let mut lines = vec![tr("error-not-monotonic")];
+        lines.extend(self.reversals.iter().map(|(previous, standard)| {
+            format!("• {}", tr_args(id, &[("standard", standard.name().into()), ("previous", previous.name().into())]))
+        }));
+        if let Some((first, second)) = &self.swap {
+            lines.push(tr_args("monotonic-swap", &[
+                ("first", first.name().into()),
+                ("first_wells", first.wells.join(", ").into()),
+                ("second", second.name().into()),
+                ("second_wells", second.wells.join(", ").into()),
+            ]));
+        }
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+impl Microplate {
+    /// Standard groups with values, sorted by concentration. Groups without a valid concentration are left out,
+    /// the fit reports those.
+    pub fn standard_means(&self) -> Vec<StandardMean> {
+        let blanks = self.blanks();
+        let values = self.fitted_values();
+        let mut sums = vec![(0.0, 0, Vec::new()); self.standard_groups.len()];
+        for (i, sample) in self.samples.iter().enumerate() {
+            if sample.typ != SampleType::Standard || sample.excluded || !self.in_region(i) { continue }
+            let (Some(value), Some(sum)) = (values[i], sums.get_mut(sample.group)) else { continue };
+            sum.0 += value - self.blank_of_well(&blanks, i);
+            sum.1 += 1;
+            sum.2.push(self.well_name(i));
+        }
+
+        let mut means: Vec<_> = sums.into_iter().enumerate().filter_map(|(group, (sum, count, wells))| {
+            let concentration = self.standard_groups[group].concentration.filter(|concentration| concentration.is_finite())?;
+            (count > 0).then(|| StandardMean { group, concentration, mean: sum / count as f64, wells })
+        }).collect();
+        means.sort_by(|a, b| a.concentration.total_cmp(&b.concentration));
+        means
+    }
+
+    /// None when the mean responses of the standards rise, or fall, steadily with the concentration
+    pub fn standard_monotonicity(&self) -> Option<NonMonotonic> {
+        check_monotonicity(&self.standard_means())
+    }
+}
+
+/// Standards sorted by concentration against the trend most of them follow, with the closest pair of standards
+/// whose swap would remove every reversal. Groups with the same concentration aren't compared with each other.
+pub fn check_monotonicity(standards: &[StandardMean]) -> Option<NonMonotonic> {
+    if standards.len() < 2 { return None }
+    let means: Vec<_> = standards.iter().map(|standard| standard.mean).collect();
+    let highest = means.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let lowest = means.iter().copied().fold(f64::INFINITY, f64::min);
+    let tolerance = TOLERANCE * (highest - lowest);
+
+    // (index of the lower standard, change of the mean to the next one)
+    let steps = |means: &[f64]| -> Vec<(usize, f64)> {
+        (0..means.len() - 1)
+            .filter(|&i| standards[i].concentration != standards[i + 1].concentration)
+            .map(|i| (i, means[i + 1] - means[i]))
+            .filter(|(_i, step)| step.abs() > tolerance)
+            .collect()
+    };
+    let rising = steps(&means).iter().filter(|(_i, step)| *step > 0.0).count();
+    let falling = steps(&means).len() - rising;
+    let increasing = if rising != falling { rising > falling } else { means[means.len() - 1] >= means[0] };
+    let against = |means: &[f64]| -> Vec<usize> {
+        steps(means).into_iter().filter(|(_i, step)| (*step > 0.0) != increasing).map(|(i, _step)| i).collect()
+    };
+
+    let reversals = against(&means);
+    if reversals.is_empty() { return None }
+
+    let n = standards.len();
+    let swap = (1..n)
+        .flat_map(|distance| (0..n - distance).map(move |i| (i, i + distance)))
+        .filter(|&(a, b)| standards[a].concentration != standards[b].concentration)
+        .find(|&(a, b)| {
+            let mut swapped = means.clone();
+            swapped.swap(a, b);
+            against(&swapped).is_empty()
+        });
+
+    Some(NonMonotonic {
+        increasing,
+        reversals: reversals.into_iter().map(|i| (standards[i].clone(), standards[i + 1].clone())).collect(),
+        swap: swap.map(|(a, b)| (standards[a].clone(), standards[b].clone())),
+    })
+}
@@ -0,0 +1,67 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{i18n::*, logistic_regression::*};
+
+/// OD of the water peak per cm, read at 977 nm less 900 nm at room temperature
+pub const WATER_K_FACTOR: f64 = 0.18;
+
+/// Values normalized to a path length of 1 cm before fitting. Plates read from above, e.g. for UV absorbance,
+/// have the height of the liquid in the well as their path length, which changes with the volume pipetted.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum PathLengthCorrection {
+    #[default]
+    None,
+    Factor(f64),                     // multiplies every value, e.g. 1.8 for 0.56 cm of liquid
+    ReferenceRead { k_factor: f64 }, // each well from its water peak read, read / k-factor is the path length in cm
+}
+
+impl PathLengthCorrection {
+    pub const ALL: [Self; 3] = [Self::None, Self::Factor(1.0), Self::ReferenceRead { k_factor: WATER_K_FACTOR }];
+
+    pub fn description(&self) -> String {
+        let id = match self {
+            Self::None => "path-length-none",
+            Self::Factor(_) => "path-length-factor",
+            Self::ReferenceRead { .. } => "path-length-reference",
+        };
+        tr(id)
+    }
+
+    /// Same kind of correction, whatever its numbers
+    pub fn same_mode(&self, other: &Self) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
+
+    /// What the value of the well is multiplied with, None without a usable path-length read
+    pub fn factor(&self, sample: &Sample) -> Option<f64> {
+        match *self {
+            Self::None => Some(1.0),
+            Self::Factor(factor) => Some(factor),
+            Self::ReferenceRead { k_factor } => sample.path_length_read.filter(|read| *read > 0.0).map(|read| k_factor / read),
+        }
+    }
+}
+
+impl Microplate {
+    /// Water peak reads of the wells, rows of the plate like `assign_values`. Wells that aren't in `data` lose theirs.
+    pub fn assign_path_length_reads(&mut self, data: Vec<Vec<Option<f64>>>) {
+        for sample in &mut self.samples {
+            sample.path_length_read = None;
+        }
+        for (y, line) in data.into_iter().enumerate().take(self.height) {
+            for (x, cell) in line.into_iter().enumerate().take(self.width) {
+                self.samples[self.height * x + y].path_length_read = cell;
+            }
+        }
+    }
+
+    /// Value of the well normalized to 1 cm by the path-length correction of the fit options, see `fitted_values`
+    pub fn normalized_value(&self, index: usize) -> Option<f64> {
+        let sample = &self.samples[index];
+        Some(sample.value? * self.fit_options.path_length.factor(sample)?)
+    }
+
+    pub fn path_length_reads(&self) -> usize {
+        self.samples.iter().filter(|sample| sample.path_length_read.is_some()).count()
+    }
+}
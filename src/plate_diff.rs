@@ -0,0 +1,92 @@
+use std::fmt::Display;
+
+use crate::{i18n::*, logistic_regression::*};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum PlateDiffError {
+    WrongDimensions(usize, usize), // of the other plate
+}
+
+impl Display for PlateDiffError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let error = match self {
+            Self::WrongDimensions(width, height) => tr_args("compare-wrong-dimensions", &[("width", (*width).into()), ("height", (*height).into())]),
+        };
+        write!(f, "{}", error)
+    }
+}
+
+/// Difference of the current value to the other one, and that in % of the other
+fn difference(current: Option<f64>, other: Option<f64>) -> (Option<f64>, Option<f64>) {
+    let (Some(current), Some(other)) = (current, other) else { return (None, None) };
+    let percent = (other != 0.0).then(|| (current - other) / other.abs() * 100.0);
+    (Some(current - other), percent)
+}
+
+/// Values of a well on both plates, as read
+#[derive(Clone, Debug, PartialEq)]
+pub struct WellDifference {
+    pub well: usize,
+    pub current: Option<f64>,
+    pub other: Option<f64>,
+    pub difference: Option<f64>,
+    pub percent: Option<f64>,
+}
+
+/// Concentrations of an unknown group on both plates, of the undiluted sample.
+/// None if the plate can't be fitted, has no wells of the group or reads it outside of the standards.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GroupDifference {
+    pub group: usize,
+    pub name: String,
+    pub current: Option<f64>,
+    pub other: Option<f64>,
+    pub difference: Option<f64>,
+    pub percent: Option<f64>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlateDiff {
+    pub wells: Vec<WellDifference>,
+    pub groups: Vec<GroupDifference>,
+    pub mean_well_percent: Option<f64>, // mean of the absolute well differences in %
+}
+
+fn concentrations(microplate: &Microplate) -> Vec<(usize, String, f64)> {
+    let Ok(regression) = Regression::new(microplate) else { return Vec::new() };
+    regression.unknowns.iter()
+        .filter(|unknown| matches!(unknown.range, CurveRange::Within | CurveRange::Extrapolated))
+        .map(|unknown| (unknown.group, unknown.name(), unknown.corrected_concentration()))
+        .collect()
+}
+
+/// Well by well and group by group, for plates of the same size with the same layout, e.g. a duplicate plate.
+/// Each plate is fitted on its own with its fit options.
+pub fn compare_plates(current: &Microplate, other: &Microplate) -> Result<PlateDiff, PlateDiffError> {
+    if current.width != other.width || current.height != other.height {
+        return Err(PlateDiffError::WrongDimensions(other.width, other.height))
+    }
+
+    let wells: Vec<WellDifference> = current.samples.iter().zip(&other.samples).enumerate()
+        .filter(|(_, (a, b))| a.value.is_some() || b.value.is_some())
+        .map(|(well, (a, b))| {
+            let (difference, percent) = difference(a.value, b.value);
+            WellDifference { well, current: a.value, other: b.value, difference, percent }
+        })
+        .collect();
+    let percents: Vec<f64> = wells.iter().filter_map(|well| well.percent).map(f64::abs).collect();
+    let mean_well_percent = (!percents.is_empty()).then(|| percents.iter().sum::<f64>() / percents.len() as f64);
+
+    let (current_concentrations, other_concentrations) = (concentrations(current), concentrations(other));
+    let find = |concentrations: &[(usize, String, f64)], group: usize| concentrations.iter().find(|(g, _, _)| *g == group).cloned();
+    let groups_len = current.unknown_groups.len().max(other.unknown_groups.len());
+    let groups = (0..groups_len).filter_map(|group| {
+        let (a, b) = (find(&current_concentrations, group), find(&other_concentrations, group));
+        let name = a.as_ref().or(b.as_ref())?.1.clone();
+        let (current, other) = (a.map(|(_, _, concentration)| concentration), b.map(|(_, _, concentration)| concentration));
+        let (difference, percent) = difference(current, other);
+        Some(GroupDifference { group, name, current, other, difference, percent })
+    }).collect();
+
+    Ok(PlateDiff { wells, groups, mean_well_percent })
+}
@@ -0,0 +1,228 @@
+use std::io;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{expression::DerivedColumn, grading::*, history::*, i18n::*, logistic_regression::*};
+
+/// Expected setup of an assay and the criteria a fit has to meet, checked after every fit
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Protocol {
+    pub name: String,
+    pub standard_concentrations: Vec<f64>,
+    pub weighting: Weighting,
+    pub cv_limit: Option<f64>,              // %, replicate CV of standards and unknowns
+    pub r_sq_limit: Option<f64>,
+    pub recovery_range: Option<(f64, f64)>, // %, backfit recovery of the standards
+    pub min_curve_grade: Option<CurveGrade>,
+    pub control_recovery: Option<(f64, f64)>, // %, recovery of the unknowns with a known spike, e.g. QC controls
+    pub max_blank: Option<f64>,               // highest mean signal of the blanks
+    pub max_dilution: Option<f64>,          // highest dilution a sample may be run at, widens the reportable range
+    pub derived_columns: Vec<DerivedColumn>, // extra columns of the results, computed from each unknown
+}
+
+impl Default for Protocol {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            standard_concentrations: Vec::new(),
+            weighting: Weighting::None,
+            cv_limit: Some(20.0),
+            r_sq_limit: Some(0.99),
+            recovery_range: Some((80.0, 120.0)),
+            min_curve_grade: None,
+            control_recovery: None,
+            max_blank: None,
+            max_dilution: None,
+            derived_columns: Vec::new(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Criterion {
+    StandardConcentrations,
+    Weighting,
+    ReplicateCv,
+    RSquared,
+    Recovery,
+    Grade,
+    ControlRecovery,
+    BlankLevel,
+}
+
+impl Criterion {
+    pub fn name(&self) -> String {
+        let id = match self {
+            Self::StandardConcentrations => "qc-concentrations",
+            Self::Weighting => "weighting",
+            Self::ReplicateCv => "qc-cv",
+            Self::RSquared => "qc-r-squared",
+            Self::Recovery => "qc-recovery",
+            Self::Grade => "qc-curve-grade",
+            Self::ControlRecovery => "qc-control-recovery",
+            Self::BlankLevel => "qc-blank-level",
+        };
+        tr(id)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct QcCheck {
+    pub criterion: Criterion,
+    pub passed: bool,
+    pub detail: String,
+}
+
+impl Protocol {
+    /// Sets the standard concentrations and the weighting of the plate to the ones of the protocol
+    pub fn apply(&self, microplate: &mut Microplate) {
+        if microplate.standard_groups.len() < self.standard_concentrations.len() {
+            microplate.standard_groups.resize_with(self.standard_concentrations.len(), Group::default);
+        }
+        for (group, &concentration) in microplate.standard_groups.iter_mut().zip(&self.standard_concentrations) {
+            group.concentration = Some(concentration);
+        }
+        microplate.fit_options.weighting = self.weighting;
+    }
+
+    pub fn evaluate(&self, microplate: &Microplate, regression: &Regression) -> Vec<QcCheck> {
+        use Criterion::*;
+        let mut checks = Vec::new();
+
+        if !self.standard_concentrations.is_empty() {
+            let mismatch = self.standard_concentrations.iter().enumerate().find(|&(i, expected)| {
+                let actual = microplate.standard_groups.get(i).and_then(|group| group.concentration);
+                actual.is_none_or(|actual| (actual - expected).abs() > expected.abs() * 1e-6)
+            });
+            let detail = match mismatch {
+                Some((i, expected)) => tr_args("qc-concentration-mismatch", &[
+                    ("number", (i + 1).into()),
+                    ("expected", format_number(*expected).into()),
+                ]),
+                None => tr_args("qc-concentrations-match", &[("count", self.standard_concentrations.len().into())]),
+            };
+            checks.push(QcCheck { criterion: StandardConcentrations, passed: mismatch.is_none(), detail });
+        }
+
+        checks.push(QcCheck {
+            criterion: Weighting,
+            passed: regression.options.weighting == self.weighting,
+            detail: regression.options.weighting.description(),
+        });
+
+        if let Some(limit) = self.cv_limit {
+            let worst = replicate_cvs(microplate).into_iter().max_by(|a, b| a.1.total_cmp(&b.1));
+            let (passed, detail) = match worst {
+                Some((name, cv)) => (cv <= limit, tr_args("qc-cv-detail", &[
+                    ("cv", format_decimal(cv, 1).into()),
+                    ("name", name.into()),
+                    ("limit", format_number(limit).into()),
+                ])),
+                None => (true, tr("qc-no-replicates")),
+            };
+            checks.push(QcCheck { criterion: ReplicateCv, passed, detail });
+        }
+
+        if let Some(limit) = self.r_sq_limit {
+            checks.push(QcCheck {
+                criterion: RSquared,
+                passed: regression.r_sq >= limit,
+                detail: tr_args("qc-r-squared-detail", &[
+                    ("r_sq", format_decimal(regression.r_sq, 4).into()),
+                    ("limit", format_number(limit).into()),
+                ]),
+            });
+        }
+
+        if let Some((min, max)) = self.recovery_range {
+            // anchors lie outside the quantitation range on purpose and aren't held to it
+            let standards: Vec<_> = regression.back_calculated_standards().into_iter().filter(|standard| !standard.anchor).collect();
+            let outside = standards.iter().filter(|standard| !(min..=max).contains(&standard.recovery)).count();
+            checks.push(QcCheck {
+                criterion: Recovery,
+                passed: outside == 0,
+                detail: tr_args("qc-recovery-detail", &[
+                    ("outside", outside.into()),
+                    ("count", standards.len().into()),
+                    ("min", format_number(min).into()),
+                    ("max", format_number(max).into()),
+                ]),
+            });
+        }
+
+        if let Some(minimum) = self.min_curve_grade {
+            let quality = CurveQuality::new(regression);
+            checks.push(QcCheck {
+                criterion: Grade,
+                passed: quality.grade <= minimum,
+                detail: tr_args("qc-curve-grade-detail", &[("grade", quality.grade.name().into()), ("minimum", minimum.name().into())]),
+            });
+        }
+
+        if let Some((min, max)) = self.control_recovery {
+            let recoveries: Vec<f64> = regression.unknowns.iter().filter_map(UnknownResult::recovery).collect();
+            let outside = recoveries.iter().filter(|recovery| !(min..=max).contains(*recovery)).count();
+            let detail = if recoveries.is_empty() {
+                tr("qc-no-spiked-controls")
+            } else {
+                tr_args("qc-recovery-detail", &[
+                    ("outside", outside.into()),
+                    ("count", recoveries.len().into()),
+                    ("min", format_number(min).into()),
+                    ("max", format_number(max).into()),
+                ])
+            };
+            checks.push(QcCheck { criterion: ControlRecovery, passed: outside == 0, detail });
+        }
+
+        if let Some(limit) = self.max_blank {
+            let (passed, detail) = match microplate.blanks().plate {
+                Some(blank) => (blank <= limit, tr_args("qc-blank-level-detail", &[
+                    ("blank", format_value(blank).into()),
+                    ("limit", format_value(limit).into()),
+                ])),
+                None => (true, tr("qc-no-blanks")),
+            };
+            checks.push(QcCheck { criterion: BlankLevel, passed, detail });
+        }
+
+        checks
+    }
+}
+
+/// CV in percent of the raw replicate values of every standard and unknown group with more than one well
+pub fn replicate_cvs(microplate: &Microplate) -> Vec<(String, f64)> {
+    let mut cvs = Vec::new();
+    for (typ, groups) in [(SampleType::Standard, microplate.standard_groups.len()), (SampleType::Unknown, microplate.unknown_groups.len())] {
+        for group in 0..groups {
+            let values: Vec<f64> = microplate.samples.iter().enumerate()
+                .filter(|&(i, sample)| sample.typ == typ && sample.group == group && !sample.excluded && microplate.in_region(i))
+                .filter_map(|(_, sample)| sample.value)
+                .collect();
+            if values.len() < 2 { continue }
+            let (mean, sd) = mean_sd(&values);
+            if mean == 0.0 { continue }
+            let name = match typ {
+                SampleType::Standard => tr_args("standard-number", &[("number", (group + 1).into())]),
+                _ => match microplate.unknown_groups[group].label.as_str() {
+                    "" => tr_args("unknown-number", &[("number", (group + 1).into())]),
+                    label => label.to_string(),
+                },
+            };
+            cvs.push((name, (sd / mean).abs() * 100.0));
+        }
+    }
+    cvs
+}
+
+pub fn load_protocols() -> Vec<Protocol> {
+    read_data("protocols.json")
+        .and_then(|buf| serde_json::from_slice(&buf).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_protocols(protocols: &[Protocol]) -> io::Result<()> {
+    let serialized = serde_json::to_string_pretty(protocols).map_err(io::Error::other)?;
+    write_data("protocols.json", &serialized)
+}
@@ -0,0 +1,121 @@
+//! Python bindings of the curve fitting, built with maturin:
+//!
+//! ```python
+//! import elisa
+//!
+//! fit = elisa.fit_4pl([(1.0, 0.08), (3.0, 0.2), (10.0, 0.55), (30.0, 1.2), (100.0, 1.9), (300.0, 2.3)], "1/y^2")
+//! print(fit.a, fit.b, fit.c, fit.d, fit.r_squared)
+//! concentration = fit.interpolate(1.0)
+//! ```
+
+use pyo3::{exceptions::PyValueError, prelude::*};
+
+use crate::{fit::*, logistic_regression::Weighting};
+
+/// "none", "1/y" or "1/y^2", like in the fit options of the app
+fn parse_weighting(weights: &str) -> Option<Weighting> {
+    match weights.replace(' ', "").to_lowercase().as_str() {
+        "none" | "" => Some(Weighting::None),
+        "1/y" => Some(Weighting::OneOverY),
+        "1/y^2" | "1/y2" | "1/y²" => Some(Weighting::OneOverYSquared),
+        _ => None,
+    }
+}
+
+/// A fitted 4PL curve, y = d + (a - d) / (1 + (x / c)^b)
+#[pyclass(name = "Fit", frozen)]
+pub struct PyFit {
+    fit: Fit,
+}
+
+#[pymethods]
+impl PyFit {
+    #[getter]
+    fn a(&self) -> f64 {
+        self.fit.parameters.a
+    }
+
+    #[getter]
+    fn b(&self) -> f64 {
+        self.fit.parameters.b
+    }
+
+    #[getter]
+    fn c(&self) -> f64 {
+        self.fit.parameters.c
+    }
+
+    #[getter]
+    fn d(&self) -> f64 {
+        self.fit.parameters.d
+    }
+
+    #[getter]
+    fn sse(&self) -> f64 {
+        self.fit.sse
+    }
+
+    #[getter]
+    fn rmse(&self) -> f64 {
+        self.fit.rmse
+    }
+
+    #[getter]
+    fn sy_x(&self) -> f64 {
+        self.fit.sy_x
+    }
+
+    #[getter]
+    fn r_squared(&self) -> f64 {
+        self.fit.r_sq
+    }
+
+    #[getter]
+    fn converged(&self) -> bool {
+        self.fit.diagnostics.converged
+    }
+
+    /// (a, b, c, d) standard errors, None with too few standards
+    #[getter]
+    fn standard_errors(&self) -> Option<(f64, f64, f64, f64)> {
+        self.fit.diagnostics.standard_errors.map(|[a, b, c, d]| (a, b, c, d))
+    }
+
+    /// Signal of the curve at a concentration
+    fn evaluate(&self, x: f64) -> f64 {
+        self.fit.evaluate(x)
+    }
+
+    /// Concentration at a signal, None where the curve never gets to it
+    fn interpolate(&self, od: f64) -> Option<f64> {
+        self.fit.inverse(od)
+    }
+
+    fn __repr__(&self) -> String {
+        let FourPLParameters { a, b, c, d } = self.fit.parameters;
+        format!("Fit(a={a}, b={b}, c={c}, d={d}, r_squared={})", self.fit.r_sq)
+    }
+}
+
+/// Fits (concentration, signal) pairs with the same optimizer as the app, the signals already blank corrected
+#[pyfunction]
+#[pyo3(signature = (standards, weights = "none", starts = 1))]
+fn fit_4pl(standards: Vec<(f64, f64)>, weights: &str, starts: usize) -> PyResult<PyFit> {
+    let weighting = parse_weighting(weights)
+        .ok_or_else(|| PyValueError::new_err(format!("unknown weights {weights:?}, expected \"none\", \"1/y\" or \"1/y^2\"")))?;
+    let fit = FitBuilder::new()
+        .standards(&standards)
+        .weighting(weighting)
+        .model(Model::FourPL)
+        .starts(starts)
+        .fit()
+        .map_err(|error| PyValueError::new_err(format!("fit failed: {error:?}")))?;
+    Ok(PyFit { fit })
+}
+
+#[pymodule]
+fn elisa(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_function(wrap_pyfunction!(fit_4pl, module)?)?;
+    module.add_class::<PyFit>()?;
+    Ok(())
+}
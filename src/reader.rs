@@ -0,0 +1,253 @@
+use std::fmt::Display;
+
+use crate::layout::ALPHABET;
+
+pub type Rows = Vec<Vec<Option<f64>>>;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ReaderFormat {
+    SoftMaxPro,
+    Gen5,
+}
+
+impl Display for ReaderFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::SoftMaxPro => write!(f, "SoftMax Pro"),
+            Self::Gen5 => write!(f, "Gen5"),
+        }
+    }
+}
+
+/// Values and read information from a plate reader text export
+#[derive(Clone, Debug)]
+pub struct ReaderExport {
+    pub format: ReaderFormat,
+    pub values: Rows, // rows of the plate
+    pub reference_values: Option<Rows>, // second read of a dual wavelength export
+    pub wavelengths: (Option<f64>, Option<f64>), // nm of both reads, if the export names them
+    pub temperature: Option<f64>,
+    pub read_time: Option<String>,
+    pub read_settings: Vec<(String, String)>, // name and value as the export writes them
+}
+
+#[derive(Debug)]
+pub enum ReaderError {
+    UnknownFormat,
+    NoPlateData,
+}
+
+impl Display for ReaderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let error = match self {
+            Self::UnknownFormat => "File is neither a SoftMax Pro nor a Gen5 export",
+            Self::NoPlateData => "Could not find the plate values in the export",
+        };
+        write!(f, "{}", error)
+    }
+}
+
+/// Reader software likes to export UTF-16, so check for a byte order mark first
+pub fn decode_export(bytes: &[u8]) -> String {
+    match bytes {
+        [0xFF, 0xFE, rest @ ..] => {
+            let units: Vec<u16> = rest.chunks_exact(2).map(|c| u16::from_le_bytes([c[0], c[1]])).collect();
+            String::from_utf16_lossy(&units)
+        },
+        [0xFE, 0xFF, rest @ ..] => {
+            let units: Vec<u16> = rest.chunks_exact(2).map(|c| u16::from_be_bytes([c[0], c[1]])).collect();
+            String::from_utf16_lossy(&units)
+        },
+        [0xEF, 0xBB, 0xBF, rest @ ..] => String::from_utf8_lossy(rest).into_owned(),
+        _ => String::from_utf8_lossy(bytes).into_owned(),
+    }
+}
+
+pub fn detect_format(text: &str) -> Option<ReaderFormat> {
+    let start = text.trim_start();
+    if start.starts_with("##BLOCKS") {
+        Some(ReaderFormat::SoftMaxPro)
+    } else if text.lines().take(20).any(|line| line.starts_with("Software Version") || line.contains("Gen5")) {
+        Some(ReaderFormat::Gen5)
+    } else {
+        None
+    }
+}
+
+pub fn parse_reader_export(text: &str) -> Result<ReaderExport, ReaderError> {
+    match detect_format(text) {
+        Some(ReaderFormat::SoftMaxPro) => parse_softmax_pro(text),
+        Some(ReaderFormat::Gen5) => parse_gen5(text),
+        None => Err(ReaderError::UnknownFormat),
+    }
+}
+
+fn cells(line: &str) -> Vec<&str> {
+    let separator = if line.contains('\t') { '\t' } else { ',' };
+    line.split(separator).map(str::trim).collect()
+}
+
+/// Readers set up for a European locale write decimal commas into tab separated exports,
+/// they never group digits, so a comma is always the decimal separator
+fn parse_number(cell: &str) -> Option<f64> {
+    cell.replace(',', ".").parse().ok()
+}
+
+/// Plate format block:
+/// ```text
+/// Plate:    Plate1    1.3    PlateFormat    Endpoint    Absorbance    ...
+///     Temperature(°C)    1    2    3    ...    12
+///     24.50    0.046    0.051    ...
+///         0.047    0.052    ...
+/// ~End
+/// ```
+fn parse_softmax_pro(text: &str) -> Result<ReaderExport, ReaderError> {
+    let mut lines = text.lines();
+    let mut temperature = None;
+    let mut values = Vec::new();
+    let mut reference_values = Vec::new();
+    let mut read_settings = Vec::new();
+
+    // the plate line holds the read type and mode after the name, version and "PlateFormat"
+    if let Some(plate) = text.lines().find(|line| line.starts_with("Plate:")) {
+        let cells = cells(plate);
+        for (name, index) in [("Read Type", 4), ("Read Mode", 5)] {
+            if let Some(value) = cells.get(index).filter(|value| !value.is_empty()) {
+                read_settings.push((name.to_string(), value.to_string()));
+            }
+        }
+    }
+
+    let header = lines.find(|line| line.trim_start().starts_with("Temperature"))
+        .ok_or(ReaderError::NoPlateData)?;
+    // reads of two wavelengths are side by side, each block of columns numbered from 1 and separated by an empty column
+    let header = cells(header);
+    let first = header.iter().position(|cell| *cell == "1").unwrap_or(2);
+    let columns = header[first.min(header.len())..].iter().take_while(|cell| !cell.is_empty()).count();
+    let second = (header.get(first + columns + 1) == Some(&"1")).then_some(first + columns + 1);
+
+    for line in lines.by_ref() {
+        if line.starts_with("~End") || line.trim().is_empty() { break }
+        let cells = cells(line);
+        // first cell is empty, second holds the temperature on the first row only
+        if cells.len() < 3 { break }
+        if temperature.is_none() {
+            temperature = parse_number(cells[1]);
+        }
+        let read = |start: usize| trim_row(cells.iter().skip(start).take(columns.max(1)).map(|cell| parse_number(cell)).collect());
+        values.push(if second.is_some() { read(first) } else { trim_row(cells[2..].iter().map(|cell| parse_number(cell)).collect()) });
+        if let Some(second) = second {
+            reference_values.push(read(second));
+        }
+    }
+    if values.is_empty() { return Err(ReaderError::NoPlateData) }
+
+    let read_time = text.lines()
+        .find_map(|line| line.split_once("Date Last Saved:"))
+        .map(|(_, date)| date.trim().to_string());
+
+    Ok(ReaderExport {
+        format: ReaderFormat::SoftMaxPro,
+        values,
+        reference_values: (!reference_values.is_empty()).then_some(reference_values),
+        wavelengths: (None, None),
+        temperature,
+        read_time,
+        read_settings,
+    })
+}
+
+/// Gen5 plate export, a header of `key<TAB>value` lines followed by the matrix:
+/// ```text
+/// Date    1/15/2024
+/// Time    10:32:11 AM
+/// Actual Temperature:    25.1
+/// Read    Absorbance Endpoint
+///     Wavelengths:  450
+///     Read Speed: Normal,  Delay: 100 msec,  Measurements/Data Point: 8
+///
+///     1    2    3    ...    12
+/// A    0.046    0.051    ...    450
+/// ```
+/// A dual wavelength read has a second matrix below the first one, labeled e.g. 570.
+fn parse_gen5(text: &str) -> Result<ReaderExport, ReaderError> {
+    let mut temperature = None;
+    let mut date = None;
+    let mut time = None;
+    let mut read_settings = Vec::new();
+    let mut reads: Vec<(Rows, Option<f64>)> = Vec::new(); // matrices with their read label
+    let mut columns = 0; // number of matrix columns, zero outside of a matrix
+
+    for line in text.lines() {
+        let cells = cells(line);
+        let key = cells.first().copied().unwrap_or_default();
+
+        if columns > 0 {
+            if let Some((values, label)) = reads.last_mut() {
+                if key.len() == 1 && key.starts_with(ALPHABET[values.len() % 26]) {
+                    // Gen5 appends the read label (e.g. "450") after the last column
+                    let row: Vec<Option<f64>> = cells[1..].iter().take(columns).map(|cell| parse_number(cell)).collect();
+                    values.push(trim_row(row));
+                    if label.is_none() {
+                        *label = cells.get(columns + 1).and_then(|cell| parse_number(cell));
+                    }
+                    continue
+                }
+            }
+            columns = 0;
+            if reads.len() == 2 { break }
+        }
+
+        match key.trim_end_matches(':') {
+            "Date" => date = cells.get(1).map(|s| s.to_string()),
+            "Time" => time = cells.get(1).map(|s| s.to_string()),
+            "Actual Temperature" | "Temperature" => {
+                temperature = cells.iter().skip(1).find_map(|cell| parse_number(cell.trim_end_matches("°C")));
+            },
+            "" if cells.get(1) == Some(&"1") => {
+                columns = cells[1..].iter().take_while(|cell| cell.parse::<usize>().is_ok()).count();
+                reads.push((Vec::new(), None));
+            },
+            name @ ("Reader Type" | "Reader Serial Number" | "Plate Type" | "Read" | "Set Temperature") => {
+                if let Some(value) = cells.get(1).filter(|value| !value.is_empty()) {
+                    read_settings.push((name.to_string(), value.to_string()));
+                }
+            },
+            // indented lines of the procedure hold several settings, separated by commas
+            "" if reads.is_empty() => {
+                for setting in cells[1..].join(" ").split(',') {
+                    if let Some((name, value)) = setting.split_once(':').filter(|(name, value)| !name.trim().is_empty() && !value.trim().is_empty()) {
+                        read_settings.push((name.trim().to_string(), value.trim().to_string()));
+                    }
+                }
+            },
+            _ => (),
+        }
+    }
+    reads.retain(|(values, _)| !values.is_empty());
+    let mut reads = reads.into_iter();
+    let Some((values, wavelength)) = reads.next() else { return Err(ReaderError::NoPlateData) };
+    let reference = reads.next();
+
+    let read_time = match (date, time) {
+        (Some(date), Some(time)) => Some(format!("{date} {time}")),
+        (date, time) => date.or(time),
+    };
+
+    Ok(ReaderExport {
+        format: ReaderFormat::Gen5,
+        values,
+        wavelengths: (wavelength, reference.as_ref().and_then(|(_, wavelength)| *wavelength)),
+        reference_values: reference.map(|(values, _)| values),
+        temperature,
+        read_time,
+        read_settings,
+    })
+}
+
+fn trim_row(mut row: Vec<Option<f64>>) -> Vec<Option<f64>> {
+    while row.last() == Some(&None) {
+        row.pop();
+    }
+    row
+}
@@ -0,0 +1,103 @@
+use crate::{i18n::*, logistic_regression::*};
+
+/// How replicates of a standard or unknown sit next to each other on the plate
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ReplicatePattern {
+    ColumnPairs, // A1 and A2, then B1 and B2
+    RowPairs,    // A1 and B1, then C1 and D1
+}
+
+impl ReplicatePattern {
+    pub const ALL: [Self; 2] = [Self::ColumnPairs, Self::RowPairs];
+
+    pub fn description(&self) -> String {
+        match self {
+            Self::ColumnPairs => tr("replicates-column-pairs"),
+            Self::RowPairs => tr("replicates-row-pairs"),
+        }
+    }
+
+    /// The well next to `index` in the pattern, if `index` is the first well of its pair
+    fn partner(&self, microplate: &Microplate, index: usize) -> Option<usize> {
+        let (column, row) = (index / microplate.height, index % microplate.height);
+        match self {
+            Self::ColumnPairs if column % 2 == 0 && column + 1 < microplate.width => Some(index + microplate.height),
+            Self::RowPairs if row % 2 == 0 && row + 1 < microplate.height => Some(index + 1),
+            _ => None,
+        }
+    }
+}
+
+/// Groups a pattern assigns to the standards and unknowns, as (well, group)
+#[derive(Clone, Debug)]
+pub struct ReplicateAssignment {
+    pub pattern: ReplicatePattern,
+    pub wells: Vec<(usize, usize)>,
+    pub difference: Option<f64>, // mean relative difference within the pairs, None without values
+}
+
+/// Pairs up the standards and unknowns by `pattern`, None if a well is left without a partner of the same type
+pub fn assign_replicates(microplate: &Microplate, pattern: ReplicatePattern) -> Option<ReplicateAssignment> {
+    let samples = &microplate.samples;
+    let paired = |typ: SampleType| matches!(typ, SampleType::Standard | SampleType::Unknown);
+
+    let mut pairs = Vec::new();
+    let mut partnered = vec![false; samples.len()];
+    for (index, sample) in samples.iter().enumerate() {
+        if !paired(sample.typ) || partnered[index] { continue }
+        let partner = pattern.partner(microplate, index)?;
+        if samples[partner].typ != sample.typ { return None }
+        partnered[index] = true;
+        partnered[partner] = true;
+        pairs.push((index, partner));
+    }
+    if pairs.is_empty() { return None }
+
+    // groups are numbered in the order of the plate, like the wells
+    let (mut standards, mut unknowns) = (0, 0);
+    let mut wells = Vec::new();
+    let mut differences = Vec::new();
+    for (a, b) in pairs {
+        let counter = if samples[a].typ == SampleType::Standard { &mut standards } else { &mut unknowns };
+        let group = *counter;
+        *counter += 1;
+        wells.push((a, group));
+        wells.push((b, group));
+
+        if let (Some(a), Some(b)) = (samples[a].value, samples[b].value) {
+            let mean = (a + b) / 2.0;
+            if mean != 0.0 {
+                differences.push(((a - b) / mean).abs());
+            }
+        }
+    }
+    if standards > MAX_GROUPS || unknowns > MAX_GROUPS { return None }
+
+    let difference = (!differences.is_empty()).then(|| differences.iter().sum::<f64>() / differences.len() as f64);
+    Some(ReplicateAssignment { pattern, wells, difference })
+}
+
+/// The pattern that fits the plate, or whose pairs agree best if both fit
+pub fn detect_replicates(microplate: &Microplate) -> Option<ReplicateAssignment> {
+    ReplicatePattern::ALL.into_iter()
+        .filter_map(|pattern| assign_replicates(microplate, pattern))
+        .min_by(|a, b| a.difference.unwrap_or(f64::INFINITY).total_cmp(&b.difference.unwrap_or(f64::INFINITY)))
+}
+
+impl Microplate {
+    pub fn apply_replicates(&mut self, assignment: &ReplicateAssignment) {
+        for &(well, group) in &assignment.wells {
+            self.samples[well].group = group;
+        }
+        for typ in [SampleType::Standard, SampleType::Unknown] {
+            let count = assignment.wells.iter()
+                .filter(|&&(well, _group)| self.samples[well].typ == typ)
+                .map(|&(_well, group)| group + 1)
+                .max()
+                .unwrap_or(1);
+            if let Some(groups) = self.groups_mut(typ) {
+                groups.resize_with(count, Group::default);
+            }
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use std::{io, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{history::{read_data, write_data}, i18n::*, logistic_regression::Microplate, sha256::sha256_hex};
+
+/// What goes into the PDF report. The sections are laid out in the order of the fields.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReportTemplate {
+    pub name: String,
+    pub header: String,
+    pub logo: Option<PathBuf>,
+    pub barcode: bool, // of the plate, next to the title
+    pub plot: bool,
+    pub parameters: bool,
+    pub notes: bool,
+    pub plate_map: bool,
+    pub residuals: bool,
+    pub results: bool,
+    pub sample_metadata: bool, // subject, timepoint, matrix and collection date below each unknown
+    pub qc: bool,
+    pub excluded_wells: bool, // with the reason each was excluded for
+    pub audit_trail: bool,
+    pub signatures: bool,
+}
+
+impl Default for ReportTemplate {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            header: String::new(),
+            logo: None,
+            barcode: false,
+            plot: true,
+            parameters: true,
+            notes: true,
+            plate_map: true,
+            residuals: false,
+            results: true,
+            sample_metadata: false,
+            qc: true,
+            excluded_wells: true,
+            audit_trail: false,
+            signatures: false,
+        }
+    }
+}
+
+/// Where the plate of a report came from
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SourceKind {
+    Project,    // an assay loaded or saved in the app
+    ReaderFile, // a reader export picked up by the watch folder
+}
+
+/// SHA-256 of the file the plate came from, for the report footer. The fingerprint of the plate at the time tells
+/// whether the fit inputs were changed since, other edits like a new plate name or run notes aren't noticed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SourceFile {
+    pub kind: SourceKind,
+    pub hash: String,
+    pub fingerprint: u64, // see Microplate::fit_fingerprint
+}
+
+impl SourceFile {
+    pub fn new(kind: SourceKind, bytes: &[u8], microplate: &Microplate) -> Self {
+        Self { kind, hash: sha256_hex(bytes), fingerprint: microplate.fit_fingerprint() }
+    }
+
+    /// Footer line of the report, with a note whether the fit inputs still match the file
+    pub fn description(&self, microplate: &Microplate) -> String {
+        let id = match self.kind {
+            SourceKind::Project => "report-source-hash",
+            SourceKind::ReaderFile => "report-reader-file-hash",
+        };
+        let text = tr_args(id, &[("hash", self.hash.as_str().into())]);
+        let note = if self.fingerprint == microplate.fit_fingerprint() { "report-source-unchanged" } else { "report-source-modified" };
+        format!("{text} ({})", tr(note))
+    }
+}
+
+pub fn load_report_templates() -> Vec<ReportTemplate> {
+    read_data("report_templates.json")
+        .and_then(|buf| serde_json::from_slice(&buf).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_report_templates(templates: &[ReportTemplate]) -> io::Result<()> {
+    let serialized = serde_json::to_string_pretty(templates).map_err(io::Error::other)?;
+    write_data("report_templates.json", &serialized)
+}
@@ -0,0 +1,93 @@
+use crate::{history::mean_sd, i18n::*, logistic_regression::*, protocol::Protocol};
+
+/// Concentrations of the undiluted samples that can be reported, from the lowest to the highest standard that meets the
+/// recovery and CV criteria of the protocol. Samples diluted up to the allowed dilution extend the upper end.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ReportableRange {
+    pub lowest: f64,  // standard concentration
+    pub highest: f64,
+    pub max_dilution: Option<f64>, // None without a limit
+    pub passed: usize, // standards meeting the criteria, anchors left out
+    pub standards: usize,
+}
+
+impl ReportableRange {
+    /// The criteria of the protocol, or of the default protocol for plates without one. None if no standard meets them.
+    pub fn new(regression: &Regression, protocol: &Protocol) -> Option<Self> {
+        let (min, max) = protocol.recovery_range.unwrap_or((*RECOVERY_RANGE.start(), *RECOVERY_RANGE.end()));
+        let standards: Vec<_> = regression.back_calculated_standards().into_iter()
+            .zip(&regression.raw_standard_replicates)
+            .filter(|(standard, _replicates)| !standard.anchor)
+            .collect();
+
+        let passing: Vec<f64> = standards.iter().filter(|(standard, replicates)| {
+            let cv_passed = match protocol.cv_limit {
+                Some(limit) if replicates.len() > 1 => {
+                    let (mean, sd) = mean_sd(replicates);
+                    mean != 0.0 && (sd / mean).abs() * 100.0 <= limit
+                },
+                _ => true,
+            };
+            (min..=max).contains(&standard.recovery) && cv_passed
+        }).map(|(standard, _replicates)| standard.concentration).collect();
+
+        if passing.is_empty() { return None }
+        let lowest = passing.iter().copied().fold(f64::INFINITY, f64::min);
+        let highest = passing.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+
+        Some(Self {
+            lowest,
+            highest,
+            max_dilution: protocol.max_dilution,
+            passed: passing.len(),
+            standards: standards.len(),
+        })
+    }
+
+    /// Highest reportable concentration of an undiluted sample, infinite without a dilution limit
+    pub fn upper(&self) -> f64 {
+        self.highest * self.max_dilution.unwrap_or(f64::INFINITY)
+    }
+
+    /// Whether the backfit lies between the passing standards and the sample wasn't diluted further than allowed
+    pub fn contains(&self, unknown: &UnknownResult) -> bool {
+        (self.lowest..=self.highest).contains(&unknown.concentration)
+            && self.max_dilution.is_none_or(|max_dilution| unknown.dilution <= max_dilution)
+    }
+
+    pub fn summary(&self) -> String {
+        let mut args = vec![
+            ("lowest", format_value(self.lowest).into()),
+            ("highest", format_value(self.highest).into()),
+            ("passed", self.passed.into()),
+            ("standards", self.standards.into()),
+        ];
+        match self.max_dilution {
+            Some(max_dilution) => {
+                args.push(("upper", format_value(self.upper()).into()));
+                args.push(("dilution", format_number(max_dilution).into()));
+                tr_args("reportable-range-summary", &args)
+            },
+            None => tr_args("reportable-range-summary-undiluted", &args),
+        }
+    }
+
+    /// Why an unknown is outside, for the hover text of the results table
+    pub fn warning(&self, unknown: &UnknownResult) -> String {
+        match self.max_dilution {
+            Some(max_dilution) if unknown.dilution > max_dilution => {
+                tr_args("reportable-range-dilution-warning", &[("dilution", format_number(max_dilution).into())])
+            },
+            _ => tr_args("reportable-range-warning", &[
+                ("lowest", format_value(self.lowest).into()),
+                ("highest", format_value(self.highest).into()),
+            ]),
+        }
+    }
+}
+
+impl Microplate {
+    pub fn reportable_range(&self, regression: &Regression) -> Option<ReportableRange> {
+        ReportableRange::new(regression, &self.protocol.clone().unwrap_or_default())
+    }
+}
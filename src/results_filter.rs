@@ -0,0 +1,195 @@
+use crate::{history::mean_sd, i18n::*, logistic_regression::*};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Field {
+    Cv,
+    Concentration,
+    Measurement,
+    Recovery,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Comparison {
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+    Equal,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RangeFilter {
+    Within,
+    OutOfRange,
+    Extrapolated,
+    Above,
+    Below,
+}
+
+/// One term of the results filter, terms are separated by ";" and all have to match
+#[derive(Clone, Debug, PartialEq)]
+pub enum FilterTerm {
+    Text(String), // part of the name, label or subject ID
+    Compare(Field, Comparison, f64),
+    Range(RangeFilter),
+}
+
+// English words always work, the translated ones in addition
+const FIELDS: [(Field, &str, &str); 4] = [
+    (Field::Cv, "cv", "filter-cv"),
+    (Field::Concentration, "conc", "filter-concentration"),
+    (Field::Measurement, "raw", "filter-measurement"),
+    (Field::Recovery, "recovery", "filter-recovery"),
+];
+
+const RANGES: [(RangeFilter, &str, &str); 5] = [
+    (RangeFilter::Within, "in range", "filter-in-range"),
+    (RangeFilter::OutOfRange, "out of range", "filter-out-of-range"),
+    (RangeFilter::Extrapolated, "extrapolated", "filter-extrapolated"),
+    (RangeFilter::Above, "above curve", "filter-above-curve"),
+    (RangeFilter::Below, "below lod", "filter-below-lod"),
+];
+
+fn matches_word(text: &str, english: &str, id: &str) -> bool {
+    text == english || text == tr(id).to_lowercase()
+}
+
+impl FilterTerm {
+    fn parse(term: &str) -> Option<Self> {
+        let term = term.trim().to_lowercase();
+        if term.is_empty() { return None }
+
+        if let Some(&(range, ..)) = RANGES.iter().find(|(_range, english, id)| matches_word(&term, english, id)) {
+            return Some(Self::Range(range))
+        }
+
+        // longer operators first, so ">=" isn't read as ">"
+        for (operator, comparison) in [
+            (">=", Comparison::GreaterOrEqual), ("<=", Comparison::LessOrEqual),
+            (">", Comparison::Greater), ("<", Comparison::Less), ("=", Comparison::Equal),
+        ] {
+            let Some((field, value)) = term.split_once(operator) else { continue };
+            let field = field.trim();
+            let Some(&(field, ..)) = FIELDS.iter().find(|(_field, english, id)| matches_word(field, english, id)) else { break };
+            let Some(value) = parse_decimal(value.trim_end_matches('%')) else { break };
+            return Some(Self::Compare(field, comparison, value))
+        }
+
+        Some(Self::Text(term))
+    }
+
+    fn matches(&self, unknown: &UnknownResult) -> bool {
+        match self {
+            Self::Text(text) => [&unknown.name(), &unknown.metadata.subject_id]
+                .iter()
+                .any(|name| name.to_lowercase().contains(text)),
+            Self::Compare(field, comparison, limit) => {
+                let value = match field {
+                    Field::Cv => unknown_cv(unknown),
+                    Field::Concentration => Some(unknown.concentration).filter(|concentration| concentration.is_finite()),
+                    Field::Measurement => Some(unknown.measurement),
+                    Field::Recovery => unknown.recovery(),
+                };
+                let Some(value) = value else { return false };
+                match comparison {
+                    Comparison::Less => value < *limit,
+                    Comparison::LessOrEqual => value <= *limit,
+                    Comparison::Greater => value > *limit,
+                    Comparison::GreaterOrEqual => value >= *limit,
+                    Comparison::Equal => value == *limit,
+                }
+            },
+            Self::Range(range) => match (range, unknown.range) {
+                (RangeFilter::Within, range) => range == CurveRange::Within,
+                (RangeFilter::OutOfRange, range) => range != CurveRange::Within,
+                (RangeFilter::Extrapolated, range) => range == CurveRange::Extrapolated,
+                (RangeFilter::Above, range) => matches!(range, CurveRange::Above(_)),
+                (RangeFilter::Below, range) => matches!(range, CurveRange::Below(_)),
+            },
+        }
+    }
+}
+
+/// CV of the replicates in %, None with fewer than 2
+pub fn unknown_cv(unknown: &UnknownResult) -> Option<f64> {
+    if unknown.replicates.len() < 2 { return None }
+    let (mean, sd) = mean_sd(&unknown.replicates);
+    (mean != 0.0).then(|| (sd / mean).abs() * 100.0)
+}
+
+pub fn parse_filter(filter: &str) -> Vec<FilterTerm> {
+    filter.split(';').filter_map(FilterTerm::parse).collect()
+}
+
+/// Indices of the unknowns matching every term
+pub fn filter_unknowns(unknowns: &[UnknownResult], filter: &str) -> Vec<usize> {
+    let terms = parse_filter(filter);
+    unknowns.iter().enumerate()
+        .filter(|(_index, unknown)| terms.iter().all(|term| term.matches(unknown)))
+        .map(|(index, _unknown)| index)
+        .collect()
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SortColumn {
+    Label,
+    Measurement,
+    Concentration,
+    Cv,
+}
+
+/// Order of the unknowns in the results table, the CSV export and the report
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ResultsSort {
+    pub column: SortColumn,
+    pub descending: bool,
+}
+
+impl ResultsSort {
+    /// Ascending, descending, then back to the order of the groups
+    pub fn next(sort: Option<Self>, column: SortColumn) -> Option<Self> {
+        match sort {
+            Some(sort) if sort.column == column && sort.descending => None,
+            Some(sort) if sort.column == column => Some(Self { column, descending: true }),
+            _ => Some(Self { column, descending: false }),
+        }
+    }
+
+    pub fn arrow(sort: Option<Self>, column: SortColumn) -> &'static str {
+        match sort {
+            Some(sort) if sort.column == column && sort.descending => " ⏷",
+            Some(sort) if sort.column == column => " ⏶",
+            _ => "",
+        }
+    }
+}
+
+/// Sorts indices into the unknowns, unknowns without a value for the column go last
+pub fn sort_unknowns(unknowns: &[UnknownResult], indices: &mut [usize], sort: Option<ResultsSort>) {
+    let Some(ResultsSort { column, descending }) = sort else { return };
+    let number = |unknown: &UnknownResult| match column {
+        SortColumn::Measurement => Some(unknown.measurement),
+        SortColumn::Concentration => Some(unknown.concentration).filter(|concentration| concentration.is_finite()),
+        SortColumn::Cv => unknown_cv(unknown),
+        SortColumn::Label => None,
+    };
+    indices.sort_by(|&a, &b| {
+        let (a, b) = (&unknowns[a], &unknowns[b]);
+        let ordering = match column {
+            SortColumn::Label => a.name().to_lowercase().cmp(&b.name().to_lowercase()),
+            _ => match (number(a), number(b)) {
+                (Some(a), Some(b)) => a.total_cmp(&b),
+                (Some(_), None) => return std::cmp::Ordering::Less,
+                (None, Some(_)) => return std::cmp::Ordering::Greater,
+                (None, None) => std::cmp::Ordering::Equal,
+            },
+        };
+        if descending { ordering.reverse() } else { ordering }
+    });
+}
+
+pub fn sorted_unknowns(unknowns: &[UnknownResult], sort: Option<ResultsSort>) -> Vec<usize> {
+    let mut indices: Vec<usize> = (0..unknowns.len()).collect();
+    sort_unknowns(unknowns, &mut indices, sort);
+    indices
+}
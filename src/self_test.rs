@@ -0,0 +1,206 @@
+use crate::{i18n::*, logistic_regression::*};
+
+/// Analytical and numerical gradients further apart than this, relative to their size, fail the check
+pub const GRADIENT_CHECK_TOLERANCE: f64 = 1e-5;
+
+/// Standards computed from known parameters, the fit has to find the parameters again.
+/// Exact signals leave the optimizer nothing to trade off, rounded ones are what a reader would report.
+/// A published dataset brings its own signals and the parameters documented for it.
+#[derive(Clone, Copy, Debug)]
+pub struct ReferenceDataset {
+    pub id: &'static str,
+    pub abcd: (f64, f64, f64, f64),
+    pub concentrations: &'static [f64],
+    pub signals: Option<&'static [f64]>, // measured, of a published dataset, otherwise computed from abcd
+    pub weighting: Weighting,
+    pub decimals: Option<i32>, // the signals rounded to this many decimals
+    pub tolerance: f64, // of b and c relative to themselves, of a and d relative to the span of the curve
+}
+
+pub const REFERENCE_DATASETS: &[ReferenceDataset] = &[
+    ReferenceDataset {
+        id: "self-test-sandwich",
+        abcd: (0.05, 1.2, 50.0, 2.5),
+        concentrations: &[1.0, 3.0, 10.0, 30.0, 100.0, 300.0, 1000.0],
+        signals: None,
+        weighting: Weighting::None,
+        decimals: None,
+        tolerance: 1e-4,
+    },
+    ReferenceDataset {
+        id: "self-test-competitive",
+        abcd: (0.1, -0.9, 8.0, 2.0), // falling, as the fit puts it with a the lower asymptote
+        concentrations: &[0.1, 0.4, 1.6, 6.4, 25.6, 102.4, 409.6, 1638.4],
+        signals: None,
+        weighting: Weighting::None,
+        decimals: None,
+        tolerance: 1e-4,
+    },
+    ReferenceDataset {
+        id: "self-test-steep",
+        abcd: (0.1, 3.0, 0.5, 1.8),
+        concentrations: &[0.05, 0.1, 0.2, 0.35, 0.5, 0.7, 1.0, 2.0, 5.0],
+        signals: None,
+        weighting: Weighting::None,
+        decimals: None,
+        tolerance: 1e-4,
+    },
+    ReferenceDataset {
+        id: "self-test-picograms",
+        abcd: (0.08, 1.0, 25_000.0, 3.2),
+        concentrations: &[500.0, 1500.0, 5000.0, 15_000.0, 50_000.0, 150_000.0, 500_000.0],
+        signals: None,
+        weighting: Weighting::OneOverYSquared,
+        decimals: None,
+        tolerance: 1e-4,
+    },
+    ReferenceDataset {
+        id: "self-test-rounded",
+        abcd: (0.05, 1.2, 50.0, 2.5),
+        concentrations: &[1.0, 2.0, 4.0, 8.0, 16.0, 32.0, 64.0, 128.0, 256.0, 512.0, 1024.0],
+        signals: None,
+        weighting: Weighting::OneOverY,
+        decimals: Some(3),
+        tolerance: 0.02,
+    },
+    // Run 1 of the DNase ELISA in R's datasets package, from the example of `?DNase`:
+    // nls(density ~ SSfpl(log(conc), A, B, xmid, scal), data = DNase[DNase$Run == 1, ])
+    // gives A = -0.007897, B = 2.377239, xmid = 1.507403, scal = 1.062579, residual standard error 0.01981
+    // on 12 degrees of freedom. Here a = A, b = 1 / scal, c = exp(xmid) and d = B.
+    ReferenceDataset {
+        id: "self-test-dnase",
+        abcd: (-0.007897, 1.0 / 1.062579, 4.514990, 2.377239),
+        concentrations: &[
+            0.04882812, 0.04882812, 0.1953125, 0.1953125, 0.390625, 0.390625, 0.78125, 0.78125,
+            1.5625, 1.5625, 3.125, 3.125, 6.25, 6.25, 12.5, 12.5,
+        ],
+        signals: Some(&[0.017, 0.018, 0.121, 0.124, 0.206, 0.215, 0.377, 0.374, 0.614, 0.609, 1.019, 1.001, 1.334, 1.364, 1.730, 1.710]),
+        weighting: Weighting::None,
+        decimals: None,
+        tolerance: 1e-4, // the published estimates have 7 digits
+    },
+];
+
+impl ReferenceDataset {
+    pub fn name(&self) -> String {
+        tr(self.id)
+    }
+
+    /// (concentration, signal) as published, or on the curve of the known parameters
+    pub fn standards(&self) -> Vec<(f64, f64)> {
+        if let Some(signals) = self.signals {
+            return self.concentrations.iter().copied().zip(signals.iter().copied()).collect()
+        }
+        let (a, b, c, d) = self.abcd;
+        self.concentrations.iter().map(|&x| {
+            let y = d + (a - d) / (1.0 + (x / c).powf(b));
+            let y = match self.decimals {
+                Some(decimals) => (y * 10f64.powi(decimals)).round() / 10f64.powi(decimals),
+                None => y,
+            };
+            (x, y)
+        }).collect()
+    }
+
+    /// Weights of the standards as the fit scales them, for checking the gradient on the same loss
+    pub fn weights(&self) -> Vec<f64> {
+        let weights: Vec<f64> = self.standards().iter().map(|&(_x, y)| self.weighting.weight(y)).collect();
+        let mean = weights.iter().sum::<f64>() / weights.len() as f64;
+        weights.iter().map(|weight| weight / mean).collect()
+    }
+
+    /// Parameters around the known ones, with c as ln c, where the gradient is far from 0 and worth checking
+    pub fn gradient_check_points(&self) -> [(f64, f64, f64, f64); 2] {
+        let (a, b, c, d) = self.abcd;
+        let span = d - a;
+        [
+            (a + 0.1 * span, b * 1.3, c.ln() + 0.4, d - 0.1 * span),
+            (a - 0.2 * span, b * 0.7, c.ln() - 0.7, d + 0.15 * span),
+        ]
+    }
+
+    /// How far each fitted parameter is off the known one, in units of the tolerance, 1 or less passes
+    pub fn deviations(&self, (a, b, c, d): (f64, f64, f64, f64)) -> [f64; 4] {
+        let (known_a, known_b, known_c, known_d) = self.abcd;
+        let span = (known_d - known_a).abs();
+        [
+            (a - known_a).abs() / span,
+            (b / known_b - 1.0).abs(),
+            (c / known_c - 1.0).abs(),
+            (d - known_d).abs() / span,
+        ].map(|deviation| deviation / self.tolerance)
+    }
+}
+
+/// The gradient of the loss as the fit calculates it, next to central differences of the loss
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GradientCheck {
+    pub analytical: [f64; 4],
+    pub numerical: [f64; 4],
+}
+
+impl GradientCheck {
+    pub fn relative_error(&self) -> f64 {
+        let norm = |gradient: [f64; 4]| gradient.iter().map(|value| value * value).sum::<f64>().sqrt();
+        let difference: [f64; 4] = std::array::from_fn(|i| self.analytical[i] - self.numerical[i]);
+        norm(difference) / (norm(self.analytical) + norm(self.numerical)).max(f64::MIN_POSITIVE)
+    }
+
+    pub fn passed(&self) -> bool {
+        self.relative_error() <= GRADIENT_CHECK_TOLERANCE
+    }
+}
+
+/// Compares loss_gradient with the central differences of weighted_loss at parameters (a, b, ln c, d)
+pub fn check_gradient(points: &[(f64, f64)], weights: &[f64], parameters: (f64, f64, f64, f64)) -> GradientCheck {
+    let (a, b, c, d) = parameters;
+    let values = [a, b, c, d];
+    let numerical = std::array::from_fn(|i| {
+        let step = 1e-6 * values[i].abs().max(1.0);
+        let mut high = values;
+        let mut low = values;
+        high[i] += step;
+        low[i] -= step;
+        let loss = |[a, b, c, d]: [f64; 4]| weighted_loss(points, weights, (a, b, c, d));
+        (loss(high) - loss(low)) / (2.0 * step)
+    });
+    GradientCheck { analytical: loss_gradient(points, weights, parameters), numerical }
+}
+
+/// What the fit made of one reference dataset
+#[derive(Clone, Debug)]
+pub struct SelfTestResult {
+    pub dataset: ReferenceDataset,
+    pub fitted: Result<(f64, f64, f64, f64), RegressionError>,
+    pub gradient_checks: Vec<GradientCheck>,
+}
+
+impl SelfTestResult {
+    pub fn deviations(&self) -> Option<[f64; 4]> {
+        self.fitted.as_ref().ok().map(|&abcd| self.dataset.deviations(abcd))
+    }
+
+    pub fn fit_passed(&self) -> bool {
+        self.deviations().is_some_and(|deviations| deviations.iter().all(|&deviation| deviation <= 1.0))
+    }
+
+    pub fn gradient_passed(&self) -> bool {
+        self.gradient_checks.iter().all(|check| check.passed())
+    }
+
+    pub fn passed(&self) -> bool {
+        self.fit_passed() && self.gradient_passed()
+    }
+}
+
+/// Checks the gradient and fits every reference dataset
+pub fn run_self_test() -> Vec<SelfTestResult> {
+    REFERENCE_DATASETS.iter().map(|&dataset| {
+        let standards = dataset.standards();
+        let points: Vec<_> = standards.iter().map(|&(x, y)| (x.ln(), y)).collect();
+        let weights = dataset.weights();
+        let gradient_checks = dataset.gradient_check_points().iter().map(|&parameters| check_gradient(&points, &weights, parameters)).collect();
+        let fitted = Regression::from_standards(&standards, dataset.weighting).map(|regression| regression.abcd);
+        SelfTestResult { dataset, fitted, gradient_checks }
+    }).collect()
+}
@@ -0,0 +1,298 @@
+use std::{fmt::Display, io::{self, BufRead, BufReader, Read, Write}, net::{Ipv4Addr, TcpListener, TcpStream}, sync::{atomic::{AtomicBool, AtomicUsize, Ordering}, Arc}, thread::{self, JoinHandle}, time::Duration};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{i18n::*, json_export::*, layout::{LayoutError, PlateLayout}, logistic_regression::*};
+
+pub const API_SCHEMA: &str = "elisa-api";
+pub const API_VERSION: u32 = 1;
+pub const DEFAULT_PORT: u16 = 8787;
+
+/// Largest request body taken, a plate with layout is a few hundred kB at most
+const MAX_BODY: usize = 16 * 1024 * 1024;
+/// Largest plate taken, 1536 wells
+const MAX_WELLS: usize = 48 * 32;
+/// How long the listener sleeps when nobody is connecting
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+/// How long a client may take to send its request
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Whether the app runs the server and on which port, saved with the settings. Off by default.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ServerSetup {
+    pub enabled: bool,
+    pub port: u16,
+}
+
+impl Default for ServerSetup {
+    fn default() -> Self {
+        Self { enabled: false, port: DEFAULT_PORT }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum ApiError {
+    BadRequest(String), // the request or its JSON could not be read
+    NotFound(String),   // path
+    MethodNotAllowed,
+    TooLarge,
+    Layout(LayoutError),
+    InvalidWell(String),
+    Fit(RegressionError),
+}
+
+impl Display for ApiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let error = match self {
+            Self::BadRequest(error) => tr_args("api-bad-request", &[("error", error.as_str().into())]),
+            Self::NotFound(path) => tr_args("api-not-found", &[("path", path.as_str().into())]),
+            Self::MethodNotAllowed => tr("api-method-not-allowed"),
+            Self::TooLarge => tr("api-too-large"),
+            Self::Layout(error) => error.to_string(),
+            Self::InvalidWell(well) => tr_args("api-invalid-well", &[("well", well.as_str().into())]),
+            Self::Fit(error) => error.to_string(),
+        };
+        write!(f, "{}", error)
+    }
+}
+
+impl ApiError {
+    /// HTTP status code
+    pub fn status(&self) -> u16 {
+        match self {
+            Self::BadRequest(_) => 400,
+            Self::NotFound(_) => 404,
+            Self::MethodNotAllowed => 405,
+            Self::TooLarge => 413,
+            Self::Layout(_) | Self::InvalidWell(_) | Self::Fit(_) => 422,
+        }
+    }
+}
+
+/// What `POST /api/fit` takes: a layout as in the layout files, the values of the wells as in the JSON export
+/// and optionally the fit options, which otherwise are the defaults of a new plate.
+///
+/// ```json
+/// {
+///   "name": "Plate 1",
+///   "layout": { "schema": "elisa-plate-layout", "version": 1, "width": 12, "height": 8, "wells": [...] },
+///   "values": [{ "well": "A1", "raw": 2.41 }, { "well": "A2", "raw": 2.38, "excluded": true }],
+///   "options": { "weighting": "OneOverY" }
+/// }
+/// ```
+#[derive(Debug, Deserialize)]
+pub struct FitRequest {
+    #[serde(default)]
+    pub name: String,
+    pub layout: PlateLayout,
+    pub values: Vec<WellValue>,
+    #[serde(default)]
+    pub options: FitOptions,
+}
+
+impl FitRequest {
+    pub fn microplate(&self) -> Result<Microplate, ApiError> {
+        let (width, height) = (self.layout.width, self.layout.height);
+        if width == 0 || height == 0 || width * height > MAX_WELLS {
+            return Err(ApiError::Layout(LayoutError::WrongDimensions(width, height)))
+        }
+        let mut microplate = Microplate::new(width, height);
+        microplate.name = self.name.clone();
+        // bounded like in the app, so a request can't keep the server thread, and the app closing, waiting for it
+        microplate.fit_options = FitOptions {
+            extra_starts: self.options.extra_starts.min(MAX_EXTRA_STARTS),
+            max_iterations: self.options.max_iterations.map(|max_iterations| max_iterations.min(MAX_ITERATIONS)),
+            ..self.options.clone()
+        };
+        microplate.apply_layout(&self.layout).map_err(ApiError::Layout)?;
+        for value in &self.values {
+            let Some(index) = microplate.well_index(&value.well) else { return Err(ApiError::InvalidWell(value.well.clone())) };
+            microplate.assign_value(index, Some(value.raw));
+            microplate.samples[index].excluded = value.excluded;
+        }
+        Ok(microplate)
+    }
+}
+
+/// What `GET /api/status` returns
+#[derive(Debug, Serialize)]
+pub struct ApiStatus {
+    pub schema: String,
+    pub version: u32,
+    pub app_version: String,
+    pub requests: usize, // answered since the server started
+}
+
+#[derive(Debug, Serialize)]
+struct ErrorResponse {
+    error: String,
+}
+
+/// A request as read from the connection, the path without its query
+#[derive(Clone, Debug, PartialEq)]
+pub struct ApiRequest {
+    pub method: String,
+    pub path: String,
+    pub body: Vec<u8>,
+}
+
+/// Status code and JSON body of the answer to a request
+pub fn handle(request: &ApiRequest, requests: usize) -> (u16, String) {
+    let result = match (request.method.as_str(), request.path.trim_end_matches('/')) {
+        ("GET", "/api/status") => serde_json::to_string_pretty(&ApiStatus {
+            schema: API_SCHEMA.to_string(),
+            version: API_VERSION,
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            requests,
+        }).map_err(|error| ApiError::BadRequest(error.to_string())),
+        ("POST", "/api/fit") => fit(&request.body),
+        (_, "/api/status" | "/api/fit") => Err(ApiError::MethodNotAllowed),
+        (_, path) => Err(ApiError::NotFound(path.to_string())),
+    };
+    match result {
+        Ok(json) => (200, json),
+        Err(error) => (error.status(), error_json(&error)),
+    }
+}
+
+/// The analysis of the plate in the format of the JSON export
+fn fit(body: &[u8]) -> Result<String, ApiError> {
+    let request: FitRequest = serde_json::from_slice(body).map_err(|error| ApiError::BadRequest(error.to_string()))?;
+    let microplate = request.microplate()?;
+    let regression = Regression::new(&microplate).map_err(ApiError::Fit)?;
+    AnalysisExport::new(&microplate, &regression).to_json().map_err(|error| ApiError::BadRequest(error.to_string()))
+}
+
+fn error_json(error: &ApiError) -> String {
+    serde_json::to_string_pretty(&ErrorResponse { error: error.to_string() }).unwrap_or_default()
+}
+
+/// The embedded HTTP server, answering one request at a time on its own thread until it is dropped
+pub struct ApiServer {
+    pub port: u16,
+    requests: Arc<AtomicUsize>,
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl ApiServer {
+    /// Listens on localhost only, so other computers can't reach it. Port 0 takes any free port.
+    pub fn start(port: u16) -> io::Result<Self> {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port))?;
+        listener.set_nonblocking(true)?;
+        let port = listener.local_addr()?.port();
+        let requests = Arc::new(AtomicUsize::new(0));
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let thread = {
+            let (requests, stop) = (requests.clone(), stop.clone());
+            thread::Builder::new().name("api-server".to_string()).spawn(move || {
+                while !stop.load(Ordering::Relaxed) {
+                    match listener.accept() {
+                        Ok((stream, _address)) => {
+                            if let Err(error) = serve(stream, &requests) {
+                                log::warn!("API connection failed: {error}");
+                            }
+                        },
+                        Err(error) if error.kind() == io::ErrorKind::WouldBlock => thread::sleep(POLL_INTERVAL),
+                        Err(error) => {
+                            log::warn!("API server could not accept a connection: {error}");
+                            thread::sleep(POLL_INTERVAL);
+                        },
+                    }
+                }
+            })?
+        };
+        log::info!("API server listening on http://127.0.0.1:{port}");
+
+        Ok(Self { port, requests, stop, thread: Some(thread) })
+    }
+
+    pub fn requests(&self) -> usize {
+        self.requests.load(Ordering::Relaxed)
+    }
+
+    pub fn url(&self) -> String {
+        format!("http://127.0.0.1:{}/api", self.port)
+    }
+}
+
+impl Drop for ApiServer {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+fn serve(mut stream: TcpStream, requests: &AtomicUsize) -> io::Result<()> {
+    stream.set_nonblocking(false)?;
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let (status, body) = match read_request(&stream) {
+        Ok(request) => {
+            let answered = requests.fetch_add(1, Ordering::Relaxed) + 1;
+            let (status, body) = handle(&request, answered);
+            log::info!("API {} {} {status}", request.method, request.path);
+            (status, body)
+        },
+        Err(error) => (error.status(), error_json(&error)),
+    };
+    write_response(&mut stream, status, &body)
+}
+
+/// HTTP/1.1 request line, headers and a body of `Content-Length` bytes
+fn read_request(stream: &TcpStream) -> Result<ApiRequest, ApiError> {
+    let bad_request = |error: io::Error| ApiError::BadRequest(error.to_string());
+    let mut reader = BufReader::new(stream);
+
+    let mut line = String::new();
+    reader.read_line(&mut line).map_err(bad_request)?;
+    let mut parts = line.split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+        return Err(ApiError::BadRequest(line.trim().to_string()))
+    };
+    let method = method.to_uppercase();
+    let path = target.split('?').next().unwrap_or_default().to_string();
+
+    let mut length = 0;
+    loop {
+        line.clear();
+        if reader.read_line(&mut line).map_err(bad_request)? == 0 { break }
+        let header = line.trim();
+        if header.is_empty() { break }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                length = value.trim().parse().map_err(|_| ApiError::BadRequest(header.to_string()))?;
+            }
+        }
+    }
+    if length > MAX_BODY { return Err(ApiError::TooLarge) }
+
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body).map_err(bad_request)?;
+    Ok(ApiRequest { method, path, body })
+}
+
+fn write_response(stream: &mut TcpStream, status: u16, body: &str) -> io::Result<()> {
+    let reason = match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        413 => "Payload Too Large",
+        422 => "Unprocessable Content",
+        _ => "",
+    };
+    let head = format!(
+        "HTTP/1.1 {status} {reason}\r\n\
+        Content-Type: application/json; charset=utf-8\r\n\
+        Content-Length: {}\r\n\
+        Connection: close\r\n\r\n",
+        body.len(),
+    );
+    stream.write_all(head.as_bytes())?;
+    stream.write_all(body.as_bytes())?;
+    stream.flush()
+}
@@ -0,0 +1,274 @@
+use std::{io, path::PathBuf};
+
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+
+use crate::{exclusion::ExclusionRules, export_hook::ExportHook, history::{mean_sd, read_data, write_data}, i18n::*, logistic_regression::Weighting, report::ReportTemplate, server::ServerSetup, watch_folder::WatchFolderSetup};
+
+/// Preferences that apply to every assay, stored next to the curve history
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Settings {
+    #[serde(default)]
+    pub language: Language,
+    #[serde(default)]
+    pub report: ReportTemplate,
+    #[serde(default)]
+    pub operator: String,
+    #[serde(default)]
+    pub number_format: NumberFormat,
+    #[serde(default)]
+    pub exclusion_rules: ExclusionRules,
+    #[serde(default)]
+    pub plot: PlotOptions,
+    #[serde(default)]
+    pub theme: AppTheme,
+    #[serde(default)]
+    pub plate_size: PlateSize, // of new plates
+    #[serde(default)]
+    pub weighting: Weighting, // of new plates
+    #[serde(default)]
+    pub cv_threshold: Option<f64>, // %, higher replicate CVs are flagged in the results
+    #[serde(default)]
+    pub autosave_minutes: u32, // 0 is off
+    #[serde(default)]
+    pub export_folder: Option<PathBuf>, // where save dialogs start
+    #[serde(default)]
+    pub shortcuts: Shortcuts,
+    #[serde(default)]
+    pub archive_plates: bool, // every fitted plate goes into the archive
+    #[serde(default)]
+    pub name_from_barcode: bool, // a scanned plate barcode becomes the name of the plate
+    #[serde(default)]
+    pub temperature_range: Option<(f64, f64)>, // °C, reads outside of it are flagged
+    #[serde(default)]
+    pub linear_range: Option<(f64, f64)>, // OD as read, the reader responds linearly in between
+    #[serde(default)]
+    pub watch_folder: WatchFolderSetup,
+    #[serde(default)]
+    pub server: ServerSetup, // the HTTP API for other programs
+    #[serde(default)]
+    pub export_hook: ExportHook, // exports and a command after every fit
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum AppTheme {
+    #[default]
+    Light,
+    Dark,
+}
+
+impl AppTheme {
+    pub const ALL: [Self; 2] = [Self::Light, Self::Dark];
+
+    pub fn description(&self) -> String {
+        let id = match self {
+            Self::Light => "theme-light",
+            Self::Dark => "theme-dark",
+        };
+        tr(id)
+    }
+
+    pub fn egui_theme(&self) -> egui::Theme {
+        match self {
+            Self::Light => egui::Theme::Light,
+            Self::Dark => egui::Theme::Dark,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum PlateSize {
+    Wells24,
+    Wells48,
+    #[default]
+    Wells96,
+    Wells384,
+}
+
+impl PlateSize {
+    pub const ALL: [Self; 4] = [Self::Wells24, Self::Wells48, Self::Wells96, Self::Wells384];
+
+    /// Columns and rows
+    pub fn dimensions(&self) -> (usize, usize) {
+        match self {
+            Self::Wells24 => (6, 4),
+            Self::Wells48 => (8, 6),
+            Self::Wells96 => (12, 8),
+            Self::Wells384 => (24, 16),
+        }
+    }
+
+    pub fn description(&self) -> String {
+        let (width, height) = self.dimensions();
+        tr_args("plate-size-wells", &[("wells", (width * height).into())])
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum ErrorBars {
+    #[default]
+    None,
+    StandardDeviation,
+    StandardError, // of the mean
+}
+
+impl ErrorBars {
+    pub const ALL: [Self; 3] = [Self::None, Self::StandardDeviation, Self::StandardError];
+
+    pub fn description(&self) -> String {
+        let id = match self {
+            Self::None => "error-bars-none",
+            Self::StandardDeviation => "error-bars-sd",
+            Self::StandardError => "error-bars-sem",
+        };
+        tr(id)
+    }
+
+    /// Half the length of the bar, None with fewer than 2 replicates
+    pub fn spread(&self, values: &[f64]) -> Option<f64> {
+        if values.len() < 2 { return None }
+        let (_mean, sd) = mean_sd(values);
+        match self {
+            Self::None => None,
+            Self::StandardDeviation => Some(sd),
+            Self::StandardError => Some(sd / (values.len() as f64).sqrt()),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum Crosshair {
+    #[default]
+    Off,
+    Free,
+    SnapToCurve, // follows the curve at the dose under the pointer
+}
+
+impl Crosshair {
+    pub const ALL: [Self; 3] = [Self::Off, Self::Free, Self::SnapToCurve];
+
+    pub fn description(&self) -> String {
+        let id = match self {
+            Self::Off => "crosshair-off",
+            Self::Free => "crosshair-free",
+            Self::SnapToCurve => "crosshair-snap",
+        };
+        tr(id)
+    }
+}
+
+/// What the standard curve plot shows besides the curve and the means
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PlotOptions {
+    pub replicates: bool,
+    pub error_bars: ErrorBars,
+    pub fit_standards: bool, // zoomed to the standards instead of free zoom and pan
+    pub crosshair: Crosshair,
+    pub raw_values: bool, // as read instead of blank corrected, in the plot and the results
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ShortcutAction {
+    Save,
+    Open,
+    ExportCsv,
+    Refit,
+}
+
+impl ShortcutAction {
+    pub const ALL: [Self; 4] = [Self::Save, Self::Open, Self::ExportCsv, Self::Refit];
+
+    pub fn description(&self) -> String {
+        let id = match self {
+            Self::Save => "shortcut-save",
+            Self::Open => "shortcut-open",
+            Self::ExportCsv => "shortcut-export-csv",
+            Self::Refit => "shortcut-refit",
+        };
+        tr(id)
+    }
+}
+
+/// Keys of the configurable shortcuts, stored as text like "Ctrl+S", empty for none
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Shortcuts {
+    pub save: String,
+    pub open: String,
+    pub export_csv: String,
+    pub refit: String,
+}
+
+impl Default for Shortcuts {
+    fn default() -> Self {
+        Self {
+            save: "Ctrl+S".to_string(),
+            open: "Ctrl+O".to_string(),
+            export_csv: "Ctrl+E".to_string(),
+            refit: "F5".to_string(),
+        }
+    }
+}
+
+impl Shortcuts {
+    pub fn text_mut(&mut self, action: ShortcutAction) -> &mut String {
+        match action {
+            ShortcutAction::Save => &mut self.save,
+            ShortcutAction::Open => &mut self.open,
+            ShortcutAction::ExportCsv => &mut self.export_csv,
+            ShortcutAction::Refit => &mut self.refit,
+        }
+    }
+
+    pub fn get(&self, action: ShortcutAction) -> Option<egui::KeyboardShortcut> {
+        let text = match action {
+            ShortcutAction::Save => &self.save,
+            ShortcutAction::Open => &self.open,
+            ShortcutAction::ExportCsv => &self.export_csv,
+            ShortcutAction::Refit => &self.refit,
+        };
+        parse_shortcut(text)
+    }
+}
+
+/// "Ctrl+Shift+S", Ctrl is ⌘ on macOS
+pub fn parse_shortcut(text: &str) -> Option<egui::KeyboardShortcut> {
+    let mut modifiers = egui::Modifiers::NONE;
+    let mut parts = text.split('+').map(str::trim).peekable();
+    while let Some(part) = parts.next() {
+        if parts.peek().is_none() {
+            return egui::Key::from_name(part).map(|key| egui::KeyboardShortcut::new(modifiers, key))
+        }
+        match part.to_lowercase().as_str() {
+            "ctrl" | "cmd" => modifiers |= egui::Modifiers::COMMAND,
+            "shift" => modifiers |= egui::Modifiers::SHIFT,
+            "alt" => modifiers |= egui::Modifiers::ALT,
+            _ => return None,
+        }
+    }
+    None
+}
+
+pub fn shortcut_text(shortcut: egui::KeyboardShortcut) -> String {
+    let egui::KeyboardShortcut { modifiers, logical_key } = shortcut;
+    let mut parts = Vec::new();
+    if modifiers.command { parts.push("Ctrl") }
+    if modifiers.shift { parts.push("Shift") }
+    if modifiers.alt { parts.push("Alt") }
+    parts.push(logical_key.name());
+    parts.join("+")
+}
+
+/// Settings live in the data directory next to the curve history and the report templates rather than in
+/// eframe::Storage, so a change is saved right away and stays one plain JSON file, see the README
+pub fn load_settings() -> Settings {
+    read_data("settings.json")
+        .and_then(|buf| serde_json::from_slice(&buf).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_settings(settings: &Settings) -> io::Result<()> {
+    let serialized = serde_json::to_string_pretty(settings).map_err(io::Error::other)?;
+    write_data("settings.json", &serialized)
+}
@@ -0,0 +1,127 @@
+use std::fmt::Display;
+
+use crate::{bootstrap::Rng, default, i18n::*, logistic_regression::{SampleType::*, *}};
+
+/// How the noise of a well depends on its signal
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum NoiseModel {
+    Constant, // standard deviation in signal units
+    #[default]
+    Proportional, // coefficient of variation in %
+}
+
+impl NoiseModel {
+    pub const ALL: [Self; 2] = [Self::Constant, Self::Proportional];
+
+    pub fn description(&self) -> String {
+        let id = match self {
+            Self::Constant => "noise-constant",
+            Self::Proportional => "noise-proportional",
+        };
+        tr(id)
+    }
+
+    /// Standard deviation of a well with this signal
+    pub fn sd(&self, level: f64, signal: f64) -> f64 {
+        match self {
+            Self::Constant => level,
+            Self::Proportional => signal.abs() * level / 100.0,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum SimulationError {
+    PlateTooSmall(usize),
+}
+
+impl Display for SimulationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let error = match self {
+            Self::PlateTooSmall(wells) => tr_args("simulation-plate-too-small", &[("wells", (*wells).into())]),
+        };
+        write!(f, "{}", error)
+    }
+}
+
+/// A known curve and noise, to see what the analysis makes of them
+#[derive(Clone, Debug, PartialEq)]
+pub struct Simulation {
+    pub abcd: (f64, f64, f64, f64), // blank corrected signal
+    pub blank: f64,                 // added to every well
+    pub noise: NoiseModel,
+    pub noise_level: f64,
+    pub top_standard: f64,
+    pub dilution_factor: f64, // between the standards
+    pub standards: usize,
+    pub unknowns: usize,
+    pub replicates: usize, // side by side in a row
+}
+
+impl Default for Simulation {
+    fn default() -> Self {
+        Self {
+            abcd: (0.05, 1.2, 20.0, 2.5),
+            blank: 0.05,
+            noise: NoiseModel::Proportional,
+            noise_level: 5.0,
+            top_standard: 500.0,
+            dilution_factor: 3.0,
+            standards: 7,
+            unknowns: 16,
+            replicates: 2,
+        }
+    }
+}
+
+impl Simulation {
+    pub fn four_pl(&self, x: f64) -> f64 {
+        let (a, b, c, d) = self.abcd;
+        d + ((a - d) / (1.0 + (x/c).powf(b)))
+    }
+
+    pub fn standard_concentrations(&self) -> Vec<f64> {
+        (0..self.standards).map(|i| self.top_standard / self.dilution_factor.powi(i as i32)).collect()
+    }
+
+    /// Wells used by the blank, the standards and the unknowns
+    pub fn wells(&self) -> usize {
+        (1 + self.standards + self.unknowns) * self.replicates
+    }
+
+    /// A plate with blanks, a dilution series of standards and unknowns spread evenly over the standards on a log scale.
+    /// The true concentration of each unknown is its spike, so the recovery shows how close the analysis gets.
+    pub fn microplate(&self, width: usize, height: usize, rng: &mut Rng) -> Result<Microplate, SimulationError> {
+        let replicates = self.replicates.max(1);
+        let slots = width / replicates * height;
+        if self.wells() > slots * replicates { return Err(SimulationError::PlateTooSmall(self.wells())) }
+
+        let mut microplate = Microplate::new(width, height);
+        let (a, b, c, d) = self.abcd;
+        microplate.name = tr("simulated-plate");
+        microplate.description = format!("4PL a = {a}, b = {b}, c = {c}, d = {d}, blank {}, {} {}", self.blank, self.noise.description(), self.noise_level);
+
+        let standards = self.standard_concentrations();
+        let (lowest, highest) = (standards.iter().copied().fold(f64::INFINITY, f64::min), standards.iter().copied().fold(0.0, f64::max));
+        microplate.standard_groups = standards.iter().map(|&concentration| Group { concentration: Some(concentration), ..default() }).collect();
+        microplate.unknown_groups = (0..self.unknowns).map(|i| {
+            let fraction = if self.unknowns > 1 { i as f64 / (self.unknowns - 1) as f64 } else { 0.5 };
+            let concentration = lowest * (highest / lowest).powf(fraction);
+            Group { spike: Some(concentration), ..default() }
+        }).collect();
+
+        let wells = std::iter::once((Blank, 0, 0.0))
+            .chain(standards.iter().enumerate().map(|(group, &concentration)| (Standard, group, concentration)))
+            .chain(microplate.unknown_groups.iter().enumerate().map(|(group, unknown)| (Unknown, group, unknown.spike.unwrap_or_default())));
+        // slots of side by side replicates, filled down the rows and then to the right
+        for (slot, (typ, group, concentration)) in wells.enumerate() {
+            let (row, first_column) = (slot % height, slot / height * replicates);
+            let signal = self.blank + if typ == Blank { 0.0 } else { self.four_pl(concentration) };
+            for column in first_column..first_column + replicates {
+                let value = signal + self.noise.sd(self.noise_level, signal) * rng.normal();
+                microplate.samples[column * height + row] = Sample { typ, group, value: Some(value), ..default() };
+            }
+        }
+        Ok(microplate)
+    }
+}
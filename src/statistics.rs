@@ -0,0 +1,82 @@
+use std::f64::consts::PI;
+
+/// ln Γ(x) for x > 0, Lanczos approximation with g = 7
+pub fn ln_gamma(x: f64) -> f64 {
+    const COEFFICIENTS: [f64; 9] = [
+        0.999_999_999_999_809_9, 676.520_368_121_885_1, -1_259.139_216_722_402_8, 771.323_428_777_653_1,
+        -176.615_029_162_140_6, 12.507_343_278_686_905, -0.138_571_095_265_720_12, 9.984_369_578_019_572e-6,
+        1.505_632_735_149_311_6e-7,
+    ];
+    if x < 0.5 {
+        // reflection, Γ(x) Γ(1 - x) = π / sin(πx)
+        return (PI / (PI * x).sin()).ln() - ln_gamma(1.0 - x)
+    }
+    let x = x - 1.0;
+    let t = x + 7.5;
+    let sum = COEFFICIENTS[1..].iter().enumerate()
+        .fold(COEFFICIENTS[0], |sum, (i, coefficient)| sum + coefficient / (x + i as f64 + 1.0));
+    0.5 * (2.0 * PI).ln() + (x + 0.5) * t.ln() - t + sum.ln()
+}
+
+/// Regularized incomplete beta function I_x(a, b)
+pub fn incomplete_beta(x: f64, a: f64, b: f64) -> f64 {
+    if x <= 0.0 { return 0.0 }
+    if x >= 1.0 { return 1.0 }
+    let front = (ln_gamma(a + b) - ln_gamma(a) - ln_gamma(b) + a * x.ln() + b * (1.0 - x).ln()).exp();
+    // the continued fraction converges quickly below the mean, above it by symmetry
+    if x < (a + 1.0) / (a + b + 2.0) {
+        front * beta_fraction(x, a, b) / a
+    } else {
+        1.0 - front * beta_fraction(1.0 - x, b, a) / b
+    }
+}
+
+/// Continued fraction of the incomplete beta function, modified Lentz's method
+fn beta_fraction(x: f64, a: f64, b: f64) -> f64 {
+    const TINY: f64 = 1e-300;
+    let mut c = 1.0;
+    let mut d = 1.0 - (a + b) * x / (a + 1.0);
+    if d.abs() < TINY { d = TINY }
+    d = 1.0 / d;
+    let mut fraction = d;
+    for m in 1..=300 {
+        let m = m as f64;
+        for numerator in [
+            m * (b - m) * x / ((a + 2.0 * m - 1.0) * (a + 2.0 * m)),
+            -(a + m) * (a + b + m) * x / ((a + 2.0 * m) * (a + 2.0 * m + 1.0)),
+        ] {
+            d = 1.0 + numerator * d;
+            if d.abs() < TINY { d = TINY }
+            c = 1.0 + numerator / c;
+            if c.abs() < TINY { c = TINY }
+            d = 1.0 / d;
+            fraction *= d * c;
+        }
+        if (d * c - 1.0).abs() < 1e-15 { break }
+    }
+    fraction
+}
+
+/// P(|T| ≥ |t|) of Student's t distribution
+pub fn t_two_sided_p(t: f64, degrees_of_freedom: f64) -> f64 {
+    if t.is_infinite() { return 0.0 }
+    incomplete_beta(degrees_of_freedom / (degrees_of_freedom + t * t), degrees_of_freedom / 2.0, 0.5)
+}
+
+/// t with P(|T| ≥ t) = alpha, e.g. 2.571 for alpha 0.05 and 5 degrees of freedom
+pub fn t_critical(alpha: f64, degrees_of_freedom: f64) -> f64 {
+    // the p-value falls with t, bisection is plenty for a handful of parameters
+    let (mut low, mut high) = (0.0, 1.0);
+    while t_two_sided_p(high, degrees_of_freedom) > alpha {
+        high *= 2.0;
+    }
+    for _ in 0..100 {
+        let middle = (low + high) / 2.0;
+        if t_two_sided_p(middle, degrees_of_freedom) > alpha {
+            low = middle;
+        } else {
+            high = middle;
+        }
+    }
+    (low + high) / 2.0
+}
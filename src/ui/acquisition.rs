@@ -0,0 +1,209 @@
+use eframe::egui::{self, ComboBox, Grid, Id, RichText, ScrollArea};
+#[cfg(all(feature = "serial", not(target_arch = "wasm32")))]
+use eframe::egui::DragValue;
+
+use crate::{acquisition::*, i18n::*, layout::ALPHABET, ui::plot::{rgb, FLAG_COLOR}, Elisa};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum AcquisitionSource {
+    #[default]
+    Mock,
+    #[cfg(all(feature = "serial", not(target_arch = "wasm32")))]
+    Serial,
+}
+
+impl AcquisitionSource {
+    pub const ALL: &[Self] = &[
+        Self::Mock,
+        #[cfg(all(feature = "serial", not(target_arch = "wasm32")))]
+        Self::Serial,
+    ];
+
+    pub fn description(&self) -> String {
+        let id = match self {
+            Self::Mock => "mock-reader",
+            #[cfg(all(feature = "serial", not(target_arch = "wasm32")))]
+            Self::Serial => "serial-reader",
+        };
+        tr(id)
+    }
+}
+
+/// How the next acquisition connects and where its readings go
+#[derive(Clone, Debug, PartialEq)]
+pub struct AcquisitionSetup {
+    pub source: AcquisitionSource,
+    pub port: String,
+    pub baud_rate: u32,
+    pub order: ReadingOrder,
+    pub live: bool, // readings go into the plate as they arrive
+}
+
+impl Default for AcquisitionSetup {
+    fn default() -> Self {
+        Self {
+            source: AcquisitionSource::Mock,
+            port: String::new(),
+            baud_rate: 9600,
+            order: ReadingOrder::RowMajor,
+            live: true,
+        }
+    }
+}
+
+impl Elisa {
+    /// Takes the new readings of a running acquisition, also while the window is closed
+    fn poll_acquisition(&mut self, ctx: &egui::Context) {
+        let Some(acquisition) = &mut self.acquisition else { return };
+        if acquisition.finished() { return }
+
+        let new = acquisition.poll();
+        if let Some(error) = &acquisition.error {
+            self.notifications.push(error.clone());
+        }
+        if new > 0 && self.acquisition_setup.live && !self.microplate.locked {
+            acquisition.apply(&mut self.microplate);
+        }
+        ctx.request_repaint();
+    }
+
+    fn connect_reader(&self) -> Result<Box<dyn ReaderDriver>, AcquisitionError> {
+        let setup = &self.acquisition_setup;
+        match setup.source {
+            AcquisitionSource::Mock => {
+                let seed = chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default() as u64;
+                Ok(Box::new(MockDriver::simulated(self.microplate.width, self.microplate.height, seed)))
+            },
+            #[cfg(all(feature = "serial", not(target_arch = "wasm32")))]
+            AcquisitionSource::Serial => Ok(Box::new(SerialDriver::open(&setup.port, setup.baud_rate)?)),
+        }
+    }
+
+    /// Values streamed from a plate reader into the current plate, with a preview of the well each reading goes to
+    pub fn acquisition_window(&mut self, ctx: &egui::Context) {
+        self.poll_acquisition(ctx);
+
+        let Self { acquisition, acquisition_setup: setup, microplate, .. } = self;
+        let running = acquisition.as_ref().is_some_and(|acquisition| !acquisition.finished());
+        // a finished acquisition can still be mapped in another order
+        if let Some(acquisition) = acquisition.as_mut().filter(|_| !running) {
+            acquisition.order = setup.order;
+        }
+        let mut connect = false;
+        let mut write = false;
+
+        egui::Window::new(tr("live-acquisition"))
+            .id(Id::new("Live Acquisition"))
+            .open(&mut self.show_acquisition)
+            .resizable(false)
+            .collapsible(false)
+            .frame(egui::Frame::window(&ctx.style()).inner_margin(10.0))
+            .show(ctx, |ui| {
+                ui.add_enabled_ui(!running, |ui| {
+                    Grid::new("Acquisition Grid").num_columns(2).spacing([20.0, 4.0]).show(ui, |ui| {
+                        ui.label(tr("reader"));
+                        ComboBox::from_id_salt("Acquisition Source").selected_text(setup.source.description()).show_ui(ui, |ui| {
+                            for &source in AcquisitionSource::ALL {
+                                ui.selectable_value(&mut setup.source, source, source.description());
+                            }
+                        });
+                        ui.end_row();
+                        #[cfg(all(feature = "serial", not(target_arch = "wasm32")))]
+                        if setup.source == AcquisitionSource::Serial {
+                            ui.label(tr("serial-port"));
+                            let selected = if setup.port.is_empty() { tr("choose") } else { setup.port.clone() };
+                            ComboBox::from_id_salt("Serial Port").selected_text(selected).show_ui(ui, |ui| {
+                                for port in SerialDriver::available_ports() {
+                                    ui.selectable_value(&mut setup.port, port.clone(), port);
+                                }
+                            });
+                            ui.end_row();
+                            ui.label(tr("baud-rate"));
+                            ui.add(DragValue::new(&mut setup.baud_rate).range(300..=921_600));
+                            ui.end_row();
+                        }
+                        ui.label(tr("reading-order"));
+                        ComboBox::from_id_salt("Reading Order").selected_text(setup.order.description()).show_ui(ui, |ui| {
+                            for order in ReadingOrder::ALL {
+                                ui.selectable_value(&mut setup.order, order, order.description());
+                            }
+                        });
+                        ui.end_row();
+                    });
+                    ui.checkbox(&mut setup.live, tr("write-live")).on_hover_text(tr("write-live-hover"));
+                });
+                ui.label(RichText::new(tr("acquisition-protocol-hint")).size(11.0));
+                ui.add_space(10.0);
+
+                ui.horizontal(|ui| {
+                    if running {
+                        if ui.button(tr("disconnect")).clicked() {
+                            *acquisition = None;
+                        }
+                    } else {
+                        let button = ui.button(tr("connect"));
+                        Self::dashed_outline(ui, &button);
+                        connect = button.clicked();
+                    }
+                    if let Some(acquisition) = acquisition.as_ref().filter(|acquisition| !acquisition.readings.is_empty()) {
+                        ui.add_space(10.0);
+                        let button = ui.add_enabled(!microplate.locked, egui::Button::new(tr("write-into-plate")));
+                        Self::dashed_outline(ui, &button);
+                        write = button.clicked();
+                        ui.add_space(10.0);
+                        ui.label(tr_args("acquisition-readings", &[("count", acquisition.readings.len().into()), ("reader", acquisition.driver.name().into())]));
+                    }
+                });
+
+                let Some(acquisition) = acquisition.as_ref() else { return };
+                if let Some(error) = &acquisition.error {
+                    ui.add_space(5.0);
+                    ui.label(RichText::new(error.to_string()).color(FLAG_COLOR));
+                }
+                ui.add_space(10.0);
+
+                // the last reading of each well, like writing them in turn
+                let mapping = acquisition.mapping(microplate);
+                let mut preview = vec![None; microplate.samples.len()];
+                for (reading, &index) in acquisition.readings.iter().zip(&mapping) {
+                    if let Some(index) = index {
+                        preview[index] = Some(reading.value);
+                    }
+                }
+                let unmapped = mapping.iter().filter(|index| index.is_none()).count();
+                ScrollArea::both().max_width(600.0).max_height(300.0).show(ui, |ui| {
+                    Grid::new("Acquisition Preview").spacing([6.0, 2.0]).striped(true).show(ui, |ui| {
+                        ui.label("");
+                        for column in 1..=microplate.width {
+                            ui.label(RichText::new(column.to_string()).size(11.0).strong());
+                        }
+                        ui.end_row();
+                        for row in 0..microplate.height {
+                            ui.label(RichText::new(ALPHABET[row % 26].to_string()).size(11.0).strong());
+                            for column in 0..microplate.width {
+                                let index = column * microplate.height + row;
+                                let text = preview[index].map(|value| format_decimal(value, 3)).unwrap_or_else(|| "·".to_string());
+                                ui.label(RichText::new(text).size(11.0).color(rgb(microplate.sample_color(&microplate.samples[index]))));
+                            }
+                            ui.end_row();
+                        }
+                    });
+                });
+                if unmapped > 0 {
+                    ui.label(RichText::new(tr_args("acquisition-unmapped", &[("count", unmapped.into())])).size(11.0).color(FLAG_COLOR));
+                }
+            });
+
+        if connect {
+            match self.connect_reader() {
+                Ok(driver) => self.acquisition = Some(Acquisition::new(driver, self.acquisition_setup.order)),
+                Err(error) => self.notifications.push(error),
+            }
+        }
+        if write {
+            if let Some(acquisition) = &self.acquisition {
+                acquisition.apply(&mut self.microplate);
+            }
+        }
+    }
+}
@@ -0,0 +1,145 @@
+use eframe::egui::{self, Grid, Id, RichText, Ui};
+
+use crate::{analysis::*, app::SerdeError, files::save_file, i18n::*, ui::{audit::csv_row, plot::FLAG_COLOR}, Elisa};
+
+impl Elisa {
+    pub fn analysis_menu(&mut self, ui: &mut Ui) {
+        let Some(regression) = &self.regression else { return };
+
+        let button = ui.button(RichText::new(tr("dilution-linearity")));
+        Self::dashed_outline(ui, &button);
+        if button.clicked() {
+            self.show_linearity = !self.show_linearity;
+        }
+
+        let series = regression.dilution_linearity();
+        egui::Window::new(tr("dilution-linearity"))
+            .id(Id::new("Dilution Linearity"))
+            .open(&mut self.show_linearity)
+            .resizable(false)
+            .collapsible(false)
+            .frame(egui::Frame::window(ui.style()).inner_margin(10.0))
+            .show(ui.ctx(), |ui| {
+                if series.is_empty() {
+                    ui.label(tr("linearity-empty"));
+                    return
+                }
+                ui.label(tr_args("linearity-info", &[("tolerance", format_number(LINEARITY_TOLERANCE).into())]));
+                ui.add_space(10.0);
+                Grid::new("Dilution Linearity Grid")
+                    .striped(true)
+                    .spacing([20.0, 5.0])
+                    .show(ui, |ui| {
+                        ui.label(tr("sample"));
+                        ui.label(tr("dilution"));
+                        ui.label(tr("corrected"));
+                        ui.label(tr("difference"));
+                        ui.label("");
+                        ui.end_row();
+
+                        for DilutionSeries { label, points } in &series {
+                            for (i, point) in points.iter().enumerate() {
+                                ui.label(if i == 0 { label.as_str() } else { "" });
+                                ui.label(format!("1:{}", format_number(point.dilution)));
+                                ui.label(format_value(point.concentration));
+                                if i == 0 {
+                                    ui.label(tr("reference"));
+                                    ui.label("");
+                                } else if let Some(flag) = point.flag {
+                                    ui.label(RichText::new(format_difference(point.difference)).color(FLAG_COLOR));
+                                    ui.label(RichText::new(flag.description()).color(FLAG_COLOR));
+                                } else {
+                                    ui.label(format_difference(point.difference));
+                                    ui.label("");
+                                }
+                                ui.end_row();
+                            }
+                        }
+                    });
+            });
+
+        ui.add_space(10.0);
+        let button = ui.button(RichText::new(tr("endpoint-titers")));
+        Self::dashed_outline(ui, &button);
+        if button.clicked() {
+            self.show_titers = !self.show_titers;
+        }
+
+        let cutoff = self.microplate.cutoff.as_ref().and_then(|cutoff| cutoff.signal_threshold(regression));
+        let titers = self.microplate.cutoff.as_ref().map(|cutoff| regression.endpoint_titers(cutoff)).unwrap_or_default();
+        let mut export = false;
+        egui::Window::new(tr("endpoint-titers"))
+            .id(Id::new("Endpoint Titers"))
+            .open(&mut self.show_titers)
+            .resizable(false)
+            .collapsible(false)
+            .frame(egui::Frame::window(ui.style()).inner_margin(10.0))
+            .show(ui.ctx(), |ui| {
+                let Some(cutoff) = cutoff else {
+                    ui.label(tr("titers-no-cutoff"));
+                    return
+                };
+                if titers.is_empty() {
+                    ui.label(tr("titers-empty"));
+                    return
+                }
+                ui.label(tr_args("titers-info", &[("cutoff", format_value(cutoff).into())]));
+                ui.add_space(10.0);
+                Grid::new("Endpoint Titers Grid")
+                    .striped(true)
+                    .spacing([20.0, 5.0])
+                    .show(ui, |ui| {
+                        ui.label(tr("subject"));
+                        ui.label(tr("dilutions"));
+                        ui.label(tr("endpoint-titer"));
+                        ui.label(tr("interpolated-titer"));
+                        ui.end_row();
+
+                        for titer in &titers {
+                            ui.label(&titer.subject);
+                            ui.label(titer.points.len().to_string());
+                            ui.label(titer_text(titer));
+                            ui.label(titer.interpolated.map(format_titer).unwrap_or_default());
+                            ui.end_row();
+                        }
+                    });
+                ui.add_space(10.0);
+                let button = ui.button(tr("export-csv"));
+                Self::dashed_outline(ui, &button);
+                export = button.clicked();
+            });
+
+        if !export { return }
+        let mut csv = csv_row(&[tr("subject"), tr("dilutions"), tr("endpoint-titer"), tr("interpolated-titer")]);
+        for titer in &titers {
+            csv.push_str(&csv_row(&[
+                titer.subject.clone(),
+                titer.points.len().to_string(),
+                titer_text(titer),
+                titer.interpolated.map(format_titer).unwrap_or_default(),
+            ]));
+        }
+        if save_file(&format!("{} Titers", self.microplate.name), ("CSV", &["csv"]), csv.as_bytes()).is_err() {
+            self.notifications.push(SerdeError::CantWriteFile);
+        }
+    }
+}
+
+fn format_titer(dilution: f64) -> String {
+    format!("1:{}", format_decimal(dilution, 0))
+}
+
+/// The endpoint, or the side of the series the cutoff lies beyond
+fn titer_text(titer: &Titer) -> String {
+    match (titer.end, titer.endpoint, titer.points.first()) {
+        (TiterEnd::AboveAll, Some(endpoint), _) => format!("≥ {}", format_titer(endpoint)),
+        (TiterEnd::BelowAll, _, Some(&(lowest, _))) => format!("< {}", format_titer(lowest)),
+        (_, Some(endpoint), _) => format_titer(endpoint),
+        _ => String::new(),
+    }
+}
+
+fn format_difference(difference: f64) -> String {
+    let sign = if difference >= 0.0 { "+" } else { "" };
+    format!("{sign}{} %", format_decimal(difference, 1))
+}
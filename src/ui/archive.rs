@@ -0,0 +1,177 @@
+use eframe::egui::{self, Grid, Id, RichText, ScrollArea, TextEdit};
+
+use crate::{app::SerdeError, archive::*, files::save_file, i18n::*, logistic_regression::SampleType, ElisaTab, Elisa};
+
+/// The archive window and what it last found
+#[derive(Clone, Debug, Default)]
+pub struct ArchiveBrowser {
+    pub show: bool,
+    pub query: ArchiveQuery,
+    pub searched: bool, // since the window was opened
+    pub plates: Vec<ArchivedPlate>, // found by the last search
+    pub selected: Option<i64>,
+    pub fingerprint: Option<u64>, // of the fit archived last
+}
+
+impl Elisa {
+    /// Archives the plate as just fitted, refits of an unchanged plate are archived once
+    pub fn archive_fit(&mut self) {
+        if !self.settings.archive_plates { return }
+        let Some(regression) = &self.regression else { return };
+        if self.archive.fingerprint == Some(regression.fingerprint) { return }
+
+        match Archive::open().and_then(|mut archive| archive.archive_plate(&self.microplate, regression)) {
+            Ok(_) => self.archive.fingerprint = Some(regression.fingerprint),
+            Err(error) => self.notifications.push(error),
+        }
+    }
+
+    fn search_archive(&mut self) {
+        match Archive::open().and_then(|archive| archive.search(&self.archive.query)) {
+            Ok(plates) => self.archive.plates = plates,
+            Err(error) => self.notifications.push(error),
+        }
+        self.archive.selected = None;
+    }
+
+    /// Plates analyzed before, searched by date, plate name and sample label
+    pub fn archive_window(&mut self, ctx: &egui::Context) {
+        if !self.archive.show { return }
+        // once each time the window opens, then on request
+        if !self.archive.searched {
+            self.archive.searched = true;
+            self.search_archive();
+        }
+
+        let query = &mut self.archive.query;
+        let plates = &self.archive.plates;
+        let selected = &mut self.archive.selected;
+        let archiving = self.settings.archive_plates;
+        let mut search = false;
+        let mut open_plate = None;
+        let mut export_results = None;
+        let mut delete_plate = None;
+
+        egui::Window::new(tr("archive"))
+            .id(Id::new("Archive"))
+            .open(&mut self.archive.show)
+            .resizable(false)
+            .collapsible(false)
+            .frame(egui::Frame::window(&ctx.style()).inner_margin(10.0))
+            .show(ctx, |ui| {
+                if !archiving {
+                    ui.label(RichText::new(tr("archive-off")).size(11.0));
+                    ui.add_space(10.0);
+                }
+                Grid::new("Archive Search").num_columns(2).spacing([10.0, 6.0]).show(ui, |ui| {
+                    let mut field = |ui: &mut egui::Ui, label: String, text: &mut String, hint: &str| {
+                        ui.label(label);
+                        let text_edit = ui.add(TextEdit::singleline(text).hint_text(hint).desired_width(150.0));
+                        Self::dashed_outline(ui, &text_edit);
+                        search |= text_edit.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                        ui.end_row();
+                    };
+                    field(ui, tr("archive-from"), &mut query.from, "YYYY-MM-DD");
+                    field(ui, tr("archive-to"), &mut query.to, "YYYY-MM-DD");
+                    field(ui, tr("archive-plate"), &mut query.plate, "");
+                    field(ui, tr("archive-label"), &mut query.label, "");
+                });
+                ui.add_space(10.0);
+                let button = ui.button(tr("search"));
+                Self::dashed_outline(ui, &button);
+                search |= button.clicked();
+                ui.add_space(10.0);
+
+                if plates.is_empty() {
+                    ui.label(tr("archive-none"));
+                    return
+                }
+                let count = if plates.len() == SEARCH_LIMIT { format!("{}+", plates.len()) } else { plates.len().to_string() };
+                ui.label(RichText::new(tr_args("archive-found", &[("count", count.into())])).size(11.0));
+                ui.add_space(5.0);
+                ScrollArea::vertical().id_salt("Archive Plates").max_height(250.0).show(ui, |ui| {
+                    Grid::new("Archive Plates Grid").num_columns(4).spacing([20.0, 4.0]).striped(true).show(ui, |ui| {
+                        for header in [tr("date"), tr("archive-plate"), tr("kit-lot"), SampleType::Unknown.name()] {
+                            ui.label(RichText::new(header).strong());
+                        }
+                        ui.end_row();
+                        for plate in plates {
+                            let name = if plate.name.is_empty() { tr("unnamed-plate") } else { plate.name.clone() };
+                            if ui.selectable_label(*selected == Some(plate.id), &plate.date).clicked() {
+                                *selected = if *selected == Some(plate.id) { None } else { Some(plate.id) };
+                            }
+                            ui.label(name);
+                            ui.label(&plate.kit_lot);
+                            ui.label(plate.results.len().to_string());
+                            ui.end_row();
+                        }
+                    });
+                });
+
+                let Some(plate) = plates.iter().find(|plate| Some(plate.id) == *selected) else { return };
+                ui.add_space(10.0);
+                ScrollArea::vertical().id_salt("Archive Results").max_height(150.0).show(ui, |ui| {
+                    Grid::new("Archive Results Grid").num_columns(2).spacing([20.0, 4.0]).striped(true).show(ui, |ui| {
+                        for result in &plate.results {
+                            ui.label(&result.label);
+                            ui.label(result.concentration.map(format_value).unwrap_or_else(|| "–".to_string()));
+                            ui.end_row();
+                        }
+                    });
+                });
+                ui.add_space(10.0);
+                ui.horizontal(|ui| {
+                    let button = ui.button(tr("open")).on_hover_text(tr("archive-open-hover"));
+                    Self::dashed_outline(ui, &button);
+                    if button.clicked() {
+                        open_plate = Some(plate.id);
+                    }
+                    ui.add_space(10.0);
+                    let button = ui.button(tr("archive-export-results"));
+                    Self::dashed_outline(ui, &button);
+                    if button.clicked() {
+                        export_results = Some(plate.id);
+                    }
+                    ui.add_space(10.0);
+                    if ui.button(tr("remove")).clicked() {
+                        delete_plate = Some(plate.id);
+                    }
+                });
+            });
+
+        if !self.archive.show {
+            self.archive.searched = false;
+        }
+        if let Some(id) = open_plate {
+            match Archive::open().and_then(|archive| archive.load_plate(id)) {
+                Ok(microplate) => {
+                    self.microplate = microplate;
+                    self.regression = None;
+                    self.audit_snapshot = None;
+                    self.source_file = None;
+                    self.hidden_labels.clear();
+                    self.reset_plot = true;
+                    self.current_tab = ElisaTab::Edit;
+                },
+                Err(error) => self.notifications.push(error),
+            }
+        }
+        if let Some(id) = export_results {
+            match Archive::open().and_then(|archive| archive.load_results(id)) {
+                Ok(results) => if save_file("Results", ("JSON", &["json"]), results.as_bytes()).is_err() {
+                    self.notifications.push(SerdeError::CantWriteFile);
+                },
+                Err(error) => self.notifications.push(error),
+            }
+        }
+        if let Some(id) = delete_plate {
+            if let Err(error) = Archive::open().and_then(|archive| archive.delete_plate(id)) {
+                self.notifications.push(error);
+            }
+            search = true;
+        }
+        if search {
+            self.search_archive();
+        }
+    }
+}
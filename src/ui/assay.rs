@@ -1,27 +1,103 @@
-use std::{cmp::Ordering::*, fmt::Display, fs::File, io::BufReader, num::ParseFloatError, path::PathBuf};
+use std::{fmt::Display, io::Cursor};
 
-use calamine::{open_workbook, DataType, Reader, ReaderRef, Xlsx, XlsxError};
-use eframe::{egui::{self, text::LayoutJob, vec2, Align2, Color32, DragValue, FontFamily, FontId, Grid, Layout, Margin, Response, RichText, ScrollArea, Sense, Shape, Stroke, TextEdit, Ui, Vec2, Widget}, epaint};
+use calamine::{DataType, Reader, ReaderRef, Xlsx, XlsxError};
+use eframe::{egui::{self, color_picker::color_edit_button_srgb, vec2, Align2, Color32, ComboBox, DragValue, FontFamily, FontId, Grid, Id, Layout, Margin, Response, RichText, ScrollArea, Sense, Shape, Stroke, TextEdit, Ui, Vec2, Widget}, epaint};
 use egui_extras::{Column, TableBuilder};
 
-use crate::{*, logistic_regression::*};
+use crate::{*, clipboard::detect_paste, files::*, i18n::*, layout::*, logistic_regression::*, reader::*, wavelength::WavelengthCorrection, well_details::WellDetails, well_swap::SwapMode, settings::ShortcutAction, ui::{plot::{rgb, FLAG_COLOR}, shortcuts::shortcut_tooltip}};
 
-const ALPHABET: [char; 26] = [
-    'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I', 'J', 'K', 'L', 'M',
-    'N', 'O', 'P', 'Q', 'R', 'S', 'T', 'U', 'V', 'W', 'X', 'Y', 'Z'
-];
+/// Cell text as a well value: `Some(None)` for an empty well, `None` if it isn't a number
+fn parse_cell(text: &str) -> Option<Option<f64>> {
+    match text.trim() {
+        "" | "_" => Some(None),
+        text => parse_decimal(text).map(Some),
+    }
+}
+
+/// Type assignment and clearing for all wells of a row or column. Cleared wells keep their values.
+fn line_actions(ui: &mut Ui, samples: &mut [Sample], wells: &[usize]) {
+    let menu_button = ui.menu_button(tr("set-type"), |ui| {
+        for typ in [SampleType::Unused, SampleType::Standard, SampleType::Control, SampleType::Unknown, SampleType::Blank] {
+            if ui.button(typ.name()).clicked() {
+                wells.iter().for_each(|&well| samples[well].typ = typ);
+                ui.close_menu();
+            }
+        }
+    });
+    Elisa::dashed_outline(ui, &menu_button.response);
+    menu_button.response.on_hover_text(tr("sample-type-keys-hover"));
+    let button = ui.button(tr("clear-line")).on_hover_text(tr("clear-line-hover"));
+    Elisa::dashed_outline(ui, &button);
+    if button.clicked() {
+        for &well in wells {
+            samples[well] = Sample { value: samples[well].value, reference: samples[well].reference, path_length_read: samples[well].path_length_read, ..default() };
+        }
+        ui.close_menu();
+    }
+}
+
+fn well_tooltip(ui: &mut Ui, details: &WellDetails) {
+    let value = |value: Option<f64>| value.map(format_value).unwrap_or_else(|| tr("not-available"));
+
+    ui.horizontal(|ui| {
+        ui.label(RichText::new(&details.well).strong());
+        ui.label(details.typ.name());
+        if details.excluded {
+            ui.label(RichText::new(tr("excluded")).color(FLAG_COLOR));
+        }
+        if !details.in_region {
+            ui.label(RichText::new(tr("outside-region")).color(FLAG_COLOR));
+        }
+    });
+    if let Some((violation, range)) = details.linear_range {
+        ui.label(RichText::new(violation.description(range)).color(FLAG_COLOR));
+    }
+    if details.typ == SampleType::Unused && details.raw.is_none() { return }
+
+    Grid::new("Well Tooltip Grid").num_columns(2).spacing([20.0, 2.0]).show(ui, |ui| {
+        if let Some(group) = &details.group {
+            ui.label(tr("group"));
+            ui.label(group);
+            ui.end_row();
+        }
+        ui.label(tr("raw-signal"));
+        ui.label(value(details.raw));
+        ui.end_row();
+        ui.label(tr("corrected"));
+        ui.label(value(details.corrected));
+        ui.end_row();
+        if details.replicates > 1 {
+            ui.label(tr_args("replicate-mean", &[("count", details.replicates.into())]));
+            ui.label(value(details.replicate_mean));
+            ui.end_row();
+            ui.label(tr("cv-percent"));
+            ui.label(value(details.replicate_cv));
+            ui.end_row();
+        }
+        if let Some(concentration) = &details.concentration {
+            ui.label(tr("concentration"));
+            ui.label(concentration);
+            ui.end_row();
+        }
+    });
+}
+
+/// Labels in front of the run notes text fields, so the fields line up in every language
+const LABEL_WIDTH: f32 = 80.0;
 
 struct SampleButton<'a> {
     sample: Sample,
+    color: Color32,
     radius: f32,
     current_value: &'a mut Option<usize>,
     alternative: usize,
 }
 
 impl<'a> SampleButton<'a> {
-    fn new(sample: Sample, radius: f32, current_value: &'a mut Option<usize>, alternative: usize) -> Self {
+    fn new(sample: Sample, color: Color32, radius: f32, current_value: &'a mut Option<usize>, alternative: usize) -> Self {
         Self {
             sample,
+            color,
             radius,
             current_value,
             alternative,
@@ -33,6 +109,7 @@ impl Widget for SampleButton<'_>{
     fn ui(self, ui: &mut Ui) -> Response {
         let Self {
             sample,
+            color,
             radius,
             current_value,
             alternative,
@@ -49,12 +126,19 @@ impl Widget for SampleButton<'_>{
         } else {
             visuals.inactive.fg_stroke
         };
+        let color = if sample.excluded { color.gamma_multiply(0.4) } else { color };
         painter.circle(
             response.rect.center(),
             radius,
-            sample.typ.color(),
+            color,
             stroke
         );
+        if sample.excluded {
+            let offset = Vec2::splat(radius * std::f32::consts::FRAC_1_SQRT_2);
+            let center = response.rect.center();
+            painter.line_segment([center - offset, center + offset], stroke);
+            painter.line_segment([center + vec2(offset.x, -offset.y), center + vec2(-offset.x, offset.y)], stroke);
+        }
         let text = match sample.typ {
             SampleType::Unknown | SampleType::Standard => true,
             SampleType::Blank | SampleType::Unused | SampleType::Control => false,
@@ -77,10 +161,20 @@ impl Widget for SampleButton<'_>{
 impl Elisa {
     pub fn measurements(&mut self, ui: &mut Ui) {
         let microplate = &mut self.microplate;
-        let textfield = &mut self.data_textfield;
+        let textfields = &mut self.value_textfields;
         let data_sheets = &mut self.sheet_names;
         let selected_sheet = &mut self.selected_sheet;
+        let reference_sheet = &mut self.reference_sheet;
+        let selected_sample = &mut self.selected_sample;
         let excel = &mut self.excel;
+        let notifications = &mut self.notifications;
+        let opened_files = &self.opened_files;
+        let show_acquisition = &mut self.show_acquisition;
+        #[cfg(not(target_arch = "wasm32"))]
+        let show_watch_folder = &mut self.watch_folder.show;
+        let pasted_values = &mut self.pasted_values;
+        let temperature_range = self.settings.temperature_range;
+        let linear_range = self.settings.linear_range;
 
         let width = 293.0;
         let space = 10.0;
@@ -90,7 +184,7 @@ impl Elisa {
         ui.vertical(|ui| {
             egui::Frame::new().show(ui, |ui| {
                 ui.set_width(width);
-                ui.vertical_centered(|ui| { ui.heading("Measurements"); });
+                ui.vertical_centered(|ui| { ui.heading(tr("measurements")); });
                 ui.add_space(space);
                 egui::Frame::new()
                     .fill(fill).stroke(stroke)
@@ -100,123 +194,256 @@ impl Elisa {
                         ui.set_height(ui.available_height());
                         ui.horizontal(|ui| {
                             egui::Frame::new().show(ui, |ui| {
-                                let button = ui.button(RichText::new("Open"));
+                                let button = ui.button(RichText::new(tr("open")));
                                 Self::dashed_outline(ui, &button);
                                 if button.clicked() {
-                                    if let Some(path) = rfd::FileDialog::new()
-                                        .add_filter("Excel Spreadsheet", &["xlsx"])
-                                        .pick_file() {
-                                        match open_workbook::<Xlsx<_>, PathBuf>(path) {
-                                            Ok(mut xlsx) => {
-                                                *data_sheets = xlsx.sheet_names();
-                                                if data_sheets.is_empty() {
-                                                    todo!();
-                                                }
-                                                match Elisa::parse_xlsx_sheet(&mut xlsx, *selected_sheet) {
-                                                    Ok(data) => {
-                                                        let string = Elisa::data_to_string(data); 
-                                                        *textfield = string;
-                                                    },
-                                                    Err(error) => eprintln!("error parsing excel sheet: {}", error)
-                                                }
-                                                *excel = Some(xlsx);  
-                                            }
-                                            Err(err) => eprintln!("Could not load excel spreadsheet: {err}"),
-                                        }
+                                    let filters = [(tr("excel-spreadsheet"), &["xlsx"][..]), (tr("plate-reader-export"), &["txt", "csv"][..])];
+                                    opened_files.pick(ui.ctx(), FileAction::Measurements, &filters);
+                                }
+                                let button = ui.button(RichText::new(tr("read-live"))).on_hover_text(tr("read-live-hover"));
+                                Self::dashed_outline(ui, &button);
+                                if button.clicked() {
+                                    *show_acquisition = !*show_acquisition;
+                                }
+                                #[cfg(not(target_arch = "wasm32"))]
+                                {
+                                    let button = ui.button(RichText::new(tr("watch-folder"))).on_hover_text(tr("watch-folder-hover"));
+                                    Self::dashed_outline(ui, &button);
+                                    if button.clicked() {
+                                        *show_watch_folder = !*show_watch_folder;
                                     }
                                 }
                             });
 
                             ui.add_space(space);
-                            ui.label(RichText::new("or edit manually:").size(15.0));
+                            ui.label(RichText::new(tr("edit-manually")).size(15.0));
+                        });
+                        ui.add_space(space);
+                        if let Some(format) = &microplate.metadata.reader_format {
+                            let mut info = tr_args("reader-export-info", &[("format", format.as_str().into())]);
+                            if let Some(temperature) = microplate.metadata.temperature {
+                                info.push_str(&format!(", {} °C", format_number(temperature)));
+                            }
+                            if let Some(read_time) = &microplate.metadata.read_time {
+                                info.push_str(&format!(", {read_time}"));
+                            }
+                            let info = RichText::new(info).size(11.0);
+                            match microplate.metadata.temperature_out_of_range(temperature_range) {
+                                Some(_) => { ui.label(info.color(FLAG_COLOR)).on_hover_text(tr("temperature-out-of-range")); },
+                                None => { ui.label(info); },
+                            }
+                            ui.add_space(space);
+                        }
+                        ui.horizontal(|ui| {
+                            let button = ui.add_enabled(!microplate.locked, egui::Button::new(RichText::new(tr("reference-read")).size(11.0)));
+                            Self::dashed_outline(ui, &button);
+                            if button.on_hover_text(tr("reference-read-hover")).clicked() {
+                                let filters = [(tr("excel-spreadsheet"), &["xlsx"][..]), (tr("plate-reader-export"), &["txt", "csv"][..])];
+                                opened_files.pick(ui.ctx(), FileAction::ReferenceRead, &filters);
+                            }
+                            if let Some(correction) = &microplate.metadata.correction {
+                                ui.label(RichText::new(correction.description(microplate.metadata.wavelength)).size(11.0));
+                                if ui.add_enabled(!microplate.locked, egui::Button::new(RichText::new(tr("remove")).size(11.0))).clicked() {
+                                    microplate.remove_correction();
+                                    *reference_sheet = None;
+                                }
+                            }
                         });
                         ui.add_space(space);
                         if let Some(excel) = excel {
-                            match data_sheets.len().cmp(&1) {
-                                Greater => {
-                                    ScrollArea::horizontal().max_height(20.0).id_salt("Sheets").show(ui, |ui| {
-                                        ui.horizontal(|ui| {
-                                            for (i, sheet) in data_sheets.iter().enumerate() {
-                                                if ui.radio_value(selected_sheet, i, sheet).clicked() {
-                                                    match Elisa::parse_xlsx_sheet(excel, *selected_sheet) {
-                                                        Ok(data) => {
-                                                           let string = Elisa::data_to_string(data);
-                                                           *textfield = string;
-                                                        },
-                                                        Err(error) => eprintln!("Error parsing excel sheet: {}", error)
-                                                    }
+                            if data_sheets.len() > 1 {
+                                ScrollArea::horizontal().max_height(20.0).id_salt("Sheets").show(ui, |ui| {
+                                    ui.horizontal(|ui| {
+                                        for (i, sheet) in data_sheets.iter().enumerate() {
+                                            if ui.radio_value(selected_sheet, i, sheet).clicked() {
+                                                match Elisa::parse_xlsx_sheet(excel, *selected_sheet) {
+                                                    Ok(data) => microplate.assign_values(data),
+                                                    Err(error) => notifications.push(AppError::Spreadsheet(error.to_string())),
                                                 }
-                                                ui.add_space(space);
+                                            }
+                                            ui.add_space(space);
+                                        }
+                                    });
+                                    ui.add_space(space);
+                                });
+                                // a second sheet read at the reference wavelength is subtracted from the values
+                                let mut changed = false;
+                                ui.add_enabled_ui(!microplate.locked, |ui| {
+                                    ui.horizontal(|ui| {
+                                        ui.label(RichText::new(tr("reference-sheet")).size(11.0));
+                                        let selected = reference_sheet.and_then(|sheet| data_sheets.get(sheet)).cloned().unwrap_or_else(|| tr("reference-sheet-none"));
+                                        ComboBox::from_id_salt("Reference Sheet").selected_text(selected).show_ui(ui, |ui| {
+                                            changed |= ui.selectable_value(reference_sheet, None, tr("reference-sheet-none")).changed();
+                                            for (i, sheet) in data_sheets.iter().enumerate() {
+                                                changed |= ui.selectable_value(reference_sheet, Some(i), sheet).changed();
                                             }
                                         });
-                                        ui.add_space(space);
                                     });
-                                },
-                                Equal => {
-                                    match Elisa::parse_xlsx_sheet(excel, *selected_sheet) {
-                                        Ok(data) => {
-                                               let string = Elisa::data_to_string(data); 
-                                               *textfield = string;
+                                });
+                                if changed {
+                                    match *reference_sheet {
+                                        Some(sheet) => match Elisa::parse_xlsx_sheet(excel, sheet) {
+                                            Ok(data) => microplate.assign_reference_values(data, WavelengthCorrection::from_label(&data_sheets[sheet])),
+                                            Err(error) => notifications.push(AppError::Spreadsheet(error.to_string())),
                                         },
-                                        Err(error) => eprintln!("error parsing excel sheet: {}", error)
+                                        None => microplate.remove_correction(),
                                     }
-                                },
-                                Less => ()
-                
+                                }
+                                ui.add_space(space);
                             }
                         }
 
-                        let mut layouter = |ui: &egui::Ui, string: &str, _wrap_width: f32| {
-                            let font_id = FontId::monospace(12.0);
-                            let layout_job = LayoutJob::simple(string.to_owned(), font_id, Color32::BLACK, f32::INFINITY);
-                            ui.fonts(|f| f.layout_job(layout_job))
-                        };
-                        
-                        let text_edit_height = ui.available_height() - 40.0;
-
-                        let scroll_area = ScrollArea::both()
-                            .max_height(text_edit_height)
-                            .id_salt("Measurements")
-                            .show(ui, |ui| {
-                                ui.add(egui::TextEdit::multiline(textfield)
-                                    .layouter(&mut layouter)
-                                    .desired_rows(microplate.height)
-                                    .desired_width(f32::INFINITY)
-                                )
-                        });
-                        let mut text_edit = scroll_area.inner;
-                        text_edit.rect = scroll_area.inner_rect;
-                        text_edit.rect.max.y = ui.cursor().min.y; // If you don't do this, the rect will grow past the cursor, for some reason
-                        Self::dashed_outline(ui, &text_edit);
-                        ui.add_space(space);
-                        match Elisa::string_to_data(textfield, microplate.width, microplate.height) {
-                            Ok(data) => {
-                                let button = ui.button("Assign values");
-                                Self::dashed_outline(ui, &button);
-                                if button.clicked() {
-                                    for (y, line) in data.into_iter().enumerate() {
-                                        for (x, cell) in line.into_iter().enumerate() {
-                                            microplate.samples[microplate.height * x + y].value = cell;
-                                        }
-                                    }
+                        Self::value_grid(ui, microplate, textfields, selected_sample, pasted_values, linear_range);
+                    });
+            });
+        });
+    }
+
+    /// Plate shaped grid of value cells. Cells are checked as you type, Tab and Enter move
+    /// to the next well in reading order, and pasting a block of values fills the plate
+    /// from the cell onwards. Reads outside the linear range of the reader are flagged.
+    fn value_grid(ui: &mut Ui, microplate: &mut Microplate, textfields: &mut Vec<String>, selected_sample: &mut Option<usize>, pasted_values: &mut Option<String>, linear_range: Option<(f64, f64)>) {
+        let (width, height) = (microplate.width, microplate.height);
+        let cell_id = |index: usize| Id::new(("Well Value", index));
+        let font_id = FontId::monospace(12.0);
+
+        // follow values that changed elsewhere, but keep text that doesn't parse so its error stays visible
+        textfields.resize(microplate.samples.len(), String::new());
+        for (sample, text) in microplate.samples.iter().zip(textfields.iter_mut()) {
+            let parsed = parse_cell(text);
+            match sample.value {
+                Some(value) if parsed != Some(Some(value)) => *text = format_number(value),
+                None if parsed.is_some_and(|parsed| parsed.is_some()) => text.clear(),
+                _ => (),
+            }
+        }
+
+        let mut pasted = None;
+        let mut focus = None;
+        ScrollArea::both()
+            .max_height(ui.available_height() - 20.0)
+            .id_salt("Measurements")
+            .show(ui, |ui| {
+                Grid::new("Well Values").spacing(vec2(3.0, 3.0)).show(ui, |ui| {
+                    ui.label("");
+                    for column in 1..=width {
+                        ui.vertical_centered(|ui| ui.label(RichText::new(column.to_string()).font(font_id.clone())));
+                    }
+                    ui.end_row();
+
+                    for row in 0..height {
+                        ui.label(RichText::new(ALPHABET[row % 26].to_string()).font(font_id.clone()));
+                        for column in 0..width {
+                            let index = column * height + row;
+                            let reading_order = row * width + column;
+                            let text = &mut textfields[index];
+                            let invalid = parse_cell(text).is_none();
+                            let outside = linear_range.and_then(|range| Some((microplate.linear_range_violation(index, range)?, range)));
+
+                            let text_edit = TextEdit::singleline(text)
+                                .id(cell_id(index))
+                                .font(font_id.clone())
+                                .desired_width(40.0)
+                                .text_color_opt((invalid || outside.is_some()).then_some(FLAG_COLOR));
+                            let mut response = ui.add(text_edit);
+                            response.rect = response.rect.expand2(vec2(2.0, 1.0));
+                            Self::dashed_outline(ui, &response);
+
+                            if response.gained_focus() {
+                                *selected_sample = Some(index);
+                            }
+                            if response.changed() {
+                                let block = ui.input(|i| i.events.iter().find_map(|event| match event {
+                                    egui::Event::Paste(paste) if paste.split_whitespace().nth(1).is_some() => Some(paste.clone()),
+                                    _ => None,
+                                }));
+                                match block {
+                                    Some(block) => pasted = Some((row, column, block)),
+                                    None => microplate.samples[index].value = parse_cell(text).flatten(),
                                 }
-                            },
-                            Err(error) => {
-                                eprintln!("Error parsing string to data: {}", error);
-                                ui.label("Could not parse data");
+                            }
+                            if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                                let shift = ui.input(|i| i.modifiers.shift);
+                                let next = if shift { reading_order.checked_sub(1) } else { Some(reading_order + 1) };
+                                focus = next.filter(|&next| next < width * height).map(|next| (next % width) * height + next / width);
+                            }
+                            if invalid {
+                                response.on_hover_text(tr_args("invalid-well-value", &[("value", text.as_str().into())]));
+                            } else if let Some((violation, range)) = outside {
+                                response.on_hover_text(violation.description(range));
                             }
                         }
-                    });
+                        ui.end_row();
+                    }
+                });
             });
-        });
+
+        // a whole plate waits for its layout to be confirmed, the cell keeps its value until then
+        if let Some((row, column, text)) = pasted.as_ref().filter(|(_, _, text)| detect_paste(text, microplate).is_some()) {
+            let index = column * height + row;
+            textfields[index] = microplate.samples[index].value.map(format_number).unwrap_or_default();
+            *pasted_values = Some(text.clone());
+        } else if let Some((row, column, text)) = pasted {
+            for (y, line) in text.lines().filter(|line| !line.trim().is_empty()).enumerate() {
+                for (x, cell) in line.split_whitespace().enumerate() {
+                    let (row, column) = (row + y, column + x);
+                    if row >= height || column >= width { continue }
+                    let index = column * height + row;
+                    textfields[index] = cell.to_string();
+                    microplate.samples[index].value = parse_cell(cell).flatten();
+                }
+            }
+        }
+        if let Some(index) = focus {
+            ui.memory_mut(|memory| memory.request_focus(cell_id(index)));
+        }
+
+        let invalid: Vec<String> = (0..textfields.len())
+            .filter(|&index| parse_cell(&textfields[index]).is_none())
+            .map(|index| microplate.well_name(index))
+            .collect();
+        if !invalid.is_empty() {
+            ui.add_space(5.0);
+            ui.label(RichText::new(tr_args("invalid-wells", &[("wells", invalid.join(", ").into())])).size(11.0).color(FLAG_COLOR));
+        }
+        let outside: Vec<String> = linear_range.map(|range| microplate.wells_outside_linear_range(range)).unwrap_or_default()
+            .into_iter()
+            .map(|(index, _violation)| microplate.well_name(index))
+            .collect();
+        if !outside.is_empty() {
+            ui.add_space(5.0);
+            ui.label(RichText::new(tr_args("wells-outside-linear-range", &[("count", outside.len().into()), ("wells", outside.join(", ").into())])).size(11.0).color(FLAG_COLOR))
+                .on_hover_text(tr("wells-outside-linear-range-hover"));
+        }
     }
     
     pub fn microplate_view(&mut self, ui: &mut Ui) {
         let microplate = &mut self.microplate;
+        let regression = self.regression.as_ref();
+        let linear_range = self.settings.linear_range;
         let radius = 30.0 / 2.0;
         let spacing = 10.0 - 4.0;
         let cell_size = 2.0 * Vec2::splat(radius);
         let response_color = ui.visuals().text_color();
+        let hovered_color = ui.visuals().widgets.hovered.fg_stroke.color;
+        let selection_stroke = ui.visuals().selection.stroke;
+
+        // clicking a row or column header selects its wells for the sample menu, right clicking offers the same actions
+        let selected_line = self.selected_line;
+        let selected_wells = selected_line.map(|line| microplate.line_wells(line)).unwrap_or_default();
+        let header_color = |response: &Response, line: PlateLine| {
+            if response.hovered() || selected_line == Some(line) { hovered_color } else { response_color }
+        };
+        let mut clicked_line = None;
+        let mut header_clicked = |response: Response, line: PlateLine, microplate: &mut Microplate| {
+            if response.clicked() {
+                clicked_line = Some(line);
+            }
+            if !microplate.locked {
+                let wells = microplate.line_wells(line);
+                response.on_hover_text(line.name()).context_menu(|ui| line_actions(ui, &mut microplate.samples, &wells));
+            }
+        };
 
         let where_to_put_background = ui.painter().add(Shape::Noop);
         
@@ -229,31 +456,49 @@ impl Elisa {
                 .show(ui, |ui| {
                     ui.allocate_exact_size(cell_size, Sense::hover());
                     for i in 1..=microplate.width {
-                        let (response, painter) = ui.allocate_painter(cell_size, Sense::hover());
+                        let (response, painter) = ui.allocate_painter(cell_size, Sense::click());
                         painter.text(
                             response.rect.center(),
                             Align2::CENTER_TOP,
                             format!("{i}"),
                             FontId::new(radius, FontFamily::default()),
-                            response_color
+                            header_color(&response, PlateLine::Column(i - 1))
                         );
+                        header_clicked(response, PlateLine::Column(i - 1), microplate);
                     }
                     ui.end_row();
                     for i in 0..microplate.height {
-                        let (response, painter) = ui.allocate_painter(cell_size, Sense::hover());
+                        let (response, painter) = ui.allocate_painter(cell_size, Sense::click());
                         painter.text(
                             response.rect.center(),
                             Align2::LEFT_CENTER,
                             ALPHABET[i%26],
                             FontId::new(radius, FontFamily::default()),
-                            response_color
+                            header_color(&response, PlateLine::Row(i))
                         );
+                        header_clicked(response, PlateLine::Row(i), microplate);
                         for ii in 0..microplate.width {
                             let index = ii * microplate.height + i;
                             let sample = microplate.samples[index].clone();
-                            let response = ui.add(SampleButton::new(sample, radius, &mut self.selected_sample, index));
+                            let color = rgb(microplate.sample_color(&sample));
+                            let color = if microplate.in_region(index) { color } else { color.gamma_multiply(0.25) };
+                            let response = ui.add(SampleButton::new(sample, color, radius, &mut self.selected_sample, index))
+                                .on_hover_ui(|ui| well_tooltip(ui, &WellDetails::new(microplate, regression, index, linear_range)));
+                            if selected_wells.contains(&index) {
+                                ui.painter().circle_stroke(response.rect.center(), radius + 2.0, selection_stroke);
+                            }
+                            if self.swap_well.is_some_and(|(first, _mode)| first == index) {
+                                ui.painter().circle_stroke(response.rect.center(), radius + 2.0, Stroke::new(2.0, hovered_color));
+                            }
                             if response.clicked() {
-                                if self.selected_sample == Some(index) {
+                                self.selected_line = None;
+                                // the second well of a swap, clicking the first one again cancels it
+                                if let Some((first, mode)) = self.swap_well.take() {
+                                    if !microplate.locked {
+                                        microplate.swap_wells(first, index, mode);
+                                    }
+                                    self.selected_sample = Some(index);
+                                } else if self.selected_sample == Some(index) {
                                     self.selected_sample = None;
                                 } else {
                                     self.selected_sample = Some(index);
@@ -278,10 +523,20 @@ impl Elisa {
         shape.fill = fill;
 
         ui.painter().set(where_to_put_background, shape);
+
+        if let Some(line) = clicked_line {
+            self.selected_line = if self.selected_line == Some(line) { None } else { Some(line) };
+            self.selected_sample = None;
+        }
     }
     
     pub fn sample_menu(&mut self, ui: &mut Ui) {
         let radius = 15.0;
+        let line_wells = self.selected_line.map(|line| (line, self.microplate.line_wells(line)));
+        let selected_color = self.selected_sample.map(|index| rgb(self.microplate.sample_color(&self.microplate.samples[index])));
+        let swap_pending = self.swap_well.map(|(first, mode)| {
+            tr_args("swap-pick-second", &[("well", self.microplate.well_name(first).into()), ("mode", mode.description().into())])
+        });
         let samples = &mut self.microplate.samples;
         let stroke = ui.visuals().noninteractive().bg_stroke;
         let fill = ui.visuals().faint_bg_color;
@@ -290,7 +545,7 @@ impl Elisa {
             egui::Frame::new().show(ui, |ui| {
                 let width = ui.available_width();
                 ui.set_width(width);
-                ui.vertical_centered(|ui| { ui.heading("Sample Menu"); });
+                ui.vertical_centered(|ui| { ui.heading(tr("sample-menu")); });
                 ui.add_space(10.0);
                 egui::Frame::new()
                     .fill(fill).stroke(stroke)
@@ -302,24 +557,31 @@ impl Elisa {
                             use SampleType::*;
                             
                             ui.horizontal(|ui| {
-                                ui.label(format!("Selected sample {}", index + 1));
+                                ui.label(tr_args("selected-sample", &[("number", (index + 1).into())]));
 
                                 let (response, painter) = ui.allocate_painter(vec2(ui.available_width(), 2.0 * radius), Sense::hover());
-                                painter.circle(response.rect.right_center() - vec2(2.0 * radius - 10.0, 0.0), radius, samples[index].typ.color(), Stroke::NONE);
+                                painter.circle(response.rect.right_center() - vec2(2.0 * radius - 10.0, 0.0), radius, selected_color.unwrap_or_default(), Stroke::NONE);
                             });
                             ui.add_space(10.0);
                             ui.separator();
                             ui.add_space(10.0);
 
                             let row_height = 30.0;
-                            let mut list = vec!["Sample Type", "Measurement"];
+                            let mut list = vec![tr("sample-type"), tr("measurement")];
+                            if samples[index].typ != Unused {
+                                list.push(tr("excluded"));
+                            }
                             match samples[index].typ {
                                 Standard => {
-                                    list.push("Group")
+                                    list.push(tr("group"));
+                                    list.push(tr("group-color"));
                                 },
                                 Unknown => {
-                                    list.push("Group");
-                                    list.push("Label");
+                                    list.push(tr("group"));
+                                    list.push(tr("group-color"));
+                                    list.push(tr("label"));
+                                    list.push(tr("dilution"));
+                                    list.push(tr("spike"));
                                 }
                                 _ => ()
                             }
@@ -332,7 +594,7 @@ impl Elisa {
                                             let index = rows.index();
                                             rows.col(|ui| {
                                                 ui.horizontal_centered(|ui| {
-                                                    ui.label(list[index]);
+                                                    ui.label(&list[index]);
                                                 });
                                             });
                                         });
@@ -343,32 +605,53 @@ impl Elisa {
                                         body.row(row_height, |mut row| {
                                             row.col(|ui| {
                                                 ui.horizontal_centered(|ui| {
-                                                    let menu_button = ui.menu_button(format!("{:?}", samples[index].typ), |ui| {
-                                                        if ui.button("Unused").clicked() { samples[index].typ = Unused }
-                                                        if ui.button("Standard").clicked() { samples[index].typ = Standard }
-                                                        if ui.button("Control").clicked() { samples[index].typ = Control }
-                                                        if ui.button("Unknown").clicked() { samples[index].typ = Unknown }
-                                                        if ui.button("Blank").clicked() { samples[index].typ = Blank }
+                                                    let menu_button = ui.menu_button(samples[index].typ.name(), |ui| {
+                                                        for typ in [Unused, Standard, Control, Unknown, Blank] {
+                                                            if ui.button(typ.name()).clicked() { samples[index].typ = typ }
+                                                        }
                                                     });
                                                     Self::dashed_outline(ui, &menu_button.response);
+                                                    menu_button.response.on_hover_text(tr("sample-type-keys-hover"));
                                                 });
                                             });
                                         });
                                         body.row(row_height, |mut row| {
                                             row.col(|ui| {
                                                 ui.horizontal_centered(|ui| {
-                                                    let measurement = samples[index].value.map(|f| format!("{:.5}", f)).unwrap_or("N/A".to_string());
+                                                    let measurement = samples[index].value.map(format_value).unwrap_or(tr("not-available"));
                                                     ui.label(measurement);
                                                 });
                                             });                                        
                                         });
 
+                                        if samples[index].typ != Unused {
+                                            body.row(row_height, |mut row| {
+                                                row.col(|ui| {
+                                                    ui.horizontal_centered(|ui| {
+                                                        // excluding asks for a reason first, with reasons set up
+                                                        let mut excluded = samples[index].excluded;
+                                                        if ui.checkbox(&mut excluded, "").on_hover_text(tr("excluded-hover")).changed() {
+                                                            if excluded && !self.settings.exclusion_rules.reasons.is_empty() {
+                                                                self.exclusion_prompt = Some(index);
+                                                            } else {
+                                                                samples[index].excluded = excluded;
+                                                                samples[index].exclusion_reason = None;
+                                                            }
+                                                        }
+                                                        if let (true, Some(reason)) = (samples[index].excluded, &samples[index].exclusion_reason) {
+                                                            ui.label(RichText::new(reason.description()).size(11.0));
+                                                        }
+                                                    });
+                                                });
+                                            });
+                                        }
+
                                         if samples[index].typ == Unknown || samples[index].typ == Standard {
                                             body.row(row_height, |mut row| {
                                                 row.col(|ui| {
                                                     ui.horizontal_centered(|ui| {
                                                         self.selected_sample_group = samples[index].group + 1;
-                                                        let drag_value = DragValue::new(&mut self.selected_sample_group).speed(0.03).range(1..=100);
+                                                        let drag_value = DragValue::new(&mut self.selected_sample_group).speed(0.03).range(1..=MAX_GROUPS);
                                                         let mut drag_value_resp = ui.add(drag_value);
                                                         samples[index].group = self.selected_sample_group - 1;
                                                 
@@ -390,13 +673,36 @@ impl Elisa {
                                                         .filter(|sample| sample.typ == SampleType::Standard)
                                                         .map(|sample| sample.group)
                                                         .max().unwrap_or_default();
-                                                    self.microplate.standard_groups.resize_with(max_standard_group + 1, default);
+                                                    // only grow, empty groups stay until they are deleted in the group list
+                                                    if self.microplate.standard_groups.len() <= max_standard_group {
+                                                        self.microplate.standard_groups.resize_with(max_standard_group + 1, default);
+                                                    }
 
                                                     let max_unknown_group = samples.iter()
                                                         .filter(|sample| sample.typ == SampleType::Unknown)
                                                         .map(|sample| sample.group)
                                                         .max().unwrap_or_default();
-                                                    self.microplate.unknown_groups.resize_with(max_unknown_group + 1, default);
+                                                    if self.microplate.unknown_groups.len() <= max_unknown_group {
+                                                        self.microplate.unknown_groups.resize_with(max_unknown_group + 1, default);
+                                                    }
+                                                });
+                                            });
+                                            body.row(row_height, |mut row| {
+                                                row.col(|ui| {
+                                                    ui.horizontal_centered(|ui| {
+                                                        let sample = &samples[index];
+                                                        let groups = if sample.typ == Standard { &mut self.microplate.standard_groups } else { &mut self.microplate.unknown_groups };
+                                                        let Some(group) = groups.get_mut(sample.group) else { return };
+                                                        let mut custom = group.color.is_some();
+                                                        let mut color = group.color.unwrap_or(sample.typ.color());
+                                                        if custom {
+                                                            color_edit_button_srgb(ui, &mut color);
+                                                            ui.add_space(5.0);
+                                                        }
+                                                        ui.checkbox(&mut custom, "")
+                                                            .on_hover_text(tr("group-color-hover"));
+                                                        group.color = custom.then_some(color);
+                                                    });
                                                 });
                                             });
                                         }
@@ -412,11 +718,75 @@ impl Elisa {
                                                     });
                                                 });
                                             });
+                                            body.row(row_height, |mut row| {
+                                                row.col(|ui| {
+                                                    ui.horizontal_centered(|ui| {
+                                                        let group = &mut self.microplate.unknown_groups[samples[index].group];
+                                                        let mut dilution = group.dilution.unwrap_or(1.0);
+                                                        let drag_value = DragValue::new(&mut dilution).speed(0.1).range(1.0..=f64::MAX).prefix("1:")
+                                                            .custom_formatter(|n, _| format_number(n))
+                                                            .custom_parser(parse_decimal);
+                                                        let drag_value_resp = ui.add(drag_value);
+                                                        group.dilution = Some(dilution).filter(|&dilution| dilution != 1.0);
+                                                        Self::dashed_outline(ui, &drag_value_resp);
+                                                    });
+                                                });
+                                            });
+                                            body.row(row_height, |mut row| {
+                                                row.col(|ui| {
+                                                    ui.horizontal_centered(|ui| {
+                                                        let group = &mut self.microplate.unknown_groups[samples[index].group];
+                                                        let mut spiked = group.spike.is_some();
+                                                        let mut spike = group.spike.unwrap_or_default();
+                                                        if spiked {
+                                                            let drag_value = DragValue::new(&mut spike).speed(0.1).range(0.0..=f64::MAX)
+                                                                .custom_formatter(|n, _| format_number(n))
+                                                                .custom_parser(parse_decimal);
+                                                            let drag_value_resp = ui.add(drag_value);
+                                                            Self::dashed_outline(ui, &drag_value_resp);
+                                                            ui.add_space(5.0);
+                                                        }
+                                                        ui.checkbox(&mut spiked, "")
+                                                            .on_hover_text(tr("spike-hover"));
+                                                        group.spike = spiked.then_some(spike);
+                                                    });
+                                                });
+                                            });
                                         }
                                     });
                             });
+
+                            ui.add_space(4.0);
+                            ui.separator();
+                            ui.add_space(4.0);
+                            ui.horizontal(|ui| match &swap_pending {
+                                Some(text) => {
+                                    ui.label(RichText::new(text).size(11.0));
+                                    let button = ui.button(tr("cancel"));
+                                    Self::dashed_outline(ui, &button);
+                                    if button.clicked() {
+                                        self.swap_well = None;
+                                    }
+                                },
+                                None => {
+                                    ui.label(tr("swap-with"));
+                                    for mode in SwapMode::ALL {
+                                        let button = ui.button(mode.description()).on_hover_text(mode.hover());
+                                        Self::dashed_outline(ui, &button);
+                                        if button.clicked() {
+                                            self.swap_well = Some((index, mode));
+                                        }
+                                    }
+                                },
+                            });
+                        } else if let Some((line, wells)) = &line_wells {
+                            ui.label(tr_args("selected-line", &[("line", line.name().into()), ("count", wells.len().into())]));
+                            ui.add_space(10.0);
+                            ui.separator();
+                            ui.add_space(10.0);
+                            ui.horizontal(|ui| line_actions(ui, samples, wells));
                         } else {
-                            ui.label("Please select a sample from the microplate.");
+                            ui.label(tr("select-sample"));
                         }
                 });
             });
@@ -433,7 +803,7 @@ impl Elisa {
             egui::Frame::new().show(ui, |ui| {
                 let width = ui.available_width();
                 ui.set_width(width);
-                ui.vertical_centered_justified(|ui| { ui.heading("Standards Concentrations") });
+                ui.vertical_centered_justified(|ui| { ui.heading(tr("standards-concentrations")) });
                 ui.add_space(10.0);
                 egui::Frame::new()
                     .fill(fill).stroke(stroke)
@@ -449,24 +819,32 @@ impl Elisa {
                                     .min_scrolled_height(height - 20.0)
                                     .max_scroll_height(height - 20.0)
                                     .columns(Column::exact(90.0), 2)
+                                    .column(Column::auto())
                                     .header(20.0, |mut header| {
-                                        header.col(|ui| { ui.label("Group"); });
-                                        header.col(|ui| { ui.label("Concentrations"); });
+                                        header.col(|ui| { ui.label(tr("group")); });
+                                        header.col(|ui| { ui.label(tr("concentrations")); });
+                                        header.col(|ui| { ui.label(tr("anchor")).on_hover_text(tr("anchor-hover")); });
                                     })
                                     .body(|body| {
                                         body.rows(25.0, groups.len(), |mut row| {
                                             let index = row.index();
                                             let text_edit = &mut self.standards_textfield[index];
-                                            row.col(|ui| { ui.label(format!("Standard {}", index + 1)); });
+                                            row.col(|ui| { ui.label(tr_args("standard-number", &[("number", (index + 1).into())])); });
+                                            // keep what was typed, e.g. a trailing separator, as long as it means the same number
                                             if let Some(concentration) = groups[index].concentration {
-                                                *text_edit = concentration.to_string();
+                                                if parse_decimal(text_edit) != Some(concentration) {
+                                                    *text_edit = format_number(concentration);
+                                                }
                                             }
                                             row.col(|ui| {
                                                 let mut text_edit = ui.text_edit_singleline(text_edit);
                                                 text_edit.rect = text_edit.rect.expand2(vec2(3.7, 1.7));
                                                 Self::dashed_outline(ui, &text_edit);    
                                             });
-                                            groups[index].concentration = text_edit.parse().ok();
+                                            groups[index].concentration = parse_decimal(text_edit);
+                                            row.col(|ui| {
+                                                ui.checkbox(&mut groups[index].anchor, "").on_hover_text(tr("anchor-hover"));
+                                            });
                                         });
                                     });
                             });
@@ -496,7 +874,7 @@ impl Elisa {
                                 if let Some(Group { concentration: Some(mut next), .. }) = groups.first() {
                                     for (i, group) in groups.iter_mut().enumerate().skip(1) {
                                         next /= 2.0;
-                                        self.standards_textfield[i] = next.to_string();
+                                        self.standards_textfield[i] = format_number(next);
                                         group.concentration = Some(next);
                                     }
                                 }
@@ -508,7 +886,11 @@ impl Elisa {
     }
     
     pub fn run_notes(&mut self, ui: &mut Ui) {
+        let refit_hint = self.shortcut_hint(ui.ctx(), ShortcutAction::Refit);
         let microplate = &mut self.microplate;
+        let name_from_barcode = self.settings.name_from_barcode;
+        let mut calculate = false;
+        let mut transform = None;
 
         let space = 10.0;
         let stroke = ui.visuals().noninteractive().bg_stroke;
@@ -517,7 +899,7 @@ impl Elisa {
         ui.vertical(|ui| {
             egui::Frame::new().show(ui, |ui| {
                 ui.set_width(200.0);
-                ui.vertical_centered_justified(|ui| { ui.heading("Run Notes") });
+                ui.vertical_centered_justified(|ui| { ui.heading(tr("run-notes")) });
                 ui.add_space(space);
                 egui::Frame::new()
                     .fill(fill).stroke(stroke)
@@ -526,86 +908,227 @@ impl Elisa {
                         ui.set_width(180.0);
                         ui.set_min_height(ui.available_height());
 
-                        ui.horizontal(|ui| {
-                            ui.label("Name");
-                            ui.add_space(50.0);
-                            let mut text_edit = ui.add(TextEdit::singleline(&mut microplate.name));
-                            text_edit.rect = text_edit.rect.expand2(vec2(4.0, 2.0)); // Account for margin
-                            Self::dashed_outline(ui, &text_edit);
-                        });
+                        ui.add_enabled_ui(!microplate.locked, |ui| {
+                            ui.horizontal(|ui| {
+                                let label = ui.label(tr("name"));
+                                ui.add_space((LABEL_WIDTH - label.rect.width()).max(5.0));
+                                let mut text_edit = ui.add(TextEdit::singleline(&mut microplate.name));
+                                text_edit.rect = text_edit.rect.expand2(vec2(4.0, 2.0)); // Account for margin
+                                Self::dashed_outline(ui, &text_edit);
+                            });
 
-                        ui.add_space(space);
-                        ui.label("Description");
-                        ui.add_space(5.0);
-                        let scroll_area = egui::ScrollArea::vertical()
-                            .max_height(ui.available_height() - 40.0)
-                            .show(ui, |ui| {
-                                ui.add(TextEdit::multiline(&mut microplate.description).desired_rows(8))
+                            ui.add_space(space);
+                            ui.horizontal(|ui| {
+                                let label = ui.label(tr("kit-lot"));
+                                ui.add_space((LABEL_WIDTH - label.rect.width()).max(5.0));
+                                let mut text_edit = ui.add(TextEdit::singleline(&mut microplate.metadata.kit_lot));
+                                text_edit.rect = text_edit.rect.expand2(vec2(4.0, 2.0));
+                                Self::dashed_outline(ui, &text_edit);
                             });
-                        let mut text_edit = scroll_area.inner;
-                        text_edit.rect = scroll_area.inner_rect;
-                        text_edit.rect.max.y = ui.cursor().min.y; // If you don't do this, the rect will grow past the cursor, for some reason
-                        Self::dashed_outline(ui, &text_edit);
+
+                            ui.add_space(space);
+                            ui.horizontal(|ui| {
+                                let label = ui.label(tr("barcode"));
+                                ui.add_space((LABEL_WIDTH - label.rect.width()).max(5.0));
+                                let mut text_edit = ui.add(TextEdit::singleline(&mut microplate.barcode).hint_text(tr("barcode-hint")));
+                                text_edit.rect = text_edit.rect.expand2(vec2(4.0, 2.0));
+                                Self::dashed_outline(ui, &text_edit);
+                                // scanners type the barcode and press enter
+                                if name_from_barcode && text_edit.lost_focus() && !microplate.barcode.trim().is_empty() {
+                                    microplate.name = microplate.barcode.trim().to_string();
+                                }
+                            });
+
+                            ui.add_space(space);
+                            ui.label(tr("description"));
+                            ui.add_space(5.0);
+                            let scroll_area = egui::ScrollArea::vertical()
+                                .max_height(ui.available_height() - 40.0)
+                                .show(ui, |ui| {
+                                    ui.add(TextEdit::multiline(&mut microplate.description).desired_rows(8))
+                                });
+                            let mut text_edit = scroll_area.inner;
+                            text_edit.rect = scroll_area.inner_rect;
+                            text_edit.rect.max.y = ui.cursor().min.y; // If you don't do this, the rect will grow past the cursor, for some reason
+                            Self::dashed_outline(ui, &text_edit);
+                        });
                         ui.add_space(space);
 
-                        let button = ui.button("Calculate");
-                        Self::dashed_outline(ui, &button);
-                        if button.clicked() {
-                            match Regression::new(microplate) {
-                                Ok(regression) => {
-                                    self.regression = Some(regression);
-                                    self.current_tab = ElisaTab::Result;
-                                },
-                                Err(error) => { self.value_error_modal = Some(error) }
+                        ui.horizontal(|ui| {
+                            let button = shortcut_tooltip(ui.button(tr("calculate")), refit_hint);
+                            Self::dashed_outline(ui, &button);
+                            calculate = button.clicked();
+                            ui.add_space(space);
+                            let button = ui.button(tr("options"));
+                            Self::dashed_outline(ui, &button);
+                            if button.clicked() {
+                                self.show_fit_options = !self.show_fit_options;
                             }
-                        }
+                            ui.add_space(space);
+                            let button = ui.button(tr("protocol"));
+                            Self::dashed_outline(ui, &button);
+                            if button.clicked() {
+                                self.show_protocol = !self.show_protocol;
+                            }
+                            ui.add_space(space);
+                            let button = ui.button(tr("paste-layout"));
+                            Self::dashed_outline(ui, &button);
+                            if button.on_hover_text(tr("paste-layout-hover")).clicked() {
+                                self.show_paste_layout = !self.show_paste_layout;
+                            }
+                        });
+                        ui.add_space(space);
+                        ui.horizontal(|ui| {
+                            let button = ui.button(tr("exclusions"));
+                            Self::dashed_outline(ui, &button);
+                            if button.on_hover_text(tr("exclusions-hover")).clicked() {
+                                self.show_exclusions = !self.show_exclusions;
+                            }
+                            ui.add_space(space);
+                            let button = ui.button(tr("groups"));
+                            Self::dashed_outline(ui, &button);
+                            if button.on_hover_text(tr("groups-hover")).clicked() {
+                                self.show_groups = !self.show_groups;
+                            }
+                            ui.add_space(space);
+                            let button = ui.button(tr("detect-replicates"));
+                            Self::dashed_outline(ui, &button);
+                            if button.on_hover_text(tr("detect-replicates-hover")).clicked() {
+                                self.show_replicates = !self.show_replicates;
+                            }
+                            ui.add_space(space);
+                            let button = ui.button(tr("sample-metadata"));
+                            Self::dashed_outline(ui, &button);
+                            if button.on_hover_text(tr("sample-metadata-hover")).clicked() {
+                                self.show_sample_metadata = !self.show_sample_metadata;
+                            }
+                            ui.add_space(space);
+                            let button = ui.button(tr("run-metadata"));
+                            Self::dashed_outline(ui, &button);
+                            if button.on_hover_text(tr("run-metadata-hover")).clicked() {
+                                self.show_run_metadata = !self.show_run_metadata;
+                            }
+                            ui.add_space(space);
+                            ui.add_enabled_ui(!microplate.locked, |ui| {
+                                let menu_button = ui.menu_button(tr("transform-plate"), |ui| {
+                                    for option in PlateTransform::ALL {
+                                        if ui.button(option.description()).clicked() {
+                                            transform = Some(option);
+                                            ui.close_menu();
+                                        }
+                                    }
+                                });
+                                Self::dashed_outline(ui, &menu_button.response.on_hover_text(tr("transform-plate-hover")));
+                            });
+                        });
                     });
             });
         });
+
+        if calculate && self.fit_curve() {
+            self.current_tab = ElisaTab::Result;
+        }
+        if let Some(transform) = transform {
+            self.microplate.transform(transform);
+            self.selected_sample = None;
+            self.selected_line = None;
+            self.exclusion_preview.clear();
+        }
     }
     
-    fn string_to_data(data: &str, width: usize, height: usize) -> Result<Vec<Vec<Option<f64>>>, StringToDataError> {
-        use StringToDataError::*;
-        let mut result = Vec::new();
-        for line in data.lines() {
-            let mut row = Vec::new();
-            for value in line.split_whitespace() {
-                if value == "_" {
-                    row.push(None);
+    /// Values of a plate reader export or of an Excel workbook, whose sheets can be picked afterwards
+    pub fn open_measurements(&mut self, file: &OpenedFile) {
+        let microplate = &mut self.microplate;
+        let notifications = &mut self.notifications;
+        self.reference_sheet = None;
+        microplate.remove_correction();
+        if !file.name.to_lowercase().ends_with(".xlsx") {
+            match parse_reader_export(&decode_export(&file.bytes)) {
+                Ok(export) => {
+                    microplate.metadata.reader_format = Some(export.format.to_string());
+                    microplate.metadata.temperature = export.temperature;
+                    microplate.metadata.read_time = export.read_time;
+                    microplate.metadata.read_settings = export.read_settings;
+                    if let Some(temperature) = microplate.metadata.temperature_out_of_range(self.settings.temperature_range) {
+                        notifications.push(AppError::Temperature(temperature, self.settings.temperature_range.unwrap_or_default()));
+                    }
+                    if export.wavelengths.0.is_some() {
+                        microplate.metadata.wavelength = export.wavelengths.0;
+                    }
+                    microplate.assign_values(export.values);
+                    // dual wavelength exports are corrected right away
+                    if let Some(reference) = export.reference_values {
+                        microplate.assign_reference_values(reference, WavelengthCorrection { reference_wavelength: export.wavelengths.1 });
+                    }
+                    self.sheet_names = Vec::new();
+                    self.excel = None;
+                },
+                Err(error) => notifications.push(AppError::ReaderExport(error.to_string())),
+            }
+            return
+        }
+        match Xlsx::new(Cursor::new(file.bytes.clone())) {
+            Ok(mut xlsx) => {
+                self.sheet_names = xlsx.sheet_names();
+                if self.sheet_names.is_empty() {
+                    notifications.push(AppError::EmptySpreadsheet);
+                    self.excel = None;
                 } else {
-                    let value = value.to_string().replace(",", ".");
-                    row.push(Some(value.parse::<f64>()?));
+                    self.selected_sheet = 0;
+                    match Elisa::parse_xlsx_sheet(&mut xlsx, self.selected_sheet) {
+                        Ok(data) => microplate.assign_values(data),
+                        Err(error) => notifications.push(AppError::Spreadsheet(error.to_string())),
+                    }
+                    self.excel = Some(xlsx);
                 }
-            }
-            if row.len() > width { return Err(WidthTooLarge) }
-            result.push(row);
+            },
+            Err(error) => notifications.push(AppError::Spreadsheet(error.to_string())),
         }
-        if result.len() > height { return Err(HeightTooLarge) }
+    }
 
-        Ok(result)
+    /// Rows of values of a reader export with the wavelength it names, or of the first sheet of a workbook
+    pub fn read_plate_file(file: &OpenedFile) -> Result<(Rows, Option<f64>), AppError> {
+        if file.name.to_lowercase().ends_with(".xlsx") {
+            Xlsx::new(Cursor::new(file.bytes.clone()))
+                .map_err(|error| AppError::Spreadsheet(error.to_string()))
+                .and_then(|mut xlsx| Elisa::parse_xlsx_sheet(&mut xlsx, 0).map_err(|error| AppError::Spreadsheet(error.to_string())))
+                .map(|data| (data, None))
+        } else {
+            parse_reader_export(&decode_export(&file.bytes))
+                .map(|export| (export.values, export.wavelengths.0))
+                .map_err(|error| AppError::ReaderExport(error.to_string()))
+        }
     }
 
-    fn data_to_string(data: Vec<Vec<Option<f64>>>) -> String {
-        let mut result = String::new();
-        for row in data {
-            for value in row {
-                if let Some(value) = value {
-                    result.push_str(&value.to_string());
-                } else {
-                    result.push('_');
-                }
-                result.push(' ');
-            }
-            result.push('\n');
+    /// A read of the same plate at the reference wavelength, from a reader export or the first sheet of a workbook
+    pub fn open_reference_read(&mut self, file: &OpenedFile) {
+        if self.microplate.locked { return }
+        match Self::read_plate_file(file) {
+            Ok((data, wavelength)) => {
+                let correction = match wavelength {
+                    Some(wavelength) => WavelengthCorrection { reference_wavelength: Some(wavelength) },
+                    None => WavelengthCorrection::from_label(&file.name),
+                };
+                self.microplate.assign_reference_values(data, correction);
+                self.reference_sheet = None;
+            },
+            Err(error) => self.notifications.push(error),
         }
-        println!("{}", result);
-        result
     }
-    
-    fn parse_xlsx_sheet(excel: &mut Xlsx<BufReader<File>>, sheet: usize) -> Result<Vec<Vec<Option<f64>>>, ParseExcelError> {
+
+    /// Water peak reads for the path-length correction of the fit options
+    pub fn open_path_length_read(&mut self, file: &OpenedFile) {
+        if self.microplate.locked { return }
+        match Self::read_plate_file(file) {
+            Ok((data, _)) => self.microplate.assign_path_length_reads(data),
+            Err(error) => self.notifications.push(error),
+        }
+    }
+
+    fn parse_xlsx_sheet(excel: &mut Xlsx<Cursor<Vec<u8>>>, sheet: usize) -> Result<Vec<Vec<Option<f64>>>, ParseExcelError> {
         use ParseExcelError::*;
 
-        let data = excel.worksheet_range_at_ref(sheet).unwrap()?;
+        let data = excel.worksheet_range_at_ref(sheet).ok_or(NoSheet)??;
         if data.get_size() < (65, 8) {
             return Err(SheetSize)
         }
@@ -630,33 +1153,9 @@ impl Elisa {
 
 // Hmmm... maybe I should use thiserror
 
-#[derive(Debug)]
-enum StringToDataError {
-    WidthTooLarge,
-    HeightTooLarge,
-    Parse(ParseFloatError),
-}
-
-impl From<ParseFloatError> for StringToDataError {
-    fn from(value: ParseFloatError) -> Self {
-        Self::Parse(value)
-    }
-}
-
-impl Display for StringToDataError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let error = match self {
-            Self::WidthTooLarge => String::from("String has more entries than microplate is wide"),
-            Self::HeightTooLarge => String::from("String has more entries than microplate is high"),
-            Self::Parse(value) => format!("{}", value),
-        };
-
-        write!(f, "{}", error)
-    }
-}
-
 #[derive(Debug)]
 enum ParseExcelError {
+    NoSheet,
     SheetSize,
     NoDimensions,
     XlsxError(XlsxError),
@@ -671,6 +1170,7 @@ impl From<XlsxError> for ParseExcelError {
 impl Display for ParseExcelError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let error = match self {
+            Self::NoSheet => String::from("Sheet does not exist"),
             Self::SheetSize => String::from("Sheet size is too small"),
             Self::NoDimensions => String::from("Could not parse table dimensions"),
             Self::XlsxError(value) => format!("{}", value)
@@ -0,0 +1,79 @@
+use eframe::egui::{self, Id, RichText};
+use egui_extras::{Column, TableBuilder};
+
+use crate::{app::SerdeError, files::save_file, i18n::*, Elisa};
+
+impl Elisa {
+    /// Diffs the plate against the last snapshot, but not while a text field is being typed in,
+    /// so a value typed into a well becomes one entry instead of one per keystroke.
+    pub fn record_changes(&mut self, ctx: &egui::Context) {
+        let focused = ctx.memory(|memory| memory.focused());
+        let editing = focused.is_some() && focused == self.audit_focus;
+        self.audit_focus = focused;
+        if editing { return }
+
+        self.microplate.audit_changes(&mut self.audit_snapshot, &self.settings.operator);
+    }
+
+    pub fn audit_trail_window(&mut self, ctx: &egui::Context) {
+        let mut export = false;
+        let audit_trail = &self.microplate.audit_trail;
+
+        egui::Window::new(tr("audit-trail"))
+            .id(Id::new("Audit Trail"))
+            .open(&mut self.show_audit_trail)
+            .collapsible(false)
+            .default_width(560.0)
+            .default_height(400.0)
+            .frame(egui::Frame::window(&ctx.style()).inner_margin(10.0))
+            .show(ctx, |ui| {
+                if audit_trail.is_empty() {
+                    ui.label(tr("audit-trail-empty"));
+                    return
+                }
+
+                let button = ui.button(tr("export-csv"));
+                Self::dashed_outline(ui, &button);
+                export = button.clicked();
+                ui.add_space(10.0);
+
+                TableBuilder::new(ui)
+                    .striped(true)
+                    .column(Column::auto().at_least(120.0))
+                    .column(Column::auto().at_least(80.0))
+                    .column(Column::remainder())
+                    .stick_to_bottom(true)
+                    .header(20.0, |mut header| {
+                        for id in ["audit-time", "operator", "audit-change"] {
+                            header.col(|ui| { ui.label(RichText::new(tr(id)).strong()); });
+                        }
+                    })
+                    .body(|body| {
+                        body.rows(18.0, audit_trail.len(), |mut row| {
+                            let entry = &audit_trail[row.index()];
+                            row.col(|ui| { ui.label(entry.display_time()); });
+                            row.col(|ui| { ui.label(&entry.operator); });
+                            row.col(|ui| { ui.label(entry.action.description()); });
+                        });
+                    });
+            });
+
+        if !export { return }
+        if save_file(&format!("{} Audit Trail", self.microplate.name), ("CSV", &["csv"]), self.audit_trail_csv().as_bytes()).is_err() {
+            self.notifications.push(SerdeError::CantWriteFile);
+        }
+    }
+
+    fn audit_trail_csv(&self) -> String {
+        let mut csv = csv_row(&[tr("audit-time"), tr("operator"), tr("audit-change")]);
+        for entry in &self.microplate.audit_trail {
+            csv.push_str(&csv_row(&[entry.time.clone(), entry.operator.clone(), entry.action.description()]));
+        }
+        csv
+    }
+}
+
+pub fn csv_row(cells: &[String]) -> String {
+    let cells: Vec<String> = cells.iter().map(|cell| format!("\"{}\"", cell.replace('"', "\"\""))).collect();
+    format!("{}\n", cells.join(","))
+}
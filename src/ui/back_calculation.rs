@@ -0,0 +1,96 @@
+use eframe::egui::{self, Grid, Id, RichText, Ui};
+
+use crate::{app::SerdeError, files::save_file, i18n::*, logistic_regression::*, ui::{audit::csv_row, plot::FLAG_COLOR}, Elisa};
+
+impl Elisa {
+    pub fn back_calculation_button(&mut self, ui: &mut Ui) {
+        if self.regression.is_none() { return }
+
+        let button = ui.button(RichText::new(tr("back-calculated-standards")));
+        Self::dashed_outline(ui, &button);
+        if button.clicked() {
+            self.show_back_calculation = !self.show_back_calculation;
+        }
+    }
+
+    /// Each standard read back through the curve, with the ones outside of the recovery range flagged
+    pub fn back_calculation_window(&mut self, ctx: &egui::Context) {
+        let Some(regression) = &self.regression else { return };
+        let standards = regression.back_calculated_standards();
+        let raw_values = self.settings.plot.raw_values;
+        let offset = if raw_values { regression.blank_offset() } else { 0.0 };
+        let mut export = false;
+
+        egui::Window::new(tr("back-calculated-standards"))
+            .id(Id::new("Back-calculated Standards"))
+            .open(&mut self.show_back_calculation)
+            .resizable(false)
+            .collapsible(false)
+            .frame(egui::Frame::window(&ctx.style()).inner_margin(10.0))
+            .show(ctx, |ui| {
+                Grid::new("Back-calculated Standards Grid").num_columns(5).spacing([20.0, 4.0]).striped(true).show(ui, |ui| {
+                    for id in ["sample-standard", "concentration", if raw_values { "raw-signal" } else { "raw-corrected" }, "backfit", "recovery-percent"] {
+                        ui.label(RichText::new(tr(id)).strong());
+                    }
+                    ui.end_row();
+                    for (i, standard) in standards.iter().enumerate() {
+                        let color = (!standard.passed()).then_some(FLAG_COLOR);
+                        let cell = |text: String| match color {
+                            Some(color) => RichText::new(text).color(color),
+                            None if standard.anchor => RichText::new(text).weak(),
+                            None => RichText::new(text),
+                        };
+                        let name = tr_args("standard-number", &[("number", (i + 1).into())]);
+                        ui.label(cell(if standard.anchor { format!("{name} ({})", tr("anchor")) } else { name }));
+                        ui.label(cell(format_value(standard.concentration)));
+                        ui.label(cell(format_value(standard.measurement + offset)));
+                        ui.label(cell(format_value(standard.backfit)));
+                        ui.label(cell(format_decimal(standard.recovery, 1)));
+                        ui.end_row();
+                    }
+                });
+                ui.add_space(10.0);
+
+                let accepted = standards.iter().filter(|standard| !standard.anchor);
+                let count = accepted.clone().count();
+                let passed = accepted.filter(|standard| standard.passed()).count();
+                let summary = tr_args("back-calculated-summary", &[
+                    ("passed", passed.into()),
+                    ("count", count.into()),
+                    ("min", format_number(*RECOVERY_RANGE.start()).into()),
+                    ("max", format_number(*RECOVERY_RANGE.end()).into()),
+                ]);
+                let summary = RichText::new(summary).size(11.0);
+                ui.label(if passed < count { summary.color(FLAG_COLOR) } else { summary });
+                ui.add_space(10.0);
+
+                let button = ui.button(tr("save-as-csv"));
+                Self::dashed_outline(ui, &button);
+                export = button.clicked();
+            });
+
+        if export {
+            let csv = back_calculation_csv(&standards, raw_values, offset);
+            if save_file(&format!("{} Standards", self.microplate.name), ("CSV", &["csv"]), csv.as_bytes()).is_err() {
+                self.notifications.push(SerdeError::CantWriteFile);
+            }
+        }
+    }
+}
+
+fn back_calculation_csv(standards: &[BackCalculatedStandard], raw_values: bool, offset: f64) -> String {
+    let header = ["sample-standard", "concentration", if raw_values { "raw-signal" } else { "raw-corrected" }, "backfit", "recovery-percent", "back-calculated-passed", "anchor"];
+    let mut csv = csv_row(&header.map(tr));
+    for (i, standard) in standards.iter().enumerate() {
+        csv.push_str(&csv_row(&[
+            tr_args("standard-number", &[("number", (i + 1).into())]),
+            format_value(standard.concentration),
+            format_value(standard.measurement + offset),
+            format_value(standard.backfit),
+            format_decimal(standard.recovery, 1),
+            tr(if standard.passed() { "yes" } else { "no" }),
+            tr(if standard.anchor { "yes" } else { "no" }),
+        ]));
+    }
+    csv
+}
@@ -0,0 +1,211 @@
+use eframe::egui::{self, DragValue, Grid, Id, ProgressBar, RichText, ScrollArea, Ui};
+
+use crate::{bootstrap::*, i18n::*, logistic_regression::*, Elisa};
+
+const PARAMETERS: [&str; 4] = ["a", "b", "c", "d"];
+
+/// Refits on a thread of their own, the window only watches the progress
+#[cfg(not(target_arch = "wasm32"))]
+pub struct BootstrapJob {
+    total: usize,
+    progress: std::sync::Arc<std::sync::atomic::AtomicUsize>,
+    cancelled: std::sync::Arc<std::sync::atomic::AtomicBool>,
+    result: std::sync::mpsc::Receiver<Option<BootstrapResult>>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl BootstrapJob {
+    pub fn start(ctx: &egui::Context, regression: Regression, total: usize, seed: u64) -> Self {
+        use std::sync::{atomic::*, mpsc, Arc};
+
+        let progress = Arc::new(AtomicUsize::new(0));
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let (sender, result) = mpsc::channel();
+        let (thread_progress, thread_cancelled, ctx) = (progress.clone(), cancelled.clone(), ctx.clone());
+        std::thread::spawn(move || {
+            let threads = std::thread::available_parallelism().map_or(1, |threads| threads.get());
+            let mut run = BootstrapRun::new(regression, total, seed);
+            while run.step_parallel(threads) {
+                if thread_cancelled.load(Ordering::Relaxed) { return }
+                thread_progress.store(run.done(), Ordering::Relaxed);
+                ctx.request_repaint();
+            }
+            // the window may be gone by now
+            let _ = sender.send(run.result());
+            ctx.request_repaint();
+        });
+        Self { total, progress, cancelled, result }
+    }
+
+    /// Refits done and the number asked for
+    pub fn progress(&self) -> (usize, usize) {
+        (self.progress.load(std::sync::atomic::Ordering::Relaxed), self.total)
+    }
+
+    /// Some once the thread is finished, with None if no refit succeeded
+    pub fn poll(&mut self, _ctx: &egui::Context) -> Option<Option<BootstrapResult>> {
+        use std::sync::mpsc::TryRecvError;
+
+        match self.result.try_recv() {
+            Ok(result) => Some(result),
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => Some(None),
+        }
+    }
+
+    pub fn cancel(self) {
+        self.cancelled.store(true, std::sync::atomic::Ordering::Relaxed);
+    }
+}
+
+/// No threads in the browser, a few refits run every frame instead
+#[cfg(target_arch = "wasm32")]
+pub struct BootstrapJob {
+    run: BootstrapRun,
+}
+
+#[cfg(target_arch = "wasm32")]
+impl BootstrapJob {
+    const FITS_PER_FRAME: usize = 2;
+
+    pub fn start(ctx: &egui::Context, regression: Regression, total: usize, seed: u64) -> Self {
+        ctx.request_repaint();
+        Self { run: BootstrapRun::new(regression, total, seed) }
+    }
+
+    pub fn progress(&self) -> (usize, usize) {
+        (self.run.done(), self.run.total)
+    }
+
+    pub fn poll(&mut self, ctx: &egui::Context) -> Option<Option<BootstrapResult>> {
+        for _ in 0..Self::FITS_PER_FRAME {
+            if !self.run.step() { return Some(self.run.result()) }
+        }
+        ctx.request_repaint();
+        None
+    }
+
+    pub fn cancel(self) {}
+}
+
+impl Elisa {
+    pub fn bootstrap_button(&mut self, ui: &mut Ui) {
+        if self.regression.is_none() { return }
+
+        let button = ui.button(RichText::new(tr("bootstrap")));
+        Self::dashed_outline(ui, &button);
+        let button = button.on_hover_text(tr("bootstrap-hover"));
+        if button.clicked() {
+            self.show_bootstrap = !self.show_bootstrap;
+        }
+    }
+
+    /// Checks on a running bootstrap, also while the window is closed
+    fn poll_bootstrap(&mut self, ctx: &egui::Context) {
+        let Some(job) = &mut self.bootstrap else { return };
+        let Some(result) = job.poll(ctx) else { return };
+        self.bootstrap = None;
+        match result {
+            Some(result) => self.bootstrap_result = Some(result),
+            None => self.notifications.push(RegressionError::DidNotConverge),
+        }
+    }
+
+    /// Empirical 95 % intervals from refitting the curve to resampled replicates of the standards
+    pub fn bootstrap_window(&mut self, ctx: &egui::Context) {
+        self.poll_bootstrap(ctx);
+        let Some(regression) = &self.regression else { return };
+
+        let Self { bootstrap, bootstrap_result, bootstrap_resamples, .. } = self;
+        let mut start = false;
+        let mut cancel = false;
+
+        egui::Window::new(tr("bootstrap"))
+            .id(Id::new("Bootstrap"))
+            .open(&mut self.show_bootstrap)
+            .resizable(false)
+            .collapsible(false)
+            .frame(egui::Frame::window(&ctx.style()).inner_margin(10.0))
+            .show(ctx, |ui| {
+                let replicates = has_replicates(regression);
+                if !replicates {
+                    ui.label(tr("bootstrap-no-replicates"));
+                    ui.add_space(10.0);
+                }
+
+                ui.horizontal(|ui| {
+                    ui.add_enabled_ui(bootstrap.is_none(), |ui| {
+                        ui.label(tr("resamples"));
+                        ui.add(DragValue::new(bootstrap_resamples).range(20..=10_000).speed(10.0));
+                    });
+                    ui.add_space(10.0);
+                    match bootstrap {
+                        Some(_) => cancel = ui.button(tr("cancel")).clicked(),
+                        None => {
+                            let button = ui.add_enabled(replicates, egui::Button::new(tr("start-bootstrap")));
+                            Self::dashed_outline(ui, &button);
+                            start = button.clicked();
+                        },
+                    }
+                });
+                if let Some(job) = bootstrap {
+                    let (done, total) = job.progress();
+                    ui.add_space(10.0);
+                    ui.add(ProgressBar::new(done as f32 / total as f32)
+                        .text(tr_args("bootstrap-progress", &[("done", done.into()), ("total", total.into())])));
+                }
+
+                // a result of an earlier fit would be misleading
+                let Some(result) = bootstrap_result.as_ref()
+                    .filter(|result| result.fitted_abcd == regression.fitted_abcd && result.concentrations.len() == regression.unknowns.len())
+                else { return };
+
+                ui.add_space(10.0);
+                ui.label(RichText::new(tr("parameters")).strong());
+                let (a, b, c, d) = regression.fitted_abcd;
+                Grid::new("Bootstrap Parameter Grid").num_columns(3).spacing([20.0, 4.0]).striped(true).show(ui, |ui| {
+                    ui.label("");
+                    ui.label(tr("estimate"));
+                    ui.label(tr("confidence-interval"));
+                    ui.end_row();
+                    for ((name, value), interval) in PARAMETERS.iter().zip([a, b, c, d]).zip(result.parameters) {
+                        ui.label(*name);
+                        ui.label(format_value(value));
+                        ui.label(format_interval(interval));
+                        ui.end_row();
+                    }
+                });
+                ui.add_space(10.0);
+
+                if !regression.unknowns.is_empty() {
+                    ui.label(RichText::new(tr("unknowns")).strong());
+                    ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                        Grid::new("Bootstrap Unknown Grid").num_columns(3).spacing([20.0, 4.0]).striped(true).show(ui, |ui| {
+                            ui.label("");
+                            ui.label(tr("concentration"));
+                            ui.label(tr("confidence-interval"));
+                            ui.end_row();
+                            for (unknown, interval) in regression.unknowns.iter().zip(&result.concentrations) {
+                                ui.label(unknown.name());
+                                ui.label(unknown.concentration_text());
+                                ui.label(interval.map(format_interval).unwrap_or_else(|| "–".to_string()));
+                                ui.end_row();
+                            }
+                        });
+                    });
+                    ui.add_space(10.0);
+                }
+                ui.label(RichText::new(tr_args("bootstrap-summary", &[("resamples", result.resamples.into()), ("failed", result.failed.into())])).size(11.0));
+            });
+
+        if cancel {
+            if let Some(job) = bootstrap.take() {
+                job.cancel();
+            }
+        }
+        if start {
+            let seed = chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default() as u64;
+            *bootstrap = Some(BootstrapJob::start(ctx, regression.clone(), *bootstrap_resamples, seed));
+        }
+    }
+}
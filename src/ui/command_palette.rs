@@ -0,0 +1,266 @@
+use eframe::egui::{self, Align2, Id, Key, KeyboardShortcut, Modifiers, RichText, ScrollArea, SelectableLabel, TextEdit, UserData};
+
+use crate::{files::*, i18n::*, ElisaTab, Elisa};
+
+pub const PALETTE_SHORTCUT: KeyboardShortcut = KeyboardShortcut::new(Modifiers::COMMAND, Key::K);
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Command {
+    Save,
+    Load,
+    OpenMeasurements,
+    LiveAcquisition,
+    ExportLayout,
+    ImportLayout,
+    DuplicatePlate,
+    FitCurve,
+    ShowPlate,
+    ShowResults,
+    SaveAsPdf,
+    SaveAsPng,
+    SaveAsCsv,
+    CopyResults,
+    SavePlotData,
+    SaveAsJson,
+    #[cfg(not(target_arch = "wasm32"))]
+    PrintReport,
+    FitOptions,
+    AdjustCurve,
+    Diagnostics,
+    Bootstrap,
+    ComparePlates,
+    Simulation,
+    AuditTrail,
+    Log,
+    Settings,
+    KeyboardShortcuts,
+    SelfTest,
+}
+
+impl Command {
+    pub const ALL: &[Self] = &[
+        Self::Save, Self::Load, Self::OpenMeasurements, Self::LiveAcquisition, Self::ExportLayout, Self::ImportLayout, Self::DuplicatePlate,
+        Self::FitCurve, Self::ShowPlate, Self::ShowResults,
+        Self::SaveAsPdf, Self::SaveAsPng, Self::SaveAsCsv, Self::CopyResults, Self::SavePlotData, Self::SaveAsJson,
+        #[cfg(not(target_arch = "wasm32"))]
+        Self::PrintReport,
+        Self::FitOptions, Self::AdjustCurve, Self::Diagnostics, Self::Bootstrap, Self::ComparePlates, Self::Simulation,
+        Self::AuditTrail, Self::Log, Self::Settings, Self::KeyboardShortcuts,
+    ];
+    /// Only offered when the query is their full name
+    pub const HIDDEN: &[Self] = &[Self::SelfTest];
+
+    pub fn description(&self) -> String {
+        let id = match self {
+            Self::Save => "save",
+            Self::Load => "load",
+            Self::OpenMeasurements => "open-measurements",
+            Self::LiveAcquisition => "live-acquisition",
+            Self::ExportLayout => "export-layout",
+            Self::ImportLayout => "import-layout",
+            Self::DuplicatePlate => "duplicate-plate",
+            Self::FitCurve => "fit-curve",
+            Self::ShowPlate => "show-plate",
+            Self::ShowResults => "show-results",
+            Self::SaveAsPdf => "save-as-pdf",
+            Self::SaveAsPng => "save-as-png",
+            Self::SaveAsCsv => "save-as-csv",
+            Self::CopyResults => "copy-results",
+            Self::SavePlotData => "save-plot-data",
+            Self::SaveAsJson => "save-as-json",
+            #[cfg(not(target_arch = "wasm32"))]
+            Self::PrintReport => "print-report",
+            Self::FitOptions => "fit-options",
+            Self::AdjustCurve => "adjust-curve",
+            Self::Diagnostics => "fit-diagnostics",
+            Self::Bootstrap => "bootstrap",
+            Self::ComparePlates => "compare-plates",
+            Self::Simulation => "simulation",
+            Self::AuditTrail => "audit-trail",
+            Self::Log => "log",
+            Self::Settings => "settings",
+            Self::KeyboardShortcuts => "keyboard-shortcuts",
+            Self::SelfTest => "self-test",
+        };
+        tr(id)
+    }
+}
+
+/// How well the query matches, None unless its letters appear in order.
+/// Letters in a row and at the start of words count more, so "pdf" ranks "Save as PDF" first.
+pub fn fuzzy_score(query: &str, text: &str) -> Option<i32> {
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut position = 0;
+    let mut previous: Option<usize> = None;
+    for letter in query.to_lowercase().chars().filter(|letter| !letter.is_whitespace()) {
+        let found = position + text[position..].iter().position(|&c| c == letter)?;
+        score += 1;
+        if previous.is_some_and(|previous| previous + 1 == found) { score += 5 }
+        if found == 0 || !text[found - 1].is_alphanumeric() { score += 3 }
+        score -= (found - position).min(3) as i32; // skipped letters
+        previous = Some(found);
+        position = found + 1;
+    }
+    Some(score)
+}
+
+impl Elisa {
+    fn command_enabled(&self, command: Command) -> bool {
+        use Command::*;
+
+        let fitted = self.regression.is_some();
+        let plot_shown = self.current_tab == ElisaTab::Result && self.plot_response.is_some();
+        match command {
+            OpenMeasurements | ImportLayout => !self.microplate.locked,
+            ShowPlate => self.current_tab == ElisaTab::Result,
+            ShowResults => fitted && self.current_tab == ElisaTab::Edit,
+            SaveAsPdf => plot_shown,
+            #[cfg(not(target_arch = "wasm32"))]
+            PrintReport => plot_shown,
+            SaveAsPng | SaveAsCsv | CopyResults | SavePlotData | SaveAsJson | AdjustCurve | Diagnostics | Bootstrap => fitted,
+            _ => true,
+        }
+    }
+
+    /// Matching commands, the best match first
+    fn palette_matches(&self) -> Vec<Command> {
+        let mut matches: Vec<(Command, i32)> = Command::ALL.iter().copied()
+            .filter(|&command| self.command_enabled(command))
+            .filter_map(|command| Some((command, fuzzy_score(&self.command_query, &command.description())?)))
+            .collect();
+        matches.sort_by_key(|&(_, score)| -score);
+        let query = self.command_query.trim().to_lowercase();
+        let hidden = Command::HIDDEN.iter().copied().filter(|command| command.description().to_lowercase() == query);
+        hidden.chain(matches.into_iter().map(|(command, _)| command)).collect()
+    }
+
+    /// Opened with Ctrl+K (⌘K on macOS), arrows pick a command and Enter runs it
+    pub fn command_palette(&mut self, ctx: &egui::Context) {
+        // run a frame after the palette closed, so it isn't in the screenshot of a report
+        if let Some(command) = self.pending_command.take() {
+            self.run_command(ctx, command);
+        }
+
+        if ctx.input_mut(|i| i.consume_shortcut(&PALETTE_SHORTCUT)) {
+            self.show_command_palette = !self.show_command_palette;
+            self.command_query.clear();
+            self.command_selected = 0;
+        }
+        if !self.show_command_palette { return }
+
+        let matches = self.palette_matches();
+        let (up, down, enter, escape) = ctx.input_mut(|i| (
+            i.consume_key(Modifiers::NONE, Key::ArrowUp),
+            i.consume_key(Modifiers::NONE, Key::ArrowDown),
+            i.consume_key(Modifiers::NONE, Key::Enter),
+            i.consume_key(Modifiers::NONE, Key::Escape),
+        ));
+        if down { self.command_selected += 1 }
+        if up { self.command_selected = self.command_selected.saturating_sub(1) }
+        self.command_selected = self.command_selected.min(matches.len().saturating_sub(1));
+
+        let Self { command_query, command_selected, .. } = self;
+        let mut chosen = enter.then(|| matches.get(*command_selected).copied()).flatten();
+
+        let window = egui::Window::new(tr("command-palette"))
+            .id(Id::new("Command Palette"))
+            .title_bar(false)
+            .anchor(Align2::CENTER_TOP, [0.0, 80.0])
+            .resizable(false)
+            .frame(egui::Frame::window(&ctx.style()).inner_margin(10.0))
+            .show(ctx, |ui| {
+                ui.set_width(340.0);
+                let text_edit = ui.add(TextEdit::singleline(command_query).hint_text(tr("command-palette-hint")).desired_width(f32::INFINITY));
+                text_edit.request_focus();
+                if text_edit.changed() {
+                    *command_selected = 0;
+                }
+                ui.add_space(5.0);
+
+                if matches.is_empty() {
+                    ui.label(RichText::new(tr("no-matching-commands")).size(11.0));
+                }
+                ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                    for (i, &command) in matches.iter().enumerate() {
+                        let label = ui.add_sized([ui.available_width(), 20.0], SelectableLabel::new(i == *command_selected, command.description()));
+                        if i == *command_selected && (up || down) {
+                            label.scroll_to_me(None);
+                        }
+                        if label.clicked() {
+                            chosen = Some(command);
+                        }
+                    }
+                });
+            });
+
+        let clicked_elsewhere = window.is_some_and(|window| window.response.clicked_elsewhere());
+        if escape || clicked_elsewhere || chosen.is_some() {
+            self.show_command_palette = false;
+        }
+        if chosen.is_some() {
+            self.pending_command = chosen;
+            ctx.request_repaint();
+        }
+    }
+
+    fn run_command(&mut self, ctx: &egui::Context, command: Command) {
+        use Command::*;
+
+        if !self.command_enabled(command) { return }
+        match command {
+            Save => self.save_assay(),
+            Load => self.opened_files.pick(ctx, FileAction::LoadAssay, &[("Elisa".into(), &PROJECT_EXTENSIONS)]),
+            OpenMeasurements => {
+                let filters = [(tr("excel-spreadsheet"), &["xlsx"][..]), (tr("plate-reader-export"), &["txt", "csv"][..])];
+                self.opened_files.pick(ctx, FileAction::Measurements, &filters);
+            },
+            LiveAcquisition => self.show_acquisition = true,
+            ExportLayout => self.export_layout(),
+            ImportLayout => self.opened_files.pick(ctx, FileAction::ImportLayout, &[("Plate Layout".into(), &["json"])]),
+            DuplicatePlate => {
+                if self.microplate.samples.iter().any(|sample| sample.value.is_some()) {
+                    self.duplicate_modal = true;
+                    self.current_tab = ElisaTab::Edit;
+                } else {
+                    self.duplicate_plate();
+                }
+            },
+            FitCurve => {
+                if self.fit_curve() {
+                    self.current_tab = ElisaTab::Result;
+                }
+            },
+            ShowPlate => self.current_tab = ElisaTab::Edit,
+            ShowResults => self.current_tab = ElisaTab::Result,
+            SaveAsPdf => {
+                ctx.send_viewport_cmd(egui::ViewportCommand::Screenshot(UserData::default()));
+                self.pdf_report = true;
+            },
+            #[cfg(not(target_arch = "wasm32"))]
+            PrintReport => {
+                ctx.send_viewport_cmd(egui::ViewportCommand::Screenshot(UserData::default()));
+                self.print_report = true;
+            },
+            SaveAsPng => self.show_png_export = true,
+            SaveAsCsv => self.export_results_csv(),
+            CopyResults => self.copy_results(ctx),
+            SavePlotData => self.export_plot_data_csv(),
+            SaveAsJson => self.export_analysis_json(),
+            FitOptions => self.show_fit_options = true,
+            AdjustCurve => self.show_manual_fit = true,
+            Diagnostics => self.show_diagnostics = true,
+            Bootstrap => self.show_bootstrap = true,
+            ComparePlates => self.show_compare = true,
+            Simulation => self.show_simulation = true,
+            AuditTrail => self.show_audit_trail = true,
+            Log => self.show_log_console = true,
+            Settings => self.show_settings = true,
+            KeyboardShortcuts => self.show_shortcuts = true,
+            SelfTest => {
+                self.self_test.clear();
+                self.show_self_test = true;
+            },
+        }
+    }
+}
@@ -0,0 +1,150 @@
+use eframe::egui::{self, Grid, Id, RichText, ScrollArea, Ui};
+
+use crate::{app::SerdeError, files::*, i18n::*, layout::ALPHABET, logistic_regression::*, plate_diff::*, ui::plot::FLAG_COLOR, Elisa};
+
+impl Elisa {
+    pub fn compare_plates_button(&mut self, ui: &mut Ui) {
+        let button = ui.button(RichText::new(tr("compare-plates")));
+        Self::dashed_outline(ui, &button);
+        let button = button.on_hover_text(tr("compare-plates-hover"));
+        if button.clicked() {
+            self.show_compare = !self.show_compare;
+        }
+    }
+
+    pub fn open_compare_plate(&mut self, file: &OpenedFile) {
+        match serde_json::from_slice::<Microplate>(&file.bytes) {
+            Ok(microplate) => {
+                self.compare_plate = Some(microplate);
+                self.plate_diff = None;
+            },
+            Err(_) => self.notifications.push(SerdeError::CantDeserialize),
+        }
+    }
+
+    /// Differences of the values and concentrations to another plate, by default the one this plate was duplicated from
+    pub fn compare_window(&mut self, ctx: &egui::Context) {
+        if !self.show_compare { return }
+
+        // both plates are fitted for the comparison, only again once the current plate changes
+        if let Some(other) = &self.compare_plate {
+            let fingerprint = self.microplate.fit_fingerprint();
+            if self.plate_diff.as_ref().is_none_or(|(diffed, _)| *diffed != fingerprint) {
+                self.plate_diff = Some((fingerprint, compare_plates(&self.microplate, other)));
+            }
+        }
+
+        let microplate = &self.microplate;
+        let other = &mut self.compare_plate;
+        let diff = &mut self.plate_diff;
+        let cv_threshold = self.settings.cv_threshold;
+        let mut load_file = false;
+
+        egui::Window::new(tr("compare-plates"))
+            .id(Id::new("Compare Plates"))
+            .open(&mut self.show_compare)
+            .resizable(false)
+            .collapsible(false)
+            .frame(egui::Frame::window(&ctx.style()).inner_margin(10.0))
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    let button = ui.button(tr("compare-from-file")).on_hover_text(tr("compare-from-file-hover"));
+                    Self::dashed_outline(ui, &button);
+                    load_file = button.clicked();
+                    ui.add_space(10.0);
+                    if ui.add_enabled(other.is_some(), egui::Button::new(tr("clear"))).clicked() {
+                        *other = None;
+                        *diff = None;
+                    }
+                });
+                ui.add_space(10.0);
+
+                let (Some(other), Some((_, diff))) = (other.as_ref(), diff.as_ref()) else {
+                    ui.label(tr("compare-none"));
+                    return
+                };
+                let name = if other.name.is_empty() { tr("unnamed-plate") } else { other.name.clone() };
+                ui.label(tr_args("compare-source", &[("plate", name.into())]));
+                ui.add_space(10.0);
+
+                let diff = match diff {
+                    Ok(diff) => diff,
+                    Err(error) => {
+                        ui.label(RichText::new(error.to_string()).color(FLAG_COLOR));
+                        return
+                    },
+                };
+
+                ui.label(RichText::new(tr("compare-wells")).strong());
+                ui.add_space(5.0);
+                ScrollArea::both().id_salt("Compare Wells").max_width(600.0).max_height(250.0).show(ui, |ui| {
+                    Grid::new("Compare Wells Grid").spacing([6.0, 2.0]).striped(true).show(ui, |ui| {
+                        ui.label("");
+                        for column in 1..=microplate.width {
+                            ui.label(RichText::new(column.to_string()).size(11.0).strong());
+                        }
+                        ui.end_row();
+                        for row in 0..microplate.height {
+                            ui.label(RichText::new(ALPHABET[row % 26].to_string()).size(11.0).strong());
+                            for column in 0..microplate.width {
+                                let index = column * microplate.height + row;
+                                let Some(well) = diff.wells.iter().find(|well| well.well == index) else {
+                                    ui.label(RichText::new("·").size(11.0));
+                                    continue
+                                };
+                                let text = match well.percent {
+                                    Some(percent) if percent > 0.0 => format!("+{}", format_decimal(percent, 0)),
+                                    Some(percent) => format_decimal(percent, 0),
+                                    None => "–".to_string(),
+                                };
+                                let flagged = well.percent.zip(cv_threshold).is_some_and(|(percent, threshold)| percent.abs() > threshold);
+                                let text = RichText::new(text).size(11.0);
+                                let value = |value: Option<f64>| value.map(format_value).unwrap_or_else(|| tr("not-available"));
+                                ui.label(if flagged { text.color(FLAG_COLOR) } else { text }).on_hover_text(tr_args("compare-well-hover", &[
+                                    ("well", microplate.well_name(index).into()),
+                                    ("current", value(well.current).into()),
+                                    ("other", value(well.other).into()),
+                                    ("difference", value(well.difference).into()),
+                                ]));
+                            }
+                            ui.end_row();
+                        }
+                    });
+                });
+                if let Some(percent) = diff.mean_well_percent {
+                    ui.label(RichText::new(tr_args("compare-mean-difference", &[("percent", format_decimal(percent, 1).into())])).size(11.0));
+                }
+                ui.add_space(10.0);
+
+                ui.label(RichText::new(tr("compare-concentrations")).strong());
+                ui.add_space(5.0);
+                if diff.groups.is_empty() {
+                    ui.label(tr("compare-no-concentrations"));
+                    return
+                }
+                ScrollArea::vertical().id_salt("Compare Groups").max_height(250.0).show(ui, |ui| {
+                    Grid::new("Compare Groups Grid").num_columns(5).spacing([20.0, 4.0]).striped(true).show(ui, |ui| {
+                        for header in [SampleType::Unknown.name(), tr("compare-current"), tr("compare-other"), "Δ".to_string(), "Δ %".to_string()] {
+                            ui.label(RichText::new(header).strong());
+                        }
+                        ui.end_row();
+                        for group in &diff.groups {
+                            let value = |value: Option<f64>| value.map(format_value).unwrap_or_else(|| "–".to_string());
+                            ui.label(&group.name);
+                            ui.label(value(group.current));
+                            ui.label(value(group.other));
+                            ui.label(value(group.difference));
+                            let flagged = group.percent.zip(cv_threshold).is_some_and(|(percent, threshold)| percent.abs() > threshold);
+                            let percent = RichText::new(group.percent.map(|percent| format_decimal(percent, 1)).unwrap_or_default());
+                            ui.label(if flagged { percent.color(FLAG_COLOR) } else { percent });
+                            ui.end_row();
+                        }
+                    });
+                });
+            });
+
+        if load_file {
+            self.opened_files.pick(ctx, FileAction::ComparePlate, &[("Elisa".into(), &PROJECT_EXTENSIONS)]);
+        }
+    }
+}
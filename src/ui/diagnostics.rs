@@ -0,0 +1,136 @@
+use eframe::egui::{self, Color32, Grid, Id, RichText, Ui};
+
+use crate::{i18n::*, logistic_regression::*, ui::plot::FLAG_COLOR, Elisa};
+
+const PARAMETERS: [&str; 4] = ["a", "b", "c", "d"];
+
+impl Elisa {
+    pub fn diagnostics_button(&mut self, ui: &mut Ui) {
+        if self.regression.is_none() { return }
+
+        let button = ui.button(RichText::new(tr("fit-diagnostics")));
+        Self::dashed_outline(ui, &button);
+        if button.clicked() {
+            self.show_diagnostics = !self.show_diagnostics;
+        }
+    }
+
+    /// Banner above the parameters when the optimizer ran out of iterations
+    pub fn convergence_warning(&mut self, ui: &mut Ui) {
+        let Some(regression) = &self.regression else { return };
+        if regression.diagnostics.converged { return }
+
+        egui::Frame::new()
+            .fill(FLAG_COLOR.gamma_multiply(0.1))
+            .stroke((1.0, FLAG_COLOR))
+            .inner_margin(8.0)
+            .show(ui, |ui| {
+                ui.set_width(ui.available_width());
+                ui.horizontal_wrapped(|ui| {
+                    ui.label(RichText::new(tr("fit-not-converged")).color(FLAG_COLOR));
+                    let button = ui.button(tr("details"));
+                    Self::dashed_outline(ui, &button);
+                    if button.clicked() {
+                        self.show_diagnostics = true;
+                    }
+                });
+            });
+        ui.add_space(20.0);
+    }
+
+    pub fn fit_diagnostics_window(&mut self, ctx: &egui::Context) {
+        let Some(regression) = &self.regression else { return };
+        let FitDiagnostics { iterations, gradient_norm, converged, correlation, starts, best_start, duration, .. } = &regression.diagnostics;
+        let max_iterations = regression.options.max_iterations.unwrap_or(MAX_ITERATIONS);
+        let estimates = regression.diagnostics.parameter_estimates(regression.fitted_abcd);
+        let log_c = regression.diagnostics.log_c_estimate(regression.fitted_abcd.2);
+
+        egui::Window::new(tr("fit-diagnostics"))
+            .id(Id::new("Fit Diagnostics"))
+            .open(&mut self.show_diagnostics)
+            .resizable(false)
+            .collapsible(false)
+            .frame(egui::Frame::window(&ctx.style()).inner_margin(10.0))
+            .show(ctx, |ui| {
+                Grid::new("Convergence Grid").num_columns(2).spacing([20.0, 4.0]).show(ui, |ui| {
+                    ui.label(tr("iterations"));
+                    ui.label(tr_args("iterations-of", &[("iterations", (*iterations).into()), ("max", max_iterations.into())]));
+                    ui.end_row();
+                    ui.label(tr("gradient-norm"));
+                    ui.label(format_value(*gradient_norm));
+                    ui.end_row();
+                    ui.label(tr("converged"));
+                    if *converged {
+                        ui.label(tr("yes"));
+                    } else {
+                        ui.label(RichText::new(tr("no")).color(FLAG_COLOR));
+                    }
+                    ui.end_row();
+                    if *starts > 0 {
+                        ui.label(tr("winning-start")).on_hover_text(tr("winning-start-hover"));
+                        ui.label(tr_args("start-of", &[("start", (best_start + 1).into()), ("starts", (*starts).into())]));
+                        ui.end_row();
+                    }
+                    if let Some(duration) = duration {
+                        ui.label(tr("fit-duration")).on_hover_text(tr("fit-duration-hover"));
+                        ui.label(format!("{} ms", format_number((duration.as_secs_f64() * 1e4).round() / 10.0)));
+                        ui.end_row();
+                    }
+                });
+                ui.add_space(10.0);
+
+                let (Some(estimates), Some(correlation)) = (estimates, correlation) else {
+                    ui.label(tr("standard-errors-unavailable"));
+                    return
+                };
+
+                ui.label(RichText::new(tr("standard-errors")).strong());
+                Grid::new("Standard Error Grid").num_columns(5).spacing([20.0, 4.0]).striped(true).show(ui, |ui| {
+                    ui.label("");
+                    ui.label(tr("estimate"));
+                    ui.label(tr("standard-error"));
+                    ui.label(tr("confidence-interval"));
+                    ui.label(tr("p-value"));
+                    ui.end_row();
+                    for (name, estimate) in PARAMETERS.iter().zip(estimates) {
+                        ui.label(*name);
+                        ui.label(format_value(estimate.value));
+                        ui.label(format_value(estimate.standard_error));
+                        ui.label(format_interval(estimate.confidence_interval));
+                        ui.label(estimate.p_value.map(format_p_value).unwrap_or_else(|| tr("parameter-fixed")));
+                        ui.end_row();
+                    }
+                    if let Some(estimate) = log_c {
+                        ui.label("log c").on_hover_text(tr("log-c-hover"));
+                        ui.label(format_value(estimate.value));
+                        ui.label(format_value(estimate.standard_error));
+                        ui.label(format_interval(estimate.confidence_interval));
+                        ui.label("–");
+                        ui.end_row();
+                    }
+                });
+                ui.label(RichText::new(tr_args("degrees-of-freedom", &[("df", regression.diagnostics.degrees_of_freedom.into())])).size(11.0));
+                ui.add_space(10.0);
+
+                ui.label(RichText::new(tr("correlation-matrix")).strong());
+                Grid::new("Correlation Grid").num_columns(5).spacing([20.0, 4.0]).striped(true).show(ui, |ui| {
+                    ui.label("");
+                    for name in PARAMETERS {
+                        ui.label(name);
+                    }
+                    ui.end_row();
+                    for (i, (name, row)) in PARAMETERS.iter().zip(correlation).enumerate() {
+                        ui.label(*name);
+                        for (j, value) in row.iter().enumerate() {
+                            // strongly correlated parameters are poorly determined by the standards
+                            let strong = i != j && value.abs() > 0.95;
+                            let color = if strong { FLAG_COLOR } else { Color32::PLACEHOLDER };
+                            ui.label(RichText::new(format_decimal(*value, 3)).color(color));
+                        }
+                        ui.end_row();
+                    }
+                });
+                ui.label(RichText::new(tr("correlation-note")).size(11.0));
+            });
+    }
+}
@@ -0,0 +1,189 @@
+use eframe::egui::{self, DragValue, Grid, Id, Modal, RichText, TextEdit};
+
+use crate::{app::*, exclusion::*, i18n::*, linear_range::DEFAULT_LINEAR_RANGE, logistic_regression::*, settings::save_settings, ui::settings::linear_range_drag_values, Elisa};
+
+pub fn sample_name(microplate: &Microplate, well: usize) -> String {
+    let sample = &microplate.samples[well];
+    match sample.typ {
+        SampleType::Standard => tr_args("standard-number", &[("number", (sample.group + 1).into())]),
+        SampleType::Unknown => tr_args("unknown-number", &[("number", (sample.group + 1).into())]),
+        typ => typ.name(),
+    }
+}
+
+impl Elisa {
+    /// Exclusion rules with a preview of the wells they catch, nothing changes until the preview is applied
+    pub fn exclusion_window(&mut self, ctx: &egui::Context) {
+        if !self.show_exclusions { return }
+
+        // follow edits to the plate and the rules, but keep what was unticked
+        let proposed = propose_exclusions(&self.microplate, &self.settings.exclusion_rules, self.settings.linear_range);
+        let preview = &mut self.exclusion_preview;
+        *preview = proposed.into_iter().map(|mut exclusion| {
+            if let Some(previous) = preview.iter().find(|previous| previous.well == exclusion.well) {
+                exclusion.accepted = previous.accepted;
+            }
+            exclusion
+        }).collect();
+
+        let locked = self.microplate.locked;
+        let microplate = &self.microplate;
+        let rules = &mut self.settings.exclusion_rules;
+        let linear_range = &mut self.settings.linear_range;
+        let mut rules_changed = false;
+        let mut apply = false;
+
+        egui::Window::new(tr("exclusions"))
+            .id(Id::new("Exclusions"))
+            .open(&mut self.show_exclusions)
+            .resizable(false)
+            .collapsible(false)
+            .frame(egui::Frame::window(&ctx.style()).inner_margin(10.0))
+            .show(ctx, |ui| {
+                if locked {
+                    ui.disable();
+                }
+
+                Grid::new("Exclusion Rules").num_columns(2).spacing([10.0, 6.0]).show(ui, |ui| {
+                    ui.label(tr("exclusion-rule-cv")).on_hover_text(tr("exclusion-rule-cv-hover"));
+                    ui.horizontal(|ui| {
+                        let mut enabled = rules.replicate_cv.is_some();
+                        rules_changed |= ui.checkbox(&mut enabled, "").changed();
+                        match (enabled, &mut rules.replicate_cv) {
+                            (true, Some(limit)) => {
+                                let drag_value = ui.add(DragValue::new(limit).speed(0.5).range(0.0..=1000.0).suffix(" %")
+                                    .custom_formatter(|n, _| format_number(n))
+                                    .custom_parser(parse_decimal));
+                                Self::dashed_outline(ui, &drag_value);
+                                rules_changed |= drag_value.changed();
+                            },
+                            (true, limit) => *limit = Some(20.0),
+                            (false, limit) => *limit = None,
+                        }
+                    });
+                    ui.end_row();
+
+                    ui.label(tr("exclusion-rule-linear-range")).on_hover_text(tr("exclusion-rule-linear-range-hover"));
+                    ui.horizontal(|ui| {
+                        rules_changed |= ui.checkbox(&mut rules.linear_range, "").changed();
+                        if !rules.linear_range { return }
+                        // the same range as in the settings
+                        match linear_range {
+                            Some((low, high)) => rules_changed |= linear_range_drag_values(ui, low, high),
+                            range => *range = Some(DEFAULT_LINEAR_RANGE),
+                        }
+                    });
+                    ui.end_row();
+                });
+                ui.add_space(10.0);
+
+                ui.label(tr("exclusion-reasons")).on_hover_text(tr("exclusion-reasons-hover"));
+                ui.add_space(4.0);
+                let mut remove = None;
+                for (i, reason) in rules.reasons.iter_mut().enumerate() {
+                    ui.horizontal(|ui| {
+                        let text_edit = ui.add(TextEdit::singleline(reason).desired_width(180.0));
+                        Self::dashed_outline(ui, &text_edit);
+                        rules_changed |= text_edit.lost_focus();
+                        let button = ui.button(tr("remove"));
+                        Self::dashed_outline(ui, &button);
+                        if button.clicked() {
+                            remove = Some(i);
+                        }
+                    });
+                }
+                if let Some(i) = remove {
+                    rules.reasons.remove(i);
+                    rules_changed = true;
+                }
+                let button = ui.button(tr("add-exclusion-reason"));
+                Self::dashed_outline(ui, &button);
+                if button.clicked() {
+                    rules.reasons.push(String::new());
+                }
+                ui.add_space(10.0);
+                ui.separator();
+                ui.add_space(10.0);
+
+                if preview.is_empty() {
+                    ui.label(tr("exclusions-none"));
+                    return
+                }
+
+                ui.label(RichText::new(tr("exclusions-proposed")).strong());
+                ui.add_space(5.0);
+                Grid::new("Exclusion Preview").num_columns(5).spacing([15.0, 4.0]).striped(true).show(ui, |ui| {
+                    ui.label("");
+                    ui.label(tr("well"));
+                    ui.label(tr("sample"));
+                    ui.label(tr("measurement"));
+                    ui.label(tr("reason"));
+                    ui.end_row();
+                    for exclusion in preview.iter_mut() {
+                        ui.checkbox(&mut exclusion.accepted, "");
+                        ui.label(microplate.well_name(exclusion.well));
+                        ui.label(sample_name(microplate, exclusion.well));
+                        ui.label(microplate.samples[exclusion.well].value.map(format_value).unwrap_or_default());
+                        ui.label(exclusion.reason.description());
+                        ui.end_row();
+                    }
+                });
+                ui.add_space(10.0);
+
+                let any_accepted = preview.iter().any(|exclusion| exclusion.accepted);
+                let button = ui.add_enabled(any_accepted, egui::Button::new(tr("apply-exclusions")));
+                Self::dashed_outline(ui, &button);
+                apply = button.clicked();
+            });
+
+        if rules_changed {
+            self.settings.exclusion_rules.reasons.retain(|reason| !reason.trim().is_empty());
+        }
+        if rules_changed && save_settings(&self.settings).is_err() {
+            self.notifications.push(SerdeError::CantWriteFile);
+        }
+
+        if !apply { return }
+        let exclusions = std::mem::take(&mut self.exclusion_preview);
+        self.microplate.apply_exclusions(&exclusions, &mut self.audit_snapshot, &self.settings.operator);
+        self.show_exclusions = false;
+        if self.fit_curve() {
+            self.current_tab = ElisaTab::Result;
+        }
+    }
+
+    /// Asks why a well excluded by hand is left out, from the reasons set up with the exclusion rules
+    pub fn exclusion_reason_modal(&mut self, ctx: &egui::Context) {
+        let Some(well) = self.exclusion_prompt else { return };
+        let mut chosen = None;
+        let mut cancel = false;
+
+        let modal = Modal::new(Id::new("Exclusion Reason")).show(ctx, |ui| {
+            ui.set_width(250.0);
+            ui.label(tr_args("exclusion-reason-prompt", &[
+                ("well", self.microplate.well_name(well).into()),
+                ("sample", sample_name(&self.microplate, well).into()),
+            ]));
+            ui.add_space(10.0);
+            for reason in &self.settings.exclusion_rules.reasons {
+                let button = ui.add_sized([ui.available_width(), 20.0], egui::Button::new(reason));
+                Self::dashed_outline(ui, &button);
+                if button.clicked() {
+                    chosen = Some(reason.clone());
+                }
+            }
+            ui.add_space(10.0);
+            ui.separator();
+            cancel = ui.button(tr("cancel")).clicked();
+        });
+
+        if let Some(reason) = chosen {
+            let sample = &mut self.microplate.samples[well];
+            sample.excluded = true;
+            sample.exclusion_reason = Some(ExclusionReason::Chosen { reason });
+            self.exclusion_prompt = None;
+        } else if cancel || modal.should_close() {
+            self.exclusion_prompt = None;
+        }
+    }
+}
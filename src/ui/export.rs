@@ -0,0 +1,385 @@
+use std::{collections::HashMap, io::Cursor};
+
+use eframe::egui::{self, epaint::{Primitive, Vertex}, pos2, Color32, Context, DragValue, ImageData, Pos2, RawInput, Rect, RichText, TextureId, Theme, ViewportId};
+use image::{ImageFormat, Rgba, RgbaImage};
+
+use crate::{app::*, cutoff::Cutoff, expression::*, files::save_file, history::mean_sd, i18n::*, json_export::AnalysisExport, logistic_regression::*, results_filter::*, settings::PlotOptions, ui::{audit::csv_row, plot::{plot_regression, PlotLayers, PlotView}}, Elisa};
+
+/// Logical pixels per inch egui assumes at a scale of 1
+const SCREEN_DPI: f32 = 96.0;
+
+/// Points of the curve in the plot data export
+const CURVE_POINTS: usize = 200;
+
+pub struct PngExport {
+    pub width: u32,
+    pub height: u32,
+    pub dpi: f32,
+}
+
+impl Default for PngExport {
+    fn default() -> Self {
+        Self {
+            width: 1500,
+            height: 1500,
+            dpi: 300.0,
+        }
+    }
+}
+
+impl Elisa {
+    pub fn png_export_window(&mut self, ctx: &egui::Context) {
+        let mut export = false;
+        let settings = &mut self.png_export;
+
+        egui::Window::new(tr("export-png"))
+            .id(egui::Id::new("Export PNG"))
+            .open(&mut self.show_png_export)
+            .resizable(false)
+            .collapsible(false)
+            .frame(egui::Frame::window(&ctx.style()).inner_margin(10.0))
+            .show(ctx, |ui| {
+                egui::Grid::new("PNG Export Grid").num_columns(2).spacing([10.0, 4.0]).show(ui, |ui| {
+                    ui.label(tr("width"));
+                    ui.add(DragValue::new(&mut settings.width).range(100..=10000).suffix(" px"));
+                    ui.end_row();
+
+                    ui.label(tr("height"));
+                    ui.add(DragValue::new(&mut settings.height).range(100..=10000).suffix(" px"));
+                    ui.end_row();
+
+                    ui.label(tr("resolution"));
+                    ui.add(DragValue::new(&mut settings.dpi).range(72.0..=1200.0).speed(1.0).suffix(" dpi"));
+                    ui.end_row();
+                });
+
+                let scale = settings.dpi / SCREEN_DPI;
+                ui.add_space(4.0);
+                let width = (settings.width as f32 / scale).round();
+                let height = (settings.height as f32 / scale).round();
+                ui.label(RichText::new(tr_args("export-png-info", &[("width", width.into()), ("height", height.into())])).size(11.0));
+                ui.add_space(10.0);
+
+                let button = ui.button(tr("export"));
+                Self::dashed_outline(ui, &button);
+                export = button.clicked();
+            });
+
+        if !export { return }
+        let Some(regression) = &self.regression else { return };
+
+        // the part of the curve that is on screen
+        let view = self.plot_bounds.map(PlotView::Fixed).unwrap_or(PlotView::Interactive);
+        let layers = PlotLayers { hidden_labels: &self.hidden_labels, reference: self.reference_curve.as_ref() };
+        let image = render_plot(regression, &self.png_export, self.settings.plot, layers, self.microplate.plot_scale, view);
+        let mut png = Vec::new();
+        let saved = image.write_to(&mut Cursor::new(&mut png), ImageFormat::Png).ok()
+            .and_then(|()| save_file(&self.microplate.name, ("png", &["png"]), &png).ok());
+        if saved.is_none() {
+            self.notifications.push(SerdeError::CantWriteFile);
+        }
+        self.show_png_export = false;
+    }
+}
+
+impl Elisa {
+    pub fn export_results_csv(&mut self) {
+        let Some(regression) = &self.regression else { return };
+        let order = sorted_unknowns(&regression.unknowns, self.results_sort);
+        let derived_columns = self.microplate.protocol.as_ref().map(|protocol| protocol.derived_columns.as_slice()).unwrap_or_default();
+        let csv = results_csv(regression, &order, self.microplate.cutoff.as_ref(), derived_columns);
+        if save_file(&format!("{} Results", self.microplate.name), ("CSV", &["csv"]), csv.as_bytes()).is_err() {
+            self.notifications.push(SerdeError::CantWriteFile);
+        }
+    }
+
+    /// The results table as shown, filtered and sorted, tab separated for pasting into Excel
+    pub fn copy_results(&self, ctx: &Context) {
+        let Some(regression) = &self.regression else { return };
+        let mut shown = filter_unknowns(&regression.unknowns, &self.results_filter);
+        sort_unknowns(&regression.unknowns, &mut shown, self.results_sort);
+        let derived_columns = self.microplate.protocol.as_ref().map(|protocol| protocol.derived_columns.as_slice()).unwrap_or_default();
+        ctx.copy_text(results_tsv(regression, &shown, self.settings.plot.raw_values, self.microplate.cutoff.as_ref(), derived_columns));
+    }
+
+    /// What the plot shows as numbers, to plot it again with Prism or Excel
+    pub fn export_plot_data_csv(&mut self) {
+        let Some(regression) = &self.regression else { return };
+        let csv = plot_data_csv(regression, self.settings.plot.raw_values, self.microplate.plot_scale.x);
+        if save_file(&format!("{} Plot Data", self.microplate.name), ("CSV", &["csv"]), csv.as_bytes()).is_err() {
+            self.notifications.push(SerdeError::CantWriteFile);
+        }
+    }
+
+    /// Everything about the analysis for R or Python, see `AnalysisExport`
+    pub fn export_analysis_json(&mut self) {
+        let Some(regression) = &self.regression else { return };
+        let saved = AnalysisExport::new(&self.microplate, regression).to_json().ok()
+            .and_then(|json| save_file(&format!("{} Analysis", self.microplate.name), ("JSON", &["json"]), json.as_bytes()).ok());
+        if saved.is_none() {
+            self.notifications.push(SerdeError::CantWriteFile);
+        }
+    }
+}
+
+/// One row per unknown in the order of the results table, with the sample metadata for tracing
+pub fn results_csv(regression: &Regression, order: &[usize], cutoff: Option<&Cutoff>, derived_columns: &[DerivedColumn]) -> String {
+    let header = ["sample", "subject-id", "timepoint", "matrix", "collection-date", "raw-corrected", "backfit-concentration", "cv-percent", "dilution", "corrected-concentration", "recovery-percent", "classification"];
+    let derived_columns = if parse_columns(derived_columns).is_ok() { derived_columns } else { &[] };
+    let header: Vec<String> = header.map(tr).into_iter().chain(derived_columns.iter().map(|column| column.name.trim().to_string())).collect();
+    let mut csv = csv_row(&header);
+    for unknown in order.iter().map(|&index| &regression.unknowns[index]) {
+        let SampleMetadata { subject_id, timepoint, matrix, collection_date } = unknown.metadata.clone();
+        let corrected = if unknown.concentration.is_finite() { format_value(unknown.corrected_concentration()) } else { String::new() };
+        csv.push_str(&csv_row(&[
+            unknown.name(), subject_id, timepoint, matrix, collection_date,
+            format_value(unknown.measurement),
+            unknown.concentration_text(),
+            unknown_cv(unknown).map(|cv| format_decimal(cv, 1)).unwrap_or_default(),
+            format_number(unknown.dilution),
+            corrected,
+            unknown.recovery().map(|recovery| format_decimal(recovery, 1)).unwrap_or_default(),
+            cutoff.and_then(|cutoff| cutoff.classify(regression, unknown)).map(|classification| classification.name()).unwrap_or_default(),
+        ].into_iter().chain(derived_values(derived_columns, unknown).into_iter().map(|value| value.map(format_value).unwrap_or_default())).collect::<Vec<_>>()));
+    }
+    csv
+}
+
+/// Rows and columns of the results table, the numbers formatted as on screen
+fn results_tsv(regression: &Regression, shown: &[usize], raw_values: bool, cutoff: Option<&Cutoff>, derived_columns: &[DerivedColumn]) -> String {
+    let derived_columns = if parse_columns(derived_columns).is_ok() { derived_columns } else { &[] };
+    let row = |cells: Vec<String>| {
+        let cells: Vec<String> = cells.into_iter().map(|cell| cell.replace(['\t', '\n', '\r'], " ")).collect();
+        cells.join("\t") + "\n"
+    };
+
+    let mut header = vec![tr("group"), tr(if raw_values { "raw-signal" } else { "raw-corrected" }), tr("backfit"), tr("cv-percent")];
+    if cutoff.is_some() {
+        header.push(tr("classification"));
+    }
+    header.extend(derived_columns.iter().map(|column| column.name.trim().to_string()));
+    header.push(tr("recovery"));
+
+    let mut tsv = row(header);
+    for unknown in shown.iter().map(|&index| &regression.unknowns[index]) {
+        let mut cells = vec![
+            unknown.name(),
+            format_value(unknown.signal(raw_values)),
+            unknown.concentration_text(),
+            unknown_cv(unknown).map(|cv| format_decimal(cv, 1)).unwrap_or_default(),
+        ];
+        if let Some(cutoff) = cutoff {
+            cells.push(cutoff.classify(regression, unknown).map(|classification| classification.name()).unwrap_or_default());
+        }
+        cells.extend(derived_values(derived_columns, unknown).into_iter().map(|value| value.map(format_value).unwrap_or_default()));
+        cells.push(unknown.recovery().map(|recovery| format!("{} %", format_decimal(recovery, 1))).unwrap_or_default());
+        tsv.push_str(&row(cells));
+    }
+    tsv
+}
+
+/// The standards with the SD of their replicates, the unknowns at their back-fitted dose and the curve sampled
+/// over the doses, evenly on the x axis of the plot. Signals are raw or blank corrected like in the plot.
+fn plot_data_csv(regression: &Regression, raw: bool, x_scale: AxisScale) -> String {
+    let (standards, standard_replicates) = if raw {
+        (&regression.raw_standards, &regression.raw_standard_replicates)
+    } else {
+        (&regression.standards, &regression.standard_replicates)
+    };
+    let offset = if raw { regression.blank_offset() } else { 0.0 };
+    let unknowns: Vec<&UnknownResult> = regression.unknowns.iter().filter(|unknown| unknown.concentration.is_finite()).collect();
+
+    let mut csv = csv_row(&["series", "label", "dose", "measurement", "sd"].map(tr));
+    for (i, (&(dose, mean), replicates)) in standards.iter().zip(standard_replicates).enumerate() {
+        let sd = if replicates.len() > 1 { format_value(mean_sd(replicates).1) } else { String::new() };
+        csv.push_str(&csv_row(&[tr("standards"), tr_args("standard-number", &[("number", (i + 1).into())]), format_value(dose), format_value(mean), sd]));
+    }
+    for unknown in &unknowns {
+        let replicates = unknown.signal_replicates(raw);
+        let sd = if replicates.len() > 1 { format_value(mean_sd(replicates).1) } else { String::new() };
+        csv.push_str(&csv_row(&[tr("unknowns"), unknown.name(), format_value(unknown.concentration), format_value(unknown.signal(raw)), sd]));
+    }
+
+    // from the lowest to the highest dose that is plotted, a log axis can't start at 0
+    let doses = standards.iter().map(|&(dose, _)| dose).chain(unknowns.iter().map(|unknown| unknown.concentration));
+    let doses: Vec<f64> = match x_scale {
+        AxisScale::Linear => doses.collect(),
+        AxisScale::Logarithmic => doses.filter(|&dose| dose > 0.0).collect(),
+    };
+    let low = doses.iter().copied().fold(f64::INFINITY, f64::min);
+    let high = doses.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    if low < high {
+        for i in 0..CURVE_POINTS {
+            let t = i as f64 / (CURVE_POINTS - 1) as f64;
+            let dose = match x_scale {
+                AxisScale::Linear => low + t * (high - low),
+                AxisScale::Logarithmic => (low.ln() + t * (high.ln() - low.ln())).exp(),
+            };
+            csv.push_str(&csv_row(&[tr("curve"), String::new(), format_value(dose), format_value(regression.four_pl(dose) + offset), String::new()]));
+        }
+    }
+    csv
+}
+
+/// Lays the plot out in a separate egui context and rasterizes it on the CPU,
+/// so the exported image does not depend on the window size or screen scale.
+pub fn render_plot(regression: &Regression, &PngExport { width, height, dpi }: &PngExport, options: PlotOptions, layers: PlotLayers, scale: PlotScale, view: PlotView) -> RgbaImage {
+    let ppp = dpi / SCREEN_DPI;
+    let screen = Rect::from_min_size(Pos2::ZERO, egui::vec2(width as f32, height as f32) / ppp);
+
+    let ctx = Context::default();
+    setup_fonts(&ctx);
+    ctx.set_theme(Theme::Light);
+    ctx.style_mut_of(Theme::Light, setup_style);
+
+    let mut textures: HashMap<TextureId, Texture> = HashMap::new();
+    let mut output = None;
+
+    // the first frames only measure text and axes, the plot settles after a couple of passes
+    for _ in 0..3 {
+        let mut input = RawInput {
+            screen_rect: Some(screen),
+            ..Default::default()
+        };
+        input.viewports.entry(ViewportId::ROOT).or_default().native_pixels_per_point = Some(ppp);
+
+        let full_output = ctx.run(input, |ctx| {
+            let frame = egui::Frame::central_panel(&ctx.style())
+                .fill(Color32::WHITE)
+                .inner_margin(15.0);
+            egui::CentralPanel::default().frame(frame).show(ctx, |ui| {
+                plot_regression(regression, ui, ui.available_size(), options, layers, scale, view);
+            });
+        });
+
+        for (id, delta) in &full_output.textures_delta.set {
+            let (size, pixels): ([usize; 2], Vec<Color32>) = match &delta.image {
+                ImageData::Color(image) => (image.size, image.pixels.clone()),
+                ImageData::Font(image) => (image.size, image.srgba_pixels(None).collect()),
+            };
+            match delta.pos {
+                Some([x, y]) => {
+                    let Some(texture) = textures.get_mut(id) else { continue };
+                    for row in 0..size[1] {
+                        let start = (y + row) * texture.size[0] + x;
+                        texture.pixels[start..start + size[0]].copy_from_slice(&pixels[row * size[0]..(row + 1) * size[0]]);
+                    }
+                },
+                None => {
+                    textures.insert(*id, Texture { size, pixels });
+                },
+            }
+        }
+        output = Some(full_output);
+    }
+
+    let Some(output) = output else { unreachable!() };
+    let primitives = ctx.tessellate(output.shapes, output.pixels_per_point);
+
+    let mut canvas = Canvas::new(width as usize, height as usize);
+    for primitive in primitives {
+        let Primitive::Mesh(mesh) = primitive.primitive else { continue };
+        let Some(texture) = textures.get(&mesh.texture_id) else { continue };
+
+        let clip = Rect::from_min_max(
+            (primitive.clip_rect.min.to_vec2() * ppp).to_pos2(),
+            (primitive.clip_rect.max.to_vec2() * ppp).to_pos2(),
+        );
+        for triangle in mesh.indices.chunks_exact(3) {
+            let vertices = [0, 1, 2].map(|i| {
+                let vertex = mesh.vertices[triangle[i] as usize];
+                Vertex { pos: pos2(vertex.pos.x * ppp, vertex.pos.y * ppp), ..vertex }
+            });
+            canvas.fill_triangle(vertices, clip, texture);
+        }
+    }
+
+    canvas.into_image()
+}
+
+struct Texture {
+    size: [usize; 2],
+    pixels: Vec<Color32>,
+}
+
+impl Texture {
+    /// Bilinear lookup, like the linear filtering egui asks the GPU for
+    fn sample(&self, u: f32, v: f32) -> [f32; 4] {
+        let [w, h] = self.size;
+        let x = (u * w as f32 - 0.5).clamp(0.0, (w - 1) as f32);
+        let y = (v * h as f32 - 0.5).clamp(0.0, (h - 1) as f32);
+        let (x0, y0) = (x.floor() as usize, y.floor() as usize);
+        let (x1, y1) = ((x0 + 1).min(w - 1), (y0 + 1).min(h - 1));
+        let (fx, fy) = (x - x0 as f32, y - y0 as f32);
+
+        let pixel = |x: usize, y: usize| self.pixels[y * w + x].to_array().map(|c| c as f32 / 255.0);
+        let (p00, p10, p01, p11) = (pixel(x0, y0), pixel(x1, y0), pixel(x0, y1), pixel(x1, y1));
+
+        std::array::from_fn(|i| {
+            let top = p00[i] + (p10[i] - p00[i]) * fx;
+            let bottom = p01[i] + (p11[i] - p01[i]) * fx;
+            top + (bottom - top) * fy
+        })
+    }
+}
+
+/// Premultiplied RGBA canvas with a white background
+struct Canvas {
+    width: usize,
+    height: usize,
+    pixels: Vec<[f32; 4]>,
+}
+
+impl Canvas {
+    fn new(width: usize, height: usize) -> Self {
+        Self { width, height, pixels: vec![[1.0; 4]; width * height] }
+    }
+
+    fn fill_triangle(&mut self, [a, b, c]: [Vertex; 3], clip: Rect, texture: &Texture) {
+        let area = edge(a.pos, b.pos, c.pos);
+        if !area.is_finite() || area.abs() < f32::EPSILON { return }
+
+        let min_x = a.pos.x.min(b.pos.x).min(c.pos.x).max(clip.min.x).max(0.0).floor() as usize;
+        let min_y = a.pos.y.min(b.pos.y).min(c.pos.y).max(clip.min.y).max(0.0).floor() as usize;
+        let max_x = (a.pos.x.max(b.pos.x).max(c.pos.x).min(clip.max.x).ceil().max(0.0) as usize).min(self.width);
+        let max_y = (a.pos.y.max(b.pos.y).max(c.pos.y).min(clip.max.y).ceil().max(0.0) as usize).min(self.height);
+
+        let color = |vertex: &Vertex| vertex.color.to_array().map(|c| c as f32 / 255.0);
+        let colors = [color(&a), color(&b), color(&c)];
+
+        for y in min_y..max_y {
+            for x in min_x..max_x {
+                let p = pos2(x as f32 + 0.5, y as f32 + 0.5);
+                let wa = edge(b.pos, c.pos, p) / area;
+                let wb = edge(c.pos, a.pos, p) / area;
+                let wc = edge(a.pos, b.pos, p) / area;
+                if !(wa >= 0.0 && wb >= 0.0 && wc >= 0.0) { continue }
+
+                let u = wa * a.uv.x + wb * b.uv.x + wc * c.uv.x;
+                let v = wa * a.uv.y + wb * b.uv.y + wc * c.uv.y;
+                let texel = texture.sample(u, v);
+                let source: [f32; 4] = std::array::from_fn(|i| {
+                    (wa * colors[0][i] + wb * colors[1][i] + wc * colors[2][i]) * texel[i]
+                });
+
+                let destination = &mut self.pixels[y * self.width + x];
+                for i in 0..4 {
+                    destination[i] = source[i] + destination[i] * (1.0 - source[3]);
+                }
+            }
+        }
+    }
+
+    fn into_image(self) -> RgbaImage {
+        let mut image = RgbaImage::new(self.width as u32, self.height as u32);
+        for (pixel, color) in image.pixels_mut().zip(self.pixels) {
+            let [r, g, b, _] = color.map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8);
+            *pixel = Rgba([r, g, b, 255]);
+        }
+        image
+    }
+}
+
+fn edge(a: Pos2, b: Pos2, p: Pos2) -> f32 {
+    (b.x - a.x) * (p.y - a.y) - (b.y - a.y) * (p.x - a.x)
+}
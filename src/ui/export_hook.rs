@@ -0,0 +1,221 @@
+use std::{collections::HashSet, fs, path::PathBuf, process::{Child, Command}, time::Duration};
+
+use eframe::egui::{self, ComboBox, Grid, Id, RichText, TextEdit};
+
+use crate::{app::{AppError, SerdeError}, export_hook::*, i18n::*, json_export::AnalysisExport, results_filter::sorted_unknowns, settings::save_settings, ui::{export::*, plot::{PlotLayers, PlotView}}, Elisa};
+
+/// How often running commands are checked for their exit code
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// The hook window and the commands the hook started
+#[derive(Default)]
+pub struct HookRunner {
+    pub show: bool,
+    pub fingerprint: Option<u64>, // of the plate the hook last ran for, refitting it unchanged doesn't run it again
+    pub running: Vec<(String, Child)>, // program and process
+    pub last_run: Option<String>, // what happened the last time, for the window
+}
+
+impl Elisa {
+    /// Writes the exports of the fitted plate and starts the command when the trigger fires, once per plate.
+    /// Forced, e.g. from "Run Now", it runs regardless.
+    pub fn run_export_hook(&mut self, forced: bool) {
+        let hook = self.settings.export_hook.clone();
+        if !hook.enabled && !forced { return }
+        let Some(regression) = &self.regression else { return };
+        if !forced && self.export_hook.fingerprint == Some(regression.fingerprint) { return }
+        self.export_hook.fingerprint = Some(regression.fingerprint);
+
+        let plate = self.microplate.name.clone();
+        let checks = self.microplate.protocol.clone().unwrap_or_default().evaluate(&self.microplate, regression);
+        if !forced && !hook.trigger.fires(&checks) {
+            log::info!("Post-analysis hook skipped, {plate} did not pass QC");
+            self.export_hook.last_run = Some(tr_args("hook-skipped", &[("plate", plate.into())]));
+            return
+        }
+
+        let started = self.write_hook_exports(&hook).and_then(|files| {
+            let process = start_hook_command(&hook.command, &files)?;
+            Ok((files, process))
+        });
+        match started {
+            Ok((files, process)) => {
+                let count = [&files.csv, &files.json, &files.pdf].iter().filter(|file| file.is_some()).count();
+                log::info!("Post-analysis hook wrote {count} exports of {plate} to {}", files.folder.display());
+                self.export_hook.last_run = Some(tr_args("hook-done", &[
+                    ("plate", plate.into()),
+                    ("count", count.into()),
+                    ("folder", files.folder.display().to_string().into()),
+                ]));
+                self.export_hook.running.extend(process);
+            },
+            Err(error) => {
+                log::warn!("Post-analysis hook failed for {plate}: {error}");
+                self.export_hook.last_run = Some(error.to_string());
+                self.notifications.push(AppError::Hook(error));
+            },
+        }
+    }
+
+    fn write_hook_exports(&self, hook: &ExportHook) -> Result<HookFiles, HookError> {
+        let Some(regression) = &self.regression else { return Ok(HookFiles::default()) };
+        let folder = hook.folder.clone().unwrap_or_else(|| std::env::temp_dir().join("Elisa"));
+        fs::create_dir_all(&folder).map_err(|error| HookError::Write(format!("{}: {error}", folder.display())))?;
+
+        let name = export_name(&self.microplate.name);
+        let write = |suffix: &str, extension: &str, bytes: &[u8]| -> Result<Option<PathBuf>, HookError> {
+            let path = folder.join(format!("{name} {suffix}.{extension}"));
+            fs::write(&path, bytes).map_err(|error| HookError::Write(format!("{}: {error}", path.display())))?;
+            Ok(Some(path))
+        };
+        let mut files = HookFiles { plate: self.microplate.name.clone(), folder: folder.clone(), ..Default::default() };
+
+        if hook.results_csv {
+            let order = sorted_unknowns(&regression.unknowns, self.results_sort);
+            let derived_columns = self.microplate.protocol.as_ref().map(|protocol| protocol.derived_columns.as_slice()).unwrap_or_default();
+            let csv = results_csv(regression, &order, self.microplate.cutoff.as_ref(), derived_columns);
+            files.csv = write("Results", "csv", csv.as_bytes())?;
+        }
+        if hook.analysis_json {
+            let json = AnalysisExport::new(&self.microplate, regression).to_json().map_err(|error| HookError::Write(error.to_string()))?;
+            files.json = write("Analysis", "json", json.as_bytes())?;
+        }
+        if hook.report_pdf {
+            let hidden_labels = HashSet::new();
+            let layers = PlotLayers { hidden_labels: &hidden_labels, reference: None };
+            let image = render_plot(regression, &PngExport::default(), self.settings.plot, layers, self.microplate.plot_scale, PlotView::Interactive);
+            files.pdf = write("Report", "pdf", &self.create_pdf(&image))?;
+        }
+        Ok(files)
+    }
+
+    /// Tells about commands that ended with an error
+    fn poll_export_hook(&mut self, ctx: &egui::Context) {
+        if self.export_hook.running.is_empty() { return }
+        ctx.request_repaint_after(POLL_INTERVAL);
+
+        let mut failed = Vec::new();
+        self.export_hook.running.retain_mut(|(program, child)| match child.try_wait() {
+            Ok(None) => true,
+            Ok(Some(status)) => {
+                if !status.success() {
+                    failed.push(HookError::Failed(program.clone(), status.code().unwrap_or(-1)));
+                }
+                false
+            },
+            Err(error) => {
+                failed.push(HookError::Start(error.to_string()));
+                false
+            },
+        });
+        for error in failed {
+            log::warn!("Post-analysis hook: {error}");
+            self.export_hook.last_run = Some(error.to_string());
+            self.notifications.push(AppError::Hook(error));
+        }
+    }
+
+    pub fn export_hook_window(&mut self, ctx: &egui::Context) {
+        self.poll_export_hook(ctx);
+
+        let hook = &mut self.settings.export_hook;
+        let fitted = self.regression.is_some();
+        let last_run = &self.export_hook.last_run;
+        let running = self.export_hook.running.len();
+        let mut changed = false;
+        let mut run_now = false;
+
+        egui::Window::new(tr("post-analysis-hook"))
+            .id(Id::new("Post-Analysis Hook"))
+            .open(&mut self.export_hook.show)
+            .resizable(false)
+            .collapsible(false)
+            .frame(egui::Frame::window(&ctx.style()).inner_margin(10.0))
+            .show(ctx, |ui| {
+                ui.label(RichText::new(tr("post-analysis-hook-info")).size(11.0));
+                ui.add_space(10.0);
+                changed |= ui.checkbox(&mut hook.enabled, tr("hook-enabled")).changed();
+                ui.add_space(10.0);
+
+                Grid::new("Post-Analysis Hook Grid").num_columns(2).spacing([10.0, 6.0]).show(ui, |ui| {
+                    ui.label(tr("hook-trigger"));
+                    ComboBox::from_id_salt("Hook Trigger").selected_text(hook.trigger.description()).show_ui(ui, |ui| {
+                        for trigger in HookTrigger::ALL {
+                            changed |= ui.selectable_value(&mut hook.trigger, trigger, trigger.description()).changed();
+                        }
+                    });
+                    ui.end_row();
+
+                    ui.label(tr("hook-folder"));
+                    ui.horizontal(|ui| {
+                        let button = ui.button(tr("choose"));
+                        Self::dashed_outline(ui, &button);
+                        if button.clicked() {
+                            if let Some(folder) = rfd::FileDialog::new().pick_folder() {
+                                hook.folder = Some(folder);
+                                changed = true;
+                            }
+                        }
+                        if hook.folder.is_some() {
+                            let button = ui.button(tr("remove"));
+                            Self::dashed_outline(ui, &button);
+                            if button.clicked() {
+                                hook.folder = None;
+                                changed = true;
+                            }
+                        }
+                        let folder = hook.folder.as_ref().map(|folder| folder.display().to_string()).unwrap_or_else(|| tr("hook-temporary-folder"));
+                        ui.label(RichText::new(folder).size(11.0));
+                    });
+                    ui.end_row();
+
+                    ui.label(tr("hook-exports"));
+                    ui.horizontal(|ui| {
+                        changed |= ui.checkbox(&mut hook.results_csv, tr("hook-results-csv")).changed();
+                        changed |= ui.checkbox(&mut hook.analysis_json, tr("hook-analysis-json")).changed();
+                        changed |= ui.checkbox(&mut hook.report_pdf, tr("hook-report-pdf")).changed();
+                    });
+                    ui.end_row();
+
+                    ui.label(tr("hook-command"));
+                    let text_edit = ui.add(TextEdit::singleline(&mut hook.command).hint_text("lims-import \"{json}\"").desired_width(300.0))
+                        .on_hover_text(tr("hook-command-hover"));
+                    Self::dashed_outline(ui, &text_edit);
+                    changed |= text_edit.lost_focus();
+                    ui.end_row();
+                });
+                ui.add_space(10.0);
+
+                ui.horizontal(|ui| {
+                    let button = ui.add_enabled(fitted, egui::Button::new(tr("hook-run-now"))).on_hover_text(tr("hook-run-now-hover"));
+                    Self::dashed_outline(ui, &button);
+                    run_now = button.clicked();
+                    if running > 0 {
+                        ui.add_space(10.0);
+                        ui.label(RichText::new(tr_args("hook-running", &[("count", running.into())])).size(11.0));
+                    }
+                });
+                if let Some(last_run) = last_run {
+                    ui.add_space(4.0);
+                    ui.label(RichText::new(last_run).size(11.0));
+                }
+            });
+
+        if changed && save_settings(&self.settings).is_err() {
+            self.notifications.push(SerdeError::CantWriteFile);
+        }
+        if run_now {
+            self.run_export_hook(true);
+        }
+    }
+}
+
+/// The command of the hook with the placeholders filled in, None without one
+fn start_hook_command(command: &str, files: &HookFiles) -> Result<Option<(String, Child)>, HookError> {
+    let arguments = command_arguments(command, files)?;
+    let Some((program, arguments)) = arguments.split_first() else { return Ok(None) };
+    let child = Command::new(program).args(arguments).current_dir(&files.folder).spawn()
+        .map_err(|error| HookError::Start(format!("{program}: {error}")))?;
+    log::info!("Post-analysis hook started {program}");
+    Ok(Some((program.clone(), child)))
+}
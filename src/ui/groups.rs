@@ -0,0 +1,110 @@
+use eframe::egui::{self, Grid, Id, RichText, TextEdit};
+
+use crate::{i18n::*, logistic_regression::*, Elisa};
+
+enum GroupEdit {
+    Add,
+    Delete(usize),
+    Swap(usize, usize),
+    Merge(usize, usize),
+}
+
+impl Elisa {
+    /// Standard and unknown groups with their wells, to fix groups that were assigned wrong
+    pub fn groups_window(&mut self, ctx: &egui::Context) {
+        if !self.show_groups { return }
+
+        let locked = self.microplate.locked;
+        let mut edits = Vec::new();
+        let microplate = &mut self.microplate;
+
+        egui::Window::new(tr("groups"))
+            .id(Id::new("Groups"))
+            .open(&mut self.show_groups)
+            .resizable(false)
+            .collapsible(false)
+            .frame(egui::Frame::window(&ctx.style()).inner_margin(10.0))
+            .show(ctx, |ui| {
+                if locked {
+                    ui.disable();
+                }
+
+                for typ in [SampleType::Standard, SampleType::Unknown] {
+                    let (heading, name_id) = match typ {
+                        SampleType::Standard => (tr("standards"), "standard-number"),
+                        _ => (tr("unknowns"), "unknown-number"),
+                    };
+                    ui.label(RichText::new(heading).strong());
+                    ui.add_space(5.0);
+
+                    let wells: Vec<usize> = (0..microplate.groups_mut(typ).map_or(0, |groups| groups.len()))
+                        .map(|group| microplate.group_wells(typ, group))
+                        .collect();
+                    let Some(groups) = microplate.groups_mut(typ) else { continue };
+                    let len = groups.len();
+                    let name = |index: usize| tr_args(name_id, &[("number", (index + 1).into())]);
+
+                    Grid::new(("Groups", typ as usize)).num_columns(6).spacing([10.0, 4.0]).striped(true).show(ui, |ui| {
+                        for (index, group) in groups.iter_mut().enumerate() {
+                            ui.label(name(index));
+                            match typ {
+                                SampleType::Standard => {
+                                    ui.label(group.concentration.map(format_number).unwrap_or_else(|| "-".to_string()));
+                                },
+                                _ => {
+                                    ui.add(TextEdit::singleline(&mut group.label).desired_width(100.0).hint_text(tr("label")));
+                                },
+                            }
+                            ui.label(tr_args("group-wells", &[("count", wells[index].into())]));
+
+                            ui.horizontal(|ui| {
+                                if ui.add_enabled(index > 0, egui::Button::new("⏶")).on_hover_text(tr("move-up")).clicked() {
+                                    edits.push((typ, GroupEdit::Swap(index, index - 1)));
+                                }
+                                if ui.add_enabled(index + 1 < len, egui::Button::new("⏷")).on_hover_text(tr("move-down")).clicked() {
+                                    edits.push((typ, GroupEdit::Swap(index, index + 1)));
+                                }
+                            });
+
+                            ui.add_enabled_ui(len > 1, |ui| {
+                                ui.menu_button(tr("merge-into"), |ui| {
+                                    for other in (0..len).filter(|&other| other != index) {
+                                        if ui.button(name(other)).clicked() {
+                                            edits.push((typ, GroupEdit::Merge(index, other)));
+                                            ui.close_menu();
+                                        }
+                                    }
+                                }).response.on_hover_text(tr("merge-into-hover"));
+                            });
+
+                            if ui.button(tr("delete")).on_hover_text(tr("delete-group-hover")).clicked() {
+                                edits.push((typ, GroupEdit::Delete(index)));
+                            }
+                            ui.end_row();
+                        }
+                    });
+                    ui.add_space(5.0);
+                    let button = ui.button(tr("add-group"));
+                    Self::dashed_outline(ui, &button);
+                    if button.clicked() {
+                        edits.push((typ, GroupEdit::Add));
+                    }
+                    ui.add_space(10.0);
+                }
+            });
+
+        if edits.is_empty() { return }
+        for (typ, edit) in edits {
+            match edit {
+                GroupEdit::Add => self.microplate.add_group(typ),
+                GroupEdit::Delete(group) => self.microplate.delete_group(typ, group),
+                GroupEdit::Swap(a, b) => self.microplate.swap_groups(typ, a, b),
+                GroupEdit::Merge(from, into) => self.microplate.merge_groups(typ, from, into),
+            }
+        }
+        // the concentration fields refill from the groups in their new order
+        self.standards_textfield.iter_mut().for_each(String::clear);
+        self.hidden_labels.clear();
+        self.selected_sample = None;
+    }
+}
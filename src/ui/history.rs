@@ -0,0 +1,166 @@
+use chrono::NaiveDateTime;
+use eframe::egui::{self, Color32, ComboBox, Grid, Id, RichText, ScrollArea, Ui};
+use egui_plot::{HLine, Line, LineStyle, Plot, PlotPoints, Points};
+
+use crate::{history::*, i18n::*, logistic_regression::*, ui::plot::{rgb, FLAG_COLOR}, Elisa};
+
+const WARNING_COLOR: Color32 = Color32::from_rgb(230, 160, 40);
+
+impl Elisa {
+    pub fn history_buttons(&mut self, ui: &mut Ui) {
+        let button = ui.button(RichText::new(tr("record-curve")));
+        Self::dashed_outline(ui, &button);
+        let button = button.on_hover_text(tr("record-curve-hover"));
+        if button.clicked() {
+            if let Some(regression) = &self.regression {
+                self.curve_history.push(CurveRecord::new(&self.microplate, regression));
+                if save_curve_history(&self.curve_history).is_err() {
+                    self.notifications.push(crate::app::SerdeError::CantWriteFile);
+                }
+            }
+        }
+        ui.add_space(10.0);
+
+        let button = ui.button(RichText::new(tr("curve-history")));
+        Self::dashed_outline(ui, &button);
+        if button.clicked() {
+            self.show_history = !self.show_history;
+        }
+    }
+
+    pub fn curve_history_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_history;
+        egui::Window::new(tr("curve-history"))
+            .id(Id::new("Curve History"))
+            .open(&mut open)
+            .collapsible(false)
+            .default_width(520.0)
+            .frame(egui::Frame::window(&ctx.style()).inner_margin(10.0))
+            .show(ctx, |ui| {
+                let mut lots: Vec<String> = self.curve_history.iter().map(|record| record.kit_lot.clone()).collect();
+                lots.sort();
+                lots.dedup();
+                if self.history_lot.is_none() {
+                    self.history_lot = Some(self.microplate.metadata.kit_lot.clone()).filter(|lot| lots.contains(lot));
+                }
+
+                ui.horizontal(|ui| {
+                    ui.label(tr("kit-lot"));
+                    ui.add_space(10.0);
+                    let selected = self.history_lot.clone().unwrap_or(tr("all-lots"));
+                    ComboBox::from_id_salt("History Lot").selected_text(selected).show_ui(ui, |ui| {
+                        ui.selectable_value(&mut self.history_lot, None, tr("all-lots"));
+                        for lot in lots {
+                            let text = if lot.is_empty() { tr("no-lot") } else { lot.clone() };
+                            ui.selectable_value(&mut self.history_lot, Some(lot), text);
+                        }
+                    });
+                    ui.add_space(20.0);
+                    ui.label(tr("history-metric"));
+                    ui.add_space(10.0);
+                    ComboBox::from_id_salt("History Metric").selected_text(self.history_metric.name()).show_ui(ui, |ui| {
+                        for metric in HistoryMetric::ALL {
+                            ui.selectable_value(&mut self.history_metric, metric, metric.name());
+                        }
+                    });
+                });
+                ui.add_space(10.0);
+
+                let records: Vec<&CurveRecord> = self.curve_history.iter()
+                    .filter(|record| self.history_lot.as_ref().is_none_or(|lot| record.kit_lot == *lot))
+                    .collect();
+                if records.is_empty() {
+                    ui.label(tr("history-empty"));
+                    return
+                }
+
+                // (run, value), runs without the metric are left out of the chart and its limits
+                let runs: Vec<(usize, f64)> = records.iter().enumerate()
+                    .filter_map(|(i, record)| Some((i + 1, self.history_metric.value(record)?)))
+                    .collect();
+                let values: Vec<f64> = runs.iter().map(|&(_, value)| value).collect();
+                let (mean, sd) = mean_sd(&values);
+                let violations = westgard_violations(&values, mean, sd);
+                self.levey_jennings_chart(ui, &runs, &violations, mean, sd);
+
+                let flagged: Vec<String> = runs.iter().zip(&violations)
+                    .filter(|(_, rules)| !rules.is_empty())
+                    .map(|(&(run, _), rules)| format!("{run}: {}", rules.iter().map(WestgardRule::name).collect::<Vec<_>>().join(", ")))
+                    .collect();
+                let text = if flagged.is_empty() { tr("westgard-none") } else { tr_args("westgard-violations", &[("runs", flagged.join("; ").into())]) };
+                let rejected = violations.iter().flatten().any(WestgardRule::rejects);
+                let text = RichText::new(text).size(11.0);
+                ui.label(if rejected { text.color(FLAG_COLOR) } else { text }).on_hover_text(tr("westgard-hover"));
+                ui.add_space(10.0);
+
+                ScrollArea::vertical().max_height(200.0).show(ui, |ui| {
+                    Grid::new("Curve History Grid").striped(true).spacing([15.0, 4.0]).show(ui, |ui| {
+                        let headers = ["#".to_string(), tr("date"), tr("plate"), tr("lot")];
+                        let metrics = ["a", "b", "c", "d", "R^2"].map(String::from).into_iter()
+                            .chain([HistoryMetric::BlankMean, HistoryMetric::ControlMean, HistoryMetric::MaxCv].map(|metric| metric.name()));
+                        for header in headers.into_iter().chain(metrics) {
+                            ui.label(header);
+                        }
+                        ui.end_row();
+                        for (i, record) in records.iter().enumerate() {
+                            let (a, b, c, d) = record.abcd;
+                            ui.label(format!("{}", i + 1));
+                            // stored as ISO, shown in the format of the language
+                            let date = NaiveDateTime::parse_from_str(&record.date, "%Y-%m-%d %H:%M")
+                                .map(|date| format_date_time(&date))
+                                .unwrap_or(record.date.clone());
+                            ui.label(date);
+                            ui.label(&record.plate);
+                            ui.label(&record.kit_lot);
+                            for value in [a, b, c, d, record.r_sq] {
+                                ui.label(format_value(value));
+                            }
+                            for value in [record.blank_mean, record.control_mean] {
+                                ui.label(value.map(format_value).unwrap_or_default());
+                            }
+                            ui.label(record.max_cv.map(|cv| format!("{} %", format_decimal(cv, 1))).unwrap_or_default());
+                            ui.end_row();
+                        }
+                    });
+                });
+            });
+        self.show_history = open;
+    }
+
+    /// Control chart of a metric over runs with mean, ±2 SD and ±3 SD limits, runs violating a Westgard rule are flagged
+    fn levey_jennings_chart(&self, ui: &mut Ui, runs: &[(usize, f64)], violations: &[Vec<WestgardRule>], mean: f64, sd: f64) {
+        let color = ui.style().noninteractive().text_color();
+        let limit_color = Color32::from_hex("#B2B6C0").unwrap();
+
+        Plot::new("Levey-Jennings")
+            .height(220.0)
+            .x_axis_label(tr("run"))
+            .y_axis_label(self.history_metric.name())
+            .show_background(false)
+            .allow_scroll(false)
+            .show(ui, |ui| {
+                let points: Vec<[f64; 2]> = runs.iter().map(|&(run, value)| [run as f64, value]).collect();
+                ui.line(Line::new(PlotPoints::from(points.clone())).color(color).allow_hover(false));
+
+                if sd.is_finite() {
+                    ui.hline(HLine::new(mean).color(color).name(tr("mean")));
+                    for k in [2.0, 3.0] {
+                        let style = if k == 2.0 { LineStyle::dashed_loose() } else { LineStyle::Solid };
+                        ui.hline(HLine::new(mean + k * sd).color(limit_color).style(style).name(format!("+{k} SD")));
+                        ui.hline(HLine::new(mean - k * sd).color(limit_color).style(style).name(format!("-{k} SD")));
+                    }
+                }
+
+                for (point, rules) in points.into_iter().zip(violations) {
+                    let color = if rules.iter().any(WestgardRule::rejects) {
+                        FLAG_COLOR
+                    } else if !rules.is_empty() {
+                        WARNING_COLOR
+                    } else {
+                        rgb(SampleType::Standard.color())
+                    };
+                    ui.points(Points::new(point).radius(4.0).color(color));
+                }
+            });
+    }
+}
@@ -0,0 +1,61 @@
+use eframe::egui::{self, Color32, Id, RichText};
+use egui_extras::{Column, TableBuilder};
+use log::Level;
+
+use crate::{i18n::*, log_console::*, ui::plot::FLAG_COLOR, Elisa};
+
+const WARN_COLOR: Color32 = Color32::from_rgb(0xC0, 0x80, 0x00);
+
+impl Elisa {
+    /// Label of the top bar button, with the number of warnings and errors
+    pub fn log_button_text() -> String {
+        match log_problems() {
+            0 => tr("log"),
+            count => tr_args("log-problems", &[("count", count.into())]),
+        }
+    }
+
+    pub fn log_console_window(&mut self, ctx: &egui::Context) {
+        let entries = log_entries();
+
+        egui::Window::new(tr("log"))
+            .id(Id::new("Log Console"))
+            .open(&mut self.show_log_console)
+            .default_width(560.0)
+            .default_height(300.0)
+            .frame(egui::Frame::window(&ctx.style()).inner_margin(10.0))
+            .show(ctx, |ui| {
+                if entries.is_empty() {
+                    ui.label(tr("log-empty"));
+                    return
+                }
+
+                let button = ui.button(tr("clear"));
+                Self::dashed_outline(ui, &button);
+                if button.clicked() {
+                    clear_log();
+                }
+                ui.add_space(10.0);
+
+                TableBuilder::new(ui)
+                    .striped(true)
+                    .column(Column::auto().at_least(60.0))
+                    .column(Column::auto().at_least(50.0))
+                    .column(Column::remainder())
+                    .stick_to_bottom(true)
+                    .body(|body| {
+                        body.rows(18.0, entries.len(), |mut row| {
+                            let entry = &entries[row.index()];
+                            let color = match entry.level {
+                                Level::Error => FLAG_COLOR,
+                                Level::Warn => WARN_COLOR,
+                                _ => Color32::PLACEHOLDER,
+                            };
+                            row.col(|ui| { ui.label(&entry.time); });
+                            row.col(|ui| { ui.label(RichText::new(entry.level.as_str()).color(color)); });
+                            row.col(|ui| { ui.label(&entry.message).on_hover_text(&entry.target); });
+                        });
+                    });
+            });
+    }
+}
@@ -0,0 +1,87 @@
+use eframe::egui::{self, Grid, Id, RichText, ScrollArea};
+
+use crate::{files::*, i18n::*, manifest::*, reader::decode_export, ui::plot::FLAG_COLOR, Elisa};
+
+impl Elisa {
+    pub fn open_manifest(&mut self, file: &OpenedFile) {
+        match read_manifest(&decode_export(&file.bytes), &self.microplate) {
+            Ok(manifest) => self.manifest = Some(manifest),
+            Err(error) => self.notifications.push(error),
+        }
+    }
+
+    /// The labels a sample manifest gives the unknown groups and where it doesn't match the plate, before they are taken over
+    pub fn manifest_window(&mut self, ctx: &egui::Context) {
+        let Some(manifest) = &self.manifest else { return };
+
+        let microplate = &self.microplate;
+        let mut open = true;
+        let mut apply = false;
+        let mut cancel = false;
+
+        egui::Window::new(tr("import-manifest"))
+            .id(Id::new("Import Manifest"))
+            .open(&mut open)
+            .resizable(false)
+            .collapsible(false)
+            .frame(egui::Frame::window(&ctx.style()).inner_margin(10.0))
+            .show(ctx, |ui| {
+                if microplate.locked {
+                    ui.disable();
+                }
+
+                ui.label(tr_args("manifest-wells", &[("wells", manifest.entries.len().into()), ("groups", manifest.groups.len().into())]));
+                ui.add_space(10.0);
+                ScrollArea::vertical().id_salt("Manifest Groups").max_height(250.0).show(ui, |ui| {
+                    Grid::new("Manifest Groups Grid").num_columns(4).spacing([20.0, 4.0]).striped(true).show(ui, |ui| {
+                        for id in ["group", "well", "label", "subject-id"] {
+                            ui.label(RichText::new(tr(id)).strong());
+                        }
+                        ui.end_row();
+                        for (index, entry) in &manifest.groups {
+                            let current = microplate.unknown_groups.get(*index).map(|group| group.label.as_str()).unwrap_or_default();
+                            ui.label(tr_args("unknown-number", &[("number", (index + 1).into())]));
+                            ui.label(microplate.well_name(entry.well));
+                            // labels the plate already has are weak
+                            let label = RichText::new(&entry.label);
+                            let label = ui.label(if current == entry.label { label.weak() } else { label });
+                            if !current.is_empty() && current != entry.label {
+                                label.on_hover_text(tr_args("manifest-replaces", &[("label", current.into())]));
+                            }
+                            ui.label(&entry.metadata.subject_id);
+                            ui.end_row();
+                        }
+                    });
+                });
+
+                if !manifest.issues.is_empty() {
+                    ui.add_space(10.0);
+                    ui.label(RichText::new(tr_args("manifest-issues", &[("count", manifest.issues.len().into())])).color(FLAG_COLOR));
+                    ui.add_space(5.0);
+                    ScrollArea::vertical().id_salt("Manifest Issues").max_height(120.0).show(ui, |ui| {
+                        for issue in &manifest.issues {
+                            ui.label(RichText::new(issue.description(microplate)).size(11.0));
+                        }
+                    });
+                }
+                ui.add_space(10.0);
+
+                ui.horizontal(|ui| {
+                    let button = ui.add_enabled(!manifest.groups.is_empty(), egui::Button::new(tr("apply-manifest")));
+                    Self::dashed_outline(ui, &button);
+                    apply = button.clicked();
+                    ui.add_space(10.0);
+                    cancel = ui.button(tr("cancel")).clicked();
+                });
+            });
+
+        if apply && !self.microplate.locked {
+            if let Some(manifest) = &self.manifest {
+                self.microplate.apply_manifest(manifest);
+            }
+        }
+        if apply || cancel || !open {
+            self.manifest = None;
+        }
+    }
+}
@@ -0,0 +1,107 @@
+use std::ops::RangeInclusive;
+
+use eframe::egui::{self, Grid, Id, RichText, Slider, Ui};
+
+use crate::{i18n::*, ui::plot::FLAG_COLOR, Elisa};
+
+/// Slider range around the values of the standards, always including `value`
+fn parameter_range(value: f64, min: f64, max: f64) -> RangeInclusive<f64> {
+    min.min(value)..=max.max(value)
+}
+
+impl Elisa {
+    pub fn manual_fit_button(&mut self, ui: &mut Ui) {
+        if self.regression.is_none() { return }
+
+        let button = ui.button(RichText::new(tr("adjust-curve")));
+        Self::dashed_outline(ui, &button);
+        let button = button.on_hover_text(tr("adjust-curve-hover"));
+        if button.clicked() {
+            self.show_manual_fit = !self.show_manual_fit;
+        }
+    }
+
+    /// Expert mode: the four parameters on sliders, with the unknowns and residuals following live
+    pub fn manual_fit_window(&mut self, ctx: &egui::Context) {
+        let Some(regression) = &mut self.regression else { return };
+        let locked = self.microplate.locked;
+        let mut adjusted = false;
+
+        egui::Window::new(tr("adjust-curve"))
+            .id(Id::new("Manual Fit"))
+            .open(&mut self.show_manual_fit)
+            .resizable(false)
+            .collapsible(false)
+            .frame(egui::Frame::window(&ctx.style()).inner_margin(10.0))
+            .show(ctx, |ui| {
+                if locked {
+                    ui.disable();
+                }
+
+                let (x_min, x_max) = regression.standards.iter()
+                    .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &(x, _y)| (min.min(x), max.max(x)));
+                let (y_min, y_max) = regression.standards.iter()
+                    .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &(_x, y)| (min.min(y), max.max(y)));
+                let span = (y_max - y_min).max(f64::EPSILON);
+
+                let (mut a, mut b, mut c, mut d) = regression.abcd;
+                let b_range = if b >= 0.0 { (0.05, 20.0) } else { (-20.0, -0.05) };
+                let ranges = [
+                    parameter_range(a, y_min - span, y_max + span),
+                    parameter_range(b, b_range.0, b_range.1),
+                    parameter_range(c, x_min / 10.0, x_max * 10.0),
+                    parameter_range(d, y_min - span, y_max + span),
+                ];
+                let sliders = [
+                    ("a", &mut a, false, "adjust-a"),
+                    ("b", &mut b, true, "adjust-b"),
+                    ("c", &mut c, true, "adjust-c"),
+                    ("d", &mut d, false, "adjust-d"),
+                ];
+
+                let mut changed = false;
+                Grid::new("Manual Fit Grid").num_columns(2).spacing([10.0, 6.0]).show(ui, |ui| {
+                    for ((name, value, logarithmic, hover), range) in sliders.into_iter().zip(ranges) {
+                        ui.label(name).on_hover_text(tr(hover));
+                        let slider = ui.add(Slider::new(value, range)
+                            .logarithmic(logarithmic)
+                            .custom_formatter(|n, _| format_value(n))
+                            .custom_parser(parse_decimal));
+                        changed |= slider.changed();
+                        adjusted |= slider.drag_stopped() || slider.lost_focus();
+                        ui.end_row();
+                    }
+                });
+                if changed {
+                    regression.set_parameters((a, b, c, d));
+                }
+                ui.add_space(10.0);
+
+                Grid::new("Manual Fit Statistics").num_columns(2).spacing([20.0, 4.0]).show(ui, |ui| {
+                    for (name, value) in [("SSE", regression.sse), ("RMSE", regression.rmse), ("Sy.x", regression.sy_x), ("R^2", regression.r_sq)] {
+                        ui.label(name);
+                        ui.label(format_value(value));
+                        ui.end_row();
+                    }
+                });
+                ui.add_space(10.0);
+
+                ui.horizontal(|ui| {
+                    let button = ui.add_enabled(regression.is_adjusted(), egui::Button::new(tr("reset-fit")));
+                    Self::dashed_outline(ui, &button);
+                    if button.clicked() {
+                        regression.set_parameters(regression.fitted_abcd);
+                        adjusted = true;
+                    }
+                    if regression.is_adjusted() {
+                        ui.add_space(10.0);
+                        ui.label(RichText::new(tr("fit-adjusted")).color(FLAG_COLOR));
+                    }
+                });
+            });
+
+        if adjusted {
+            self.microplate.audit_fit(regression, &self.settings.operator);
+        }
+    }
+}
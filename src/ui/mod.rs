@@ -1,2 +1,38 @@
+pub mod acquisition;
+pub mod analysis;
+#[cfg(all(feature = "archive", not(target_arch = "wasm32")))]
+pub mod archive;
 pub mod assay;
-pub mod plot;
\ No newline at end of file
+pub mod back_calculation;
+pub mod diagnostics;
+pub mod exclusion;
+pub mod audit;
+pub mod bootstrap;
+pub mod command_palette;
+pub mod compare;
+pub mod export;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod export_hook;
+pub mod groups;
+pub mod history;
+pub mod log_console;
+pub mod manifest;
+pub mod manual_fit;
+pub mod options;
+pub mod paste_layout;
+pub mod paste_values;
+pub mod plot;
+pub mod protocol;
+pub mod reference;
+pub mod replicates;
+pub mod run_metadata;
+pub mod sample_metadata;
+pub mod report;
+pub mod self_test;
+pub mod settings;
+pub mod shortcuts;
+pub mod simulation;
+pub mod status_bar;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod watch_folder;
+pub mod well_swap;
@@ -0,0 +1,252 @@
+use eframe::egui::{self, DragValue, Id, RichText};
+
+use crate::{acquisition::ReadingOrder, cutoff::*, drift::*, files::FileAction, i18n::*, layout::ALPHABET, logistic_regression::*, path_length::*, Elisa};
+
+impl Elisa {
+    pub fn fit_options_window(&mut self, ctx: &egui::Context) {
+        let (width, height) = (self.microplate.width, self.microplate.height);
+        let path_length_reads = self.microplate.path_length_reads();
+        let drift = self.microplate.drift();
+        let drift_comparison = drift.map(|_| self.microplate.drift_comparison());
+        let options = &mut self.microplate.fit_options;
+        let cutoff = &mut self.microplate.cutoff;
+        let regression = &self.regression;
+        let locked = self.microplate.locked;
+        let opened_files = &self.opened_files;
+
+        egui::Window::new(tr("fit-options"))
+            .id(Id::new("Fit Options"))
+            .open(&mut self.show_fit_options)
+            .resizable(false)
+            .collapsible(false)
+            .frame(egui::Frame::window(&ctx.style()).inner_margin(10.0))
+            .show(ctx, |ui| {
+                if locked {
+                    ui.disable();
+                }
+                ui.spacing_mut().item_spacing.y = 4.0;
+                ui.label(RichText::new(tr("blank-correction")).strong());
+                for correction in BlankCorrection::ALL {
+                    ui.radio_value(&mut options.blank_correction, correction, correction.description());
+                }
+                if matches!(options.blank_correction, BlankCorrection::PerRow | BlankCorrection::PerColumn) {
+                    ui.label(RichText::new(tr("blank-per-row-note")).size(11.0));
+                }
+
+                ui.add_space(10.0);
+                ui.label(RichText::new(tr("path-length")).strong());
+                for correction in PathLengthCorrection::ALL {
+                    if ui.radio(options.path_length.same_mode(&correction), correction.description()).clicked() && !options.path_length.same_mode(&correction) {
+                        options.path_length = correction;
+                    }
+                }
+                match &mut options.path_length {
+                    PathLengthCorrection::None => (),
+                    PathLengthCorrection::Factor(factor) => {
+                        ui.horizontal(|ui| {
+                            ui.label(tr("path-length-factor-value")).on_hover_text(tr("path-length-factor-hover"));
+                            let drag_value = DragValue::new(factor).speed(0.01).range(0.01..=100.0).prefix("× ")
+                                .custom_formatter(|n, _| format_number(n))
+                                .custom_parser(parse_decimal);
+                            let drag_value = ui.add(drag_value);
+                            Self::dashed_outline(ui, &drag_value);
+                        });
+                    },
+                    PathLengthCorrection::ReferenceRead { k_factor } => {
+                        ui.horizontal(|ui| {
+                            ui.label(tr("path-length-k-factor")).on_hover_text(tr("path-length-k-factor-hover"));
+                            let drag_value = DragValue::new(k_factor).speed(0.001).range(0.001..=10.0)
+                                .custom_formatter(|n, _| format_number(n))
+                                .custom_parser(parse_decimal);
+                            let drag_value = ui.add(drag_value);
+                            Self::dashed_outline(ui, &drag_value);
+                        });
+                        ui.horizontal(|ui| {
+                            let button = ui.button(tr("path-length-read"));
+                            Self::dashed_outline(ui, &button);
+                            if button.clicked() {
+                                let filters = [(tr("excel-spreadsheet"), &["xlsx"][..]), (tr("plate-reader-export"), &["txt", "csv"][..])];
+                                opened_files.pick(ui.ctx(), FileAction::PathLengthRead, &filters);
+                            }
+                            ui.label(RichText::new(tr_args("path-length-reads", &[("count", path_length_reads.into())])).size(11.0));
+                        });
+                    },
+                }
+
+                ui.add_space(10.0);
+                ui.label(RichText::new(tr("drift-correction")).strong()).on_hover_text(tr("drift-correction-hover"));
+                for correction in DriftCorrection::ALL {
+                    ui.radio_value(&mut options.drift_correction, correction, correction.description());
+                }
+                if options.drift_correction != DriftCorrection::None {
+                    ui.horizontal(|ui| {
+                        ui.label(tr("reading-order"));
+                        for order in ReadingOrder::ALL {
+                            ui.radio_value(&mut options.reading_order, order, order.description());
+                        }
+                    });
+                    match (drift, drift_comparison) {
+                        (Some(drift), Some(comparison)) => {
+                            let percent = |cv: Option<f64>| cv.map(|cv| format!("{} %", format_decimal(cv, 1))).unwrap_or("-".into());
+                            let value = |sd: Option<f64>| sd.map(format_value).unwrap_or("-".into());
+                            ui.label(RichText::new(tr_args("drift-estimate", &[
+                                ("total", format_value(drift.total()).into()),
+                                ("wells", drift.wells.into()),
+                            ])).size(11.0));
+                            ui.label(RichText::new(tr_args("drift-comparison", &[
+                                ("cv_before", percent(comparison.cv_before).into()),
+                                ("cv_after", percent(comparison.cv_after).into()),
+                                ("sd_before", value(comparison.blank_sd_before).into()),
+                                ("sd_after", value(comparison.blank_sd_after).into()),
+                            ])).size(11.0));
+                        },
+                        _ => { ui.label(RichText::new(tr("drift-not-estimated")).size(11.0)); },
+                    }
+                }
+
+                ui.add_space(10.0);
+                ui.label(RichText::new(tr("control")).strong());
+                for handling in ControlHandling::ALL {
+                    ui.radio_value(&mut options.control_handling, handling, handling.description());
+                }
+                if options.control_handling == ControlHandling::Anchor {
+                    ui.horizontal(|ui| {
+                        let mut automatic = options.zero_dose.is_none();
+                        ui.checkbox(&mut automatic, tr("automatic-zero-dose"))
+                            .on_hover_text(tr("automatic-zero-dose-hover"));
+                        if automatic {
+                            options.zero_dose = None;
+                        } else {
+                            let zero_dose = options.zero_dose.get_or_insert(0.01);
+                            ui.add_space(10.0);
+                            let drag_value = DragValue::new(zero_dose).speed(0.001).range(f64::MIN_POSITIVE..=f64::MAX)
+                                .custom_formatter(|n, _| format_number(n))
+                                .custom_parser(parse_decimal);
+                            let drag_value = ui.add(drag_value);
+                            Self::dashed_outline(ui, &drag_value);
+                        }
+                    });
+                }
+
+                ui.add_space(10.0);
+                ui.label(RichText::new(tr("weighting")).strong());
+                for weighting in Weighting::ALL {
+                    ui.radio_value(&mut options.weighting, weighting, weighting.description());
+                }
+
+                ui.add_space(10.0);
+                ui.label(RichText::new(tr("initial-guesses")).strong());
+                ui.horizontal(|ui| {
+                    let mut starts = options.extra_starts + 1;
+                    let drag_value = ui.add(DragValue::new(&mut starts).range(1..=MAX_EXTRA_STARTS + 1))
+                        .on_hover_text(tr("initial-guesses-hover"));
+                    Self::dashed_outline(ui, &drag_value);
+                    options.extra_starts = starts - 1;
+                    ui.label(tr("starts"));
+                });
+
+                ui.add_space(10.0);
+                ui.label(RichText::new(tr("extrapolation")).strong());
+                ui.horizontal(|ui| {
+                    let mut extrapolate = options.extrapolation.is_some();
+                    ui.checkbox(&mut extrapolate, tr("extrapolate"))
+                        .on_hover_text(tr("extrapolate-hover"));
+                    if !extrapolate {
+                        options.extrapolation = None;
+                    } else {
+                        let percent = options.extrapolation.get_or_insert(10.0);
+                        ui.add_space(10.0);
+                        let drag_value = DragValue::new(percent).speed(0.5).range(0.0..=100.0).suffix(" %")
+                            .custom_formatter(|n, _| format_number(n))
+                            .custom_parser(parse_decimal);
+                        let drag_value = ui.add(drag_value);
+                        Self::dashed_outline(ui, &drag_value);
+                    }
+                });
+
+                ui.add_space(10.0);
+                ui.label(RichText::new(tr("standard-curve")).strong());
+                ui.checkbox(&mut options.allow_non_monotonic, tr("allow-non-monotonic"))
+                    .on_hover_text(tr("allow-non-monotonic-hover"));
+
+                ui.add_space(10.0);
+                ui.label(RichText::new(tr("analyzed-wells")).strong());
+                let mut partial = options.region.is_some();
+                ui.checkbox(&mut partial, tr("analyze-region"))
+                    .on_hover_text(tr("analyze-region-hover"));
+                if !partial {
+                    options.region = None;
+                } else {
+                    let region = options.region.get_or_insert_with(|| PlateRegion::whole_plate(width, height));
+                    let row_name = |n: f64, _| ALPHABET.get(n as usize).map(char::to_string).unwrap_or_default();
+                    let row_number = |text: &str| {
+                        let letter = text.trim().chars().next()?.to_ascii_uppercase();
+                        ALPHABET.iter().position(|&c| c == letter).map(|row| row as f64)
+                    };
+                    egui::Grid::new("Region").num_columns(4).spacing([10.0, 4.0]).show(ui, |ui| {
+                        ui.label(tr("region-rows"));
+                        for row in [&mut region.first_row, &mut region.last_row] {
+                            let drag_value = DragValue::new(row).speed(0.05).range(0..=height - 1)
+                                .custom_formatter(row_name)
+                                .custom_parser(row_number);
+                            let drag_value = ui.add(drag_value);
+                            Self::dashed_outline(ui, &drag_value);
+                        }
+                        ui.end_row();
+                        ui.label(tr("region-columns"));
+                        for column in [&mut region.first_column, &mut region.last_column] {
+                            let drag_value = DragValue::new(column).speed(0.05).range(0..=width - 1)
+                                .custom_formatter(|n, _| format!("{}", n as usize + 1))
+                                .custom_parser(|text| text.trim().parse::<usize>().ok().and_then(|n| n.checked_sub(1)).map(|n| n as f64));
+                            let drag_value = ui.add(drag_value);
+                            Self::dashed_outline(ui, &drag_value);
+                        }
+                        ui.end_row();
+                    });
+                    // the last row or column can't come before the first
+                    region.last_row = region.last_row.max(region.first_row);
+                    region.last_column = region.last_column.max(region.first_column);
+                    ui.label(RichText::new(tr_args("region-wells", &[("region", region.name().into())])).size(11.0));
+                }
+
+                // classification doesn't change the fit, the results follow right away
+                ui.add_space(10.0);
+                ui.label(RichText::new(tr("cutoff")).strong());
+                let mut classify = cutoff.is_some();
+                ui.checkbox(&mut classify, tr("classify"))
+                    .on_hover_text(tr("classify-hover"));
+                if !classify {
+                    *cutoff = None;
+                    return
+                }
+                let cutoff = cutoff.get_or_insert_with(Cutoff::default);
+                for mode in CutoffMode::ALL {
+                    ui.radio_value(&mut cutoff.mode, mode, mode.description());
+                }
+                egui::Grid::new("Cutoff").num_columns(2).spacing([10.0, 4.0]).show(ui, |ui| {
+                    let (label, prefix) = match cutoff.mode {
+                        CutoffMode::Concentration => (tr("cutoff-value"), ""),
+                        CutoffMode::ControlFactor => (tr("cutoff-factor"), "× "),
+                    };
+                    ui.label(label);
+                    let drag_value = DragValue::new(&mut cutoff.value).speed(0.01).range(0.0..=f64::MAX).prefix(prefix)
+                        .custom_formatter(|n, _| format_number(n))
+                        .custom_parser(parse_decimal);
+                    let drag_value = ui.add(drag_value);
+                    Self::dashed_outline(ui, &drag_value);
+                    ui.end_row();
+
+                    ui.label(tr("cutoff-borderline")).on_hover_text(tr("cutoff-borderline-hover"));
+                    let drag_value = DragValue::new(&mut cutoff.borderline).speed(0.5).range(0.0..=100.0).prefix("± ").suffix(" %")
+                        .custom_formatter(|n, _| format_number(n))
+                        .custom_parser(parse_decimal);
+                    let drag_value = ui.add(drag_value);
+                    Self::dashed_outline(ui, &drag_value);
+                    ui.end_row();
+                });
+                if let Some(regression) = regression {
+                    ui.label(RichText::new(cutoff.threshold_text(regression)).size(11.0));
+                }
+            });
+    }
+}
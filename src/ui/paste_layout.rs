@@ -0,0 +1,83 @@
+use eframe::egui::{self, Grid, Id, RichText, TextEdit};
+
+use crate::{app::SerdeError, i18n::*, logistic_regression::*, ui::plot::{rgb, FLAG_COLOR}, Elisa};
+
+impl Elisa {
+    /// Plate layout typed or pasted as a grid of tokens, with a preview before it replaces the layout
+    pub fn paste_layout_window(&mut self, ctx: &egui::Context) {
+        if !self.show_paste_layout { return }
+
+        let locked = self.microplate.locked;
+        let parsed = self.microplate.parse_layout_grid(&self.layout_text);
+        let microplate = &self.microplate;
+        let text = &mut self.layout_text;
+        let mut apply = false;
+
+        egui::Window::new(tr("paste-layout"))
+            .id(Id::new("Paste Layout"))
+            .open(&mut self.show_paste_layout)
+            .resizable(false)
+            .collapsible(false)
+            .frame(egui::Frame::window(&ctx.style()).inner_margin(10.0))
+            .show(ctx, |ui| {
+                if locked {
+                    ui.disable();
+                }
+
+                ui.label(tr("paste-layout-help"));
+                ui.add_space(5.0);
+                let text_edit = ui.add(TextEdit::multiline(text)
+                    .font(egui::TextStyle::Monospace)
+                    .desired_rows(microplate.height)
+                    .desired_width(420.0)
+                    .hint_text("S1 S1 U1 U1 …\nS2 S2 U2 U2 …"));
+                Self::dashed_outline(ui, &text_edit);
+                ui.add_space(10.0);
+
+                let layout = match &parsed {
+                    Ok(layout) => layout,
+                    Err(error) => {
+                        ui.label(RichText::new(error.to_string()).color(FLAG_COLOR));
+                        return
+                    },
+                };
+
+                // preview of the plate as it will be, the same colors as the plate map
+                Grid::new("Paste Layout Preview").spacing([4.0, 4.0]).show(ui, |ui| {
+                    for row in 0..microplate.height {
+                        for column in 0..microplate.width {
+                            let name = microplate.well_name(column * microplate.height + row);
+                            let well = layout.wells.iter().find(|well| well.well == name);
+                            let typ = well.map_or(SampleType::Unused, |well| well.typ);
+                            let token = match (typ, well.and_then(|well| well.group)) {
+                                (SampleType::Standard, Some(group)) => format!("S{group}"),
+                                (SampleType::Unknown, Some(group)) => format!("U{group}"),
+                                (SampleType::Blank, _) => "B".to_string(),
+                                (SampleType::Control, _) => "C".to_string(),
+                                _ => "-".to_string(),
+                            };
+                            ui.label(RichText::new(format!("{token:^4}")).monospace().size(11.0).background_color(rgb(typ.color())));
+                        }
+                        ui.end_row();
+                    }
+                });
+                ui.add_space(10.0);
+
+                let button = ui.add_enabled(!layout.wells.is_empty(), egui::Button::new(tr("apply-layout")));
+                Self::dashed_outline(ui, &button);
+                apply = button.on_hover_text(tr("apply-layout-hover")).clicked();
+            });
+
+        if !apply { return }
+        let Ok(layout) = parsed else { return };
+        match self.microplate.apply_layout(&layout) {
+            Ok(()) => {
+                self.standards_textfield.iter_mut().for_each(String::clear);
+                self.hidden_labels.clear();
+                self.selected_sample = None;
+                self.show_paste_layout = false;
+            },
+            Err(error) => self.notifications.push(SerdeError::InvalidLayout(error)),
+        }
+    }
+}
@@ -0,0 +1,118 @@
+use eframe::egui::{self, ComboBox, Grid, Id, RichText, ScrollArea};
+
+use crate::{clipboard::*, i18n::*, layout::ALPHABET, ui::plot::FLAG_COLOR, ElisaTab, Elisa};
+
+impl Elisa {
+    /// Values pasted in one go, in the layout they were detected in or the one chosen, with a preview before they replace the values of the plate
+    pub fn paste_values_window(&mut self, ctx: &egui::Context) {
+        // a paste with no cell to take it, e.g. after clicking on the plate
+        if self.current_tab == ElisaTab::Edit && !self.microplate.locked && !ctx.wants_keyboard_input() {
+            let pasted = ctx.input(|i| i.events.iter().find_map(|event| match event {
+                egui::Event::Paste(paste) if detect_paste(paste, &self.microplate).is_some() => Some(paste.clone()),
+                _ => None,
+            }));
+            if pasted.is_some() {
+                self.pasted_values = pasted;
+                self.paste_layout = None;
+            }
+        }
+        let Some(text) = &self.pasted_values else { return };
+
+        let detected = detect_paste(text, &self.microplate);
+        let layout = self.paste_layout.or(detected.as_ref().map(|pasted| pasted.layout));
+        let pasted = layout.and_then(|layout| read_paste(text, &self.microplate, layout));
+
+        let locked = self.microplate.locked;
+        let microplate = &self.microplate;
+        let chosen = &mut self.paste_layout;
+        let mut open = true;
+        let mut apply = false;
+        let mut cancel = false;
+
+        egui::Window::new(tr("paste-values"))
+            .id(Id::new("Paste Values"))
+            .open(&mut open)
+            .resizable(false)
+            .collapsible(false)
+            .frame(egui::Frame::window(&ctx.style()).inner_margin(10.0))
+            .show(ctx, |ui| {
+                if locked {
+                    ui.disable();
+                }
+
+                match &detected {
+                    Some(detected) => ui.label(tr_args("paste-detected", &[("layout", detected.layout.description(microplate).into())])),
+                    None => ui.label(tr("paste-not-detected")),
+                };
+                ui.add_space(10.0);
+
+                let selected_text = layout.map(|layout| layout.description(microplate)).unwrap_or_default();
+                let combo_box = ComboBox::from_id_salt("Paste Layout")
+                    .selected_text(selected_text)
+                    .show_ui(ui, |ui| {
+                        for option in PasteLayout::ALL {
+                            if ui.selectable_label(layout == Some(option), option.description(microplate)).clicked() {
+                                *chosen = Some(option);
+                            }
+                        }
+                    });
+                Self::dashed_outline(ui, &combo_box.response);
+                ui.add_space(10.0);
+
+                let Some(pasted) = &pasted else {
+                    ui.label(RichText::new(tr("paste-layout-mismatch")).color(FLAG_COLOR));
+                    return
+                };
+
+                // the plate as it will be, wells the paste leaves out keep their values
+                let mut preview: Vec<Option<f64>> = microplate.samples.iter().map(|sample| sample.value).collect();
+                let mut changed = vec![false; preview.len()];
+                for &(index, value) in &pasted.values {
+                    preview[index] = value;
+                    changed[index] = true;
+                }
+                ScrollArea::both().max_width(600.0).max_height(300.0).show(ui, |ui| {
+                    Grid::new("Paste Values Preview").spacing([6.0, 2.0]).striped(true).show(ui, |ui| {
+                        ui.label("");
+                        for column in 1..=microplate.width {
+                            ui.label(RichText::new(column.to_string()).size(11.0).strong());
+                        }
+                        ui.end_row();
+                        for row in 0..microplate.height {
+                            ui.label(RichText::new(ALPHABET[row % 26].to_string()).size(11.0).strong());
+                            for column in 0..microplate.width {
+                                let index = column * microplate.height + row;
+                                let text = preview[index].map(|value| format_decimal(value, 3)).unwrap_or_else(|| "·".to_string());
+                                let text = RichText::new(text).size(11.0);
+                                ui.label(if changed[index] { text } else { text.weak() });
+                            }
+                            ui.end_row();
+                        }
+                    });
+                });
+                ui.add_space(5.0);
+                ui.label(RichText::new(tr_args("paste-wells", &[("count", pasted.values.len().into())])).size(11.0));
+                ui.add_space(10.0);
+
+                ui.horizontal(|ui| {
+                    let button = ui.button(tr("apply-paste"));
+                    Self::dashed_outline(ui, &button);
+                    apply = button.clicked();
+                    ui.add_space(10.0);
+                    cancel = ui.button(tr("cancel")).clicked();
+                });
+            });
+
+        if apply {
+            if let Some(pasted) = pasted {
+                for (index, value) in pasted.values {
+                    self.microplate.assign_value(index, value);
+                }
+            }
+        }
+        if apply || cancel || !open {
+            self.pasted_values = None;
+            self.paste_layout = None;
+        }
+    }
+}
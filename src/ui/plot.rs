@@ -1,88 +1,446 @@
-use std::path::PathBuf;
+use std::collections::HashSet;
 
-use eframe::egui::{self, vec2, Color32, Label, RichText, Ui, UserData};
+use eframe::egui::{self, vec2, Align, Align2, Color32, ComboBox, FontId, Pos2, Rect, ScrollArea, Vec2b, Label, Layout, Response, RichText, Sense, TextEdit, Ui, UiBuilder, UserData, Vec2};
 use egui_extras::{Column, TableBuilder};
-use egui_plot::{AxisTransforms, Line, Plot, PlotPoint, PlotPoints, Points, Text};
-use image::{ImageBuffer, Pixel, Rgba, RgbaImage};
-use pdf_writer::{Content, Finish, Name, Pdf, Ref, Str, TextStr};
+use egui_plot::{AxisTransform, AxisTransforms, Corner, HLine, Legend, Line, LineStyle, MarkerShape, Plot, PlotBounds, PlotPoint, PlotPoints, PlotUi, Points, Text, VLine};
+use image::{codecs::png::{CompressionType, FilterType as PngFilterType, PngEncoder}, imageops, ExtendedColorType, ImageBuffer, ImageEncoder, Rgba, RgbaImage};
+use pdf_writer::{Content, Filter, Finish, Name, Pdf, Ref, Str, TextStr};
 
-use crate::{logistic_regression::*, Elisa};
+use crate::{app::{AppError, SerdeError}, barcode::code128, cutoff::Classification, exclusion::ExclusionReason, expression::*, files::save_file, grading::CurveQuality, history::CurveRecord, i18n::*, layout::ALPHABET, logistic_regression::*, results_filter::*, settings::*, ui::{exclusion::sample_name, shortcuts::shortcut_tooltip}, Elisa};
+
+pub const MIN_PLOT_SIZE: f32 = 300.0;
+pub const SIDE_PANEL_WIDTH: f32 = 300.0;
+pub const FLAG_COLOR: Color32 = Color32::from_rgb(0xD0, 0x30, 0x30);
+
+/// Colors of the samples and grades, which the library keeps as RGB
+pub fn rgb([r, g, b]: [u8; 3]) -> Color32 {
+    Color32::from_rgb(r, g, b)
+}
+
+fn recovery_range_text(id: &str) -> String {
+    tr_args(id, &[
+        ("min", format_number(*RECOVERY_RANGE.start()).into()),
+        ("max", format_number(*RECOVERY_RANGE.end()).into()),
+    ])
+}
+
+/// Encodes text for the standard PDF fonts, which use WinAnsiEncoding.
+/// Latin-1 characters map to the same byte, anything else is replaced.
+fn pdf_text(text: &str) -> Vec<u8> {
+    text.chars().map(|char| u8::try_from(u32::from(char)).unwrap_or(b'?')).collect()
+}
+
+/// Labels further from their point than this get a leader line
+const LEADER_DISTANCE: f32 = 12.0;
+
+/// Greedy label placement in screen space: every label takes the first spot around its anchor
+/// that stays inside `bounds` and is clear of the points and the labels placed before it,
+/// or the spot with the least overlap if there is none.
+fn place_labels(anchors: &[Pos2], sizes: &[Vec2], points: &[Pos2], bounds: Rect) -> Vec<Rect> {
+    let point_rects: Vec<Rect> = points.iter().map(|&point| Rect::from_center_size(point, Vec2::splat(10.0))).collect();
+    let mut placed: Vec<Rect> = Vec::new();
+
+    for (&anchor, &size) in anchors.iter().zip(sizes) {
+        let side = size.x / 2.0 + 8.0;
+        let candidates = [
+            vec2(0.0, -15.0), vec2(0.0, 15.0), vec2(side, 0.0), vec2(-side, 0.0),
+            vec2(side, -15.0), vec2(-side, -15.0), vec2(side, 15.0), vec2(-side, 15.0),
+            vec2(0.0, -30.0), vec2(0.0, 30.0), vec2(0.0, -45.0), vec2(0.0, 45.0),
+        ];
+        let intersection = |a: Rect, b: Rect| {
+            let intersection = a.intersect(b);
+            if intersection.is_positive() { intersection.area() } else { 0.0 }
+        };
+        let overlap = |rect: &Rect| -> f32 {
+            let outside = rect.area() - intersection(*rect, bounds);
+            let covered: f32 = placed.iter().chain(&point_rects).map(|other| intersection(*rect, *other)).sum();
+            outside + covered
+        };
+
+        let rects = candidates.map(|offset| Rect::from_center_size(anchor + offset, size + vec2(4.0, 2.0)));
+        let rect = rects.iter().find(|rect| overlap(rect) == 0.0).copied()
+            .or_else(|| rects.iter().min_by(|a, b| overlap(a).total_cmp(&overlap(b))).copied())
+            .unwrap_or(rects[0]);
+        placed.push(rect);
+    }
+    placed
+}
+
+/// Which part of the curve the plot shows
+#[derive(Clone, Copy, Debug)]
+pub enum PlotView {
+    Interactive, // zoom and pan, the view is kept from frame to frame
+    Reset,
+    Fixed(PlotBounds), // e.g. the view on screen for an export
+}
+
+/// Vertical bar of ± `spread` around `mean`, with caps of the same width on the log axis
+fn error_bar(ui: &mut PlotUi, dose: f64, mean: f64, spread: f64, color: Color32, name: &str) {
+    let (low, high) = (mean - spread, mean + spread);
+    let cap = 10f64.powf(0.03);
+    for points in [[[dose, low], [dose, high]], [[dose / cap, low], [dose * cap, low]], [[dose / cap, high], [dose * cap, high]]] {
+        ui.line(Line::new(PlotPoints::new(points.to_vec())).allow_hover(false).color(color).name(name));
+    }
+}
+
+/// What the plot shows besides the fit, kept by the app rather than in the settings
+#[derive(Clone, Copy)]
+pub struct PlotLayers<'a> {
+    pub hidden_labels: &'a HashSet<usize>, // unknown groups without a label
+    pub reference: Option<&'a CurveRecord>, // drawn dashed to compare with the current curve
+}
+
+/// Draws the standard curve with its standards and unknowns, also used to render the plot offscreen.
+/// Items of the same kind share a name, so each kind gets one legend entry. Returns the bounds that were shown.
+pub fn plot_regression(regression: &Regression, ui: &mut Ui, size: Vec2, options: PlotOptions, layers: PlotLayers, scale: PlotScale, view: PlotView) -> (Response, PlotBounds) {
+    let Regression { abcd, unknowns, standards, standard_replicates, raw_standards, raw_standard_replicates, standard_colors, anchor, ..} = regression;
+    let PlotLayers { hidden_labels, reference } = layers;
+
+    // the curve is fitted to the corrected values, over the raw ones it's moved up by the blank
+    let raw = options.raw_values;
+    let (standards, standard_replicates) = if raw { (raw_standards, raw_standard_replicates) } else { (standards, standard_replicates) };
+    let offset = if raw { regression.blank_offset() } else { 0.0 };
+    let &(a, b, c, d) = abcd;
+    let (a, d) = (a + offset, d + offset);
+    
+    let stroke = ui.visuals().noninteractive().bg_stroke;
+    let color = ui.style().noninteractive().text_color();
+
+    let four_pl = move |x: f64| {
+        d + ((a - d) / (1.0 + (x/c).powf(b)))
+    };
+
+    let transform = |scale: AxisScale| match scale {
+        AxisScale::Linear => AxisTransform::Linear,
+        AxisScale::Logarithmic => AxisTransform::Logarithmic(10.0),
+    };
+    let axis_transforms = AxisTransforms::new(transform(scale.x), transform(scale.y));
+    
+    let interactive = matches!(view, PlotView::Interactive) && !options.fit_standards;
+    let plot = Plot::new("4PL Plot")
+        .allow_zoom(interactive)
+        .allow_drag(interactive)
+        .allow_scroll(interactive)
+        .allow_double_click_reset(interactive)
+        .show_x(false)
+        .show_y(false)
+        .axis_transforms(axis_transforms)
+        .x_axis_label(tr("dose"))
+        .y_axis_label(tr("measurement"))
+        .show_background(false)
+        .legend(Legend::default().position(Corner::LeftTop).background_alpha(0.7))
+        .height(size.y)
+        .width(size.x)
+        .show(ui, |ui| {
+        // Curve
+        let line_points = PlotPoints::from_explicit_callback(four_pl, .., 5000);
+        let line = Line::new(line_points)
+            .allow_hover(false)
+            .color(color)
+            .name(tr("curve"));
+        ui.line(line);
+
+        // Reference curve, e.g. of the kit lot, to compare the shape and position with
+        if let Some(&CurveRecord { abcd: (a, b, c, d), .. }) = reference {
+            let (a, d) = (a + offset, d + offset);
+            let reference_points = PlotPoints::from_explicit_callback(move |x: f64| d + ((a - d) / (1.0 + (x/c).powf(b))), .., 5000);
+            ui.line(Line::new(reference_points)
+                .allow_hover(false)
+                .color(color.gamma_multiply(0.6))
+                .style(LineStyle::dashed_loose())
+                .name(tr("reference-curve")));
+        }
+
+        // Below the lowest and above the highest standard that isn't an anchor, unknowns are reported as out of range
+        let (lowest, highest) = regression.quantitation_range();
+        for dose in [lowest, highest].into_iter().filter(|dose| dose.is_finite()) {
+            ui.vline(VLine::new(dose)
+                .color(color.gamma_multiply(0.5))
+                .style(LineStyle::dashed_loose())
+                .name(tr("lod-lines")));
+        }
+    
+        // Standards replicates, smaller and fainter than the means
+        if options.replicates {
+            for ((&(dose, _mean), values), &color) in standards.iter().zip(standard_replicates).zip(standard_colors) {
+                let points: Vec<[f64; 2]> = values.iter().map(|&value| [dose, value]).collect();
+                ui.points(Points::new(points).radius(2.5).color(rgb(color).gamma_multiply(0.5)).name(tr("replicates")));
+            }
+        }
+
+        // Error bars from the replicates of the standards and the unknowns
+        let error_bars = tr_args("error-bars-legend", &[("bars", options.error_bars.description().into())]);
+        for ((&(dose, mean), values), &color) in standards.iter().zip(standard_replicates).zip(standard_colors) {
+            if let Some(spread) = options.error_bars.spread(values) {
+                error_bar(ui, dose, mean, spread, rgb(color), &error_bars);
+            }
+        }
+        for unknown in unknowns.iter().filter(|unknown| unknown.concentration.is_finite()) {
+            if let Some(spread) = options.error_bars.spread(unknown.signal_replicates(raw)) {
+                let color = if unknown.in_range() { rgb(unknown.color) } else { FLAG_COLOR };
+                error_bar(ui, unknown.concentration, unknown.signal(raw), spread, color, &error_bars);
+            }
+        }
+
+        // Standards points, anchors as hollow diamonds
+        for (i, (&(dose, value), &color)) in standards.iter().zip(standard_colors).enumerate() {
+            let point = Points::new([dose, value]).radius(5.0).color(rgb(color));
+            let point = if regression.is_anchor(i) {
+                point.name(tr("anchor-standards")).shape(MarkerShape::Diamond).filled(false).radius(6.0)
+            } else {
+                point.name(tr("standards"))
+            };
+            ui.points(point);
+        }
+    
+        // Control anchor point
+        if let &Some((dose, value)) = anchor {
+            let point = Points::new([dose, value + offset])
+                .name(tr("sample-control"))
+                .radius(5.0)
+                .color(rgb(SampleType::Control.color()));
+            ui.points(point);
+        }
+
+        // Unknowns points
+        let white = Color32::from_hex("#FBFBFE").unwrap();
+        let mut labels = Vec::new();
+        for unknown in unknowns {
+            let (dose, value) = (unknown.concentration, unknown.signal(raw));
+            if !dose.is_finite() { continue } // past an asymptote, there is no point on the curve
+            let (color, kind) = if unknown.in_range() {
+                (rgb(unknown.color), tr("unknowns"))
+            } else {
+                (FLAG_COLOR, tr("unknowns-out-of-range"))
+            };
+
+            let point = Points::new([dose, value])
+                .name(kind)
+                .radius(5.0)
+                .color(color);
+
+            ui.points(point);
+
+            if hidden_labels.contains(&unknown.group) { continue }
+            labels.push((ui.screen_from_plot(PlotPoint::new(dose, value)), unknown.name()));
+        }
+
+        // Labels go next to their point where they don't cover another label or point
+        let points: Vec<Pos2> = standards.iter().map(|&(dose, value)| ui.screen_from_plot(PlotPoint::new(dose, value)))
+            .chain(labels.iter().map(|&(point, _)| point))
+            .collect();
+        let sizes: Vec<Vec2> = labels.iter().map(|(_, name)| {
+            ui.ctx().fonts(|fonts| fonts.layout_no_wrap(name.clone(), FontId::proportional(11.0), color).size())
+        }).collect();
+        let anchors: Vec<Pos2> = labels.iter().map(|&(point, _)| point).collect();
+        let rects = place_labels(&anchors, &sizes, &points, ui.response().rect);
+        for ((point, name), rect) in labels.into_iter().zip(rects) {
+            if rect.distance_to_pos(point) > LEADER_DISTANCE {
+                let end = rect.clamp(point);
+                let leader = [ui.plot_from_screen(point), ui.plot_from_screen(end)].map(|point| [point.x, point.y]);
+                ui.line(Line::new(PlotPoints::new(leader.to_vec())).allow_hover(false).color(color.gamma_multiply(0.4)));
+            }
+            ui.text(Text::new(
+                ui.plot_from_screen(rect.center()),
+                RichText::new(name).size(11.0).background_color(white.gamma_multiply(0.7))
+            ));
+        }
+
+        // Crosshair with a readout of the dose and measurement under the pointer
+        if let Some(pointer) = ui.response().hover_pos().filter(|_| options.crosshair != Crosshair::Off) {
+            let PlotPoint { x: dose, y: pointer_value } = ui.plot_from_screen(pointer);
+            let curve_value = four_pl(dose);
+            let value = if options.crosshair == Crosshair::SnapToCurve { curve_value } else { pointer_value };
+            if dose.is_finite() && value.is_finite() {
+                let crosshair_color = color.gamma_multiply(0.6);
+                ui.vline(VLine::new(dose).color(crosshair_color).allow_hover(false));
+                ui.hline(HLine::new(value).color(crosshair_color).allow_hover(false));
+                if options.crosshair == Crosshair::SnapToCurve {
+                    ui.points(Points::new([dose, value]).radius(4.0).color(color).allow_hover(false));
+                }
+
+                let mut readout = tr_args("crosshair-readout", &[("dose", format_value(dose).into()), ("value", format_value(value).into())]);
+                if options.crosshair == Crosshair::Free && curve_value.is_finite() {
+                    readout.push('\n');
+                    readout.push_str(&tr_args("crosshair-curve", &[("value", format_value(curve_value).into())]));
+                }
+                let position = ui.screen_from_plot(PlotPoint::new(dose, value)) + vec2(8.0, -8.0);
+                ui.text(Text::new(ui.plot_from_screen(position), RichText::new(readout).size(11.0).background_color(white.gamma_multiply(0.85)))
+                    .anchor(Align2::LEFT_BOTTOM)
+                    .allow_hover(false));
+            }
+        }
+
+        match view {
+            PlotView::Fixed(bounds) => ui.set_plot_bounds(bounds),
+            PlotView::Reset => ui.set_auto_bounds(Vec2b::TRUE),
+            PlotView::Interactive => if options.fit_standards {
+                if let Some(bounds) = standards_bounds(ui, standards) {
+                    ui.set_plot_bounds(bounds);
+                }
+            },
+        }
+        ui.plot_bounds()
+    });
+    ui.painter().rect_stroke(plot.response.rect, 0.0, stroke, eframe::egui::StrokeKind::Inside);
+    (plot.response, plot.inner)
+}
+
+/// Bounds around the standards with a margin. The bounds of the log axis aren't doses,
+/// so the doses are mapped through the screen, which is linear in the bounds either way.
+fn standards_bounds(ui: &PlotUi, standards: &[(f64, f64)]) -> Option<PlotBounds> {
+    let transform = ui.transform();
+    let (frame, bounds) = (transform.frame(), transform.bounds());
+    if frame.width() <= 0.0 || frame.height() <= 0.0 { return None }
+
+    let to_bounds = |dose: f64, value: f64| {
+        let screen = ui.screen_from_plot(PlotPoint::new(dose, value));
+        [
+            bounds.min()[0] + ((screen.x - frame.left()) / frame.width()) as f64 * bounds.width(),
+            bounds.max()[1] - ((screen.y - frame.top()) / frame.height()) as f64 * bounds.height(),
+        ]
+    };
+    let corners: Vec<[f64; 2]> = standards.iter().map(|&(dose, value)| to_bounds(dose, value)).collect();
+    let min = corners.iter().fold([f64::INFINITY; 2], |min, corner| [min[0].min(corner[0]), min[1].min(corner[1])]);
+    let max = corners.iter().fold([f64::NEG_INFINITY; 2], |max, corner| [max[0].max(corner[0]), max[1].max(corner[1])]);
+    if !(min[0] < max[0] && min[1] < max[1]) { return None }
+
+    let margin = [(max[0] - min[0]) * 0.1, (max[1] - min[1]) * 0.1];
+    Some(PlotBounds::from_min_max([min[0] - margin[0], min[1] - margin[1]], [max[0] + margin[0], max[1] + margin[1]]))
+}
 
 impl Elisa {
     pub fn plot(&mut self, ui: &mut Ui) {
+        let changed = self.plate_changed();
         let Some(regression) = self.regression.as_ref() else { return };
-        let Regression { abcd, unknowns, standards, ..} = regression;
 
-        let &(a, b, c, d) = abcd;
-        
-        let stroke = ui.visuals().noninteractive().bg_stroke;
-        let color = ui.style().noninteractive().text_color();
+        // leave room for the parameter tables on the right
+        let size = (ui.available_width() - SIDE_PANEL_WIDTH)
+            .min(ui.available_height() - 10.0)
+            .max(MIN_PLOT_SIZE);
 
-        let four_pl = move |x: f64| {
-            d + ((a - d) / (1.0 + (x/c).powf(b)))
-        };
+        ui.add_space(10.0);
+        let view = if std::mem::take(&mut self.reset_plot) { PlotView::Reset } else { PlotView::Interactive };
+        let layers = PlotLayers { hidden_labels: &self.hidden_labels, reference: self.reference_curve.as_ref() };
+        let (mut response, bounds) = plot_regression(regression, ui, Vec2::splat(size), self.settings.plot, layers, self.microplate.plot_scale, view);
+        self.plot_bounds = Some(bounds);
+        response.rect = response.rect.expand(10.0);
+        response.rect.min.x -= 40.0;
+        response.rect.max.y += 40.0;
+
+        // over the top right corner of the plot, the legend is on the left
+        if changed {
+            let rect = Rect::from_min_max(response.rect.min + vec2(50.0, 20.0), response.rect.max - vec2(20.0, 50.0));
+            let mut child = ui.new_child(UiBuilder::new().max_rect(rect).layout(Layout::right_to_left(Align::Min)));
+            egui::Frame::new()
+                .fill(child.visuals().window_fill)
+                .stroke((1.0, FLAG_COLOR))
+                .inner_margin(6.0)
+                .show(&mut child, |ui| {
+                    let button = ui.button(tr("fit-curve"));
+                    Self::dashed_outline(ui, &button);
+                    ui.label(RichText::new(tr("plate-changed")).color(FLAG_COLOR));
+                    if button.clicked() {
+                        self.fit_curve();
+                    }
+                });
+        }
+        self.plot_response = Some(response);
+    }
 
-        let axis_transforms = AxisTransforms::new(egui_plot::AxisTransform::Logarithmic(10.0), egui_plot::AxisTransform::Linear);
-        
+    pub fn plot_options(&mut self, ui: &mut Ui) {
+        if self.regression.is_none() { return }
+
+        let options = &mut self.settings.plot;
+        let mut changed = ui.checkbox(&mut options.replicates, tr("plot-replicates"))
+            .on_hover_text(tr("plot-replicates-hover"))
+            .changed();
+        ui.add_space(10.0);
+        changed |= ui.checkbox(&mut options.raw_values, tr("raw-values"))
+            .on_hover_text(tr("raw-values-hover"))
+            .changed();
+        ui.add_space(10.0);
+        let combo_box = ComboBox::from_id_salt("Error Bars")
+            .selected_text(tr_args("error-bars", &[("bars", options.error_bars.description().into())]))
+            .show_ui(ui, |ui| {
+                for error_bars in ErrorBars::ALL {
+                    changed |= ui.selectable_value(&mut options.error_bars, error_bars, error_bars.description()).changed();
+                }
+            });
+        Self::dashed_outline(ui, &combo_box.response);
+        ui.add_space(10.0);
+        let combo_box = ComboBox::from_id_salt("Crosshair")
+            .selected_text(tr_args("crosshair", &[("mode", options.crosshair.description().into())]))
+            .show_ui(ui, |ui| {
+                for crosshair in Crosshair::ALL {
+                    changed |= ui.selectable_value(&mut options.crosshair, crosshair, crosshair.description()).changed();
+                }
+            });
+        Self::dashed_outline(ui, &combo_box.response.on_hover_text(tr("crosshair-hover")));
         ui.add_space(10.0);
-        let mut plot = Plot::new("4PL Plot")
-            .show_x(false)
-            .show_y(false)
-            .axis_transforms(axis_transforms)
-            .x_axis_label("Dose")
-            .y_axis_label("Measurement")
-            .show_background(false)
-            .height(500.0)
-            .width(500.0)
-            .show(ui, |ui| {
-            // Curve
-            let line_points = PlotPoints::from_explicit_callback(four_pl, .., 5000);
-            let line = Line::new(line_points)
-                .allow_hover(false)
-                .color(color)
-                .name("4PL");
-            ui.line(line);
-        
-            // Standards points
-            for &(dose, value) in standards {
-                let color = SampleType::Standard.color();
-                let point = Points::new([dose, value])
-                    .radius(5.0)
-                    .color(color);
-                ui.points(point);
-            }
-        
-            // Unknowns points
-            let white = Color32::from_hex("#FBFBFE").unwrap();
-            let color = SampleType::Unknown.color();
-            for (i, (dose, value, label)) in unknowns.iter().enumerate() {
-                let name = if label.is_empty() {
-                    format!("Unknown {}", i + 1)
-                } else {
-                    label.to_owned()
-                };
 
-                let point = Points::new([*dose, *value])
-                    .name(name.clone())
-                    .radius(5.0)
-                    .color(color);
+        let Some(regression) = &self.regression else { return };
+        let hidden_labels = &mut self.hidden_labels;
+        let menu_button = ui.menu_button(tr("labels"), |ui| {
+            ui.horizontal(|ui| {
+                if ui.button(tr("show-all")).clicked() {
+                    hidden_labels.clear();
+                }
+                if ui.button(tr("hide-all")).clicked() {
+                    hidden_labels.extend(regression.unknowns.iter().map(|unknown| unknown.group));
+                }
+            });
+            ui.separator();
+            ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                for unknown in &regression.unknowns {
+                    let mut shown = !hidden_labels.contains(&unknown.group);
+                    if ui.checkbox(&mut shown, unknown.name()).changed() {
+                        if shown {
+                            hidden_labels.remove(&unknown.group);
+                        } else {
+                            hidden_labels.insert(unknown.group);
+                        }
+                    }
+                }
+            });
+        });
+        Self::dashed_outline(ui, &menu_button.response);
+        ui.add_space(10.0);
 
-                ui.points(point);
+        let plot_scale = &mut self.microplate.plot_scale;
+        let mut scale_changed = false;
+        for (scale, id) in [(&mut plot_scale.x, "x-axis"), (&mut plot_scale.y, "y-axis")] {
+            let combo_box = ComboBox::from_id_salt(id)
+                .selected_text(tr_args(id, &[("scale", scale.description().into())]))
+                .show_ui(ui, |ui| {
+                    for option in [AxisScale::Linear, AxisScale::Logarithmic] {
+                        scale_changed |= ui.selectable_value(scale, option, option.description()).changed();
+                    }
+                });
+            Self::dashed_outline(ui, &combo_box.response);
+            ui.add_space(10.0);
+        }
+        self.reset_plot |= scale_changed;
 
-                let mut point = ui.screen_from_plot(PlotPoint::new(*dose, *value));
-                point.y -= 15.0;
-                let point = ui.plot_from_screen(point);
-                ui.text(Text::new(
-                    point,
-                    RichText::new(name.clone()).size(11.0).background_color(white.gamma_multiply(0.7))
-                ));
+        let options = &mut self.settings.plot;
+        ui.add_enabled_ui(!options.fit_standards, |ui| {
+            let button = ui.button(tr("reset-view")).on_hover_text(tr("reset-view-hover"));
+            Self::dashed_outline(ui, &button);
+            if button.clicked() {
+                self.reset_plot = true;
             }
         });
-        ui.painter().rect_stroke(plot.response.rect, 0.0, stroke, eframe::egui::StrokeKind::Inside);
-        plot.response.rect = plot.response.rect.expand(10.0);
-        plot.response.rect.min.x -= 40.0;
-        plot.response.rect.max.y += 40.0;
-        self.plot_response = Some(plot.response);
+        ui.add_space(10.0);
+        let checkbox = ui.checkbox(&mut options.fit_standards, tr("fit-to-standards"))
+            .on_hover_text(tr("fit-to-standards-hover"));
+        if checkbox.changed() {
+            changed = true;
+            self.reset_plot = !options.fit_standards;
+        }
+
+        if changed && save_settings(&self.settings).is_err() {
+            self.notifications.push(SerdeError::CantWriteFile);
+        }
     }
 
     pub fn plot_parameters(&mut self, ui: &mut Ui) -> Option<()> {
@@ -98,6 +456,8 @@ impl Elisa {
         // let sy_x = regression.sy_x();
         // let rmse = regression.root_mean_squared_error();
         let list = [("a", a), ("b", b), ("c", c), ("d", d), ("MSE", mse), ("SSE", sse), ("Sy.x", sy_x), ("RMSE", rmse), ("R^2", r_sq)];
+        // the intervals belong to the automatic fit, not to a manual adjustment
+        let estimates = regression.diagnostics.parameter_estimates(regression.fitted_abcd).filter(|_| !regression.is_adjusted());
 
         self.plot_parameters = Some(list);
 
@@ -105,7 +465,15 @@ impl Elisa {
             let width = ui.available_width().max(20.0);
             ui.set_width(width);
 
-            ui.vertical_centered(|ui| ui.heading("Parameters"));
+            ui.vertical_centered(|ui| {
+                ui.heading(tr("parameters"));
+                let quality = CurveQuality::new(regression);
+                let grade = RichText::new(tr_args("curve-grade", &[("grade", quality.grade.name().into())])).size(16.0).strong().color(rgb(quality.grade.color()));
+                ui.label(grade).on_hover_text(quality.summary());
+                if regression.is_adjusted() {
+                    ui.label(RichText::new(tr("fit-adjusted")).size(11.0).color(FLAG_COLOR));
+                }
+            });
             ui.add_space(10.0);
             egui::Frame::new()
                 .fill(background).stroke(stroke)
@@ -118,12 +486,27 @@ impl Elisa {
                         // .max_scroll_height(100.0)
                         .min_scrolled_height(150.0)
                         .column(Column::auto())
+                        .columns(Column::auto(), if estimates.is_some() { 2 } else { 0 })
                         .column(Column::remainder())
                         .body(|body| {
                             body.rows(20.0, list.len(), |mut row| {
                                 let index = row.index();
                                 row.col(|ui| { ui.add(Label::new(list[index].0).selectable(true)); });
-                                row.col(|ui| { ui.add(Label::new(format!("{}", list[index].1)).selectable(true)); });
+                                row.col(|ui| { ui.add(Label::new(format_value(list[index].1)).selectable(true)); });
+                                let Some(estimates) = &estimates else { return };
+                                let estimate = estimates.get(index);
+                                row.col(|ui| {
+                                    if let Some(estimate) = estimate {
+                                        ui.add(Label::new(format!("± {}", format_value(estimate.standard_error))).selectable(true))
+                                            .on_hover_text(tr("standard-error"));
+                                    }
+                                });
+                                row.col(|ui| {
+                                    if let Some(estimate) = estimate {
+                                        ui.add(Label::new(format_interval(estimate.confidence_interval)).selectable(true))
+                                            .on_hover_text(tr("confidence-interval"));
+                                    }
+                                });
                             });
                         });
                 });
@@ -131,8 +514,21 @@ impl Elisa {
         Some(())
     }
 
-    pub fn backfit_concentrations(&self, ui: &mut Ui) {
-        let Some(Regression { unknowns, .. }) = &self.regression else { return };
+    pub fn backfit_concentrations(&mut self, ui: &mut Ui) {
+        let Some(regression @ Regression { unknowns, .. }) = &self.regression else { return };
+        let cutoff = &self.microplate.cutoff;
+        let reportable_range = self.microplate.reportable_range(regression);
+        let derived_columns = self.microplate.protocol.as_ref().map(|protocol| protocol.derived_columns.as_slice()).unwrap_or_default();
+        let derived_names: Vec<&str> = if parse_columns(derived_columns).is_ok() {
+            derived_columns.iter().map(|column| column.name.trim()).collect()
+        } else {
+            Vec::new()
+        };
+        let cv_threshold = self.settings.cv_threshold;
+        let raw_values = self.settings.plot.raw_values;
+        let filter = &mut self.results_filter;
+        let sort = &mut self.results_sort;
+        let mut copy = false;
         
         let background = ui.visuals().faint_bg_color;
         let stroke = ui.visuals().noninteractive().bg_stroke;
@@ -141,7 +537,7 @@ impl Elisa {
             let width = ui.available_width().max(20.0);
             ui.set_width(width);
 
-            ui.vertical_centered(|ui| ui.heading("Backfit Concentrations"));
+            ui.vertical_centered(|ui| ui.heading(tr("backfit-concentrations")));
             ui.add_space(10.0);
             egui::Frame::new()
                 .fill(background).stroke(stroke)
@@ -150,55 +546,187 @@ impl Elisa {
                     let height = ui.available_height();
                     ui.set_min_height(height);
                     ui.set_width(width - 20.0);
+
+                    let mut shown = filter_unknowns(unknowns, filter);
+                    sort_unknowns(unknowns, &mut shown, *sort);
+                    ui.horizontal(|ui| {
+                        let text_edit = ui.add(TextEdit::singleline(filter).desired_width(180.0).hint_text(tr("filter-results")))
+                            .on_hover_text(tr("filter-results-hover"));
+                        Self::dashed_outline(ui, &text_edit);
+                        if !filter.is_empty() {
+                            ui.label(RichText::new(tr_args("filter-count", &[("shown", shown.len().into()), ("total", unknowns.len().into())])).size(11.0));
+                        }
+                        let button = ui.button(tr("copy-results"));
+                        Self::dashed_outline(ui, &button);
+                        copy = button.on_hover_text(tr("copy-results-hover")).clicked();
+                    });
+                    let summary = match &reportable_range {
+                        Some(range) => RichText::new(range.summary()),
+                        None => RichText::new(tr("reportable-range-none")).color(FLAG_COLOR),
+                    };
+                    ui.add_space(4.0);
+                    ui.label(summary.size(11.0));
+                    ui.add_space(8.0);
                     ui.spacing_mut().item_spacing = vec2(20.0, 0.0);
 
                     TableBuilder::new(ui)
                         .id_salt("Backfit Concentrations")
-                        .min_scrolled_height(height - 20.0)
-                        .max_scroll_height(height - 20.0)
-                        .columns(Column::auto(), 2)
+                        .min_scrolled_height(height - 50.0)
+                        .max_scroll_height(height - 50.0)
+                        .columns(Column::auto(), if cutoff.is_some() { 5 } else { 4 } + derived_names.len())
                         .column(Column::remainder())
                         .header(20.0, |mut header| {
-                            header.col(|ui| { ui.add(Label::new("Group").selectable(true)); });
-                            header.col(|ui| { ui.add(Label::new("Raw Corrected").selectable(true)); });
-                            header.col(|ui| { ui.add(Label::new("Backfit").selectable(true)); });
+                            // clicking a header sorts ascending, then descending, then back to the group order
+                            let mut sort_header = |ui: &mut Ui, id: &str, column: SortColumn| {
+                                let text = format!("{}{}", tr(id), ResultsSort::arrow(*sort, column));
+                                if ui.add(Label::new(text).sense(Sense::click())).on_hover_text(tr("sort-hover")).clicked() {
+                                    *sort = ResultsSort::next(*sort, column);
+                                }
+                            };
+                            header.col(|ui| sort_header(ui, "group", SortColumn::Label));
+                            header.col(|ui| sort_header(ui, if raw_values { "raw-signal" } else { "raw-corrected" }, SortColumn::Measurement));
+                            header.col(|ui| sort_header(ui, "backfit", SortColumn::Concentration));
+                            header.col(|ui| sort_header(ui, "cv-percent", SortColumn::Cv));
+                            if cutoff.is_some() {
+                                header.col(|ui| { ui.add(Label::new(tr("classification")).selectable(true)); });
+                            }
+                            for name in &derived_names {
+                                header.col(|ui| { ui.add(Label::new(*name).selectable(true)); });
+                            }
+                            header.col(|ui| { ui.add(Label::new(tr("recovery")).selectable(true)); });
                         })
                         .body(|body| {
-                            body.rows(25.0, unknowns.len(), |mut row| {
-                                let index = row.index();
-                                let (backfit, raw, label) = &unknowns[index];
-
-                                let mut backfit = backfit.to_string();
-                                let mut raw = raw.to_string();
-                                backfit.truncate(10);
-                                raw.truncate(10);
+                            body.rows(25.0, shown.len(), |mut row| {
+                                let unknown = &unknowns[shown[row.index()]];
+
+                                // within the curve but outside of the standards that pass, or diluted further than allowed
+                                let outside_reportable = unknown.in_range() && reportable_range.as_ref().is_some_and(|range| !range.contains(unknown));
+                                let backfit = if unknown.in_range() && !outside_reportable {
+                                    RichText::new(unknown.concentration_text())
+                                } else {
+                                    RichText::new(unknown.concentration_text()).color(FLAG_COLOR)
+                                };
+                                let raw = format_value(unknown.signal(raw_values));
+                                let cv = match unknown_cv(unknown) {
+                                    Some(cv) if cv_threshold.is_some_and(|threshold| cv > threshold) => RichText::new(format_decimal(cv, 1)).color(FLAG_COLOR),
+                                    cv => RichText::new(cv.map(|cv| format_decimal(cv, 1)).unwrap_or_default()),
+                                };
+
+                                let recovery = unknown.recovery().map(|recovery| format!("{} %", format_decimal(recovery, 1))).unwrap_or_default();
+                                let recovery = if unknown.recovery_out_of_range() {
+                                    RichText::new(recovery).color(FLAG_COLOR)
+                                } else {
+                                    RichText::new(recovery)
+                                };
                                 
-                                row.col(|ui| { ui.add(Label::new(label).selectable(true)); });
+                                row.col(|ui| { ui.add(Label::new(unknown.name()).selectable(true)); });
                                 row.col(|ui| { ui.add(Label::new(raw).selectable(true)); });
-                                row.col(|ui| { ui.add(Label::new(backfit).selectable(true)); });
+                                row.col(|ui| {
+                                    let label = ui.add(Label::new(backfit).selectable(true));
+                                    if let Some(warning) = unknown.range_warning() {
+                                        label.on_hover_text(warning);
+                                    } else if let Some(range) = reportable_range.as_ref().filter(|_| outside_reportable) {
+                                        label.on_hover_text(range.warning(unknown));
+                                    }
+                                });
+                                row.col(|ui| { ui.add(Label::new(cv).selectable(true)); });
+                                if let Some(cutoff) = cutoff {
+                                    let classification = cutoff.classify(regression, unknown);
+                                    let text = classification.map(|classification| classification.name()).unwrap_or_default();
+                                    let text = match classification {
+                                        Some(Classification::Positive) => RichText::new(text).strong(),
+                                        Some(Classification::Borderline) => RichText::new(text).italics(),
+                                        _ => RichText::new(text),
+                                    };
+                                    row.col(|ui| { ui.add(Label::new(text).selectable(true)); });
+                                }
+                                if !derived_names.is_empty() {
+                                    for value in derived_values(derived_columns, unknown) {
+                                        row.col(|ui| { ui.add(Label::new(value.map(format_value).unwrap_or_default()).selectable(true)); });
+                                    }
+                                }
+                                row.col(|ui| {
+                                    let label = ui.add(Label::new(recovery).selectable(true));
+                                    if unknown.recovery_out_of_range() {
+                                        label.on_hover_text(recovery_range_text("recovery-out-of-range"));
+                                    }
+                                });
                             });
                         });
                 });
         });
+
+        if copy {
+            self.copy_results(ui.ctx());
+        }
     }
 
     pub fn save_as(&mut self, ui: &mut Ui) {
+        let mut export_csv = false;
+        let mut export_json = false;
+        let mut export_plot_data = false;
+        let csv_hint = self.shortcut_hint(ui.ctx(), ShortcutAction::ExportCsv);
         ui.horizontal(|ui| {
             let Some(plot_response) = &self.plot_response else { return };
 
-            let button = ui.button(RichText::new("Save as PNG"));
+            let button = ui.button(RichText::new(tr("save-as-png")));
             Self::dashed_outline(ui, &button);
             if button.clicked() {
-                ui.ctx().send_viewport_cmd(egui::ViewportCommand::Screenshot(UserData::default()));
+                self.show_png_export = !self.show_png_export;
             }
             ui.add_space(10.0);
 
-            let button = ui.button(RichText::new("Save as PDF"));
+            let button = ui.button(RichText::new(tr("save-as-pdf")));
             Self::dashed_outline(ui, &button);
             if button.clicked() {
                 ui.ctx().send_viewport_cmd(egui::ViewportCommand::Screenshot(UserData::default()));
                 self.pdf_report = true;
             }
+            ui.add_space(10.0);
+
+            let button = shortcut_tooltip(ui.button(RichText::new(tr("save-as-csv"))), csv_hint);
+            Self::dashed_outline(ui, &button);
+            export_csv = button.clicked();
+            ui.add_space(10.0);
+
+            let button = ui.button(RichText::new(tr("save-plot-data"))).on_hover_text(tr("save-plot-data-hover"));
+            Self::dashed_outline(ui, &button);
+            export_plot_data = button.clicked();
+            ui.add_space(10.0);
+
+            let button = ui.button(RichText::new(tr("save-as-json"))).on_hover_text(tr("save-as-json-hover"));
+            Self::dashed_outline(ui, &button);
+            export_json = button.clicked();
+            ui.add_space(10.0);
+
+            // browsers print the downloaded PDF themselves
+            #[cfg(not(target_arch = "wasm32"))]
+            {
+                let button = ui.button(RichText::new(tr("print-report")));
+                Self::dashed_outline(ui, &button);
+                if button.clicked() {
+                    ui.ctx().send_viewport_cmd(egui::ViewportCommand::Screenshot(UserData::default()));
+                    self.print_report = true;
+                }
+                ui.add_space(10.0);
+            }
+
+            let button = ui.button(RichText::new(tr("report-options")));
+            Self::dashed_outline(ui, &button);
+            if button.clicked() {
+                self.show_report_options = !self.show_report_options;
+            }
+            ui.add_space(10.0);
+
+            let button = ui.button(RichText::new(tr("signatures")));
+            Self::dashed_outline(ui, &button);
+            if button.clicked() {
+                self.show_signatures = !self.show_signatures;
+                let analyzed_by = &mut self.microplate.metadata.analyzed_by;
+                if analyzed_by.name.is_empty() {
+                    analyzed_by.name = self.settings.operator.clone();
+                }
+            }
 
             let image = ui.ctx().input(|i| {
                 i.events.iter()
@@ -222,268 +750,438 @@ impl Elisa {
 
                 // could be done async, but it's fine for now
                 let Some(image) = RgbaImage::from_raw(width as u32, height as u32, image.as_raw().to_vec()) else {
-                    eprintln!("Image dimensions are wrong, how did we get here...");
+                    self.pdf_report = false;
+                    self.print_report = false;
+                    self.notifications.push(AppError::Screenshot);
                     return
                 };
 
                 if self.pdf_report {
                     self.pdf_report = false;
 
-                    if let Some(path) = rfd::FileDialog::new()
-                        .add_filter("pdf", &["pdf"])
-                        .set_file_name(self.microplate.name.clone())
-                        .save_file() {
-                        self.create_pdf(path, image);
+                    if save_file(&self.microplate.name, ("pdf", &["pdf"]), &self.create_pdf(&image)).is_err() {
+                        self.notifications.push(SerdeError::CantWriteFile);
                     }
-                } else if let Some(path) = rfd::FileDialog::new()
-                    .add_filter("png", &["png"])
-                    .set_file_name(self.microplate.name.clone())
-                    .save_file() {
-                    if let Err(error) = image.save(path) {
-                        eprintln!("{error}");
-                        todo!()
+                }
+
+                #[cfg(not(target_arch = "wasm32"))]
+                if self.print_report {
+                    self.print_report = false;
+
+                    if let Err(error) = print_pdf(&self.microplate.name, &self.create_pdf(&image)) {
+                        log::error!("Could not print report: {error}");
+                        self.notifications.push(SerdeError::CantPrint);
                     }
                 }
             }
 
         });
+
+        if export_csv {
+            self.export_results_csv();
+        }
+        if export_json {
+            self.export_analysis_json();
+        }
+        if export_plot_data {
+            self.export_plot_data_csv();
+        }
     }
     
 
-    fn create_pdf(&self, path: PathBuf, image: ImageBuffer<Rgba<u8>, Vec<u8>>) {
-        // Importing my own width table is not ideal, especially since I only have the widths for ASCII symbols.
-        const TIMES_NEW_ROMAN_WIDTH_TABLE: [usize; 128] = [
-            778, 778, 778, 778, 778, 778, 778, 778, 778, 778, 778, 778, 778, 778, 778, 778,
-            778, 778, 778, 778, 778, 778, 778, 778, 778, 778, 778, 778, 778, 778, 778, 778,
-            250, 333, 408, 500, 500, 833, 778, 180, 333, 333, 500, 564, 250, 333, 250, 278,
-            500, 500, 500, 500, 500, 500, 500, 500, 500, 500, 278, 278, 564, 564, 564, 444,
-            921, 722, 667, 667, 722, 611, 556, 722, 722, 333, 389, 722, 611, 889, 722, 722,
-            556, 722, 667, 556, 611, 722, 722, 944, 722, 722, 611, 333, 278, 333, 469, 500,
-            333, 444, 500, 444, 500, 444, 333, 500, 500, 278, 278, 500, 278, 778, 500, 500,
-            500, 500, 333, 389, 278, 500, 500, 722, 500, 500, 444, 480, 200, 480, 541, 778
-        ];
-
-        let Microplate { name, description, .. } = &self.microplate;
-        let Some(regression) = &self.regression else { return };
+    pub fn create_pdf(&self, image: &ImageBuffer<Rgba<u8>, Vec<u8>>) -> Vec<u8> {
+        let Microplate { name, description, barcode, .. } = &self.microplate;
+        let Some(regression) = &self.regression else { return Vec::new() };
         let Regression { abcd, unknowns, standards, sse, mse, rmse, sy_x, r_sq,  .. } = regression;
         let (a, b, c, d) = abcd;
-        let parameters = [("a", a), ("b", b), ("c", c), ("d", d), ("SSE", sse), ("MSE", mse), ("RMSE", rmse), ("Sy.x", sy_x), ("R^2", r_sq)];
+        // with the unit of each, the signal of the reader or the concentration of the standards
+        let parameters = [
+            ("a", "unit-signal", a), ("b", "", b), ("c", "unit-concentration", c), ("d", "unit-signal", d),
+            ("SSE", "unit-signal-squared", sse), ("MSE", "unit-signal-squared", mse), ("RMSE", "unit-signal", rmse), ("Sy.x", "unit-signal", sy_x), ("R²", "", r_sq),
+        ];
+        let estimates = regression.diagnostics.parameter_estimates(regression.fitted_abcd).filter(|_| !regression.is_adjusted());
+        let template = &self.settings.report;
 
         let mut pdf = Pdf::new();
 
         let catalog_id = Ref::new(1);
         let page_tree_id = Ref::new(2);
-        let page_id = Ref::new(3);
-        let content_id = Ref::new(4);
-        let font_id = Ref::new(5);
-        let image_id = Ref::new(6);
-        let annotation_id = Ref::new(7);
+        let font_id = Ref::new(3);
+        let image_id = Ref::new(4);
+        let logo_id = Ref::new(5);
+        let annotation_id = Ref::new(6);
+        let first_page_id = 7;
 
-        let font_name = Name(b"Times-Roman");
         let font_size_body = 12.0;
         let font_size_details = 10.0;
         let image_name = Name(b"Plot");
+        let logo_name = Name(b"Logo");
 
-        // Page tree
-        pdf.catalog(catalog_id).pages(page_tree_id);
-        pdf.pages(page_tree_id).kids([page_id]).count(1);
-        pdf.type1_font(font_id).base_font(font_name);
-
-        // A4 page
-        let mut page = pdf.page(page_id);
-        let a4 = pdf_writer::Rect::new(0.0, 0.0, 595.0, 842.0);
-        page.media_box(a4);
-        page.parent(page_tree_id);
-        page.contents(content_id);
-
-        let mut resources = page.resources();
-        resources.fonts().pair(font_name, font_id);
-        resources.x_objects().pair(image_name, image_id);
-        resources.finish();
-        page.annotations([annotation_id]);
-        page.finish();
-
-        let mut content = Content::new();
-
-        // Title
-        content.begin_text();
-        content.set_font(font_name, 24.0);
-        content.next_line(50.0, 842.0 - 80.0);
-        content.show(Str(b"Assay Analysis - 4PL"));
+        pdf.type1_font(font_id).base_font(PDF_FONT).encoding_predefined(Name(b"WinAnsiEncoding"));
+
+        let mut pages = ReportPages::new();
+
+        // Header
+        let logo = template.logo.as_ref().and_then(|path| match image::open(path) {
+            Ok(logo) => Some(logo.to_rgba8()),
+            Err(error) => {
+                log::warn!("Could not load report logo: {error}");
+                None
+            }
+        });
+        if logo.is_some() || !template.header.trim().is_empty() {
+            let top = pages.y;
+            let mut y = top - font_size_details;
+            for line in template.header.lines() {
+                pages.text(font_size_details, 50.0, y, line);
+                y -= 13.0;
+            }
+
+            let mut height = top - y;
+            if let Some(logo) = &logo {
+                let logo_height = 40.0;
+                let logo_width = (logo.width() as f32 / logo.height() as f32 * logo_height).min(150.0);
+                let logo_height = logo_width * logo.height() as f32 / logo.width() as f32;
+                embed_image(&mut pdf, logo_id, logo, (logo_width, logo_height));
+                let content = pages.content();
+                content.save_state();
+                content.transform([logo_width, 0.0, 0.0, logo_height, A4_WIDTH - 50.0 - logo_width, top - logo_height]);
+                content.x_object(logo_name);
+                content.restore_state();
+                height = height.max(logo_height);
+            }
+            pages.y = top - height - 10.0;
+        } else {
+            pages.y -= 12.0;
+        }
+
+        // Title, with the barcode of the plate on the right
+        let y = pages.reserve(30.0);
+        pages.text(24.0, 50.0, y - 18.0, &tr("report-title"));
+        let barcode = barcode.trim();
+        if template.barcode && !barcode.is_empty() {
+            let modules = code128(barcode).unwrap_or_default();
+            let module_width = (200.0 / modules.len() as f32).min(1.0);
+            let x = A4_WIDTH - 50.0 - (modules.len() as f32 * module_width).max(text_width(barcode, font_size_details));
+            // text only for barcodes Code 128 can't show
+            if !modules.is_empty() {
+                pages.bars(x, y - 28.0, module_width, 28.0, &modules);
+            }
+            pages.text(font_size_details, x, y - 40.0, barcode);
+        }
 
         // Date
         let date_time = chrono::offset::Local::now();
-        let date = format!("{}", date_time.format("%d.%m.%Y, %H:%M"));
-        content.set_font(font_name, font_size_body);
-        content.next_line(-10.0, -20.0);
-        content.show(Str(date.as_bytes()));
+        let date = format_date_time(&date_time.naive_local());
+        let y = pages.reserve(30.0);
+        pages.text(font_size_body, 40.0, y - 8.0, &date);
 
         // Name
-        content.next_line(0.0, -30.0);
-        content.show(Str(format!("Name: {}", name).as_bytes()));
-        content.end_text();
+        let y = pages.reserve(20.0);
+        pages.text(font_size_body, 40.0, y - 8.0, &tr_args("report-name", &[("name", name.as_str().into())]));
 
-        // Image
-        let image_rgb: Vec<u8> = image.pixels().flat_map(|p| {
-            let p = p.to_rgb().0;
-            // A tad hacky, but it works
-            match p {
-                [251, 251, 254] => [255, 255, 255],
-                _ => p
-            }
-        }).collect();
-
-        let mut image_obj = pdf.image_xobject(image_id, &image_rgb);
-        image_obj.width(image.width() as i32);
-        image_obj.height(image.height() as i32);
-        image_obj.color_space().device_rgb();
-        image_obj.bits_per_component(8);
-        image_obj.finish();
+        // Run metadata
+        for line in wrap_text(&self.microplate.metadata.summary(), font_size_details, A4_WIDTH - 90.0) {
+            let y = pages.reserve(13.0);
+            pages.text(font_size_details, 40.0, y - 8.0, &line);
+        }
 
-        content.save_state();
-        content.transform([300.0, 0.0, 0.0, 300.0, 20.0, 842.0 - 440.0]);
-        content.x_object(image_name);
-        content.restore_state();
+        // Curve grade
+        if template.parameters {
+            for line in wrap_text(&CurveQuality::new(regression).summary(), font_size_details, A4_WIDTH - 90.0) {
+                let y = pages.reserve(13.0);
+                pages.text(font_size_details, 40.0, y - 8.0, &line);
+            }
+        }
 
-        // Parameter Table
-        content.begin_text();
-        content.set_font(font_name, font_size_details);
-        content.next_line(400.0, 842.0 - 175.0);
+        // Image and parameter table
+        if template.plot || template.parameters {
+            let interval_rows = if estimates.is_some() { 6 } else { 0 };
+            let table_height = (parameters.len() + interval_rows + 1) as f32 * 15.0 + 35.0;
+            let height = if template.plot { 300.0 } else { table_height };
+            let top = pages.reserve(height + 10.0) - 10.0;
+
+            if template.plot {
+                embed_image(&mut pdf, image_id, image, (300.0, 300.0));
+                let content = pages.content();
+                content.save_state();
+                content.transform([300.0, 0.0, 0.0, 300.0, 20.0, top - 300.0]);
+                content.x_object(image_name);
+                content.restore_state();
+            }
 
-        for (name, value) in parameters {
-            content.show(Str(name.as_bytes()));
-            content.next_line(40.0, 0.0);
-            content.show(Str(value.to_string().as_bytes()));
-            content.next_line(-40.0, -15.0);
+            if template.parameters {
+                // the parameter with its unit on the left, the values right-aligned so their digits line up
+                let x = if template.plot { 400.0 } else { 60.0 };
+                let right = if template.plot { A4_WIDTH - 50.0 } else { x + 220.0 };
+                let mut y = top - 35.0;
+                let row = |pages: &mut ReportPages, y: f32, name: &str, unit: &str, value: &str| {
+                    pages.text(font_size_details, x, y, name);
+                    pages.text(font_size_details - 2.0, x + text_width(name, font_size_details) + 4.0, y, unit);
+                    pages.text(font_size_details, right - text_width(value, font_size_details), y, value);
+                };
+                row(&mut pages, y, &tr("parameter"), "", &tr("value"));
+                y -= 15.0;
+                for (index, (name, unit, value)) in parameters.into_iter().enumerate() {
+                    let mut text = format_value(*value);
+                    if let Some(estimate) = estimates.as_ref().and_then(|estimates| estimates.get(index)) {
+                        text.push_str(&format!(" ± {}", format_value(estimate.standard_error)));
+                    }
+                    let unit = if unit.is_empty() { String::new() } else { format!("({})", tr(unit)) };
+                    row(&mut pages, y, name, &unit, &text);
+                    y -= 15.0;
+                }
+                if let Some(estimates) = &estimates {
+                    y -= 15.0;
+                    pages.text(font_size_details, x, y, &tr("confidence-interval"));
+                    y -= 15.0;
+                    for (name, estimate) in ["a", "b", "c", "d"].iter().zip(estimates) {
+                        row(&mut pages, y, name, "", &format_interval(estimate.confidence_interval));
+                        y -= 15.0;
+                    }
+                }
+                if regression.is_adjusted() {
+                    pages.text(font_size_details, x, y - 5.0, &tr("fit-adjusted"));
+                }
+            }
         }
-        content.end_text();
     
         // Description
-        content.begin_text();
-        content.set_font(font_name, font_size_body);
-        content.next_line(60.0, 842.0 - 460.0);
-        content.show(Str(b"Description"));
-
-        content.next_line(0.0, -20.0);
-
-        let mut parsed_description = String::new();
-        let max_width = a4.x2 as usize * 3 * 1000 / 4 / 12; // convert 3/4 A4 width
-
-        let mut lines = 0;
-        let mut width = 0;
-
-        for word in description.split_whitespace() {
-            let mut word_width = 0;
-            for char in word.chars() {
-                // I can't be bothered to deal with pdf encoding, if someone knows how to render non-ASCII stuff lmk
-                if !char.is_ascii() { continue }
-                word_width += TIMES_NEW_ROMAN_WIDTH_TABLE[char as usize];
-            }
-            width += word_width;
-            width += TIMES_NEW_ROMAN_WIDTH_TABLE[' ' as usize];
-            if width > max_width {
-                width = word_width;
-                lines += 1;
-                if lines >= 5 {
-                    parsed_description.push_str("...");
-                    break
+        if template.notes {
+            pages.heading(font_size_body, &tr("description"), 15.0);
+
+            // paragraphs keep their breaks, empty lines between them their space
+            let max_width = A4_WIDTH * 3.0 / 4.0;
+            for paragraph in description.lines() {
+                let lines = wrap_text(paragraph, font_size_body, max_width);
+                if lines.is_empty() {
+                    pages.reserve(15.0);
+                }
+                for line in lines {
+                    let y = pages.reserve(15.0);
+                    pages.text(font_size_body, 60.0, y, &line);
                 }
-                parsed_description.push('\n');
             }
-            parsed_description.push_str(word);
-            parsed_description.push(' ');
+            pages.reserve(20.0);
         }
 
-        for line in parsed_description.lines() {
-            content.show(Str(line.as_bytes()));
-            content.next_line(0.0, -15.0);
+        // Plate map
+        if template.plate_map {
+            plate_map(&mut pages, &self.microplate, font_size_body);
         }
-        content.end_text();
 
-        // Calibration table
         let column_width = 75.0;
-        let table_width = column_width * 5.0;
 
-        content.begin_text();
-        content.next_line((a4.x2 - table_width) / 2.0, 842.0 - 585.0);
-        content.set_font(font_name, font_size_details);
-
-        content.show(Str(b"Standard"));
-        content.next_line(column_width, 0.0);
-        content.show(Str(b"Concentration"));
-        content.next_line(column_width, 0.0);
-        content.show(Str(b"Raw Corrected"));
-        content.next_line(column_width, 0.0);
-        content.show(Str(b"Backfit"));
-        content.next_line(column_width, 0.0);
-        content.show(Str(b"Recovery %"));
-        content.next_line(-column_width * 4.0, -15.0);
-        
-        for (i, (x, y)) in standards.iter().enumerate() {
-            let name = format!("Standard {}", i + 1);
-            let backfit = regression.inverse_four_pl(*y);
-            let recovery = backfit / x * 100.0;
-
-            content.show(Str(name.as_bytes()));
-
-            let list = [*x, *y, backfit, recovery];
-            for val in list {
-                let mut val = val.to_string();
-                val.truncate(10);
-                content.next_line(column_width, 0.0);
-                content.show(Str(val.as_bytes()));
-            }
-            content.next_line(-column_width * 4.0, -15.0);
-        }    
-
-        content.next_line(0.0, -15.0);
-
-        // Sample Table
-        content.show(Str(b"Sample"));
-        content.next_line(column_width, 0.0);
-        content.show(Str(b"Raw Corrected"));
-        content.next_line(column_width, 0.0);
-        content.show(Str(b"Backfit Concentration"));
-        content.next_line(-column_width * 2.0, -15.0);
-
-        for (i, (x, y, label)) in unknowns.iter().enumerate() {
-            let name = if label.is_empty() {
-                format!("Unknown {}", i + 1)
-            } else {
-                label.to_owned()
-            };
-            let mut raw_corrected = y.to_string();
-            let mut backfit = x.to_string();
-            raw_corrected.truncate(10);
-            backfit.truncate(10);
-            
-            content.show(Str(name.as_bytes()));
-            content.next_line(column_width, 0.0);
-            content.show(Str(raw_corrected.as_bytes()));
-            content.next_line(column_width, 0.0);
-            content.show(Str(backfit.as_bytes()));
-            content.next_line(-column_width * 2.0, -15.0);
+        // Residuals table
+        if template.residuals {
+            let left = (A4_WIDTH - column_width * 5.0) / 2.0;
+            pages.reserve(20.0);
+            pages.begin_table(font_size_details, spaced_columns(left, column_width, [
+                tr("sample-standard"), tr("concentration"), tr("raw-corrected"), tr("report-fitted"), tr("report-residual"),
+            ]));
+            for (i, (x, y)) in standards.iter().enumerate() {
+                let fitted = regression.four_pl(*x);
+                let mut row = vec![tr_args("standard-number", &[("number", (i + 1).into())])];
+                row.extend([*x, *y, fitted, y - fitted].map(format_value));
+                pages.row(font_size_details, left, column_width, &row);
+            }
+            pages.end_table();
+            pages.reserve(15.0);
         }
-        
-        content.end_text();
-    
+
+        // Calibration table
+        if template.results {
+            let left = (A4_WIDTH - column_width * 5.0) / 2.0;
+            pages.reserve(20.0);
+            pages.begin_table(font_size_details, spaced_columns(left, column_width, [
+                tr("sample-standard"), tr("concentration"), tr("raw-corrected"), tr("backfit"), tr("recovery-percent"),
+            ]));
+            let back_calculated = regression.back_calculated_standards();
+            for (i, standard) in back_calculated.iter().enumerate() {
+                let mut row = vec![tr_args("standard-number", &[("number", (i + 1).into())])];
+                row.extend([standard.concentration, standard.measurement, standard.backfit].map(format_value));
+                let recovery = format_decimal(standard.recovery, 1);
+                row.push(match (standard.anchor, standard.passed()) {
+                    (true, _) => format!("{recovery} ({})", tr("anchor")),
+                    (false, true) => recovery,
+                    (false, false) => format!("{recovery} *"),
+                });
+                pages.row(font_size_details, left, column_width, &row);
+            }
+            pages.end_table();
+            if back_calculated.iter().any(|standard| !standard.passed()) {
+                pages.reserve(5.0);
+                let y = pages.reserve(15.0);
+                pages.text(font_size_details, left, y, &recovery_range_text("report-standards-recovery-note"));
+            }
+            pages.reserve(15.0);
+
+            // Sample Table
+            let cutoff = &self.microplate.cutoff;
+            let derived_columns = self.microplate.protocol.as_ref().map(|protocol| protocol.derived_columns.as_slice()).unwrap_or_default();
+            let columns = [0.0, column_width, column_width * 2.0, column_width * 3.5, column_width * 4.5];
+            let raw_values = self.settings.plot.raw_values;
+            let mut header = vec![tr("sample"), tr(if raw_values { "raw-signal" } else { "raw-corrected" }), tr("backfit-concentration"), tr("recovery-percent")];
+            if cutoff.is_some() {
+                header.push(tr("classification"));
+            }
+            pages.begin_table(font_size_details, columns.iter().map(|x| left + x).zip(header).collect());
+
+            for unknown in sorted_unknowns(unknowns, self.results_sort).into_iter().map(|index| &unknowns[index]) {
+                let raw_corrected = format_value(unknown.signal(raw_values));
+                let backfit = unknown.concentration_text();
+                let recovery = match unknown.recovery() {
+                    Some(recovery) if unknown.recovery_out_of_range() => format!("{} *", format_decimal(recovery, 1)),
+                    Some(recovery) => format_decimal(recovery, 1),
+                    None => String::new(),
+                };
+
+                let mut cells = vec![unknown.name(), raw_corrected, backfit, recovery];
+                if let Some(cutoff) = cutoff {
+                    cells.push(cutoff.classify(regression, unknown).map(|classification| classification.name()).unwrap_or_default());
+                }
+                // metadata and derived columns don't fit next to the others, they get lines of their own below the sample
+                let mut details = Vec::new();
+                if template.sample_metadata && !unknown.metadata.is_empty() {
+                    details.extend(wrap_text(&unknown.metadata.summary(), font_size_details - 2.0, A4_WIDTH - 2.0 * left - 10.0));
+                }
+                let derived: Vec<String> = derived_columns.iter().zip(derived_values(derived_columns, unknown))
+                    .filter_map(|(column, value)| Some(format!("{} = {}", column.name.trim(), format_value(value?))))
+                    .collect();
+                if !derived.is_empty() {
+                    details.extend(wrap_text(&derived.join("; "), font_size_details - 2.0, A4_WIDTH - 2.0 * left - 10.0));
+                }
+
+                pages.keep(15.0 + 12.0 * details.len() as f32);
+                let y = pages.reserve(15.0);
+                for (x, text) in columns.iter().zip(cells) {
+                    pages.text(font_size_details, left + x, y, &text);
+                }
+                for line in details {
+                    let y = pages.reserve(12.0);
+                    pages.text(font_size_details - 2.0, left + 10.0, y, &line);
+                }
+            }
+            pages.end_table();
+
+            if unknowns.iter().any(UnknownResult::recovery_out_of_range) {
+                pages.reserve(5.0);
+                let y = pages.reserve(15.0);
+                pages.text(font_size_details, left, y, &recovery_range_text("report-recovery-note"));
+            }
+            if let Some(cutoff) = cutoff {
+                pages.reserve(5.0);
+                let y = pages.reserve(15.0);
+                pages.text(font_size_details, left, y, &cutoff.threshold_text(regression));
+            }
+            if unknowns.iter().any(|unknown| unknown.range == CurveRange::Extrapolated) {
+                pages.reserve(5.0);
+                let y = pages.reserve(15.0);
+                pages.text(font_size_details, left, y, &tr("report-extrapolated-note"));
+            }
+            if unknowns.iter().any(|unknown| matches!(unknown.range, CurveRange::Above(_) | CurveRange::Below(_))) {
+                pages.reserve(5.0);
+                for line in wrap_text(&tr("report-range-note"), font_size_details, A4_WIDTH - 2.0 * left) {
+                    let y = pages.reserve(15.0);
+                    pages.text(font_size_details, left, y, &line);
+                }
+            }
+        }
+
+        // Acceptance criteria
+        if let (true, Some(protocol)) = (template.qc, &self.microplate.protocol) {
+            pages.heading(font_size_body, &tr_args("qc-summary", &[("protocol", protocol.name.as_str().into())]), 15.0);
+            for check in protocol.evaluate(&self.microplate, regression) {
+                let lines = wrap_text(&check.detail, font_size_details, A4_WIDTH - 50.0 - 245.0);
+                pages.keep(15.0 + 12.0 * lines.len().saturating_sub(1) as f32);
+                let y = pages.reserve(15.0);
+                pages.text(font_size_details, 60.0, y, &tr(if check.passed { "qc-pass" } else { "qc-fail" }));
+                pages.text(font_size_details, 110.0, y, &check.criterion.name());
+                for (i, line) in lines.iter().enumerate() {
+                    let y = if i == 0 { y } else { pages.reserve(12.0) };
+                    pages.text(font_size_details, 245.0, y, line);
+                }
+            }
+            pages.reserve(15.0);
+        }
+
+        // Excluded wells
+        let excluded_wells = self.microplate.excluded_wells();
+        if template.excluded_wells && !excluded_wells.is_empty() {
+            pages.heading(font_size_body, &tr("excluded-wells"), 30.0);
+            let columns = [60.0, 110.0, 245.0];
+            pages.begin_table(font_size_details, columns.iter().copied().zip(["well", "sample", "reason"].map(tr)).collect());
+            for (well, reason) in excluded_wells {
+                let reason = reason.map(ExclusionReason::description).unwrap_or_else(|| tr("exclusion-no-reason"));
+                let lines = wrap_text(&reason, font_size_details, A4_WIDTH - 50.0 - columns[2]);
+                pages.keep(15.0 + 12.0 * lines.len().saturating_sub(1) as f32);
+                let y = pages.reserve(15.0);
+                pages.text(font_size_details, columns[0], y, &self.microplate.well_name(well));
+                pages.text(font_size_details, columns[1], y, &sample_name(&self.microplate, well));
+                for (i, line) in lines.iter().enumerate() {
+                    let y = if i == 0 { y } else { pages.reserve(12.0) };
+                    pages.text(font_size_details, columns[2], y, line);
+                }
+            }
+            pages.end_table();
+            pages.reserve(15.0);
+        }
+
+        // Audit trail
+        if template.audit_trail {
+            pages.heading(font_size_body, &tr("audit-trail"), 30.0);
+            let columns = [60.0, 165.0, 245.0];
+            pages.begin_table(font_size_details, columns.iter().copied().zip(["audit-time", "operator", "audit-change"].map(tr)).collect());
+
+            for entry in &self.microplate.audit_trail {
+                let lines = wrap_text(&entry.action.description(), font_size_details, A4_WIDTH - 50.0 - columns[2]);
+                pages.keep(15.0 + 12.0 * lines.len().saturating_sub(1) as f32);
+                let y = pages.reserve(15.0);
+                pages.text(font_size_details, columns[0], y, &entry.display_time());
+                pages.text(font_size_details, columns[1], y, &entry.operator);
+                for (i, line) in lines.iter().enumerate() {
+                    let y = if i == 0 { y } else { pages.reserve(12.0) };
+                    pages.text(font_size_details, columns[2], y, line);
+                }
+            }
+            pages.end_table();
+        }
+
+        // Signatures
+        if template.signatures {
+            let top = pages.reserve(130.0) - 30.0;
+            let metadata = &self.microplate.metadata;
+            for (x, signatory, id) in [(60.0, &metadata.analyzed_by, "analyzed-by"), (320.0, &metadata.reviewed_by, "reviewed-by")] {
+                pages.text(font_size_body, x, top, &tr(id));
+                pages.text(font_size_details, x, top - 20.0, &format!("{}: {}", tr("name"), signatory.name));
+                pages.text(font_size_details, x, top - 35.0, &format!("{}: {}", tr("role"), signatory.role));
+                for (y, id) in [(top - 65.0, "signature"), (top - 90.0, "date")] {
+                    pages.text(font_size_details, x, y, &tr(id));
+                    pages.line(x + 55.0, y - 2.0, x + 215.0);
+                }
+            }
+        }
+
+        let source = self.source_file.as_ref().map(|source| source.description(&self.microplate));
+        pages.footer(font_size_details, &date, source.as_deref());
+
         // Link
-        content.begin_text();
-        content.set_font(font_name, font_size_details);
-        content.next_line(595.0 - 80.0, 40.0);
-        content.show(Str(b"Eliavaux"));
-        content.end_text();
+        pages.pages[0].begin_text();
+        pages.pages[0].set_font(PDF_FONT, font_size_details);
+        pages.pages[0].next_line(A4_WIDTH - 80.0, 40.0);
+        pages.pages[0].show(Str(b"Eliavaux"));
+        pages.pages[0].end_text();
     
         let mut annotation = pdf.annotation(annotation_id);
         annotation.subtype(pdf_writer::types::AnnotationType::Link);
         let padding = 3.0;
         annotation.rect(pdf_writer::Rect::new(
-            595.0 - 80.0 - padding,
+            A4_WIDTH - 80.0 - padding,
             40.0 - padding,
-            595.0 - 80.0 + 35.0 + padding,
+            A4_WIDTH - 80.0 + 35.0 + padding,
             40.0 + 6.0 + padding
         ));
-        annotation.contents(TextStr("Link to Eliavaux's GitHub"));
+        annotation.contents(TextStr(&tr("report-link")));
         annotation.color_rgb(0.0, 0.0, 1.0);
 
         annotation.action()
@@ -491,9 +1189,403 @@ impl Elisa {
             .uri(Str(b"https://www.github.com/eliavaux"));
         annotation.finish();
 
+        // Page tree, A4 pages
+        let page_ids: Vec<Ref> = (0..pages.pages.len()).map(|i| Ref::new(first_page_id + 2 * i as i32)).collect();
+        pdf.catalog(catalog_id).pages(page_tree_id);
+        pdf.pages(page_tree_id).kids(page_ids.iter().copied()).count(page_ids.len() as i32);
+
+        for (i, (content, page_id)) in pages.pages.into_iter().zip(page_ids).enumerate() {
+            let content_id = Ref::new(page_id.get() + 1);
+            let mut page = pdf.page(page_id);
+            page.media_box(pdf_writer::Rect::new(0.0, 0.0, A4_WIDTH, A4_HEIGHT));
+            page.parent(page_tree_id);
+            page.contents(content_id);
+
+            let mut resources = page.resources();
+            resources.fonts().pair(PDF_FONT, font_id);
+            let mut x_objects = resources.x_objects();
+            if template.plot {
+                x_objects.pair(image_name, image_id);
+            }
+            if logo.is_some() {
+                x_objects.pair(logo_name, logo_id);
+            }
+            x_objects.finish();
+            resources.finish();
+            if i == 0 {
+                page.annotations([annotation_id]);
+            }
+            page.finish();
+
+            pdf.stream(content_id, &content.finish());
+        }
+
+        pdf.finish()
+    }
+}
+
+const PDF_FONT: Name = Name(b"Times-Roman");
+const A4_WIDTH: f32 = 595.0;
+const A4_HEIGHT: f32 = 842.0;
+/// Resolution of the plot and logo in the report, sharp in print while keeping the file small
+const REPORT_IMAGE_DPI: f32 = 200.0;
+
+/// Content streams of the report, one per page, with a cursor running down the current page
+struct ReportPages {
+    pages: Vec<Content>,
+    y: f32,
+    table_header: Option<TableHeader>, // of the table being drawn, repeated at the top of every page it continues on
+}
+
+/// Column titles of a table with their x positions
+#[derive(Clone)]
+struct TableHeader {
+    size: f32,
+    columns: Vec<(f32, String)>,
+}
+
+impl ReportPages {
+    const TOP: f32 = A4_HEIGHT - 50.0;
+    const BOTTOM: f32 = 70.0; // leaves room for the link
+
+    fn new() -> Self {
+        Self { pages: vec![Content::new()], y: Self::TOP, table_header: None }
+    }
+
+    fn content(&mut self) -> &mut Content {
+        self.pages.last_mut().unwrap()
+    }
+
+    /// Takes `height` points off the current page, or off a new one if it doesn't fit.
+    /// Returns the top of the reserved space.
+    fn reserve(&mut self, height: f32) -> f32 {
+        self.keep(height);
+        let top = self.y;
+        self.y -= height;
+        top
+    }
+
+    /// Breaks to a new page unless `height` points are left on this one, without taking them.
+    /// Keeps what belongs together, e.g. a sample with its metadata, on one page.
+    fn keep(&mut self, height: f32) {
+        if self.y - height < Self::BOTTOM && self.y < Self::TOP {
+            self.new_page();
+        }
+    }
+
+    fn new_page(&mut self) {
+        self.pages.push(Content::new());
+        self.y = Self::TOP;
+        if let Some(header) = self.table_header.clone() {
+            self.header_row(&header);
+        }
+    }
+
+    /// Section heading, kept on a page with the first `following` points of the section
+    fn heading(&mut self, size: f32, text: &str, following: f32) {
+        self.keep(40.0 + following);
+        let y = self.reserve(40.0);
+        self.text(size, 60.0, y - 20.0, text);
+    }
+
+    /// Draws the column titles, kept on a page with the first row, and again on every new page until end_table
+    fn begin_table(&mut self, size: f32, columns: Vec<(f32, String)>) {
+        self.keep(30.0);
+        let header = TableHeader { size, columns };
+        self.header_row(&header);
+        self.table_header = Some(header);
+    }
+
+    fn end_table(&mut self) {
+        self.table_header = None;
+    }
+
+    fn header_row(&mut self, header: &TableHeader) {
+        let y = self.y;
+        self.y -= 15.0;
+        for (x, text) in &header.columns {
+            self.text(header.size, *x, y, text);
+        }
+    }
+
+    fn text(&mut self, size: f32, x: f32, y: f32, text: &str) {
+        if text.is_empty() { return }
+        let content = self.content();
+        content.begin_text();
+        content.set_font(PDF_FONT, size);
+        content.next_line(x, y);
+        content.show(Str(&pdf_text(text)));
+        content.end_text();
+    }
+
+    /// Filled circle with a gray outline, made of four Bézier curves
+    fn circle(&mut self, x: f32, y: f32, radius: f32, color: Color32) {
+        let k = radius * 0.5523; // control point distance for a quarter circle
+        let content = self.content();
+        content.save_state();
+        content.set_fill_rgb(color.r() as f32 / 255.0, color.g() as f32 / 255.0, color.b() as f32 / 255.0);
+        content.set_stroke_rgb(0.7, 0.71, 0.75);
+        content.set_line_width(0.5);
+        content.move_to(x + radius, y);
+        content.cubic_to(x + radius, y + k, x + k, y + radius, x, y + radius);
+        content.cubic_to(x - k, y + radius, x - radius, y + k, x - radius, y);
+        content.cubic_to(x - radius, y - k, x - k, y - radius, x, y - radius);
+        content.cubic_to(x + k, y - radius, x + radius, y - k, x + radius, y);
+        content.close_path();
+        content.fill_nonzero_and_stroke();
+        content.restore_state();
+    }
+
+    /// Bars of a barcode from its lower left corner, true modules are black
+    fn bars(&mut self, x: f32, y: f32, module_width: f32, height: f32, modules: &[bool]) {
+        let content = self.content();
+        content.save_state();
+        content.set_fill_gray(0.0);
+        for (i, _) in modules.iter().enumerate().filter(|(_, bar)| **bar) {
+            content.rect(x + i as f32 * module_width, y, module_width, height);
+        }
+        content.fill_nonzero();
+        content.restore_state();
+    }
+
+    /// Thin horizontal line to write on
+    fn line(&mut self, x1: f32, y: f32, x2: f32) {
+        let content = self.content();
+        content.save_state();
+        content.set_line_width(0.5);
+        content.move_to(x1, y);
+        content.line_to(x2, y);
+        content.stroke();
+        content.restore_state();
+    }
+
+    /// Footer at the bottom left of every page: the page number, when and by which version the report was made
+    /// and the hash of the project file, so a printout can be matched to the data it came from
+    fn footer(&mut self, size: f32, date: &str, source: Option<&str>) {
+        let count = self.pages.len();
+        let generated = tr_args("report-generated", &[("date", date.into()), ("version", env!("CARGO_PKG_VERSION").into())]);
+        for (i, content) in self.pages.iter_mut().enumerate() {
+            let page = tr_args("report-page", &[("page", (i + 1).into()), ("pages", count.into())]);
+            let mut lines = vec![(size, format!("{page} · {generated}"))];
+            if let Some(source) = source {
+                lines.push((size - 2.0, source.to_string()));
+            }
+            for ((size, text), y) in lines.iter().zip([40.0, 28.0]) {
+                content.begin_text();
+                content.set_font(PDF_FONT, *size);
+                content.next_line(50.0, y);
+                content.show(Str(&pdf_text(text)));
+                content.end_text();
+            }
+        }
+    }
+
+    /// Table row with evenly spaced columns
+    fn row(&mut self, size: f32, left: f32, column_width: f32, cells: &[String]) {
+        let y = self.reserve(15.0);
+        for (i, cell) in cells.iter().enumerate() {
+            self.text(size, left + i as f32 * column_width, y, cell);
+        }
+    }
+}
+
+/// Adds an image to the PDF as an RGB XObject, transparent parts become white. Larger images are scaled down to
+/// REPORT_IMAGE_DPI at `size`, where they are placed in points, and compressed without loss.
+fn embed_image(pdf: &mut Pdf, id: Ref, image: &RgbaImage, size: (f32, f32)) {
+    let width = ((size.0 / 72.0 * REPORT_IMAGE_DPI).ceil() as u32).clamp(1, image.width());
+    let height = ((size.1 / 72.0 * REPORT_IMAGE_DPI).ceil() as u32).clamp(1, image.height());
+    let resized;
+    let image = if (width, height) != image.dimensions() {
+        resized = imageops::resize(image, width, height, imageops::FilterType::Triangle);
+        &resized
+    } else {
+        image
+    };
+
+    let image_rgb: Vec<u8> = image.pixels().flat_map(|p| {
+        let [r, g, b, alpha] = p.0;
+        let blend = |channel: u8| ((channel as u16 * alpha as u16 + 255 * (255 - alpha as u16)) / 255) as u8;
+        // A tad hacky, but it works
+        match [r, g, b] {
+            [251, 251, 254] => [255, 255, 255],
+            _ => [blend(r), blend(g), blend(b)],
+        }
+    }).collect();
+
+    let compressed = flate_image_data(&image_rgb, width, height);
+    let mut image_obj = pdf.image_xobject(id, compressed.as_deref().unwrap_or(&image_rgb));
+    image_obj.width(width as i32);
+    image_obj.height(height as i32);
+    image_obj.color_space().device_rgb();
+    image_obj.bits_per_component(8);
+    if compressed.is_some() {
+        image_obj.filter(Filter::FlateDecode);
+        image_obj.insert(Name(b"DecodeParms")).dict()
+            .pair(Name(b"Predictor"), 15)
+            .pair(Name(b"Colors"), 3)
+            .pair(Name(b"BitsPerComponent"), 8)
+            .pair(Name(b"Columns"), width as i32);
+    }
+    image_obj.finish();
+}
+
+/// RGB pixels as the zlib stream of a PNG, which a PDF reads with FlateDecode and the PNG predictors.
+/// None if the encoder fails, the image is embedded uncompressed then.
+fn flate_image_data(rgb: &[u8], width: u32, height: u32) -> Option<Vec<u8>> {
+    let mut png = Vec::new();
+    PngEncoder::new_with_quality(&mut png, CompressionType::Best, PngFilterType::Adaptive)
+        .write_image(rgb, width, height, ExtendedColorType::Rgb8)
+        .ok()?;
+
+    // the image data is split over the IDAT chunks after the 8 byte signature, each chunk is length, type, data and CRC
+    let mut data = Vec::new();
+    let mut chunks = png.get(8..)?;
+    while chunks.len() >= 12 {
+        let length = u32::from_be_bytes(chunks[0..4].try_into().ok()?) as usize;
+        let chunk = chunks.get(8..8 + length)?;
+        if &chunks[4..8] == b"IDAT" {
+            data.extend_from_slice(chunk);
+        }
+        chunks = chunks.get(12 + length..)?;
+    }
+    (!data.is_empty()).then_some(data)
+}
+
+// Importing my own width table is not ideal, especially since I only have the widths for ASCII symbols.
+const TIMES_NEW_ROMAN_WIDTH_TABLE: [usize; 128] = [
+    778, 778, 778, 778, 778, 778, 778, 778, 778, 778, 778, 778, 778, 778, 778, 778,
+    778, 778, 778, 778, 778, 778, 778, 778, 778, 778, 778, 778, 778, 778, 778, 778,
+    250, 333, 408, 500, 500, 833, 778, 180, 333, 333, 500, 564, 250, 333, 250, 278,
+    500, 500, 500, 500, 500, 500, 500, 500, 500, 500, 278, 278, 564, 564, 564, 444,
+    921, 722, 667, 667, 722, 611, 556, 722, 722, 333, 389, 722, 611, 889, 722, 722,
+    556, 722, 667, 556, 611, 722, 722, 944, 722, 722, 611, 333, 278, 333, 469, 500,
+    333, 444, 500, 444, 500, 444, 333, 500, 500, 278, 278, 500, 278, 778, 500, 500,
+    500, 500, 333, 389, 278, 500, 500, 722, 500, 500, 444, 480, 200, 480, 541, 778
+];
+
+/// Width of a character in thousandths of the font size
+fn char_width(char: char) -> usize {
+    TIMES_NEW_ROMAN_WIDTH_TABLE.get(char as usize).copied().unwrap_or(500)
+}
+
+fn text_width(text: &str, font_size: f32) -> f32 {
+    text.chars().map(char_width).sum::<usize>() as f32 * font_size / 1000.0
+}
+
+/// Draws the plate as it looks in the app: wells colored by sample type, with their
+/// group and value, and a legend of the types underneath.
+fn plate_map(pages: &mut ReportPages, microplate: &Microplate, font_size_heading: f32) {
+    use SampleType::*;
+
+    let Microplate { width, height, .. } = *microplate;
+    let cell = ((A4_WIDTH - 100.0) / (width + 1) as f32).min(40.0);
+    let radius = cell / 2.0 - 2.0;
+    let label_size = (cell * 0.25).min(8.0);
+    let value_size = (cell * 0.18).min(6.5);
+    let left = (A4_WIDTH - cell * (width + 1) as f32) / 2.0;
+
+    let top = pages.reserve(25.0 + cell * (height + 1) as f32 + 30.0);
+    pages.text(font_size_heading, 60.0, top - 20.0, &tr("plate-map"));
+    let top = top - 25.0;
+
+    for column in 0..width {
+        let text = (column + 1).to_string();
+        let x = left + cell * (column + 1) as f32 + (cell - text_width(&text, label_size)) / 2.0;
+        pages.text(label_size, x, top - cell / 2.0 - label_size / 3.0, &text);
+    }
 
-        pdf.stream(content_id, &content.finish());    
-        std::fs::write(path, pdf.finish()).unwrap();
+    for row in 0..height {
+        let center_y = top - cell * (row + 1) as f32 - cell / 2.0;
+        pages.text(label_size, left + cell / 3.0, center_y - label_size / 3.0, &ALPHABET[row % 26].to_string());
+
+        for column in 0..width {
+            let sample = &microplate.samples[column * height + row];
+            let center_x = left + cell * (column + 1) as f32 + cell / 2.0;
+            pages.circle(center_x, center_y, radius, rgb(microplate.sample_color(sample)));
+
+            let label = match sample.typ {
+                Standard | Unknown => format!("{}{}", sample.typ.name().chars().next().unwrap_or(' '), sample.group + 1),
+                Unused | Blank | Control => String::new(),
+            };
+            let value = sample.value.map(format_value).unwrap_or_default();
+
+            let label_y = if value.is_empty() { center_y - label_size / 3.0 } else { center_y + 1.0 };
+            pages.text(label_size, center_x - text_width(&label, label_size) / 2.0, label_y, &label);
+            let value_y = if label.is_empty() { center_y - value_size / 3.0 } else { center_y - value_size - 1.0 };
+            pages.text(value_size, center_x - text_width(&value, value_size) / 2.0, value_y, &value);
+        }
+    }
+
+    // Legend
+    let mut x = left + cell;
+    let y = top - cell * (height + 1) as f32 - 15.0;
+    for typ in [Unused, Blank, Control, Standard, Unknown] {
+        pages.circle(x + 4.0, y + 3.0, 4.0, rgb(typ.color()));
+        let name = typ.name();
+        pages.text(8.0, x + 12.0, y, &name);
+        x += 12.0 + text_width(&name, 8.0) + 15.0;
+    }
+}
+
+/// Column titles evenly spaced from `left`, for a table drawn with ReportPages::row
+fn spaced_columns(left: f32, column_width: f32, titles: impl IntoIterator<Item = String>) -> Vec<(f32, String)> {
+    titles.into_iter().enumerate().map(|(i, title)| (left + i as f32 * column_width, title)).collect()
+}
+
+/// Breaks text into lines no wider than `max_width` points in Times-Roman
+fn wrap_text(text: &str, font_size: f32, max_width: f32) -> Vec<String> {
+    let max_width = (max_width * 1000.0 / font_size) as usize;
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    let mut width = 0;
+
+    for word in text.split_whitespace() {
+        let word_width: usize = word.chars().map(char_width).sum();
+        if !line.is_empty() && width + char_width(' ') + word_width > max_width {
+            lines.push(std::mem::take(&mut line));
+            width = 0;
+        }
+        if !line.is_empty() {
+            line.push(' ');
+            width += char_width(' ');
+        }
+        line.push_str(word);
+        width += word_width;
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+/// Hands the report to the system for printing. Windows prints through the default PDF
+/// application, elsewhere it goes to the default CUPS printer, or opens in the default
+/// viewer if there's no `lp`.
+#[cfg(not(target_arch = "wasm32"))]
+fn print_pdf(name: &str, pdf: &[u8]) -> std::io::Result<()> {
+    use std::process::Command;
+
+    let name = if name.trim().is_empty() { "Elisa Report" } else { name.trim() };
+    let path = std::env::temp_dir().join(format!("{}.pdf", name.replace(['/', '\\'], "_")));
+    std::fs::write(&path, pdf)?;
+
+    if cfg!(target_os = "windows") {
+        let status = Command::new("powershell")
+            .args(["-NoProfile", "-Command", "Start-Process -FilePath $args[0] -Verb Print"])
+            .arg(&path)
+            .status()?;
+        if status.success() { return Ok(()) }
+        return Err(std::io::Error::other(format!("powershell exited with {status}")))
+    }
+
+    match Command::new("lp").arg(&path).status() {
+        Ok(status) if status.success() => Ok(()),
+        _ => {
+            let viewer = if cfg!(target_os = "macos") { "open" } else { "xdg-open" };
+            let status = Command::new(viewer).arg(&path).status()?;
+            if status.success() { return Ok(()) }
+            Err(std::io::Error::other(format!("{viewer} exited with {status}")))
+        }
     }
 }
 
@@ -0,0 +1,375 @@
+use eframe::egui::{self, Button, Color32, ComboBox, DragValue, Grid, Id, RichText, TextEdit, Ui};
+
+use crate::{app::SerdeError, expression::*, grading::CurveGrade, i18n::*, logistic_regression::*, protocol::*, results_filter::*, ui::plot::FLAG_COLOR, ElisaTab, Elisa};
+
+pub const PASS_COLOR: egui::Color32 = egui::Color32::from_rgb(0x2E, 0x8B, 0x3E);
+
+fn concentrations_text(concentrations: &[f64]) -> String {
+    concentrations.iter().map(|&concentration| format_number(concentration)).collect::<Vec<_>>().join("; ")
+}
+
+fn parse_concentrations(text: &str) -> Option<Vec<f64>> {
+    text.split([';', '\n']).map(str::trim).filter(|text| !text.is_empty()).map(parse_decimal).collect()
+}
+
+pub fn percent_drag_value(ui: &mut Ui, value: &mut f64) -> bool {
+    let drag_value = ui.add(DragValue::new(value).speed(0.5).range(0.0..=1000.0).suffix(" %")
+        .custom_formatter(|n, _| format_number(n))
+        .custom_parser(parse_decimal));
+    Elisa::dashed_outline(ui, &drag_value);
+    drag_value.changed()
+}
+
+impl Elisa {
+    pub fn protocol_window(&mut self, ctx: &egui::Context) {
+        let mut library_changed = false;
+        let locked = self.microplate.locked;
+        let microplate = &mut self.microplate;
+        let protocols = &mut self.protocols;
+        let concentrations = &mut self.protocol_concentrations;
+
+        egui::Window::new(tr("protocol"))
+            .id(Id::new("Protocol"))
+            .open(&mut self.show_protocol)
+            .resizable(false)
+            .collapsible(false)
+            .frame(egui::Frame::window(&ctx.style()).inner_margin(10.0))
+            .show(ctx, |ui| {
+                if locked {
+                    ui.disable();
+                }
+
+                let selected = microplate.protocol.as_ref().map(|protocol| protocol.name.clone()).unwrap_or(tr("no-protocol"));
+                ComboBox::from_id_salt("Protocol Library").selected_text(selected).width(200.0).show_ui(ui, |ui| {
+                    if ui.selectable_label(microplate.protocol.is_none(), tr("no-protocol")).clicked() {
+                        microplate.protocol = None;
+                    }
+                    if ui.selectable_label(false, tr("new-protocol")).clicked() {
+                        microplate.protocol = Some(Protocol::default());
+                        concentrations.clear();
+                    }
+                    for protocol in protocols.iter() {
+                        if ui.selectable_label(microplate.protocol.as_ref() == Some(protocol), &protocol.name).clicked() {
+                            microplate.protocol = Some(protocol.clone());
+                            *concentrations = concentrations_text(&protocol.standard_concentrations);
+                        }
+                    }
+                });
+
+                let Some(protocol) = &mut microplate.protocol else {
+                    ui.add_space(4.0);
+                    ui.label(RichText::new(tr("protocol-info")).size(11.0));
+                    return
+                };
+                ui.add_space(10.0);
+
+                let parsed = parse_concentrations(concentrations);
+                if parsed.as_ref() != Some(&protocol.standard_concentrations) && !ui.memory(|memory| memory.has_focus(Id::new("Protocol Concentrations"))) {
+                    *concentrations = concentrations_text(&protocol.standard_concentrations);
+                }
+
+                Grid::new("Protocol Grid").num_columns(2).spacing([10.0, 6.0]).show(ui, |ui| {
+                    ui.label(tr("name"));
+                    let text_edit = ui.add(TextEdit::singleline(&mut protocol.name).desired_width(200.0));
+                    Self::dashed_outline(ui, &text_edit);
+                    ui.end_row();
+
+                    ui.label(tr("concentrations"));
+                    ui.vertical(|ui| {
+                        let text_edit = ui.add(TextEdit::singleline(concentrations)
+                            .id(Id::new("Protocol Concentrations"))
+                            .desired_width(200.0)
+                            .hint_text("1000; 500; 250"));
+                        Self::dashed_outline(ui, &text_edit);
+                        match parse_concentrations(concentrations) {
+                            Some(parsed) => if text_edit.changed() { protocol.standard_concentrations = parsed },
+                            None => { ui.label(RichText::new(tr("protocol-concentrations-invalid")).size(11.0).color(FLAG_COLOR)); },
+                        }
+                    });
+                    ui.end_row();
+
+                    ui.label(tr("weighting"));
+                    ComboBox::from_id_salt("Protocol Weighting").selected_text(protocol.weighting.description()).show_ui(ui, |ui| {
+                        for weighting in Weighting::ALL {
+                            ui.selectable_value(&mut protocol.weighting, weighting, weighting.description());
+                        }
+                    });
+                    ui.end_row();
+
+                    ui.label(tr("qc-cv-limit"));
+                    ui.horizontal(|ui| {
+                        let mut enabled = protocol.cv_limit.is_some();
+                        ui.checkbox(&mut enabled, "");
+                        match (enabled, &mut protocol.cv_limit) {
+                            (true, Some(limit)) => { percent_drag_value(ui, limit); },
+                            (true, limit) => *limit = Some(20.0),
+                            (false, limit) => *limit = None,
+                        }
+                    });
+                    ui.end_row();
+
+                    ui.label(tr("qc-r-squared-limit"));
+                    ui.horizontal(|ui| {
+                        let mut enabled = protocol.r_sq_limit.is_some();
+                        ui.checkbox(&mut enabled, "");
+                        match (enabled, &mut protocol.r_sq_limit) {
+                            (true, Some(limit)) => {
+                                let drag_value = ui.add(DragValue::new(limit).speed(0.001).range(0.0..=1.0)
+                                    .custom_formatter(|n, _| format_number(n))
+                                    .custom_parser(parse_decimal));
+                                Self::dashed_outline(ui, &drag_value);
+                            },
+                            (true, limit) => *limit = Some(0.99),
+                            (false, limit) => *limit = None,
+                        }
+                    });
+                    ui.end_row();
+
+                    ui.label(tr("qc-recovery-range"));
+                    ui.horizontal(|ui| {
+                        let mut enabled = protocol.recovery_range.is_some();
+                        ui.checkbox(&mut enabled, "");
+                        match (enabled, &mut protocol.recovery_range) {
+                            (true, Some((min, max))) => {
+                                percent_drag_value(ui, min);
+                                ui.label(" - ");
+                                percent_drag_value(ui, max);
+                            },
+                            (true, range) => *range = Some((80.0, 120.0)),
+                            (false, range) => *range = None,
+                        }
+                    });
+                    ui.end_row();
+
+                    ui.label(tr("qc-min-curve-grade")).on_hover_text(tr("qc-min-curve-grade-hover"));
+                    ui.horizontal(|ui| {
+                        let mut enabled = protocol.min_curve_grade.is_some();
+                        ui.checkbox(&mut enabled, "");
+                        match (enabled, &mut protocol.min_curve_grade) {
+                            (true, Some(minimum)) => {
+                                ComboBox::from_id_salt("Protocol Curve Grade").selected_text(minimum.name()).show_ui(ui, |ui| {
+                                    for grade in CurveGrade::ALL {
+                                        ui.selectable_value(minimum, grade, grade.name());
+                                    }
+                                });
+                            },
+                            (true, minimum) => *minimum = Some(CurveGrade::B),
+                            (false, minimum) => *minimum = None,
+                        }
+                    });
+                    ui.end_row();
+
+                    ui.label(tr("qc-control-recovery-range")).on_hover_text(tr("qc-control-recovery-range-hover"));
+                    ui.horizontal(|ui| {
+                        let mut enabled = protocol.control_recovery.is_some();
+                        ui.checkbox(&mut enabled, "");
+                        match (enabled, &mut protocol.control_recovery) {
+                            (true, Some((min, max))) => {
+                                percent_drag_value(ui, min);
+                                ui.label(" - ");
+                                percent_drag_value(ui, max);
+                            },
+                            (true, range) => *range = Some((80.0, 120.0)),
+                            (false, range) => *range = None,
+                        }
+                    });
+                    ui.end_row();
+
+                    ui.label(tr("qc-max-blank"));
+                    ui.horizontal(|ui| {
+                        let mut enabled = protocol.max_blank.is_some();
+                        ui.checkbox(&mut enabled, "");
+                        match (enabled, &mut protocol.max_blank) {
+                            (true, Some(limit)) => {
+                                let drag_value = ui.add(DragValue::new(limit).speed(0.001).range(f64::MIN..=f64::MAX)
+                                    .custom_formatter(|n, _| format_number(n))
+                                    .custom_parser(parse_decimal));
+                                Self::dashed_outline(ui, &drag_value);
+                            },
+                            (true, limit) => *limit = Some(0.1),
+                            (false, limit) => *limit = None,
+                        }
+                    });
+                    ui.end_row();
+
+                    ui.label(tr("max-dilution")).on_hover_text(tr("max-dilution-hover"));
+                    ui.horizontal(|ui| {
+                        let mut enabled = protocol.max_dilution.is_some();
+                        ui.checkbox(&mut enabled, "");
+                        match (enabled, &mut protocol.max_dilution) {
+                            (true, Some(dilution)) => {
+                                let drag_value = ui.add(DragValue::new(dilution).speed(0.1).range(1.0..=f64::MAX).prefix("1:")
+                                    .custom_formatter(|n, _| format_number(n))
+                                    .custom_parser(parse_decimal));
+                                Self::dashed_outline(ui, &drag_value);
+                            },
+                            (true, dilution) => *dilution = Some(10.0),
+                            (false, dilution) => *dilution = None,
+                        }
+                    });
+                    ui.end_row();
+                });
+                ui.add_space(10.0);
+
+                ui.label(RichText::new(tr("derived-columns")).strong());
+                ui.label(RichText::new(tr_args("derived-columns-info", &[("variables", VARIABLES.join(", ").into())])).size(11.0));
+                ui.add_space(5.0);
+                let columns = &mut protocol.derived_columns;
+                let error = parse_columns(columns).err();
+                let mut removed = None;
+                Grid::new("Derived Columns").num_columns(3).spacing([10.0, 4.0]).show(ui, |ui| {
+                    for (index, column) in columns.iter_mut().enumerate() {
+                        let text_edit = ui.add(TextEdit::singleline(&mut column.name).desired_width(90.0).hint_text(tr("name")));
+                        Self::dashed_outline(ui, &text_edit);
+                        let text_edit = ui.add(TextEdit::singleline(&mut column.expression).desired_width(200.0).hint_text("corrected / 0.05"));
+                        Self::dashed_outline(ui, &text_edit);
+                        if ui.button(tr("delete")).clicked() {
+                            removed = Some(index);
+                        }
+                        ui.end_row();
+                    }
+                });
+                if let Some(index) = removed {
+                    columns.remove(index);
+                }
+                if let Some((index, error)) = error {
+                    let name = columns.get(index).map(|column| column.name.clone()).unwrap_or_default();
+                    ui.label(RichText::new(tr_args("derived-column-invalid", &[("name", name.into()), ("error", error.to_string().into())])).size(11.0).color(FLAG_COLOR));
+                }
+                let button = ui.button(tr("add-derived-column"));
+                Self::dashed_outline(ui, &button);
+                if button.clicked() {
+                    columns.push(DerivedColumn::default());
+                }
+                ui.add_space(10.0);
+
+                ui.horizontal(|ui| {
+                    let button = ui.button(tr("protocol-apply"));
+                    Self::dashed_outline(ui, &button);
+                    let button = button.on_hover_text(tr("protocol-apply-hover"));
+                    if button.clicked() {
+                        if let Some(protocol) = microplate.protocol.clone() {
+                            protocol.apply(microplate);
+                        }
+                    }
+                    ui.add_space(10.0);
+
+                    let Some(protocol) = &microplate.protocol else { return };
+                    let button = ui.add_enabled(!protocol.name.trim().is_empty(), egui::Button::new(tr("protocol-save")));
+                    Self::dashed_outline(ui, &button);
+                    if button.clicked() {
+                        match protocols.iter_mut().find(|existing| existing.name == protocol.name) {
+                            Some(existing) => *existing = protocol.clone(),
+                            None => protocols.push(protocol.clone()),
+                        }
+                        library_changed = true;
+                    }
+
+                    if let Some(i) = protocols.iter().position(|existing| existing.name == protocol.name) {
+                        ui.add_space(10.0);
+                        let button = ui.button(tr("protocol-delete"));
+                        Self::dashed_outline(ui, &button);
+                        if button.clicked() {
+                            protocols.remove(i);
+                            library_changed = true;
+                        }
+                    }
+                });
+            });
+
+        if library_changed && save_protocols(&self.protocols).is_err() {
+            self.notifications.push(SerdeError::CantWriteFile);
+        }
+    }
+
+    /// One verdict over every acceptance criterion of the protocol, above the fit results. Failed criteria lead to
+    /// where they can be looked into.
+    pub fn qc_banner(&mut self, ui: &mut Ui) -> Option<()> {
+        let regression = self.regression.as_ref()?;
+        let protocol = self.microplate.protocol.as_ref()?;
+        let checks = protocol.evaluate(&self.microplate, regression);
+        let failed: Vec<&QcCheck> = checks.iter().filter(|check| !check.passed).collect();
+        let outdated = self.plate_changed();
+        let (verdict, color, summary) = if failed.is_empty() {
+            (tr("qc-pass"), PASS_COLOR, tr_args("qc-banner-passed", &[("count", checks.len().into())]))
+        } else {
+            (tr("qc-fail"), FLAG_COLOR, tr_args("qc-banner-failed", &[("failed", failed.len().into()), ("count", checks.len().into())]))
+        };
+        let mut clicked = None;
+
+        egui::Frame::new().fill(color).inner_margin(10.0).show(ui, |ui| {
+            ui.set_width(ui.available_width());
+            ui.horizontal(|ui| {
+                ui.label(RichText::new(verdict).size(28.0).strong().color(Color32::WHITE));
+                ui.add_space(15.0);
+                ui.vertical(|ui| {
+                    ui.label(RichText::new(summary).color(Color32::WHITE));
+                    ui.horizontal_wrapped(|ui| {
+                        for check in &failed {
+                            let text = RichText::new(check.criterion.name()).underline().strong().color(Color32::WHITE);
+                            if ui.add(Button::new(text).frame(false)).on_hover_text(&check.detail).clicked() {
+                                clicked = Some(check.criterion);
+                            }
+                        }
+                    });
+                    if outdated {
+                        ui.label(RichText::new(tr("qc-banner-outdated")).size(11.0).color(Color32::WHITE));
+                    }
+                });
+            });
+        });
+        ui.add_space(20.0);
+
+        if let Some(criterion) = clicked {
+            self.open_criterion(criterion);
+        }
+        Some(())
+    }
+
+    /// Where a failed criterion can be looked into
+    fn open_criterion(&mut self, criterion: Criterion) {
+        match criterion {
+            Criterion::StandardConcentrations | Criterion::Weighting => self.show_protocol = true,
+            Criterion::ReplicateCv => self.results_sort = Some(ResultsSort { column: SortColumn::Cv, descending: true }),
+            Criterion::RSquared | Criterion::Grade => self.show_diagnostics = true,
+            Criterion::Recovery => self.show_back_calculation = true,
+            Criterion::ControlRecovery => self.results_filter = "recovery >= 0".to_string(), // the spiked unknowns
+            Criterion::BlankLevel => self.current_tab = ElisaTab::Edit,
+        }
+    }
+
+    /// Pass or fail of each acceptance criterion of the protocol, shown next to the fit results
+    pub fn qc_summary(&mut self, ui: &mut Ui) -> Option<()> {
+        let regression = self.regression.as_ref()?;
+        let protocol = self.microplate.protocol.as_ref()?;
+        let checks = protocol.evaluate(&self.microplate, regression);
+
+        let background = ui.visuals().faint_bg_color;
+        let stroke = ui.visuals().noninteractive().bg_stroke;
+
+        egui::Frame::new().show(ui, |ui| {
+            let width = ui.available_width().max(20.0);
+            ui.set_width(width);
+
+            ui.vertical_centered(|ui| ui.heading(tr_args("qc-summary", &[("protocol", protocol.name.as_str().into())])));
+            ui.add_space(10.0);
+            egui::Frame::new()
+                .fill(background).stroke(stroke)
+                .inner_margin(10.0)
+                .show(ui, |ui| {
+                    ui.set_width(width - 20.0);
+                    Grid::new("QC Summary").num_columns(3).spacing([10.0, 5.0]).show(ui, |ui| {
+                        for check in &checks {
+                            let (text, color) = if check.passed { (tr("qc-pass"), PASS_COLOR) } else { (tr("qc-fail"), FLAG_COLOR) };
+                            ui.label(RichText::new(text).color(color).strong());
+                            ui.label(check.criterion.name());
+                            ui.label(RichText::new(&check.detail).size(11.0));
+                            ui.end_row();
+                        }
+                    });
+                });
+        });
+        ui.add_space(30.0);
+        Some(())
+    }
+}
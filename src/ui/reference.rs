@@ -0,0 +1,117 @@
+use eframe::egui::{self, Grid, Id, RichText, ScrollArea, Ui};
+
+use crate::{app::{AppError, SerdeError}, files::*, history::*, i18n::*, logistic_regression::*, Elisa};
+
+/// Reference from a saved assay, dated by the read time since the file doesn't keep the date of the fit
+fn reference_from_file(buf: &[u8]) -> Result<CurveRecord, AppError> {
+    let microplate = serde_json::from_slice::<Microplate>(buf).map_err(|_| SerdeError::CantDeserialize)?;
+    let regression = Regression::new(&microplate)?;
+    Ok(CurveRecord {
+        date: microplate.metadata.read_time.clone().unwrap_or_default(),
+        ..CurveRecord::new(&microplate, &regression)
+    })
+}
+
+impl Elisa {
+    pub fn reference_curve_button(&mut self, ui: &mut Ui) {
+        if self.regression.is_none() { return }
+
+        let button = ui.button(RichText::new(tr("reference-curve")));
+        Self::dashed_outline(ui, &button);
+        let button = button.on_hover_text(tr("reference-curve-hover"));
+        if button.clicked() {
+            self.show_reference = !self.show_reference;
+        }
+    }
+
+    /// Picks the curve overlaid on the plot and compares its parameters with the current fit
+    pub fn reference_curve_window(&mut self, ctx: &egui::Context) {
+        if !self.show_reference { return }
+
+        let history = &self.curve_history;
+        let reference = &mut self.reference_curve;
+        let mut load_file = false;
+
+        egui::Window::new(tr("reference-curve"))
+            .id(Id::new("Reference Curve"))
+            .open(&mut self.show_reference)
+            .resizable(false)
+            .collapsible(false)
+            .frame(egui::Frame::window(&ctx.style()).inner_margin(10.0))
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    let button = ui.button(tr("reference-from-file")).on_hover_text(tr("reference-from-file-hover"));
+                    Self::dashed_outline(ui, &button);
+                    load_file = button.clicked();
+                    ui.add_space(10.0);
+
+                    ui.add_enabled_ui(!history.is_empty(), |ui| {
+                        let menu_button = ui.menu_button(tr("reference-from-history"), |ui| {
+                            ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                                for record in history.iter().rev() {
+                                    let text = [&record.date, &record.plate, &record.kit_lot].iter()
+                                        .filter(|text| !text.is_empty())
+                                        .map(|text| text.as_str())
+                                        .collect::<Vec<_>>()
+                                        .join(" · ");
+                                    if ui.button(text).clicked() {
+                                        *reference = Some(record.clone());
+                                        ui.close_menu();
+                                    }
+                                }
+                            });
+                        });
+                        Self::dashed_outline(ui, &menu_button.response);
+                    });
+                    ui.add_space(10.0);
+
+                    if ui.add_enabled(reference.is_some(), egui::Button::new(tr("clear"))).clicked() {
+                        *reference = None;
+                    }
+                });
+                ui.add_space(10.0);
+
+                let Some(record) = reference.as_ref() else {
+                    ui.label(tr("reference-none"));
+                    return
+                };
+                let source = tr_args("reference-source", &[
+                    ("plate", record.plate.clone().into()),
+                    ("lot", if record.kit_lot.is_empty() { tr("no-lot") } else { record.kit_lot.clone() }.into()),
+                    ("date", record.date.clone().into()),
+                ]);
+                ui.label(source);
+                ui.add_space(10.0);
+
+                let Some(regression) = &self.regression else { return };
+                let (a, b, c, d) = regression.abcd;
+                let (ra, rb, rc, rd) = record.abcd;
+                Grid::new("Reference Delta").num_columns(5).spacing([20.0, 4.0]).striped(true).show(ui, |ui| {
+                    for header in [tr("reference-parameter"), tr("reference-current"), tr("reference-value"), "Δ".to_string(), "Δ %".to_string()] {
+                        ui.label(RichText::new(header).strong());
+                    }
+                    ui.end_row();
+                    for (name, current, previous) in [("a", a, ra), ("b", b, rb), ("c", c, rc), ("d", d, rd), ("R^2", regression.r_sq, record.r_sq)] {
+                        ui.label(name);
+                        ui.label(format_value(current));
+                        ui.label(format_value(previous));
+                        ui.label(format_value(current - previous));
+                        let percent = (previous != 0.0).then(|| (current - previous) / previous.abs() * 100.0);
+                        ui.label(percent.map(|percent| format_decimal(percent, 1)).unwrap_or_default());
+                        ui.end_row();
+                    }
+                });
+            });
+
+        if load_file {
+            self.opened_files.pick(ctx, FileAction::ReferenceCurve, &[("Elisa".into(), &PROJECT_EXTENSIONS)]);
+        }
+    }
+
+    pub fn open_reference_curve(&mut self, file: &OpenedFile) {
+        match reference_from_file(&file.bytes) {
+            Ok(record) => self.reference_curve = Some(record),
+            Err(error) => self.notifications.push(error),
+        }
+    }
+}
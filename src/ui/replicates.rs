@@ -0,0 +1,91 @@
+use eframe::egui::{self, ComboBox, Grid, Id, RichText, ScrollArea};
+
+use crate::{i18n::*, logistic_regression::*, replicates::*, Elisa};
+
+impl Elisa {
+    /// Replicate pattern found on the plate, the groups change only once the user applies it
+    pub fn replicates_window(&mut self, ctx: &egui::Context) {
+        if !self.show_replicates { return }
+
+        let detected = detect_replicates(&self.microplate);
+        let pattern = self.replicate_pattern.or(detected.as_ref().map(|assignment| assignment.pattern));
+        let assignment = pattern.and_then(|pattern| assign_replicates(&self.microplate, pattern));
+
+        let locked = self.microplate.locked;
+        let microplate = &self.microplate;
+        let chosen = &mut self.replicate_pattern;
+        let mut apply = false;
+
+        egui::Window::new(tr("detect-replicates"))
+            .id(Id::new("Detect Replicates"))
+            .open(&mut self.show_replicates)
+            .resizable(false)
+            .collapsible(false)
+            .frame(egui::Frame::window(&ctx.style()).inner_margin(10.0))
+            .show(ctx, |ui| {
+                if locked {
+                    ui.disable();
+                }
+
+                match &detected {
+                    Some(assignment) => ui.label(tr_args("replicates-detected", &[("pattern", assignment.pattern.description().into())])),
+                    None => ui.label(tr("replicates-none")),
+                };
+                ui.add_space(10.0);
+
+                let selected_text = pattern.map(|pattern| pattern.description()).unwrap_or_default();
+                let combo_box = ComboBox::from_id_salt("Replicate Pattern")
+                    .selected_text(selected_text)
+                    .show_ui(ui, |ui| {
+                        for option in ReplicatePattern::ALL {
+                            if ui.selectable_label(pattern == Some(option), option.description()).clicked() {
+                                *chosen = Some(option);
+                            }
+                        }
+                    });
+                Self::dashed_outline(ui, &combo_box.response);
+                ui.add_space(10.0);
+
+                let Some(assignment) = &assignment else {
+                    if pattern.is_some() {
+                        ui.label(tr("replicates-pattern-mismatch"));
+                    }
+                    return
+                };
+
+                ui.label(RichText::new(tr("replicates-preview")).strong());
+                ui.add_space(5.0);
+                ScrollArea::vertical().max_height(250.0).show(ui, |ui| {
+                    Grid::new("Replicate Preview").num_columns(2).spacing([15.0, 4.0]).striped(true).show(ui, |ui| {
+                        for pair in assignment.wells.chunks(2) {
+                            let &[(a, group), (b, _)] = pair else { continue };
+                            let name_id = match microplate.samples[a].typ {
+                                SampleType::Standard => "standard-number",
+                                _ => "unknown-number",
+                            };
+                            ui.label(tr_args(name_id, &[("number", (group + 1).into())]));
+                            ui.label(format!("{}, {}", microplate.well_name(a), microplate.well_name(b)));
+                            ui.end_row();
+                        }
+                    });
+                });
+                if let Some(difference) = assignment.difference {
+                    ui.add_space(5.0);
+                    ui.label(tr_args("replicates-difference", &[("difference", format_decimal(difference * 100.0, 1).into())]));
+                }
+                ui.add_space(10.0);
+
+                let button = ui.button(tr("apply-replicates"));
+                Self::dashed_outline(ui, &button);
+                apply = button.clicked();
+            });
+
+        if !apply { return }
+        let Some(assignment) = assignment else { return };
+        self.microplate.apply_replicates(&assignment);
+        self.standards_textfield.iter_mut().for_each(String::clear);
+        self.hidden_labels.clear();
+        self.replicate_pattern = None;
+        self.show_replicates = false;
+    }
+}
@@ -0,0 +1,162 @@
+use eframe::egui::{self, Grid, Id, RichText, TextEdit};
+
+use crate::{app::SerdeError, i18n::*, report::*, settings::save_settings, Elisa};
+
+impl Elisa {
+    pub fn report_options_window(&mut self, ctx: &egui::Context) {
+        let mut changed = false;
+        let mut templates_changed = false;
+        let report = &mut self.settings.report;
+        let templates = &mut self.report_templates;
+        let template_name = &mut self.template_name;
+
+        egui::Window::new(tr("report-options"))
+            .id(Id::new("Report Options"))
+            .open(&mut self.show_report_options)
+            .resizable(false)
+            .collapsible(false)
+            .frame(egui::Frame::window(&ctx.style()).inner_margin(10.0))
+            .show(ctx, |ui| {
+                ui.label(tr("report-header"));
+                ui.add_space(4.0);
+                let text_edit = ui.add(TextEdit::multiline(&mut report.header).desired_rows(2).desired_width(280.0));
+                Self::dashed_outline(ui, &text_edit);
+                changed |= text_edit.changed();
+                ui.add_space(10.0);
+
+                // the logo is read from its path when the report is made, which the browser doesn't give
+                #[cfg(not(target_arch = "wasm32"))]
+                {
+                    ui.horizontal(|ui| {
+                        ui.label(tr("report-logo"));
+                        ui.add_space(10.0);
+                        let button = ui.button(tr("choose"));
+                        Self::dashed_outline(ui, &button);
+                        if button.clicked() {
+                            if let Some(path) = rfd::FileDialog::new()
+                                .add_filter(tr("image"), &["png", "jpg", "jpeg"])
+                                .pick_file() {
+                                report.logo = Some(path);
+                                changed = true;
+                            }
+                        }
+                        if report.logo.is_some() {
+                            ui.add_space(5.0);
+                            let button = ui.button(tr("remove"));
+                            Self::dashed_outline(ui, &button);
+                            if button.clicked() {
+                                report.logo = None;
+                                changed = true;
+                            }
+                        }
+                    });
+                    if let Some(file_name) = report.logo.as_ref().and_then(|logo| logo.file_name()) {
+                        ui.label(RichText::new(file_name.to_string_lossy()).size(11.0));
+                    }
+                    ui.add_space(10.0);
+                }
+
+                ui.label(tr("report-sections"));
+                ui.add_space(4.0);
+                for (checked, id) in [
+                    (&mut report.barcode, "report-section-barcode"),
+                    (&mut report.plot, "report-section-plot"),
+                    (&mut report.parameters, "parameters"),
+                    (&mut report.notes, "description"),
+                    (&mut report.plate_map, "plate-map"),
+                    (&mut report.residuals, "report-section-residuals"),
+                    (&mut report.results, "report-section-results"),
+                    (&mut report.sample_metadata, "report-section-sample-metadata"),
+                    (&mut report.qc, "report-section-qc"),
+                    (&mut report.excluded_wells, "report-section-excluded-wells"),
+                    (&mut report.audit_trail, "audit-trail"),
+                    (&mut report.signatures, "signatures"),
+                ] {
+                    changed |= ui.checkbox(checked, tr(id)).changed();
+                }
+                ui.add_space(10.0);
+                ui.separator();
+                ui.add_space(10.0);
+
+                ui.label(tr("report-templates"));
+                ui.add_space(4.0);
+                ui.horizontal(|ui| {
+                    let text_edit = ui.add(TextEdit::singleline(template_name).desired_width(150.0).hint_text(tr("name")));
+                    Self::dashed_outline(ui, &text_edit);
+                    ui.add_space(5.0);
+                    let button = ui.add_enabled(!template_name.trim().is_empty(), egui::Button::new(tr("save-template")));
+                    Self::dashed_outline(ui, &button);
+                    if button.clicked() {
+                        let template = ReportTemplate { name: template_name.trim().to_string(), ..report.clone() };
+                        match templates.iter_mut().find(|existing| existing.name == template.name) {
+                            Some(existing) => *existing = template,
+                            None => templates.push(template),
+                        }
+                        templates_changed = true;
+                    }
+                });
+                ui.add_space(4.0);
+
+                let mut delete = None;
+                Grid::new("Report Templates").spacing([5.0, 4.0]).show(ui, |ui| {
+                    for (i, template) in templates.iter().enumerate() {
+                        ui.label(&template.name);
+                        let button = ui.button(tr("apply"));
+                        Self::dashed_outline(ui, &button);
+                        if button.clicked() {
+                            *report = template.clone();
+                            *template_name = template.name.clone();
+                            changed = true;
+                        }
+                        let button = ui.button(tr("remove"));
+                        Self::dashed_outline(ui, &button);
+                        if button.clicked() {
+                            delete = Some(i);
+                        }
+                        ui.end_row();
+                    }
+                });
+                if let Some(i) = delete {
+                    templates.remove(i);
+                    templates_changed = true;
+                }
+            });
+
+        if changed && save_settings(&self.settings).is_err() {
+            self.notifications.push(SerdeError::CantWriteFile);
+        }
+        if templates_changed && save_report_templates(&self.report_templates).is_err() {
+            self.notifications.push(SerdeError::CantWriteFile);
+        }
+    }
+
+    pub fn signatures_window(&mut self, ctx: &egui::Context) {
+        let metadata = &mut self.microplate.metadata;
+
+        egui::Window::new(tr("signatures"))
+            .id(Id::new("Signatures"))
+            .open(&mut self.show_signatures)
+            .resizable(false)
+            .collapsible(false)
+            .frame(egui::Frame::window(&ctx.style()).inner_margin(10.0))
+            .show(ctx, |ui| {
+                Grid::new("Signatures Grid").num_columns(3).spacing([10.0, 4.0]).show(ui, |ui| {
+                    ui.label("");
+                    ui.label(tr("name"));
+                    ui.label(tr("role"));
+                    ui.end_row();
+
+                    for (signatory, id) in [(&mut metadata.analyzed_by, "analyzed-by"), (&mut metadata.reviewed_by, "reviewed-by")] {
+                        ui.label(tr(id));
+                        let text_edit = ui.add(TextEdit::singleline(&mut signatory.name).desired_width(140.0));
+                        Self::dashed_outline(ui, &text_edit);
+                        let text_edit = ui.add(TextEdit::singleline(&mut signatory.role).desired_width(140.0));
+                        Self::dashed_outline(ui, &text_edit);
+                        ui.end_row();
+                    }
+                });
+                ui.add_space(4.0);
+                ui.label(RichText::new(tr("signatures-info")).size(11.0));
+            });
+    }
+}
@@ -0,0 +1,82 @@
+use eframe::egui::{self, DragValue, Grid, Id, RichText, TextEdit};
+
+use crate::{i18n::*, ui::plot::FLAG_COLOR, Elisa};
+
+impl Elisa {
+    /// Operator, instrument, wavelength, read time and kit of the plate, with the read settings of the reader export
+    pub fn run_metadata_window(&mut self, ctx: &egui::Context) {
+        let locked = self.microplate.locked;
+        let metadata = &mut self.microplate.metadata;
+        let operator = &self.settings.operator;
+        let temperature_range = self.settings.temperature_range;
+
+        egui::Window::new(tr("run-metadata"))
+            .id(Id::new("Run Metadata"))
+            .open(&mut self.show_run_metadata)
+            .resizable(false)
+            .collapsible(false)
+            .frame(egui::Frame::window(&ctx.style()).inner_margin(10.0))
+            .show(ctx, |ui| {
+                if locked {
+                    ui.disable();
+                }
+
+                Grid::new("Run Metadata Grid").num_columns(2).spacing([10.0, 6.0]).show(ui, |ui| {
+                    let mut read_time = metadata.read_time.clone().unwrap_or_default();
+                    for (id, text, hint) in [
+                        ("operator", &mut metadata.operator, operator.as_str()),
+                        ("instrument", &mut metadata.instrument, ""),
+                        ("read-time", &mut read_time, "YYYY-MM-DD HH:MM"),
+                        ("kit-lot", &mut metadata.kit_lot, ""),
+                        ("kit-expiry", &mut metadata.kit_expiry, "YYYY-MM-DD"),
+                    ] {
+                        ui.label(tr(id));
+                        let text_edit = ui.add(TextEdit::singleline(text).desired_width(150.0).hint_text(hint));
+                        Self::dashed_outline(ui, &text_edit);
+                        ui.end_row();
+                    }
+                    // read from the export of the reader, or typed
+                    metadata.read_time = Some(read_time).filter(|read_time| !read_time.trim().is_empty());
+
+                    ui.label(tr("wavelength"));
+                    ui.horizontal(|ui| {
+                        let mut enabled = metadata.wavelength.is_some();
+                        ui.checkbox(&mut enabled, "");
+                        match (enabled, &mut metadata.wavelength) {
+                            (true, Some(wavelength)) => {
+                                let drag_value = ui.add(DragValue::new(wavelength).range(200.0..=1000.0).suffix(" nm"));
+                                Self::dashed_outline(ui, &drag_value);
+                            },
+                            (true, wavelength) => *wavelength = Some(450.0),
+                            (false, wavelength) => *wavelength = None,
+                        }
+                    });
+                    ui.end_row();
+
+                    // temperature and read settings come with the reader export
+                    if let Some(temperature) = metadata.temperature {
+                        ui.label(tr("temperature"));
+                        let text = RichText::new(format!("{} °C", format_number(temperature)));
+                        match metadata.temperature_out_of_range(temperature_range) {
+                            Some(_) => { ui.label(text.color(FLAG_COLOR)).on_hover_text(tr("temperature-out-of-range")); },
+                            None => { ui.label(text); },
+                        }
+                        ui.end_row();
+                    }
+                });
+
+                if !metadata.read_settings.is_empty() {
+                    ui.add_space(10.0);
+                    ui.label(RichText::new(tr("read-settings")).strong());
+                    ui.add_space(5.0);
+                    Grid::new("Read Settings Grid").num_columns(2).spacing([10.0, 4.0]).show(ui, |ui| {
+                        for (name, value) in &metadata.read_settings {
+                            ui.label(RichText::new(name).size(11.0));
+                            ui.label(RichText::new(value).size(11.0));
+                            ui.end_row();
+                        }
+                    });
+                }
+            });
+    }
+}
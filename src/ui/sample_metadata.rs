@@ -0,0 +1,62 @@
+use eframe::egui::{self, Id, TextEdit};
+use egui_extras::{Column, TableBuilder};
+
+use crate::{files::FileAction, i18n::*, Elisa};
+
+impl Elisa {
+    /// Subject, timepoint, matrix and collection date of every unknown group
+    pub fn sample_metadata_window(&mut self, ctx: &egui::Context) {
+        let locked = self.microplate.locked;
+        let groups = &mut self.microplate.unknown_groups;
+        let opened_files = &self.opened_files;
+
+        egui::Window::new(tr("sample-metadata"))
+            .id(Id::new("Sample Metadata"))
+            .open(&mut self.show_sample_metadata)
+            .default_height(300.0)
+            .collapsible(false)
+            .frame(egui::Frame::window(&ctx.style()).inner_margin(10.0))
+            .show(ctx, |ui| {
+                if locked {
+                    ui.disable();
+                }
+
+                let button = ui.button(tr("import-manifest")).on_hover_text(tr("import-manifest-hover"));
+                Self::dashed_outline(ui, &button);
+                if button.clicked() {
+                    opened_files.pick(ui.ctx(), FileAction::Manifest, &[(tr("manifest"), &["csv", "txt", "tsv"])]);
+                }
+                ui.add_space(10.0);
+
+                TableBuilder::new(ui)
+                    .striped(true)
+                    .column(Column::auto().at_least(80.0))
+                    .columns(Column::auto().at_least(100.0), 5)
+                    .header(20.0, |mut header| {
+                        for id in ["group", "label", "subject-id", "timepoint", "matrix", "collection-date"] {
+                            header.col(|ui| { ui.label(tr(id)); });
+                        }
+                    })
+                    .body(|body| {
+                        body.rows(25.0, groups.len(), |mut row| {
+                            let index = row.index();
+                            let group = &mut groups[index];
+                            row.col(|ui| { ui.label(tr_args("unknown-number", &[("number", (index + 1).into())])); });
+                            let metadata = &mut group.metadata;
+                            for (text, hint) in [
+                                (&mut group.label, ""),
+                                (&mut metadata.subject_id, ""),
+                                (&mut metadata.timepoint, ""),
+                                (&mut metadata.matrix, ""),
+                                (&mut metadata.collection_date, "YYYY-MM-DD"),
+                            ] {
+                                row.col(|ui| {
+                                    let text_edit = ui.add(TextEdit::singleline(text).desired_width(100.0).hint_text(hint));
+                                    Self::dashed_outline(ui, &text_edit);
+                                });
+                            }
+                        });
+                    });
+            });
+    }
+}
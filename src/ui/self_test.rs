@@ -0,0 +1,76 @@
+use eframe::egui::{self, Grid, Id, RichText};
+
+use crate::{i18n::*, self_test::*, ui::{plot::FLAG_COLOR, protocol::PASS_COLOR}, Elisa};
+
+impl Elisa {
+    /// Checks the gradient of the fit against finite differences and fits standards made from known parameters.
+    /// Opened from the command palette by typing its full name.
+    pub fn self_test_window(&mut self, ctx: &egui::Context) {
+        if !self.show_self_test { return }
+        if self.self_test.is_empty() {
+            self.self_test = run_self_test();
+            let failed = self.self_test.iter().filter(|result| !result.passed()).count();
+            log::info!("Self-test ran {} datasets, {failed} failed", self.self_test.len());
+        }
+
+        let results = &self.self_test;
+        let mut run_again = false;
+
+        egui::Window::new(tr("self-test"))
+            .id(Id::new("Self-Test"))
+            .open(&mut self.show_self_test)
+            .resizable(false)
+            .collapsible(false)
+            .frame(egui::Frame::window(&ctx.style()).inner_margin(10.0))
+            .show(ctx, |ui| {
+                ui.label(RichText::new(tr("self-test-info")).size(11.0));
+                ui.add_space(10.0);
+
+                let verdict = |ui: &mut egui::Ui, passed: bool| {
+                    let (text, color) = if passed { (tr("qc-pass"), PASS_COLOR) } else { (tr("qc-fail"), FLAG_COLOR) };
+                    ui.label(RichText::new(text).color(color).strong())
+                };
+                Grid::new("Self-Test Grid").num_columns(4).spacing([20.0, 4.0]).striped(true).show(ui, |ui| {
+                    ui.label(tr("self-test-dataset"));
+                    ui.label(tr("self-test-gradient"));
+                    ui.label(tr("self-test-fit"));
+                    ui.label(tr("self-test-parameters"));
+                    ui.end_row();
+
+                    for result in results {
+                        ui.label(result.dataset.name());
+                        let worst = result.gradient_checks.iter().map(|check| check.relative_error()).fold(0.0, f64::max);
+                        verdict(ui, result.gradient_passed())
+                            .on_hover_text(tr_args("self-test-gradient-hover", &[("error", format!("{worst:.1e}").into())]));
+
+                        let (a, b, c, d) = result.dataset.abcd;
+                        let expected = tr_args("self-test-expected", &[("parameters", format_parameters((a, b, c, d)).into())]);
+                        match &result.fitted {
+                            Ok(abcd) => {
+                                verdict(ui, result.fit_passed()).on_hover_text(expected);
+                                ui.label(format_parameters(*abcd));
+                            },
+                            Err(error) => {
+                                verdict(ui, false).on_hover_text(expected);
+                                ui.label(RichText::new(error.to_string()).color(FLAG_COLOR));
+                            },
+                        }
+                        ui.end_row();
+                    }
+                });
+                ui.add_space(10.0);
+
+                let button = ui.button(tr("self-test-run-again"));
+                Self::dashed_outline(ui, &button);
+                run_again = button.clicked();
+            });
+
+        if run_again {
+            self.self_test.clear();
+        }
+    }
+}
+
+fn format_parameters((a, b, c, d): (f64, f64, f64, f64)) -> String {
+    [a, b, c, d].map(format_value).join(", ")
+}
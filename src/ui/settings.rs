@@ -0,0 +1,267 @@
+use eframe::egui::{self, ComboBox, DragValue, Id, RichText, TextEdit, Ui};
+
+use crate::{app::SerdeError, i18n::*, linear_range::DEFAULT_LINEAR_RANGE, logistic_regression::Weighting, settings::*, ui::protocol::percent_drag_value, Elisa};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::{app::AppError, files::set_export_folder, server::ApiServer};
+
+/// Lower and upper end of the linear range of the reader
+pub fn linear_range_drag_values(ui: &mut Ui, low: &mut f64, high: &mut f64) -> bool {
+    let mut changed = false;
+    let ranges = [-1.0..=*high, *low..=10.0];
+    for (value, range) in [low, high].into_iter().zip(ranges) {
+        let drag_value = ui.add(DragValue::new(value).speed(0.01).range(range).suffix(" OD")
+            .custom_formatter(|n, _| format_number(n))
+            .custom_parser(parse_decimal));
+        Elisa::dashed_outline(ui, &drag_value);
+        changed |= drag_value.changed();
+    }
+    changed
+}
+
+impl Elisa {
+    pub fn settings_window(&mut self, ctx: &egui::Context) {
+        let mut changed = false;
+        let mut show_shortcuts = false;
+        #[cfg(not(target_arch = "wasm32"))]
+        let mut show_export_hook = false;
+        let settings = &mut self.settings;
+        #[cfg(not(target_arch = "wasm32"))]
+        let api_url = self.api_server.as_ref().map(|server| (server.url(), server.requests()));
+
+        egui::Window::new(tr("settings"))
+            .id(Id::new("Settings"))
+            .open(&mut self.show_settings)
+            .resizable(false)
+            .collapsible(false)
+            .frame(egui::Frame::window(&ctx.style()).inner_margin(10.0))
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(tr("language"));
+                    ui.add_space(10.0);
+                    ComboBox::from_id_salt("Language").selected_text(settings.language.name()).show_ui(ui, |ui| {
+                        for language in Language::ALL {
+                            changed |= ui.selectable_value(&mut settings.language, language, language.name()).changed();
+                        }
+                    });
+                });
+                ui.add_space(10.0);
+                ui.horizontal(|ui| {
+                    ui.label(tr("operator"));
+                    ui.add_space(10.0);
+                    let text_edit = ui.add(TextEdit::singleline(&mut settings.operator).desired_width(150.0));
+                    Self::dashed_outline(ui, &text_edit);
+                    changed |= text_edit.lost_focus();
+                });
+                ui.label(RichText::new(tr("operator-hover")).size(11.0));
+                ui.add_space(10.0);
+                ui.horizontal(|ui| {
+                    let format = &mut settings.number_format;
+                    ui.label(tr("number-format"));
+                    ui.add_space(10.0);
+                    let drag_value = ui.add(DragValue::new(&mut format.digits).range(1..=12));
+                    Self::dashed_outline(ui, &drag_value);
+                    changed |= drag_value.changed();
+                    ComboBox::from_id_salt("Precision").selected_text(format.precision.description()).show_ui(ui, |ui| {
+                        for precision in Precision::ALL {
+                            changed |= ui.selectable_value(&mut format.precision, precision, precision.description()).changed();
+                        }
+                    });
+                });
+                changed |= ui.checkbox(&mut settings.number_format.scientific, tr("scientific-notation"))
+                    .on_hover_text(tr("scientific-notation-hover"))
+                    .changed();
+                ui.label(RichText::new(tr_args("number-format-example", &[("example", settings.number_format.format(1234.5678).into())])).size(11.0));
+                ui.add_space(10.0);
+                ui.horizontal(|ui| {
+                    ui.label(tr("theme"));
+                    ui.add_space(10.0);
+                    ComboBox::from_id_salt("Theme").selected_text(settings.theme.description()).show_ui(ui, |ui| {
+                        for theme in AppTheme::ALL {
+                            changed |= ui.selectable_value(&mut settings.theme, theme, theme.description()).changed();
+                        }
+                    });
+                });
+                ui.add_space(10.0);
+                ui.horizontal(|ui| {
+                    ui.label(tr("cv-threshold"));
+                    ui.add_space(10.0);
+                    let mut enabled = settings.cv_threshold.is_some();
+                    changed |= ui.checkbox(&mut enabled, "").changed();
+                    match (enabled, &mut settings.cv_threshold) {
+                        (true, Some(threshold)) => changed |= percent_drag_value(ui, threshold),
+                        (true, threshold) => *threshold = Some(20.0),
+                        (false, threshold) => *threshold = None,
+                    }
+                });
+                ui.label(RichText::new(tr("cv-threshold-hover")).size(11.0));
+                ui.add_space(10.0);
+                ui.horizontal(|ui| {
+                    ui.label(tr("temperature-range"));
+                    ui.add_space(10.0);
+                    let mut enabled = settings.temperature_range.is_some();
+                    changed |= ui.checkbox(&mut enabled, "").changed();
+                    match (enabled, &mut settings.temperature_range) {
+                        (true, Some((min, max))) => {
+                            let ranges = [-10.0..=*max, *min..=100.0];
+                            for (value, range) in [min, max].into_iter().zip(ranges) {
+                                let drag_value = ui.add(DragValue::new(value).speed(0.1).range(range).suffix(" °C"));
+                                Self::dashed_outline(ui, &drag_value);
+                                changed |= drag_value.changed();
+                            }
+                        },
+                        (true, range) => *range = Some((18.0, 30.0)),
+                        (false, range) => *range = None,
+                    }
+                });
+                ui.label(RichText::new(tr("temperature-range-hover")).size(11.0));
+                ui.add_space(10.0);
+                ui.horizontal(|ui| {
+                    ui.label(tr("linear-range"));
+                    ui.add_space(10.0);
+                    let mut enabled = settings.linear_range.is_some();
+                    changed |= ui.checkbox(&mut enabled, "").changed();
+                    match (enabled, &mut settings.linear_range) {
+                        (true, Some((low, high))) => changed |= linear_range_drag_values(ui, low, high),
+                        (true, range) => *range = Some(DEFAULT_LINEAR_RANGE),
+                        (false, range) => *range = None,
+                    }
+                });
+                ui.label(RichText::new(tr("linear-range-hover")).size(11.0));
+                ui.add_space(10.0);
+                ui.horizontal(|ui| {
+                    ui.label(tr("autosave"));
+                    ui.add_space(10.0);
+                    let drag_value = ui.add(DragValue::new(&mut settings.autosave_minutes).range(0..=120).suffix(" min"));
+                    Self::dashed_outline(ui, &drag_value);
+                    changed |= drag_value.changed();
+                });
+                ui.label(RichText::new(tr("autosave-hover")).size(11.0));
+                ui.add_space(10.0);
+                changed |= ui.checkbox(&mut settings.name_from_barcode, tr("name-from-barcode")).changed();
+                ui.label(RichText::new(tr("name-from-barcode-hover")).size(11.0));
+                ui.add_space(10.0);
+                ui.horizontal(|ui| {
+                    let button = ui.button(tr("keyboard-shortcuts"));
+                    Self::dashed_outline(ui, &button);
+                    show_shortcuts = button.clicked();
+                    #[cfg(not(target_arch = "wasm32"))]
+                    {
+                        let button = ui.button(tr("post-analysis-hook")).on_hover_text(tr("post-analysis-hook-hover"));
+                        Self::dashed_outline(ui, &button);
+                        show_export_hook = button.clicked();
+                    }
+                });
+                #[cfg(not(target_arch = "wasm32"))]
+                {
+                    ui.add_space(10.0);
+                    ui.horizontal(|ui| {
+                        ui.label(tr("export-folder"));
+                        ui.add_space(10.0);
+                        let button = ui.button(tr("choose"));
+                        Self::dashed_outline(ui, &button);
+                        if button.clicked() {
+                            if let Some(folder) = rfd::FileDialog::new().pick_folder() {
+                                settings.export_folder = Some(folder);
+                                changed = true;
+                            }
+                        }
+                        if settings.export_folder.is_some() {
+                            ui.add_space(5.0);
+                            let button = ui.button(tr("remove"));
+                            Self::dashed_outline(ui, &button);
+                            if button.clicked() {
+                                settings.export_folder = None;
+                                changed = true;
+                            }
+                        }
+                    });
+                    let folder = settings.export_folder.as_ref().map(|folder| folder.display().to_string()).unwrap_or_else(|| tr("export-folder-system"));
+                    ui.label(RichText::new(folder).size(11.0));
+                }
+                #[cfg(all(feature = "archive", not(target_arch = "wasm32")))]
+                {
+                    ui.add_space(10.0);
+                    changed |= ui.checkbox(&mut settings.archive_plates, tr("archive-plates")).changed();
+                    ui.label(RichText::new(tr("archive-plates-hover")).size(11.0));
+                }
+                #[cfg(not(target_arch = "wasm32"))]
+                {
+                    ui.add_space(10.0);
+                    ui.horizontal(|ui| {
+                        changed |= ui.checkbox(&mut settings.server.enabled, tr("api-server")).changed();
+                        ui.add_space(10.0);
+                        ui.label(tr("api-port"));
+                        let drag_value = ui.add(DragValue::new(&mut settings.server.port).range(1024..=65535));
+                        Self::dashed_outline(ui, &drag_value);
+                        changed |= drag_value.changed();
+                    });
+                    let status = match &api_url {
+                        Some((url, requests)) => tr_args("api-server-running", &[("url", url.as_str().into()), ("requests", (*requests).into())]),
+                        None => tr("api-server-hover"),
+                    };
+                    ui.label(RichText::new(status).size(11.0));
+                }
+
+                ui.add_space(10.0);
+                ui.label(RichText::new(tr("new-plates")).strong());
+                ui.add_space(4.0);
+                ui.horizontal(|ui| {
+                    ui.label(tr("plate-size"));
+                    ui.add_space(10.0);
+                    ComboBox::from_id_salt("Plate Size").selected_text(settings.plate_size.description()).show_ui(ui, |ui| {
+                        for size in PlateSize::ALL {
+                            changed |= ui.selectable_value(&mut settings.plate_size, size, size.description()).changed();
+                        }
+                    });
+                });
+                ui.add_space(4.0);
+                ui.horizontal(|ui| {
+                    ui.label(tr("weighting"));
+                    ui.add_space(10.0);
+                    ComboBox::from_id_salt("Default Weighting").selected_text(settings.weighting.description()).show_ui(ui, |ui| {
+                        for weighting in Weighting::ALL {
+                            changed |= ui.selectable_value(&mut settings.weighting, weighting, weighting.description()).changed();
+                        }
+                    });
+                });
+                ui.label(RichText::new(tr("new-plates-hover")).size(11.0));
+            });
+
+        if show_shortcuts {
+            self.show_shortcuts = true;
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        if show_export_hook {
+            self.export_hook.show = true;
+        }
+        if changed {
+            set_language(self.settings.language);
+            set_number_format(self.settings.number_format);
+            ctx.set_theme(self.settings.theme.egui_theme());
+            #[cfg(not(target_arch = "wasm32"))]
+            {
+                set_export_folder(self.settings.export_folder.clone());
+                self.update_api_server();
+            }
+            if save_settings(&self.settings).is_err() {
+                self.notifications.push(SerdeError::CantWriteFile);
+            }
+        }
+    }
+
+    /// Starts, stops or moves the API server to match the settings
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn update_api_server(&mut self) {
+        let setup = self.settings.server;
+        if setup.enabled && self.api_server.as_ref().is_some_and(|server| server.port == setup.port) { return }
+        self.api_server = None;
+        if !setup.enabled { return }
+        match ApiServer::start(setup.port) {
+            Ok(server) => self.api_server = Some(server),
+            Err(error) => {
+                log::warn!("Could not start the API server on port {}: {error}", setup.port);
+                self.notifications.push(AppError::Server(error.to_string()));
+            },
+        }
+    }
+}
@@ -0,0 +1,140 @@
+use eframe::egui::{self, Event, Grid, Id, Key, KeyboardShortcut, Modifiers, Response, RichText};
+
+use crate::{app::SerdeError, files::*, i18n::*, logistic_regression::SampleType, settings::*, ui::command_palette::PALETTE_SHORTCUT, ElisaTab, Elisa};
+
+/// Set the type of the selected wells with the number keys, in the order of the sample menu
+pub const SAMPLE_TYPE_KEYS: [(Key, SampleType); 5] = [
+    (Key::Num1, SampleType::Unused),
+    (Key::Num2, SampleType::Standard),
+    (Key::Num3, SampleType::Control),
+    (Key::Num4, SampleType::Unknown),
+    (Key::Num5, SampleType::Blank),
+];
+
+/// Tooltip with the shortcut of the button, if it has one
+pub fn shortcut_tooltip(response: Response, hint: Option<String>) -> Response {
+    match hint {
+        Some(hint) => response.on_hover_text(hint),
+        None => response,
+    }
+}
+
+impl Elisa {
+    /// Shortcut of an action as shown to the user, e.g. "Ctrl+S" or "⌘S"
+    pub fn shortcut_hint(&self, ctx: &egui::Context, action: ShortcutAction) -> Option<String> {
+        self.settings.shortcuts.get(action).map(|shortcut| ctx.format_shortcut(&shortcut))
+    }
+
+    pub fn keyboard_shortcuts(&mut self, ctx: &egui::Context) {
+        // the next key goes to the shortcut being changed
+        if self.recording_shortcut.is_some() { return }
+
+        let pressed = |shortcut: Option<KeyboardShortcut>| shortcut.is_some_and(|shortcut| ctx.input_mut(|i| i.consume_shortcut(&shortcut)));
+        let shortcuts = &self.settings.shortcuts;
+        let [save, open, export_csv, refit] = ShortcutAction::ALL.map(|action| pressed(shortcuts.get(action)));
+
+        if save {
+            self.save_assay();
+        }
+        if open {
+            self.opened_files.pick(ctx, FileAction::LoadAssay, &[("Elisa".into(), &PROJECT_EXTENSIONS)]);
+        }
+        if export_csv && self.regression.is_some() {
+            self.export_results_csv();
+        }
+        if refit && self.fit_curve() {
+            self.current_tab = ElisaTab::Result;
+        }
+
+        if self.current_tab != ElisaTab::Edit || self.microplate.locked || ctx.wants_keyboard_input() { return }
+        let wells = match (self.selected_sample, self.selected_line) {
+            (Some(index), _) => vec![index],
+            (None, Some(line)) => self.microplate.line_wells(line),
+            (None, None) => return,
+        };
+        for (key, typ) in SAMPLE_TYPE_KEYS {
+            if ctx.input_mut(|i| i.consume_key(Modifiers::NONE, key)) {
+                wells.iter().for_each(|&well| self.microplate.samples[well].typ = typ);
+            }
+        }
+    }
+
+    /// All shortcuts, the ones of the actions can be changed by clicking them and pressing the new keys
+    pub fn shortcuts_window(&mut self, ctx: &egui::Context) {
+        let Self { settings, recording_shortcut, .. } = self;
+        let mut changed = false;
+
+        // Escape keeps the old shortcut, Backspace removes it
+        if let Some(action) = *recording_shortcut {
+            let pressed = ctx.input(|i| i.events.iter().find_map(|event| match event {
+                Event::Key { key, pressed: true, modifiers, .. } => Some((*key, *modifiers)),
+                _ => None,
+            }));
+            match pressed {
+                Some((Key::Escape, _)) => *recording_shortcut = None,
+                Some((Key::Backspace | Key::Delete, _)) => {
+                    settings.shortcuts.text_mut(action).clear();
+                    *recording_shortcut = None;
+                    changed = true;
+                },
+                Some((key, modifiers)) => {
+                    let modifiers = Modifiers { command: modifiers.command, shift: modifiers.shift, alt: modifiers.alt, ..Modifiers::NONE };
+                    *settings.shortcuts.text_mut(action) = shortcut_text(KeyboardShortcut::new(modifiers, key));
+                    *recording_shortcut = None;
+                    changed = true;
+                },
+                None => (),
+            }
+        }
+
+        egui::Window::new(tr("keyboard-shortcuts"))
+            .id(Id::new("Keyboard Shortcuts"))
+            .open(&mut self.show_shortcuts)
+            .resizable(false)
+            .collapsible(false)
+            .frame(egui::Frame::window(&ctx.style()).inner_margin(10.0))
+            .show(ctx, |ui| {
+                Grid::new("Shortcuts Grid").num_columns(2).spacing([20.0, 4.0]).striped(true).show(ui, |ui| {
+                    for action in ShortcutAction::ALL {
+                        ui.label(action.description());
+                        let text = if *recording_shortcut == Some(action) {
+                            tr("press-keys")
+                        } else {
+                            settings.shortcuts.get(action).map(|shortcut| ctx.format_shortcut(&shortcut)).unwrap_or_else(|| "–".to_string())
+                        };
+                        let button = ui.button(text).on_hover_text(tr("change-shortcut-hover"));
+                        Self::dashed_outline(ui, &button);
+                        if button.clicked() {
+                            *recording_shortcut = Some(action);
+                        }
+                        ui.end_row();
+                    }
+                    ui.label(tr("command-palette"));
+                    ui.label(ctx.format_shortcut(&PALETTE_SHORTCUT));
+                    ui.end_row();
+                    for (key, typ) in SAMPLE_TYPE_KEYS {
+                        ui.label(tr_args("shortcut-sample-type", &[("type", typ.name().into())]));
+                        ui.label(key.symbol_or_name());
+                        ui.end_row();
+                    }
+                });
+                ui.add_space(10.0);
+                ui.label(RichText::new(tr("shortcuts-hover")).size(11.0));
+                ui.add_space(10.0);
+                let button = ui.button(tr("reset-shortcuts"));
+                Self::dashed_outline(ui, &button);
+                if button.clicked() {
+                    settings.shortcuts = Shortcuts::default();
+                    *recording_shortcut = None;
+                    changed = true;
+                }
+            });
+
+        if !self.show_shortcuts {
+            self.recording_shortcut = None;
+        }
+        if changed && save_settings(&self.settings).is_err() {
+            self.notifications.push(SerdeError::CantWriteFile);
+        }
+    }
+}
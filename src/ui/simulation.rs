@@ -0,0 +1,99 @@
+use eframe::egui::{self, ComboBox, DragValue, Grid, Id, RichText};
+
+use crate::{bootstrap::Rng, i18n::*, simulation::*, ui::plot::FLAG_COLOR, Elisa};
+
+impl Elisa {
+    /// Plates from a chosen curve and noise, analyzed like measured ones
+    pub fn simulation_window(&mut self, ctx: &egui::Context) {
+        let simulation = &mut self.simulation;
+        let (width, height) = self.settings.plate_size.dimensions();
+        let mut simulated = None;
+
+        egui::Window::new(tr("simulation"))
+            .id(Id::new("Simulation"))
+            .open(&mut self.show_simulation)
+            .resizable(false)
+            .collapsible(false)
+            .frame(egui::Frame::window(&ctx.style()).inner_margin(10.0))
+            .show(ctx, |ui| {
+                ui.label(RichText::new(tr("simulation-curve")).strong());
+                let (a, b, c, d) = &mut simulation.abcd;
+                Grid::new("Simulation Curve Grid").num_columns(2).spacing([20.0, 4.0]).show(ui, |ui| {
+                    for (name, value, hover) in [("a", a, "adjust-a"), ("b", b, "adjust-b"), ("c", c, "adjust-c"), ("d", d, "adjust-d")] {
+                        ui.label(name).on_hover_text(tr(hover));
+                        let drag_value = ui.add(DragValue::new(value).speed(0.01).custom_formatter(|n, _| format_value(n)).custom_parser(parse_decimal));
+                        Self::dashed_outline(ui, &drag_value);
+                        ui.end_row();
+                    }
+                    ui.label(tr("simulation-blank"));
+                    let drag_value = ui.add(DragValue::new(&mut simulation.blank).speed(0.01).custom_formatter(|n, _| format_value(n)).custom_parser(parse_decimal));
+                    Self::dashed_outline(ui, &drag_value);
+                    ui.end_row();
+                    ui.label(tr("simulation-noise"));
+                    ui.horizontal(|ui| {
+                        ComboBox::from_id_salt("Noise Model").selected_text(simulation.noise.description()).show_ui(ui, |ui| {
+                            for noise in NoiseModel::ALL {
+                                ui.selectable_value(&mut simulation.noise, noise, noise.description());
+                            }
+                        });
+                        let drag_value = ui.add(DragValue::new(&mut simulation.noise_level).range(0.0..=f64::INFINITY).speed(0.1)
+                            .custom_formatter(|n, _| format_value(n)).custom_parser(parse_decimal));
+                        Self::dashed_outline(ui, &drag_value);
+                    });
+                    ui.end_row();
+                });
+                ui.add_space(10.0);
+
+                ui.label(RichText::new(tr("simulation-layout")).strong());
+                Grid::new("Simulation Layout Grid").num_columns(2).spacing([20.0, 4.0]).show(ui, |ui| {
+                    ui.label(tr("simulation-top-standard"));
+                    let drag_value = ui.add(DragValue::new(&mut simulation.top_standard).range(f64::MIN_POSITIVE..=f64::INFINITY)
+                        .custom_formatter(|n, _| format_value(n)).custom_parser(parse_decimal));
+                    Self::dashed_outline(ui, &drag_value);
+                    ui.end_row();
+                    ui.label(tr("simulation-dilution-factor"));
+                    let drag_value = ui.add(DragValue::new(&mut simulation.dilution_factor).range(1.1..=100.0).speed(0.1));
+                    Self::dashed_outline(ui, &drag_value);
+                    ui.end_row();
+                    for (label, value, min) in [
+                        ("simulation-standards", &mut simulation.standards, 5),
+                        ("simulation-unknowns", &mut simulation.unknowns, 1),
+                        ("simulation-replicates", &mut simulation.replicates, 1),
+                    ] {
+                        ui.label(tr(label));
+                        let drag_value = ui.add(DragValue::new(value).range(min..=96));
+                        Self::dashed_outline(ui, &drag_value);
+                        ui.end_row();
+                    }
+                });
+                ui.add_space(10.0);
+
+                let mut rng = Rng::new(chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default() as u64);
+                let microplate = simulation.microplate(width, height, &mut rng);
+                match &microplate {
+                    Ok(_) => ui.label(RichText::new(tr_args("simulation-wells", &[("wells", simulation.wells().into()), ("total", (width * height).into())])).size(11.0)),
+                    Err(error) => ui.label(RichText::new(error.to_string()).color(FLAG_COLOR)),
+                };
+                ui.label(RichText::new(tr("simulation-hover")).size(11.0));
+                ui.add_space(10.0);
+                let button = ui.add_enabled(microplate.is_ok(), egui::Button::new(tr("simulate-plate")));
+                Self::dashed_outline(ui, &button);
+                if button.clicked() {
+                    simulated = microplate.ok();
+                }
+            });
+
+        // replaces the assay like loading one
+        if let Some(mut microplate) = simulated {
+            microplate.fit_options.weighting = self.settings.weighting;
+            self.microplate = microplate;
+            self.regression = None;
+            self.audit_snapshot = None;
+            self.source_file = None;
+            self.hidden_labels.clear();
+            self.selected_sample = None;
+            self.standards_textfield.iter_mut().for_each(String::clear);
+            self.reset_plot = true;
+        }
+    }
+}
@@ -0,0 +1,127 @@
+use eframe::egui::{self, Button, Color32, Response, RichText, Ui};
+
+use crate::{history::mean_sd, i18n::*, logistic_regression::{SampleType::*, *}, results_filter::*, ui::plot::FLAG_COLOR, ElisaTab, Elisa};
+
+enum StatusClick {
+    Plate,
+    LinearRange,
+    Monotonicity,
+    Fit,
+    Diagnostics,
+    Cv,
+    Qc,
+}
+
+fn status_item(ui: &mut Ui, text: String, color: Option<Color32>, hover: String) -> Response {
+    let text = RichText::new(text).size(12.0);
+    let text = match color {
+        Some(color) => text.color(color),
+        None => text,
+    };
+    let response = ui.add(Button::new(text).frame(false)).on_hover_text(hover);
+    ui.separator();
+    response
+}
+
+impl Elisa {
+    /// Highest CV of the replicates of a standard or unknown, with its name
+    fn worst_cv(regression: &Regression) -> Option<(f64, String)> {
+        let standards = regression.standards.iter().zip(&regression.standard_replicates)
+            .filter(|(_, replicates)| replicates.len() > 1)
+            .filter_map(|(&(concentration, _), replicates)| {
+                let (mean, sd) = mean_sd(replicates);
+                (mean != 0.0).then(|| ((sd / mean).abs() * 100.0, format!("{} {}", Standard.name(), format_value(concentration))))
+            });
+        let unknowns = regression.unknowns.iter().filter_map(|unknown| Some((unknown_cv(unknown)?, unknown.name())));
+        standards.chain(unknowns).max_by(|(a, _), (b, _)| a.total_cmp(b))
+    }
+
+    /// Wells, fit, R², worst CV and QC of the assay at a glance, each leading to its details
+    pub fn status_bar(&mut self, ctx: &egui::Context) {
+        let mut clicked = None;
+
+        egui::TopBottomPanel::bottom("Status Bar").exact_height(24.0).show(ctx, |ui| {
+            ui.horizontal_centered(|ui| {
+                let count = |typ: SampleType| self.microplate.samples.iter().filter(|sample| sample.typ == typ).count();
+                let wells = tr_args("status-wells", &[
+                    ("standards", count(Standard).into()),
+                    ("unknowns", count(Unknown).into()),
+                    ("blanks", count(Blank).into()),
+                    ("controls", count(Control).into()),
+                ]);
+                if status_item(ui, wells, None, tr("status-wells-hover")).clicked() {
+                    clicked = Some(StatusClick::Plate);
+                }
+
+                let outside = self.settings.linear_range.map(|range| self.microplate.wells_outside_linear_range(range).len()).unwrap_or_default();
+                if outside > 0 {
+                    let text = tr_args("status-linear-range", &[("count", outside.into())]);
+                    if status_item(ui, text, Some(FLAG_COLOR), tr("status-linear-range-hover")).clicked() {
+                        clicked = Some(StatusClick::LinearRange);
+                    }
+                }
+
+                // checked before the fit, which stops on it
+                let out_of_order = !self.microplate.fit_options.allow_non_monotonic && self.microplate.standard_monotonicity().is_some();
+                if out_of_order && status_item(ui, tr("status-not-monotonic"), Some(FLAG_COLOR), tr("status-not-monotonic-hover")).clicked() {
+                    clicked = Some(StatusClick::Monotonicity);
+                }
+
+                let Some(regression) = &self.regression else {
+                    if status_item(ui, tr("status-not-fitted"), None, tr("status-fit-hover")).clicked() {
+                        clicked = Some(StatusClick::Fit);
+                    }
+                    return
+                };
+                let (fit, color) = if self.plate_changed() {
+                    (tr("status-fit-outdated"), Some(FLAG_COLOR))
+                } else {
+                    (tr("status-fit-current"), None)
+                };
+                if status_item(ui, fit, color, tr("status-fit-hover")).clicked() {
+                    clicked = Some(StatusClick::Fit);
+                }
+
+                let r_squared = tr_args("status-r-squared", &[("value", format_value(regression.r_sq).into())]);
+                let color = (!regression.diagnostics.converged).then_some(FLAG_COLOR);
+                if status_item(ui, r_squared, color, tr("status-r-squared-hover")).clicked() {
+                    clicked = Some(StatusClick::Diagnostics);
+                }
+
+                if let Some((cv, name)) = Self::worst_cv(regression) {
+                    let text = tr_args("status-worst-cv", &[("cv", format_decimal(cv, 1).into()), ("name", name.into())]);
+                    let color = self.settings.cv_threshold.is_some_and(|threshold| cv > threshold).then_some(FLAG_COLOR);
+                    if status_item(ui, text, color, tr("status-cv-hover")).clicked() {
+                        clicked = Some(StatusClick::Cv);
+                    }
+                }
+
+                let checks = self.microplate.protocol.as_ref().map(|protocol| protocol.evaluate(&self.microplate, regression)).unwrap_or_default();
+                if !checks.is_empty() {
+                    let failed = checks.iter().filter(|check| !check.passed).count();
+                    let (text, color) = match failed {
+                        0 => (tr("status-qc-passed"), None),
+                        failed => (tr_args("status-qc-failed", &[("count", failed.into())]), Some(FLAG_COLOR)),
+                    };
+                    if status_item(ui, text, color, tr("status-qc-hover")).clicked() {
+                        clicked = Some(StatusClick::Qc);
+                    }
+                }
+            });
+        });
+
+        match clicked {
+            Some(StatusClick::Plate) => self.current_tab = ElisaTab::Edit,
+            Some(StatusClick::LinearRange) => self.show_exclusions = true,
+            Some(StatusClick::Monotonicity) => self.show_swap_suggestion = true,
+            Some(StatusClick::Fit) if self.fit_curve() => self.current_tab = ElisaTab::Result,
+            Some(StatusClick::Diagnostics) => self.show_diagnostics = true,
+            Some(StatusClick::Cv) => {
+                self.current_tab = ElisaTab::Result;
+                self.results_sort = Some(ResultsSort { column: SortColumn::Cv, descending: true });
+            },
+            Some(StatusClick::Qc) => self.current_tab = ElisaTab::Result,
+            _ => (),
+        }
+    }
+}
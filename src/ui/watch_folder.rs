@@ -0,0 +1,241 @@
+use std::{collections::HashSet, fs, path::{Path, PathBuf}, time::{Duration, Instant}};
+
+use eframe::egui::{self, ComboBox, Grid, Id, RichText, ScrollArea, TextEdit};
+
+use crate::{app::{AppError, SerdeError}, files::*, i18n::*, logistic_regression::*, results_filter::sorted_unknowns, report::{SourceFile, SourceKind}, settings::save_settings, ui::{export::*, plot::{PlotLayers, PlotView, FLAG_COLOR}}, watch_folder::*, Elisa};
+
+/// How often the watched folder is looked at
+const SCAN_INTERVAL: Duration = Duration::from_secs(2);
+
+/// The watch folder window and the queue while watching
+#[derive(Default)]
+pub struct WatchFolder {
+    pub show: bool,
+    pub queue: Option<WatchQueue>, // None while not watching
+    pub template: Option<Microplate>, // layout and fit settings every imported plate gets
+    pub last_scan: Option<Instant>,
+}
+
+impl Elisa {
+    /// Scans the folder now and then and works through the queue one file per frame, also while the window is closed
+    fn poll_watch_folder(&mut self, ctx: &egui::Context) {
+        let Some(queue) = &mut self.watch_folder.queue else { return };
+        ctx.request_repaint_after(SCAN_INTERVAL);
+
+        if self.watch_folder.last_scan.is_none_or(|scan| scan.elapsed() >= SCAN_INTERVAL) {
+            self.watch_folder.last_scan = Some(Instant::now());
+            if let Err(error) = queue.scan(&self.settings.watch_folder) {
+                log::warn!("Stopped watching the folder: {error}");
+                self.notifications.push(AppError::WatchFolder(error.to_string()));
+                self.watch_folder.queue = None;
+                return
+            }
+        }
+
+        let Some(index) = queue.next_waiting() else { return };
+        let path = queue.files[index].path.clone();
+        let result = self.process_watched_file(&path);
+        let Some(file) = self.watch_folder.queue.as_mut().and_then(|queue| queue.files.get_mut(index)) else { return };
+        match result {
+            Ok(outputs) => {
+                file.status = QueueStatus::Done;
+                file.outputs = outputs;
+            },
+            Err(error) => {
+                log::warn!("Could not process {}: {error}", file.name);
+                file.status = QueueStatus::Failed(error);
+            },
+        }
+        ctx.request_repaint();
+    }
+
+    /// Reads the export onto a copy of the template, fits it and writes the results CSV and the PDF report
+    fn process_watched_file(&mut self, path: &Path) -> Result<Vec<PathBuf>, String> {
+        let Some(template) = &self.watch_folder.template else { return Err(tr("watch-no-template")) };
+        let Some(output) = self.settings.watch_folder.output.clone() else { return Err(tr("watch-no-output")) };
+
+        let file = OpenedFile {
+            action: FileAction::Measurements,
+            name: path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default(),
+            bytes: fs::read(path).map_err(|error| error.to_string())?,
+            path: path.to_path_buf(),
+        };
+        let (data, wavelength) = Self::read_plate_file(&file).map_err(|error| error.message())?;
+        let mut plate = template.duplicate();
+        plate.name = path.file_stem().map(|stem| stem.to_string_lossy().to_string()).unwrap_or_default();
+        plate.assign_values(data);
+        if wavelength.is_some() {
+            plate.metadata.wavelength = wavelength;
+        }
+        let regression = Regression::new(&plate).map_err(|error| AppError::from(error).message())?;
+        plate.audit_fit(&regression, &self.settings.operator);
+
+        let order = sorted_unknowns(&regression.unknowns, None);
+        let derived_columns = plate.protocol.as_ref().map(|protocol| protocol.derived_columns.as_slice()).unwrap_or_default();
+        let csv = results_csv(&regression, &order, plate.cutoff.as_ref(), derived_columns);
+        let csv_path = output_path(&output, path, "Results", "csv");
+        fs::write(&csv_path, csv).map_err(|error| error.to_string())?;
+
+        // the report is made like the one of the current plate, which steps aside meanwhile
+        let hidden_labels = HashSet::new();
+        let layers = PlotLayers { hidden_labels: &hidden_labels, reference: None };
+        let image = render_plot(&regression, &PngExport::default(), self.settings.plot, layers, plate.plot_scale, PlotView::Interactive);
+        let microplate = std::mem::replace(&mut self.microplate, plate);
+        let current = self.regression.replace(regression);
+        let source_file = self.source_file.replace(SourceFile::new(SourceKind::ReaderFile, &file.bytes, &self.microplate));
+        let pdf = self.create_pdf(&image);
+        #[cfg(all(feature = "archive", not(target_arch = "wasm32")))]
+        self.archive_fit();
+        self.run_export_hook(false);
+        self.microplate = microplate;
+        self.regression = current;
+        self.source_file = source_file;
+
+        let pdf_path = output_path(&output, path, "Report", "pdf");
+        fs::write(&pdf_path, pdf).map_err(|error| error.to_string())?;
+        Ok(vec![csv_path, pdf_path])
+    }
+
+    /// Takes the current plate as the template and the chosen protocol, then waits for new files
+    fn start_watching(&mut self) {
+        let setup = &self.settings.watch_folder;
+        let mut template = self.microplate.duplicate();
+        if let Some(protocol) = setup.protocol.as_ref().and_then(|name| self.protocols.iter().find(|protocol| &protocol.name == name)) {
+            protocol.apply(&mut template);
+            template.protocol = Some(protocol.clone());
+        }
+        match WatchQueue::start(setup) {
+            Ok(queue) => {
+                self.watch_folder.queue = Some(queue);
+                self.watch_folder.template = Some(template);
+                self.watch_folder.last_scan = None;
+            },
+            Err(error) => self.notifications.push(AppError::WatchFolder(error.to_string())),
+        }
+    }
+
+    /// Reader exports appearing in a folder are fitted with the layout of the current plate, the results go to another folder
+    pub fn watch_folder_window(&mut self, ctx: &egui::Context) {
+        self.poll_watch_folder(ctx);
+
+        let setup = &mut self.settings.watch_folder;
+        let protocols = &self.protocols;
+        let watching = self.watch_folder.queue.is_some();
+        let mut changed = false;
+        let mut start = false;
+        let mut stop = false;
+        let mut clear = false;
+
+        egui::Window::new(tr("watch-folder"))
+            .id(Id::new("Watch Folder"))
+            .open(&mut self.watch_folder.show)
+            .resizable(false)
+            .collapsible(false)
+            .frame(egui::Frame::window(&ctx.style()).inner_margin(10.0))
+            .show(ctx, |ui| {
+                ui.label(RichText::new(tr("watch-folder-info")).size(11.0));
+                ui.add_space(10.0);
+
+                ui.add_enabled_ui(!watching, |ui| {
+                    Grid::new("Watch Folder Grid").num_columns(2).spacing([10.0, 6.0]).show(ui, |ui| {
+                        for (label, folder) in [("watch-input", &mut setup.input), ("watch-output", &mut setup.output)] {
+                            ui.label(tr(label));
+                            ui.horizontal(|ui| {
+                                let button = ui.button(tr("choose"));
+                                Self::dashed_outline(ui, &button);
+                                if button.clicked() {
+                                    if let Some(picked) = rfd::FileDialog::new().pick_folder() {
+                                        *folder = Some(picked);
+                                        changed = true;
+                                    }
+                                }
+                                let text = folder.as_ref().map(|folder| folder.display().to_string()).unwrap_or_default();
+                                ui.label(RichText::new(text).size(11.0));
+                            });
+                            ui.end_row();
+                        }
+
+                        ui.label(tr("watch-pattern"));
+                        let text_edit = ui.add(TextEdit::singleline(&mut setup.pattern).desired_width(200.0))
+                            .on_hover_text(tr("watch-pattern-hover"));
+                        Self::dashed_outline(ui, &text_edit);
+                        changed |= text_edit.lost_focus();
+                        ui.end_row();
+
+                        ui.label(tr("protocol"));
+                        let selected = setup.protocol.clone().unwrap_or(tr("no-protocol"));
+                        ComboBox::from_id_salt("Watch Folder Protocol").selected_text(selected).width(200.0).show_ui(ui, |ui| {
+                            changed |= ui.selectable_value(&mut setup.protocol, None, tr("no-protocol")).changed();
+                            for protocol in protocols {
+                                changed |= ui.selectable_value(&mut setup.protocol, Some(protocol.name.clone()), &protocol.name).changed();
+                            }
+                        });
+                        ui.end_row();
+                    });
+                });
+                ui.add_space(10.0);
+
+                ui.horizontal(|ui| {
+                    if watching {
+                        let button = ui.button(tr("watch-stop"));
+                        Self::dashed_outline(ui, &button);
+                        stop = button.clicked();
+                    } else {
+                        let ready = setup.input.is_some() && setup.output.is_some();
+                        let button = ui.add_enabled(ready, egui::Button::new(tr("watch-start")))
+                            .on_hover_text(tr("watch-start-hover"));
+                        Self::dashed_outline(ui, &button);
+                        start = button.clicked();
+                    }
+                    let Some(queue) = &self.watch_folder.queue else { return };
+                    ui.add_space(10.0);
+                    let button = ui.button(tr("watch-clear"));
+                    Self::dashed_outline(ui, &button);
+                    clear = button.clicked();
+                    ui.add_space(10.0);
+                    ui.label(RichText::new(tr_args("watch-status", &[("waiting", queue.waiting().into()), ("count", queue.files.len().into())])).size(11.0));
+                });
+
+                let Some(queue) = &self.watch_folder.queue else { return };
+                if queue.files.is_empty() { return }
+                ui.add_space(10.0);
+                ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                    Grid::new("Watch Folder Queue").num_columns(3).spacing([20.0, 4.0]).striped(true).show(ui, |ui| {
+                        for id in ["watch-found", "watch-file", "watch-state"] {
+                            ui.label(RichText::new(tr(id)).strong());
+                        }
+                        ui.end_row();
+                        for file in &queue.files {
+                            ui.label(&file.found);
+                            ui.label(&file.name);
+                            match &file.status {
+                                QueueStatus::Failed(error) => { ui.label(RichText::new(file.status.description()).color(FLAG_COLOR)).on_hover_text(error); },
+                                QueueStatus::Done => {
+                                    let outputs: Vec<String> = file.outputs.iter().map(|output| output.display().to_string()).collect();
+                                    ui.label(file.status.description()).on_hover_text(outputs.join("\n"));
+                                },
+                                QueueStatus::Waiting => { ui.label(file.status.description()); },
+                            }
+                            ui.end_row();
+                        }
+                    });
+                });
+            });
+
+        if changed && save_settings(&self.settings).is_err() {
+            self.notifications.push(SerdeError::CantWriteFile);
+        }
+        if start {
+            self.start_watching();
+        }
+        if stop {
+            self.watch_folder.queue = None;
+            self.watch_folder.template = None;
+        }
+        if clear {
+            if let Some(queue) = &mut self.watch_folder.queue {
+                queue.clear_finished();
+            }
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use eframe::egui::{self, Id, RichText};
+
+use crate::{app::ElisaTab, i18n::*, Elisa};
+
+impl Elisa {
+    /// The standards that break the order of the curve, with the two that were likely swapped to put back before fitting again
+    pub fn swap_suggestion_window(&mut self, ctx: &egui::Context) {
+        if !self.show_swap_suggestion { return }
+
+        let check = self.microplate.standard_monotonicity();
+        let locked = self.microplate.locked;
+        let mut swap = None;
+
+        egui::Window::new(tr("swap-suggestion"))
+            .id(Id::new("Swap Suggestion"))
+            .open(&mut self.show_swap_suggestion)
+            .resizable(false)
+            .collapsible(false)
+            .frame(egui::Frame::window(&ctx.style()).inner_margin(10.0))
+            .show(ctx, |ui| {
+                let Some(check) = &check else {
+                    ui.label(tr("swap-suggestion-monotonic"));
+                    return
+                };
+                ui.label(check.to_string());
+                ui.add_space(10.0);
+
+                let Some((first, second)) = &check.swap else {
+                    ui.label(RichText::new(tr("swap-suggestion-none")).size(11.0));
+                    return
+                };
+                let text = tr_args("swap-standards", &[("first", first.name().into()), ("second", second.name().into())]);
+                let button = ui.add_enabled(!locked, egui::Button::new(text)).on_hover_text(tr("swap-standards-hover"));
+                Self::dashed_outline(ui, &button);
+                if button.clicked() {
+                    swap = Some((first.group, second.group));
+                }
+            });
+
+        let Some((first, second)) = swap else { return };
+        self.microplate.swap_standard_groups(first, second);
+        self.show_swap_suggestion = false;
+        if self.fit_curve() {
+            self.current_tab = ElisaTab::Result;
+        }
+    }
+}
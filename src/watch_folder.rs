@@ -0,0 +1,143 @@
+use std::{collections::HashSet, fs, io, path::{Path, PathBuf}, time::{Duration, SystemTime}};
+
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::*;
+
+/// How long a new file has to stay unchanged before it is imported, the reader may still be writing it
+pub const SETTLE_TIME: Duration = Duration::from_secs(2);
+
+/// Where the watch folder mode looks for reader exports and where the results go, saved with the settings
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WatchFolderSetup {
+    pub input: Option<PathBuf>,
+    pub output: Option<PathBuf>,
+    pub pattern: String,          // file names to import, see `matches_pattern`
+    pub protocol: Option<String>, // name of a protocol of the library, applied to every plate
+}
+
+impl Default for WatchFolderSetup {
+    fn default() -> Self {
+        Self {
+            input: None,
+            output: None,
+            pattern: "*.txt; *.csv; *.xlsx".to_string(),
+            protocol: None,
+        }
+    }
+}
+
+/// Whether a file name matches one of the patterns separated by ";", ignoring case.
+/// "*" stands for any characters and "?" for one, e.g. "Plate_*.txt".
+pub fn matches_pattern(pattern: &str, name: &str) -> bool {
+    fn matches(pattern: &[char], name: &[char]) -> bool {
+        match (pattern.first(), name.first()) {
+            (None, None) => true,
+            (Some('*'), _) => matches(&pattern[1..], name) || (!name.is_empty() && matches(pattern, &name[1..])),
+            (Some('?'), Some(_)) => matches(&pattern[1..], &name[1..]),
+            (Some(p), Some(n)) if p == n => matches(&pattern[1..], &name[1..]),
+            _ => false,
+        }
+    }
+    let name: Vec<char> = name.to_lowercase().chars().collect();
+    pattern.split(';').map(str::trim).filter(|pattern| !pattern.is_empty())
+        .any(|pattern| matches(&pattern.to_lowercase().chars().collect::<Vec<_>>(), &name))
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum QueueStatus {
+    Waiting,
+    Done,
+    Failed(String), // why, e.g. the export could not be read or the curve not fitted
+}
+
+impl QueueStatus {
+    pub fn description(&self) -> String {
+        match self {
+            Self::Waiting => tr("watch-waiting"),
+            Self::Done => tr("watch-done"),
+            Self::Failed(_) => tr("watch-failed"),
+        }
+    }
+}
+
+/// A reader export found in the watched folder
+#[derive(Clone, Debug, PartialEq)]
+pub struct QueuedFile {
+    pub path: PathBuf,
+    pub name: String,
+    pub found: String, // time of day it was found
+    pub status: QueueStatus,
+    pub outputs: Vec<PathBuf>, // results written for it
+}
+
+/// Files of the watched folder in the order they appeared
+#[derive(Clone, Debug, Default)]
+pub struct WatchQueue {
+    pub files: Vec<QueuedFile>,
+    known: HashSet<PathBuf>, // queued, or already there when watching started
+}
+
+impl WatchQueue {
+    /// Files already in the folder are left alone, only the ones that appear while watching are imported
+    pub fn start(setup: &WatchFolderSetup) -> io::Result<Self> {
+        let folder = setup.input.as_deref().ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no input folder"))?;
+        let known = matching_files(folder, &setup.pattern)?.into_iter().map(|(path, _modified)| path).collect();
+        Ok(Self { files: Vec::new(), known })
+    }
+
+    /// Queues the new files that haven't changed for `SETTLE_TIME`, the number queued
+    pub fn scan(&mut self, setup: &WatchFolderSetup) -> io::Result<usize> {
+        let Some(folder) = setup.input.as_deref() else { return Ok(0) };
+        let now = SystemTime::now();
+        let mut queued = 0;
+        for (path, modified) in matching_files(folder, &setup.pattern)? {
+            if self.known.contains(&path) { continue }
+            if now.duration_since(modified).unwrap_or_default() < SETTLE_TIME { continue }
+            self.files.push(QueuedFile {
+                name: path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default(),
+                path: path.clone(),
+                found: chrono::offset::Local::now().format("%H:%M:%S").to_string(),
+                status: QueueStatus::Waiting,
+                outputs: Vec::new(),
+            });
+            self.known.insert(path);
+            queued += 1;
+        }
+        Ok(queued)
+    }
+
+    /// Index of the oldest file still waiting
+    pub fn next_waiting(&self) -> Option<usize> {
+        self.files.iter().position(|file| file.status == QueueStatus::Waiting)
+    }
+
+    pub fn waiting(&self) -> usize {
+        self.files.iter().filter(|file| file.status == QueueStatus::Waiting).count()
+    }
+
+    /// Forgets the done and failed files, they are not imported again
+    pub fn clear_finished(&mut self) {
+        self.files.retain(|file| file.status == QueueStatus::Waiting);
+    }
+}
+
+/// Files directly in the folder that match the pattern, with the time they were last changed, sorted by it
+fn matching_files(folder: &Path, pattern: &str) -> io::Result<Vec<(PathBuf, SystemTime)>> {
+    let mut files = Vec::new();
+    for entry in fs::read_dir(folder)? {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+        if !metadata.is_file() || !matches_pattern(pattern, &entry.file_name().to_string_lossy()) { continue }
+        files.push((entry.path(), metadata.modified()?));
+    }
+    files.sort_by_key(|&(_, modified)| modified);
+    Ok(files)
+}
+
+/// Where a result of the file goes, e.g. "Plate 3 Results.csv" in the output folder for "Plate 3.txt"
+pub fn output_path(output: &Path, file: &Path, suffix: &str, extension: &str) -> PathBuf {
+    let stem = file.file_stem().map(|stem| stem.to_string_lossy().to_string()).unwrap_or_default();
+    output.join(format!("{stem} {suffix}.{extension}"))
+}
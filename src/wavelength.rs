@@ -0,0 +1,57 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{i18n::*, logistic_regression::*};
+
+/// Values corrected with a second read at a reference wavelength, e.g. 450 nm minus 570 nm,
+/// which takes out scratches, fingerprints and differences between the wells of the plate itself
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct WavelengthCorrection {
+    pub reference_wavelength: Option<f64>, // nm, the measurement wavelength is in the run metadata
+}
+
+impl WavelengthCorrection {
+    /// Reference wavelength from the name of a sheet or file, e.g. "570 nm" or "OD570"
+    pub fn from_label(label: &str) -> Self {
+        let reference_wavelength = label.split(|c: char| !c.is_ascii_digit())
+            .filter_map(|part| part.parse::<f64>().ok())
+            .find(|nm| (200.0..=1000.0).contains(nm));
+        Self { reference_wavelength }
+    }
+
+    pub fn description(&self, wavelength: Option<f64>) -> String {
+        let nm = |wavelength: Option<f64>| wavelength.map(|wavelength| format!("{} nm", format_number(wavelength)));
+        match (nm(wavelength), nm(self.reference_wavelength)) {
+            (Some(measurement), Some(reference)) => tr_args("wavelength-correction", &[("measurement", measurement.into()), ("reference", reference.into())]),
+            (None, Some(reference)) => tr_args("wavelength-correction-reference", &[("reference", reference.into())]),
+            (_, None) => tr("wavelength-correction-unknown"),
+        }
+    }
+}
+
+impl Microplate {
+    /// Subtracts a reference read from the values, rows of the plate like `assign_values`. A reference read
+    /// that was subtracted before is added back first, wells without a reference value keep theirs as they are.
+    pub fn assign_reference_values(&mut self, data: Vec<Vec<Option<f64>>>, correction: WavelengthCorrection) {
+        self.remove_correction();
+        for (y, line) in data.into_iter().enumerate().take(self.height) {
+            for (x, cell) in line.into_iter().enumerate().take(self.width) {
+                let sample = &mut self.samples[self.height * x + y];
+                sample.reference = cell;
+                if let (Some(value), Some(reference)) = (&mut sample.value, cell) {
+                    *value -= reference;
+                }
+            }
+        }
+        self.metadata.correction = Some(correction);
+    }
+
+    /// Back to the values as read at the measurement wavelength
+    pub fn remove_correction(&mut self) {
+        if self.metadata.correction.take().is_none() { return }
+        for sample in &mut self.samples {
+            if let (Some(value), Some(reference)) = (&mut sample.value, sample.reference.take()) {
+                *value += reference;
+            }
+        }
+    }
+}
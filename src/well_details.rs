@@ -0,0 +1,82 @@
+use crate::{history::mean_sd, i18n::*, linear_range::RangeViolation, logistic_regression::{SampleType::*, *}};
+
+/// What is known about a well, for the tooltip of the plate
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct WellDetails {
+    pub well: String, // e.g. B7
+    pub typ: SampleType,
+    pub group: Option<String>, // name of the standard or unknown group
+    pub excluded: bool,
+    pub in_region: bool, // analyzed with the region of the fit options
+    pub raw: Option<f64>,
+    pub corrected: Option<f64>, // with the blank correction of the fit options
+    pub replicates: usize,      // wells of the group with a value, not excluded
+    pub replicate_mean: Option<f64>, // blank corrected, blanks as read
+    pub replicate_cv: Option<f64>,   // %, None with fewer than 2 replicates
+    pub concentration: Option<String>, // of an unknown, from a fit of the plate as it is
+    pub linear_range: Option<(RangeViolation, (f64, f64))>, // a read outside the linear range of the reader, with the range
+}
+
+impl WellDetails {
+    pub fn new(microplate: &Microplate, regression: Option<&Regression>, index: usize, linear_range: Option<(f64, f64)>) -> Self {
+        let sample = &microplate.samples[index];
+        let blanks = microplate.blanks();
+        let corrected = |i: usize| microplate.samples[i].value.map(|value| value - microplate.blank_of_well(&blanks, i));
+
+        let group = match sample.typ {
+            Standard => microplate.standard_groups.get(sample.group).map(|group| {
+                let name = tr_args("standard-number", &[("number", (sample.group + 1).into())]);
+                match group.concentration {
+                    Some(concentration) => format!("{name} ({})", format_value(concentration)),
+                    None => name,
+                }
+            }),
+            Unknown => microplate.unknown_groups.get(sample.group).map(|group| {
+                if group.label.is_empty() {
+                    tr_args("unknown-number", &[("number", (sample.group + 1).into())])
+                } else {
+                    group.label.clone()
+                }
+            }),
+            Blank | Control | Unused => None,
+        };
+
+        // blanks and controls are one group each, corrected blanks would be around 0 with a meaningless CV
+        let replicate_values: Vec<f64> = match sample.typ {
+            Unused => Vec::new(),
+            typ => microplate.samples.iter().enumerate()
+                .filter(|&(i, other)| other.typ == typ && !other.excluded && microplate.in_region(i) && (matches!(typ, Blank | Control) || other.group == sample.group))
+                .filter_map(|(i, other)| if typ == Blank { other.value } else { corrected(i) })
+                .collect(),
+        };
+        let (replicate_mean, replicate_cv) = match replicate_values.len() {
+            0 => (None, None),
+            1 => (Some(replicate_values[0]), None),
+            _ => {
+                let (mean, sd) = mean_sd(&replicate_values);
+                (Some(mean), (mean != 0.0).then(|| (sd / mean).abs() * 100.0))
+            },
+        };
+
+        // a fit of an earlier state of the plate could be for other wells
+        let concentration = regression
+            .filter(|regression| sample.typ == Unknown && regression.fingerprint == microplate.fit_fingerprint())
+            .and_then(|regression| regression.unknowns.iter().find(|unknown| unknown.group == sample.group))
+            .map(UnknownResult::concentration_text);
+
+        Self {
+            well: microplate.well_name(index),
+            typ: sample.typ,
+            group,
+            excluded: sample.excluded,
+            in_region: microplate.in_region(index),
+            raw: sample.value,
+            corrected: corrected(index),
+            replicates: replicate_values.len(),
+            replicate_mean,
+            replicate_cv,
+            concentration,
+            linear_range: linear_range.and_then(|range| Some((microplate.linear_range_violation(index, range)?, range))),
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use std::mem::swap;
+
+use crate::{i18n::*, logistic_regression::*};
+
+/// What changes places when two wells are swapped
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SwapMode {
+    #[default]
+    Values,      // the reads and the exclusion, the layout stays. For samples pipetted into each other's wells.
+    Assignments, // sample type and group, the reads stay. For a layout entered the wrong way round.
+}
+
+impl SwapMode {
+    pub const ALL: [Self; 2] = [Self::Values, Self::Assignments];
+
+    pub fn description(&self) -> String {
+        let id = match self {
+            Self::Values => "swap-values",
+            Self::Assignments => "swap-assignments",
+        };
+        tr(id)
+    }
+
+    pub fn hover(&self) -> String {
+        let id = match self {
+            Self::Values => "swap-values-hover",
+            Self::Assignments => "swap-assignments-hover",
+        };
+        tr(id)
+    }
+}
+
+impl Microplate {
+    pub fn swap_wells(&mut self, a: usize, b: usize, mode: SwapMode) {
+        if a == b { return }
+        let (low, high) = (a.min(b), a.max(b));
+        let (left, right) = self.samples.split_at_mut(high);
+        let (first, second) = (&mut left[low], &mut right[0]);
+        match mode {
+            SwapMode::Values => {
+                swap(&mut first.value, &mut second.value);
+                swap(&mut first.reference, &mut second.reference);
+                swap(&mut first.path_length_read, &mut second.path_length_read);
+                swap(&mut first.excluded, &mut second.excluded);
+                swap(&mut first.exclusion_reason, &mut second.exclusion_reason);
+            },
+            SwapMode::Assignments => {
+                swap(&mut first.typ, &mut second.typ);
+                swap(&mut first.group, &mut second.group);
+            },
+        }
+    }
+
+    /// The wells of one standard group become the other's and the other way round, their reads stay.
+    /// Undoes a transposition of two standards, e.g. the pair suggested by the monotonicity check.
+    pub fn swap_standard_groups(&mut self, a: usize, b: usize) {
+        for sample in self.samples.iter_mut().filter(|sample| sample.typ == SampleType::Standard) {
+            if sample.group == a {
+                sample.group = b;
+            } else if sample.group == b {
+                sample.group = a;
+            }
+        }
+    }
+}
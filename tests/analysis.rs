@@ -0,0 +1,61 @@
+use elisa::{analysis::*, cutoff::*, logistic_regression::*};
+
+fn unknown(label: &str, dilution: f64, concentration: f64) -> UnknownResult {
+    UnknownResult { label: label.to_string(), dilution, concentration, ..Default::default() }
+}
+
+#[test]
+fn replicates_of_a_dilution_are_averaged() {
+    // the 1:2 dilution on two places of the plate, 10 and 8 once corrected for the dilution
+    let unknowns = vec![
+        unknown("S1", 1.0, 10.0),
+        unknown("S1", 2.0, 5.0),
+        unknown("S1", 2.0, 4.0),
+        unknown("S1", 4.0, 2.5),
+    ];
+    let regression = Regression { unknowns, ..Default::default() };
+    let series = regression.dilution_linearity();
+    assert_eq!(series.len(), 1);
+
+    let points: Vec<(f64, f64)> = series[0].points.iter().map(|point| (point.dilution, point.concentration)).collect();
+    assert_eq!(points, [(1.0, 10.0), (2.0, 9.0), (4.0, 10.0)]);
+    assert!((series[0].points[1].difference + 10.0).abs() < 1e-9, "{}", series[0].points[1].difference);
+}
+
+fn subject(dilution: f64, measurement: f64) -> UnknownResult {
+    UnknownResult { label: "P1".to_string(), dilution, measurement, ..Default::default() }
+}
+
+#[test]
+fn titers_average_replicates_and_skip_missing_dilutions() {
+    let unknowns = vec![
+        subject(0.0, 2.0), // no dilution entered
+        subject(100.0, 1.0),
+        subject(200.0, 0.5),
+        subject(200.0, 0.3),
+        subject(400.0, 0.1),
+    ];
+    let regression = Regression { unknowns, has_control: true, control: 0.1, ..Default::default() };
+    let cutoff = Cutoff { mode: CutoffMode::ControlFactor, value: 2.0, ..Default::default() };
+    let titers = regression.endpoint_titers(&cutoff);
+    assert_eq!(titers.len(), 1);
+
+    let titer = &titers[0];
+    assert_eq!(titer.points, [(100.0, 1.0), (200.0, 0.4), (400.0, 0.1)]);
+    assert_eq!(titer.end, TiterEnd::Reached);
+    assert_eq!(titer.endpoint, Some(200.0));
+    // 0.2 is two thirds of the way from 0.4 at 1:200 to 0.1 at 1:400
+    let interpolated = titer.interpolated.unwrap();
+    assert!((interpolated - 200.0 * 2f64.powf(2.0 / 3.0)).abs() < 1e-9, "{interpolated}");
+}
+
+#[test]
+fn concentration_cutoff_is_compared_as_a_signal() {
+    // the curve is at 1.0 at the concentration of the cutoff, its midpoint c
+    let unknowns = vec![subject(10.0, 1.5), subject(20.0, 1.2), subject(40.0, 0.8)];
+    let regression = Regression { unknowns, abcd: (0.0, 1.0, 5.0, 2.0), ..Default::default() };
+    let cutoff = Cutoff { mode: CutoffMode::Concentration, value: 5.0, ..Default::default() };
+    let titers = regression.endpoint_titers(&cutoff);
+    assert_eq!(titers[0].end, TiterEnd::Reached);
+    assert_eq!(titers[0].endpoint, Some(20.0));
+}
@@ -0,0 +1,47 @@
+use std::time::{Duration, Instant};
+
+use elisa::{layout::ALPHABET, logistic_regression::*, server::*};
+use serde_json::json;
+
+/// Seven standards in duplicate down columns 1 and 2, on the sandwich curve of the reference datasets
+fn fit_body(options: serde_json::Value) -> Vec<u8> {
+    let (a, b, c, d) = (0.05, 1.2, 50.0, 2.5);
+    let concentrations: [f64; 7] = [1000.0, 300.0, 100.0, 30.0, 10.0, 3.0, 1.0];
+    let mut wells = Vec::new();
+    let mut values = Vec::new();
+    for (group, x) in concentrations.iter().enumerate() {
+        let y = d + (a - d) / (1.0 + (x / c).powf(b));
+        for column in 1..=2 {
+            let well = format!("{}{column}", ALPHABET[group]);
+            wells.push(json!({ "well": well, "type": "Standard", "group": group + 1, "concentration": x }));
+            values.push(json!({ "well": well, "raw": y }));
+        }
+    }
+    let layout = json!({ "schema": "elisa-plate-layout", "version": 1, "width": 12, "height": 8, "wells": wells });
+    serde_json::to_vec(&json!({ "name": "API", "layout": layout, "values": values, "options": options })).unwrap()
+}
+
+fn post_fit(body: Vec<u8>) -> (u16, String) {
+    handle(&ApiRequest { method: "POST".to_string(), path: "/api/fit".to_string(), body }, 1)
+}
+
+#[test]
+fn fits_a_plate() {
+    let (status, body) = post_fit(fit_body(json!({})));
+    assert_eq!(status, 200, "{body}");
+}
+
+#[test]
+fn oversized_options_are_capped() {
+    let options = json!({ "extra_starts": usize::MAX, "max_iterations": usize::MAX });
+    let request: FitRequest = serde_json::from_slice(&fit_body(options.clone())).unwrap();
+    let microplate = request.microplate().unwrap();
+    assert_eq!(microplate.fit_options.extra_starts, MAX_EXTRA_STARTS);
+    assert_eq!(microplate.fit_options.max_iterations, Some(MAX_ITERATIONS));
+
+    // answered like any other request instead of overflowing or running for good
+    let started = Instant::now();
+    let (status, body) = post_fit(fit_body(options));
+    assert_eq!(status, 200, "{body}");
+    assert!(started.elapsed() < Duration::from_secs(300), "took {:?}", started.elapsed());
+}
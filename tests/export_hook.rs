@@ -0,0 +1,23 @@
+use std::path::PathBuf;
+
+use elisa::export_hook::*;
+
+fn files(plate: &str) -> HookFiles {
+    HookFiles { plate: plate.to_string(), folder: PathBuf::from("/exports"), json: Some(PathBuf::from("/exports/plate.json")), ..Default::default() }
+}
+
+#[test]
+fn placeholders_are_replaced() {
+    let arguments = command_arguments(r#"lims-import --plate "{plate}" {json} "{csv}""#, &files("Plate 7")).unwrap();
+    assert_eq!(arguments, ["lims-import", "--plate", "Plate 7", "/exports/plate.json", ""]);
+}
+
+#[test]
+fn plate_names_cant_pass_options() {
+    let arguments = command_arguments("lims-import {plate} {json}", &files("--delete-all")).unwrap();
+    assert_eq!(arguments, ["lims-import", "_--delete-all", "/exports/plate.json"]);
+
+    // the same wherever the name is in the argument
+    let arguments = command_arguments("lims-import --plate={plate}", &files("-5")).unwrap();
+    assert_eq!(arguments, ["lims-import", "--plate=_-5"]);
+}
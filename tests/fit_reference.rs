@@ -0,0 +1,51 @@
+use elisa::{logistic_regression::*, self_test::*};
+
+#[test]
+fn analytical_gradient_matches_numerical() {
+    for dataset in REFERENCE_DATASETS {
+        let points: Vec<_> = dataset.standards().iter().map(|&(x, y)| (x.ln(), y)).collect();
+        let weights = dataset.weights();
+        for parameters in dataset.gradient_check_points() {
+            let check = check_gradient(&points, &weights, parameters);
+            assert!(check.passed(), "{} at {parameters:?}: analytical {:?}, numerical {:?}", dataset.id, check.analytical, check.numerical);
+        }
+    }
+}
+
+#[test]
+fn fit_reproduces_reference_parameters() {
+    for dataset in REFERENCE_DATASETS {
+        let regression = Regression::from_standards(&dataset.standards(), dataset.weighting)
+            .unwrap_or_else(|error| panic!("{}: {error:?}", dataset.id));
+        let deviations = dataset.deviations(regression.abcd);
+        assert!(deviations.iter().all(|&deviation| deviation <= 1.0), "{}: fitted {:?}, expected {:?}", dataset.id, regression.abcd, dataset.abcd);
+        assert!(regression.diagnostics.converged, "{}: gradient norm {}", dataset.id, regression.diagnostics.gradient_norm);
+    }
+}
+
+#[test]
+fn self_test_passes() {
+    let results = run_self_test();
+    assert_eq!(results.len(), REFERENCE_DATASETS.len());
+    for result in results {
+        assert!(result.passed(), "{}: {:?}", result.dataset.id, result.fitted);
+    }
+}
+
+#[test]
+fn multi_start_is_repeatable_and_no_worse() {
+    for dataset in REFERENCE_DATASETS {
+        let standards = dataset.standards();
+        let options = FitOptions { weighting: dataset.weighting, extra_starts: 5, ..Default::default() };
+        let single = Regression::from_standards(&standards, dataset.weighting).unwrap();
+        let first = Regression::from_standards_with_options(&standards, options.clone()).unwrap();
+        let second = Regression::from_standards_with_options(&standards, options).unwrap();
+        assert_eq!(first.abcd, second.abcd, "{}", dataset.id);
+        assert_eq!(first.diagnostics.starts, 6);
+
+        // the starts are compared on the loss the fit minimizes, with c as ln c
+        let points: Vec<_> = standards.iter().map(|&(x, y)| (x.ln(), y)).collect();
+        let loss = |(a, b, c, d): (f64, f64, f64, f64)| weighted_loss(&points, &dataset.weights(), (a, b, c.ln(), d));
+        assert!(loss(first.abcd) <= loss(single.abcd) * (1.0 + 1e-9), "{}: {} > {}", dataset.id, loss(first.abcd), loss(single.abcd));
+    }
+}
@@ -0,0 +1,30 @@
+use elisa::{grading::*, logistic_regression::*, self_test::*};
+
+/// The DNase ELISA of R, whose four-parameter fit has an SSE of 0.0047073 against a total sum of squares of 5.4119689
+fn dnase() -> Regression {
+    let dataset = REFERENCE_DATASETS.iter().find(|dataset| dataset.id == "self-test-dnase").unwrap();
+    Regression::from_standards(&dataset.standards(), dataset.weighting).unwrap()
+}
+
+#[test]
+fn r_squared_is_one_minus_sse_over_sst() {
+    let regression = dnase();
+    assert!((regression.r_sq - 0.9991302).abs() < 1e-6, "R² {}", regression.r_sq);
+
+    let n = regression.standards.len() as f64;
+    let mean = regression.standards.iter().map(|&(_x, y)| y).sum::<f64>() / n;
+    let total: f64 = regression.standards.iter().map(|&(_x, y)| (y - mean).powi(2)).sum();
+    assert!((regression.r_sq - (1.0 - regression.sse / total)).abs() < 1e-12);
+    assert_eq!(CurveQuality::new(&regression).r_sq, regression.r_sq);
+}
+
+#[test]
+fn four_standards_are_graded_without_sy_x() {
+    let (a, b, c, d) = (0.05, 1.2, 50.0, 2.5);
+    let standards: Vec<_> = [1.0, 20.0, 150.0, 1000.0].iter().map(|&x: &f64| (x, d + (a - d) / (1.0 + (x / c).powf(b)))).collect();
+    let regression = Regression::from_standards(&standards, Weighting::None).unwrap();
+    let quality = CurveQuality::new(&regression);
+    assert_eq!(quality.sy_x_percent, None);
+    assert_eq!(quality.grade, CurveGrade::A, "{}", quality.summary());
+    assert!(quality.summary().contains("no Sy.x"), "{}", quality.summary());
+}
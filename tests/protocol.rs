@@ -0,0 +1,18 @@
+use elisa::{logistic_regression::*, protocol::*, self_test::*};
+
+fn r_squared_check(protocol: &Protocol, regression: &Regression) -> QcCheck {
+    let microplate = Microplate::new(12, 8);
+    protocol.evaluate(&microplate, regression).into_iter().find(|check| check.criterion == Criterion::RSquared).unwrap()
+}
+
+// The DNase ELISA of R has an R² of 0.99913, its square 0.99826 was once compared with the limit instead
+#[test]
+fn r_squared_limit_is_met_at_the_limit() {
+    let dataset = REFERENCE_DATASETS.iter().find(|dataset| dataset.id == "self-test-dnase").unwrap();
+    let regression = Regression::from_standards(&dataset.standards(), dataset.weighting).unwrap();
+
+    for (limit, passed) in [(0.999, true), (regression.r_sq, true), (0.9992, false)] {
+        let protocol = Protocol { r_sq_limit: Some(limit), ..Default::default() };
+        assert_eq!(r_squared_check(&protocol, &regression).passed, passed, "limit {limit}, R² {}", regression.r_sq);
+    }
+}
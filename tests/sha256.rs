@@ -0,0 +1,26 @@
+use elisa::sha256::*;
+
+// Examples of FIPS 180-4, from the NIST cryptographic standards and guidelines examples for SHA-256
+#[test]
+fn fips_examples() {
+    let examples = [
+        ("", "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"),
+        ("abc", "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"),
+        // 448 bits, the padding doesn't fit into the block and takes a second one
+        ("abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq", "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1"),
+        // 896 bits, longer than a block
+        (
+            "abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmnhijklmnoijklmnopjklmnopqklmnopqrlmnopqrsmnopqrstnopqrstu",
+            "cf5b16a778af8380036ce59e7b0492370b249b11e8f07a51afac45037afee9d1",
+        ),
+    ];
+    for (message, digest) in examples {
+        assert_eq!(sha256_hex(message.as_bytes()), digest, "{message:?}");
+    }
+}
+
+#[test]
+fn million_a() {
+    let message = vec![b'a'; 1_000_000];
+    assert_eq!(sha256_hex(&message), "cdc76e5c9914fb9281a1c7e284d73e67f1809a48a497200e046d39ccc7112cd0");
+}